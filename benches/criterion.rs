@@ -24,6 +24,18 @@ fn asn1tools(c: &mut Criterion) {
     bench_encoding_rules!(ber, der, cer, uper);
 }
 
+fn nested(c: &mut Criterion) {
+    // Eight levels of SEQUENCE OF around 64 KiB of contents.
+    let leaf = vec![rasn::types::OctetString::from(vec![0xA5; 1024]); 64];
+    let value = black_box(vec![vec![vec![vec![vec![vec![vec![leaf]]]]]]]);
+
+    let mut group = c.benchmark_group("nested");
+    group.bench_function("der/encode", |b| {
+        b.iter_with_large_drop(|| black_box(rasn::der::encode(&value).unwrap()))
+    });
+    group.finish();
+}
+
 fn x509(c: &mut Criterion) {
     use x509_parser::prelude::*;
 
@@ -38,5 +50,5 @@ fn x509(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(codec, x509, asn1tools);
+criterion_group!(codec, x509, asn1tools, nested);
 criterion_main!(codec);
@@ -14,3 +14,4 @@ required_arg $TARGET_TRIPLE '<Target Triple>'
 
 $CROSS test --target $TARGET_TRIPLE --workspace
 $CROSS build --target $TARGET_TRIPLE --all-features --workspace
+$CROSS test --target $TARGET_TRIPLE --features tokio
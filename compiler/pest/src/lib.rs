@@ -17,6 +17,20 @@ mod tests {
         Asn1Parser::parse(Rule::ModuleDefinition, input).unwrap_or_else(|e| panic!("{}", e));
     }
 
+    #[test]
+    fn contents_constraints() {
+        let input = include_str!("../../tests/containing.asn1");
+
+        Asn1Parser::parse(Rule::ModuleDefinition, input).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    #[test]
+    fn subtype_constraints() {
+        let input = include_str!("../../tests/constraints.asn1");
+
+        Asn1Parser::parse(Rule::ModuleDefinition, input).unwrap_or_else(|e| panic!("{}", e));
+    }
+
     #[test]
     fn pkcs12() {
         let input = include_str!("../../asn1/pkcs12.asn1");
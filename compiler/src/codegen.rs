@@ -1,20 +1,32 @@
 mod constant;
 mod constant_enum;
+mod constraints;
 mod imports;
 mod structs;
 
-use std::{collections::HashSet, fmt, io::Write, mem};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    io::Write,
+    marker::PhantomData,
+    mem,
+};
 
 use failure::Fallible as Result;
 use heck::*;
 
 use self::{
     constant::Constant,
+    constraints::EffectiveConstraints,
     imports::*,
     structs::{Field as StructField, *},
 };
 use crate::codegen::constant_enum::{ConstantEnum, Triple};
-use crate::{parser::*, semantics::SemanticChecker};
+use crate::{
+    diagnostics::{Diagnostics, Span},
+    parser::*,
+    semantics::SemanticChecker,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub enum TagEnvironment {
@@ -41,44 +53,94 @@ impl Default for TagEnvironment {
     }
 }
 
+/// What to generate for types that aren't supported yet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unsupported {
+    /// Leave out the type, or the field that uses it.
+    Skip,
+    /// Generate `rasn::types::Any`, which keeps the encoded value as-is.
+    Any,
+}
+
+impl Default for Unsupported {
+    fn default() -> Self {
+        Unsupported::Skip
+    }
+}
+
 pub trait Backend: Default {
     fn tag_environment(&mut self, environment: TagEnvironment);
+    fn unsupported(&mut self, unsupported: Unsupported);
+    /// Sets the traits derived by generated types, in addition to the
+    /// `rasn` traits.
+    fn derives(&mut self, derives: Vec<String>);
+    /// Sets the module's type and value assignments, which are used to
+    /// resolve references in values.
+    fn symbols(&mut self, types: BTreeMap<String, Type>, values: BTreeMap<String, (Type, Value)>);
+    /// Sets the names of the types that were skipped, so that anything that
+    /// references them is skipped as well.
+    fn skipped(&mut self, skipped: HashSet<String>);
+    /// Sets the location of the assignment being generated, which is used
+    /// in diagnostics.
+    fn assignment_span(&mut self, span: Option<Span>);
+    fn take_diagnostics(&mut self) -> Diagnostics;
+    /// Generates `ty`, returning `None` if it was skipped.
     fn generate_type(
         &mut self,
         ty: &Type,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<(String, Option<Prefix>)>;
+    ) -> Result<Option<(String, Option<Prefix>)>>;
     fn generate_value(&mut self, value: &Value) -> Result<String>;
     fn generate_value_assignment(&mut self, name: String, ty: Type, value: Value) -> Result<()>;
+    /// Generates a struct for a `SEQUENCE`, returning `None` if it was
+    /// skipped because one of its components couldn't be generated.
     fn generate_sequence(
         &mut self,
         name: &str,
         components: &ComponentTypeList,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<String>;
+    ) -> Result<Option<String>>;
     fn generate_sequence_of(
         &mut self,
         name: &str,
         ty: &Type,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<String>;
+    ) -> Result<Option<String>>;
+    /// Generates a struct for a `SET`, returning `None` if it was skipped
+    /// because one of its components couldn't be generated.
     fn generate_set(
         &mut self,
         name: &str,
         components: &ComponentTypeList,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<String>;
+    ) -> Result<Option<String>>;
+    /// Generates `name` as an alias of `generated`, the code generated for
+    /// `ty`. Aliases with constraints or a tag are generated as newtypes,
+    /// as a type alias can't carry them.
+    fn generate_alias(
+        &mut self,
+        name: &str,
+        ty: &Type,
+        generated: String,
+        prefix: Option<&Prefix>,
+    ) -> Result<Option<String>>;
     fn generate_field(
         &mut self,
         field: &ComponentType,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<StructField>;
+    ) -> Result<Option<StructField>>;
     fn generate_builtin(
         &mut self,
         name: &str,
         builtin: &BuiltinType,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<(String, Option<Prefix>)>;
+    ) -> Result<Option<(String, Option<Prefix>)>>;
+    fn generate_containing(
+        &mut self,
+        inner: &Type,
+        encoding: Option<&Value>,
+        bit_string: bool,
+    ) -> Result<Option<String>>;
     fn write_prelude<W: Write>(&mut self, writer: &mut W) -> Result<()>;
     fn write_footer<W: Write>(&self, writer: &mut W) -> Result<()>;
 }
@@ -87,15 +149,132 @@ pub trait Backend: Default {
 pub struct Rust {
     environment: TagEnvironment,
     constant_enums: HashSet<ConstantEnum>,
-    consts: HashSet<Constant>,
+    consts: Vec<Constant>,
     structs: Vec<Struct>,
     prelude: HashSet<Import>,
+    unsupported: Unsupported,
+    derives: Vec<String>,
+    types: BTreeMap<String, Type>,
+    values: BTreeMap<String, (Type, Value)>,
+    skipped: HashSet<String>,
+    span: Option<Span>,
+    diagnostics: Diagnostics,
+}
+
+impl Rust {
+    fn import(&mut self, path: &[&str]) {
+        self.prelude.insert(Import::new(
+            Visibility::Private,
+            path.iter().map(ToString::to_string).collect(),
+        ));
+    }
+
+    /// Whether `name` was generated as a newtype struct.
+    fn is_newtype(&self, name: &str) -> bool {
+        self.structs
+            .iter()
+            .any(|generated| generated.name() == name && generated.is_newtype())
+    }
+
+    /// Adds the fields generated for `components` to `generated_struct`,
+    /// returning `false` if any of them couldn't be generated, as the struct
+    /// would then have a different encoding than the ASN.1 type.
+    fn generate_fields(
+        &mut self,
+        generated_struct: &mut Struct,
+        name: &str,
+        components: &ComponentTypeList,
+        parent_prefix: Option<&Prefix>,
+    ) -> Result<bool> {
+        for component in components.components.iter().flatten() {
+            match self.generate_field(component, parent_prefix)? {
+                Some(field) => generated_struct.add_field(field),
+                None => {
+                    let component = component
+                        .as_type()
+                        .and_then(|(ty, ..)| ty.name.clone())
+                        .unwrap_or_else(|| String::from("COMPONENTS OF"));
+                    self.diagnostics.warning(
+                        format!(
+                            "`{}` was skipped, as its component `{}` couldn't be generated",
+                            name, component
+                        ),
+                        self.span,
+                    );
+
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Reports that `description` isn't supported, and returns what to
+    /// generate in its place.
+    fn unsupported_type(&mut self, description: &str) -> Option<(String, Option<Prefix>)> {
+        match self.unsupported {
+            Unsupported::Skip => {
+                self.diagnostics.warning(
+                    format!("{} isn't supported yet and was skipped", description),
+                    self.span,
+                );
+
+                None
+            }
+            Unsupported::Any => {
+                self.diagnostics.warning(
+                    format!(
+                        "{} isn't supported yet and was generated as `Any`",
+                        description
+                    ),
+                    self.span,
+                );
+
+                self.prelude.insert(Import::new(
+                    Visibility::Private,
+                    ["rasn", "types", "Any"]
+                        .into_iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                ));
+
+                Some((String::from("Any"), None))
+            }
+        }
+    }
 }
 
 impl Backend for Rust {
     fn tag_environment(&mut self, environment: TagEnvironment) {
         self.environment = environment;
     }
+
+    fn unsupported(&mut self, unsupported: Unsupported) {
+        self.unsupported = unsupported;
+    }
+
+    fn derives(&mut self, derives: Vec<String>) {
+        self.derives = derives;
+    }
+
+    fn symbols(&mut self, types: BTreeMap<String, Type>, values: BTreeMap<String, (Type, Value)>) {
+        self.types = types;
+        self.values = values;
+    }
+
+    fn skipped(&mut self, skipped: HashSet<String>) {
+        self.skipped = skipped;
+    }
+
+    fn assignment_span(&mut self, span: Option<Span>) {
+        self.span = span;
+    }
+
+    fn take_diagnostics(&mut self) -> Diagnostics {
+        mem::take(&mut self.diagnostics)
+    }
+
     /// As Rust doesn't allow you to have anonymous structs,
     /// `generate_sequence` returns the name of the struct and
     /// stores the definition seperately.
@@ -104,18 +283,16 @@ impl Backend for Rust {
         name: &str,
         components: &ComponentTypeList,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<String> {
-        let mut generated_struct = Struct::new(Visibility::Public, name);
+    ) -> Result<Option<String>> {
+        let mut generated_struct = Struct::new(Visibility::Public, name).add_derives(&self.derives);
 
         if let Some(prefix) = parent_prefix {
             generated_struct =
                 generated_struct.add_rasn_attributes(vec![Rasn::Prefix(prefix.clone())]);
         }
 
-        for field in components.components.as_ref().unwrap() {
-            let field = self.generate_field(field, parent_prefix)?;
-
-            generated_struct.add_field(field);
+        if !self.generate_fields(&mut generated_struct, name, components, parent_prefix)? {
+            return Ok(None);
         }
 
         self.structs.push(generated_struct);
@@ -144,7 +321,7 @@ impl Backend for Rust {
                 .collect(),
         ));
 
-        Ok(name.to_camel_case())
+        Ok(Some(name.to_camel_case()))
     }
 
     fn generate_sequence_of(
@@ -152,13 +329,13 @@ impl Backend for Rust {
         name: &str,
         ty: &Type,
         _parent_prefix: Option<&Prefix>,
-    ) -> Result<String> {
-        let inner_type = match ty.raw_type {
-            RawType::Referenced(ref reference) => &reference.item,
-            _ => unimplemented!(),
+    ) -> Result<Option<String>> {
+        let inner_type = match self.generate_type(ty, None)? {
+            Some((inner_type, _)) => inner_type,
+            None => return Ok(None),
         };
 
-        Ok(format!("pub type {} = Vec<{}>;", name, inner_type))
+        Ok(Some(format!("Vec<{}>", inner_type)))
     }
 
     fn generate_set(
@@ -166,8 +343,8 @@ impl Backend for Rust {
         name: &str,
         components: &ComponentTypeList,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<String> {
-        let mut generated_struct = Struct::new(Visibility::Public, name);
+    ) -> Result<Option<String>> {
+        let mut generated_struct = Struct::new(Visibility::Public, name).add_derives(&self.derives);
         let mut attributes = vec![Rasn::Type("set")];
 
         if let Some(prefix) = parent_prefix {
@@ -176,9 +353,8 @@ impl Backend for Rust {
 
         generated_struct = generated_struct.add_rasn_attributes(attributes);
 
-        for field in components.components.as_ref().unwrap() {
-            let field = self.generate_field(field, parent_prefix)?;
-            generated_struct.add_field(field);
+        if !self.generate_fields(&mut generated_struct, name, components, parent_prefix)? {
+            return Ok(None);
         }
 
         self.structs.push(generated_struct);
@@ -207,46 +383,128 @@ impl Backend for Rust {
                 .collect(),
         ));
 
-        Ok(name.to_camel_case())
+        Ok(Some(name.to_camel_case()))
+    }
+
+    fn generate_alias(
+        &mut self,
+        name: &str,
+        ty: &Type,
+        generated: String,
+        prefix: Option<&Prefix>,
+    ) -> Result<Option<String>> {
+        let mut attributes = Vec::new();
+        if let Some(prefix) = prefix {
+            attributes.push(Rasn::Prefix(prefix.clone()));
+        }
+        attributes.extend(EffectiveConstraints::new(&inner_constraints(ty)).attributes());
+
+        // Named numbers are associated constants, which need a type of
+        // their own.
+        let named_numbers = matches!(
+            &ty.raw_type,
+            RawType::Builtin(BuiltinType::Integer(named_numbers)) if !named_numbers.is_empty()
+        );
+
+        if attributes.is_empty() && !named_numbers {
+            return Ok(Some(format!("pub type {} = {};", name, generated)));
+        }
+
+        attributes.insert(0, Rasn::Type("delegate"));
+        self.structs.push(
+            Struct::newtype(Visibility::Public, name, generated)
+                .add_derives(&self.derives)
+                .add_rasn_attributes(attributes),
+        );
+
+        for path in [["rasn", "AsnType"], ["rasn", "Encode"], ["rasn", "Decode"]] {
+            self.import(&path);
+        }
+
+        Ok(None)
     }
 
     fn generate_field(
         &mut self,
         field: &ComponentType,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<StructField> {
-        // Unwrap currently needed as i haven't created the simplified AST without
-        // `ComponentsOf` yet.
-        let (ty, optional, default) = field.as_type().unwrap();
-        let (field_ty, prefix) = self.generate_type(&ty, parent_prefix)?;
+    ) -> Result<Option<StructField>> {
+        let (ty, optional, default) = match field.as_type() {
+            Some(field) => field,
+            None => {
+                self.diagnostics.warning(
+                    "`COMPONENTS OF` isn't supported yet and was skipped",
+                    self.span,
+                );
+                return Ok(None);
+            }
+        };
+        let (field_ty, prefix) = match self.generate_type(&ty, parent_prefix)? {
+            Some(generated) => generated,
+            None => return Ok(None),
+        };
         let mut builder = FieldBuilder::new(ty.name.as_ref().unwrap().to_snake_case(), field_ty)
             .optional(*optional)
             .visibility(Visibility::Public)
             .default_value(default.clone().and_then(|v| self.generate_value(&v).ok()));
+
+        let mut attributes = Vec::new();
         if let Some(prefix) = prefix {
-            builder = builder.add_rasn_attribute(vec![Rasn::Prefix(prefix)]);
+            attributes.push(Rasn::Prefix(prefix));
+        }
+        attributes.extend(EffectiveConstraints::new(&inner_constraints(ty)).attributes());
+
+        if !attributes.is_empty() {
+            builder = builder.add_rasn_attribute(attributes);
         }
         let field = builder.build();
-        Ok(field)
+        Ok(Some(field))
     }
 
     fn generate_type(
         &mut self,
         ty: &Type,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<(String, Option<Prefix>)> {
+    ) -> Result<Option<(String, Option<Prefix>)>> {
+        if let Some((Some(inner), encoding)) = ty.contents_constraint() {
+            let bit_string = match ty.raw_type {
+                RawType::Builtin(BuiltinType::OctetString) => Some(false),
+                RawType::Builtin(BuiltinType::BitString(_)) => Some(true),
+                _ => None,
+            };
+
+            if let Some(bit_string) = bit_string {
+                return Ok(self
+                    .generate_containing(inner, encoding, bit_string)?
+                    .map(|containing| (containing, None)));
+            }
+        }
+
         match ty.raw_type {
             RawType::Builtin(ref builtin) => self.generate_builtin(
                 &ty.name.as_ref().unwrap_or(&String::from("Error")),
                 builtin,
                 parent_prefix,
             ),
+            RawType::Referenced(ref reference)
+                if reference.is_internal() && self.skipped.contains(&reference.item) =>
+            {
+                Ok(self.unsupported_type(&format!(
+                    "a reference to the skipped type `{}`",
+                    reference.item
+                )))
+            }
             RawType::Referenced(ref reference) if reference.is_internal() => {
-                Ok((reference.item.clone(), None))
+                Ok(Some((reference.item.clone(), None)))
+            }
+            RawType::Referenced(ref reference) => {
+                Ok(self.unsupported_type(&format!("`{}` from another module", reference)))
+            }
+            RawType::ParameterizedReference(ref reference, _) => {
+                Ok(self.unsupported_type(&format!("the parameterized type `{}`", reference.item)))
             }
-            ref raw => {
-                warn!("UNKNOWN TYPE: {:?}", raw);
-                Ok((String::from("UNIMPLEMENTED"), None))
+            RawType::ReferencedFromObject(_) => {
+                Ok(self.unsupported_type("a type from an information object"))
             }
         }
     }
@@ -256,7 +514,7 @@ impl Backend for Rust {
         name: &str,
         builtin: &BuiltinType,
         parent_prefix: Option<&Prefix>,
-    ) -> Result<(String, Option<Prefix>)> {
+    ) -> Result<Option<(String, Option<Prefix>)>> {
         let (output, prefix) = match builtin {
             BuiltinType::Boolean => (String::from("bool"), None),
             BuiltinType::ObjectIdentifier => {
@@ -292,10 +550,16 @@ impl Backend for Rust {
                 ));
 
                 if named_numbers.len() > 0 {
+                    // Numbers defined by other values aren't resolved yet.
                     let variants: HashSet<Triple> = named_numbers
                         .iter()
-                        .map(|(key, value)| {
-                            Triple::new(key.to_string(), String::from("Integer"), value.to_string())
+                        .filter_map(|(key, value)| match value {
+                            Number::Literal(value) => Some(Triple::new(
+                                key.to_string(),
+                                String::from("Integer"),
+                                value.to_string(),
+                            )),
+                            Number::DefinedValue(_) => None,
                         })
                         .collect();
                     self.constant_enums.insert(ConstantEnum::new(
@@ -307,43 +571,131 @@ impl Backend for Rust {
 
                 (String::from("Integer"), None)
             }
-            BuiltinType::Sequence(components) => (
-                self.generate_sequence(&name.to_camel_case(), components, parent_prefix)?,
-                None,
-            ),
-            BuiltinType::Prefixed(prefix, ty) => {
-                let (ty, _) = self.generate_type(&ty, parent_prefix)?;
-                (ty, Some(prefix.clone()))
+            BuiltinType::CharacterString(string) => match character_string(string) {
+                Some(string) => {
+                    self.import(&["rasn", "types", string]);
+                    (String::from(string), None)
+                }
+                None => return Ok(self.unsupported_type(&format!("`{}`", builtin))),
+            },
+            BuiltinType::Sequence(components) => {
+                match self.generate_sequence(&name.to_camel_case(), components, parent_prefix)? {
+                    Some(name) => (name, None),
+                    None => return Ok(None),
+                }
             }
-            ref builtin => {
-                warn!("UNKNOWN BUILTIN TYPE: {:?}", builtin);
-                (String::from("UNIMPLEMENTED"), None)
+            BuiltinType::Prefixed(prefix, ty) => match self.generate_type(&ty, parent_prefix)? {
+                Some((ty, _)) => (ty, Some(prefix.clone())),
+                None => return Ok(None),
+            },
+            builtin => return Ok(self.unsupported_type(&format!("`{}`", builtin))),
+        };
+
+        Ok(Some((output, prefix)))
+    }
+
+    /// Generates a `Containing` wrapper for an `OCTET STRING` or
+    /// `BIT STRING` constrained with `CONTAINING inner ENCODED BY encoding`.
+    fn generate_containing(
+        &mut self,
+        inner: &Type,
+        encoding: Option<&Value>,
+        bit_string: bool,
+    ) -> Result<Option<String>> {
+        let (inner, prefix) = match self.generate_type(inner, None)? {
+            Some(generated) => generated,
+            None => return Ok(None),
+        };
+        if prefix.is_some() {
+            self.diagnostics.warning(
+                "tags on contained types aren't supported yet and were ignored",
+                self.span,
+            );
+        }
+
+        self.prelude.insert(Import::new(
+            Visibility::Private,
+            ["rasn", "types", "Containing"]
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+        ));
+
+        let codec = match encoding {
+            Some(Value::ObjectIdentifier(oid)) => {
+                let codec = encoding_rules(oid);
+                if codec.is_none() {
+                    self.diagnostics.warning(
+                        format!(
+                            "unknown encoding rules `{}`, the outer encoding rules are used instead",
+                            oid
+                        ),
+                        self.span,
+                    );
+                }
+                codec
+            }
+            Some(_) => {
+                self.diagnostics.warning(
+                    "encoding rules can only be given as an object identifier, the outer \
+                     encoding rules are used instead",
+                    self.span,
+                );
+                None
             }
+            None => None,
         };
 
-        Ok((output, prefix))
+        let mut parameters = vec![inner];
+
+        if codec.is_some() || bit_string {
+            self.prelude.insert(Import::new(
+                Visibility::Private,
+                ["rasn", "types", "containing"]
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ));
+
+            parameters.push(format!("containing::{}", codec.unwrap_or("Inherited")));
+        }
+
+        if bit_string {
+            self.prelude.insert(Import::new(
+                Visibility::Private,
+                ["rasn", "types", "BitString"]
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ));
+
+            parameters.push(String::from("BitString"));
+        }
+
+        Ok(Some(format!("Containing<{}>", parameters.join(", "))))
     }
 
     fn write_prelude<W: Write>(&mut self, writer: &mut W) -> Result<()> {
+        // Constants are generated first, as they can add imports.
+        let consts: Vec<_> = mem::take(&mut self.consts)
+            .into_iter()
+            .filter_map(|c| c.generate(self))
+            .collect();
+
         let prelude = mem::replace(&mut self.prelude, HashSet::new());
         writer
             .write_all(itertools::join(prelude.iter().map(ToString::to_string), "\n").as_bytes())?;
+        writeln!(writer)?;
 
-        let consts = mem::replace(&mut self.consts, HashSet::new());
-        writer.write_all(
-            itertools::join(
-                consts.into_iter().filter_map(|c| c.generate(self).ok()),
-                "\n",
-            )
-            .as_bytes(),
-        )?;
+        writer.write_all(itertools::join(consts, "\n").as_bytes())?;
 
         let constant_enums = mem::replace(&mut self.constant_enums, HashSet::new());
         writer.write_all(
             itertools::join(
-                constant_enums
-                    .into_iter()
-                    .filter_map(|c| Some(c.generate())),
+                constant_enums.into_iter().map(|c| {
+                    let newtype = self.is_newtype(c.name());
+                    c.generate(newtype)
+                }),
                 "\n",
             )
             .as_bytes(),
@@ -366,67 +718,230 @@ impl Backend for Rust {
     }
 
     fn generate_value_assignment(&mut self, name: String, ty: Type, value: Value) -> Result<()> {
-        self.consts
-            .insert(Constant::new(Visibility::Public, name, ty, value));
+        self.consts.push(Constant::new(
+            Visibility::Public,
+            name,
+            ty,
+            value,
+            self.span,
+        ));
         Ok(())
     }
 }
 
+/// The constraints of `ty`, followed by those of any type it prefixes, as
+/// the generated field has the type of the innermost type.
+fn inner_constraints(ty: &Type) -> Vec<Constraint> {
+    let mut constraints: Vec<_> = ty.constraints.iter().flatten().cloned().collect();
+
+    if let RawType::Builtin(BuiltinType::Prefixed(_, inner)) = &ty.raw_type {
+        constraints.extend(inner_constraints(inner));
+    }
+
+    constraints
+}
+
+/// The `rasn` type of a character string type, or `None` if there isn't one.
+fn character_string(string: &CharacterStringType) -> Option<&'static str> {
+    match string {
+        CharacterStringType::Bmp => Some("BmpString"),
+        CharacterStringType::General => Some("GeneralString"),
+        CharacterStringType::Ia5 => Some("Ia5String"),
+        CharacterStringType::Iso646 | CharacterStringType::Visible => Some("VisibleString"),
+        CharacterStringType::Numeric => Some("NumericString"),
+        CharacterStringType::Printable => Some("PrintableString"),
+        CharacterStringType::T61 | CharacterStringType::Teletex => Some("TeletexString"),
+        CharacterStringType::Universal => Some("UniversalString"),
+        CharacterStringType::Utf8 => Some("Utf8String"),
+        CharacterStringType::Graphic
+        | CharacterStringType::Unrestricted
+        | CharacterStringType::Videotex => None,
+    }
+}
+
+/// Maps the object identifier of a set of encoding rules (X.690 §8.1,
+/// X.691 §29) to the name of the matching codec in `rasn::types::containing`.
+fn encoding_rules(oid: &ObjectIdentifier) -> Option<&'static str> {
+    let arcs = oid
+        .iter()
+        .map(|component| match component {
+            ObjIdComponent::Number(Number::Literal(number))
+            | ObjIdComponent::NameAndNumber(_, Number::Literal(number)) => Some(*number),
+            ObjIdComponent::Name(name) => match &**name {
+                "joint-iso-itu-t" | "joint-iso-ccitt" | "Joint-ISO-ITU-T" => Some(2),
+                "asn1"
+                | "basic-encoding"
+                | "distinguished-encoding"
+                | "canonical"
+                | "unaligned" => Some(1),
+                "ber-derived" => Some(2),
+                "packed-encoding" => Some(3),
+                "canonical-encoding" | "basic" | "aligned" => Some(0),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match &*arcs {
+        [2, 1, 1] => Some("Ber"),
+        [2, 1, 2, 0] => Some("Cer"),
+        [2, 1, 2, 1] => Some("Der"),
+        [2, 1, 3, 0..=1, 0] => Some("Aper"),
+        [2, 1, 3, 0..=1, 1] => Some("Uper"),
+        _ => None,
+    }
+}
+
 pub struct CodeGenerator<'a, W: Write, B: Backend> {
-    backend: B,
+    unsupported: Unsupported,
+    derives: Vec<String>,
     semantic_tree: SemanticChecker,
     writer: &'a mut W,
+    backend: PhantomData<B>,
 }
 
 impl<'a, W: Write, B: Backend> CodeGenerator<'a, W, B> {
     pub fn new(semantic_tree: SemanticChecker, writer: &'a mut W) -> Self {
         Self {
-            backend: B::default(),
+            unsupported: Unsupported::default(),
+            derives: Vec::new(),
             semantic_tree,
             writer,
+            backend: PhantomData,
         }
     }
 
-    pub fn generate(mut self) -> Result<()> {
-        let table = self.semantic_tree.table;
+    pub fn unsupported(mut self, unsupported: Unsupported) -> Self {
+        self.unsupported = unsupported;
+        self
+    }
+
+    pub fn derives(mut self, derives: Vec<String>) -> Self {
+        self.derives = derives;
+        self
+    }
+
+    /// Writes the generated code, returning the warnings about anything
+    /// that couldn't be generated.
+    ///
+    /// Skipping a type means that the types that reference it have to be
+    /// skipped as well, so the module is generated again with every skipped
+    /// type known until no more types are skipped.
+    pub fn generate(self) -> Result<Diagnostics> {
+        let mut skipped = HashSet::new();
+
+        loop {
+            let mut output = Vec::new();
+            let (diagnostics, newly_skipped) = self.generate_pass(&mut output, &skipped)?;
+
+            if newly_skipped.is_subset(&skipped) {
+                self.writer.write_all(&output)?;
+                return Ok(diagnostics);
+            }
+
+            skipped.extend(newly_skipped);
+        }
+    }
+
+    /// Generates the module into `writer`, returning the diagnostics and the
+    /// names of the types that were skipped.
+    fn generate_pass(
+        &self,
+        writer: &mut Vec<u8>,
+        skipped: &HashSet<String>,
+    ) -> Result<(Diagnostics, HashSet<String>)> {
+        let table = &self.semantic_tree.table;
+        let spans = &self.semantic_tree.spans;
+        let mut backend = B::default();
+        let mut newly_skipped = HashSet::new();
+
+        backend.unsupported(self.unsupported);
+        backend.derives(self.derives.clone());
+        backend.skipped(skipped.clone());
+        backend.symbols((*table.types).clone(), (*table.values).clone());
 
         for (name, (ty, value)) in table.values.clone().into_iter() {
-            self.backend.generate_value_assignment(name, ty, value)?;
+            backend.assignment_span(spans.get(&name).copied());
+            backend.generate_value_assignment(name, ty, value)?;
         }
 
         for (name, ty) in table.types.iter() {
-            match &ty.raw_type {
-                RawType::Builtin(BuiltinType::Sequence(components)) => {
-                    self.backend.generate_sequence(&name, components, None)?;
-                }
-                RawType::Builtin(BuiltinType::SequenceOf(ty)) => {
-                    write!(
-                        self.writer,
-                        "{}\n",
-                        self.backend.generate_sequence_of(&name, &ty, None)?
-                    )?;
+            backend.assignment_span(spans.get(name).copied());
+
+            let generated = match &ty.raw_type {
+                RawType::Builtin(BuiltinType::Sequence(components)) => backend
+                    .generate_sequence(&name, components, None)?
+                    .map(drop),
+                RawType::Builtin(BuiltinType::SequenceOf(inner)) => {
+                    match backend.generate_sequence_of(&name, &inner, None)? {
+                        Some(generated) => {
+                            write_alias(writer, backend.generate_alias(name, ty, generated, None)?)?;
+                            Some(())
+                        }
+                        None => None,
+                    }
                 }
-                RawType::Builtin(BuiltinType::Set(Set::Concrete(components))) => {
-                    self.backend.generate_set(&name, components, None)?;
+                RawType::Builtin(BuiltinType::Set(Set::Concrete(components))) => backend
+                    .generate_set(&name, components, None)?
+                    .map(drop),
+                RawType::Builtin(BuiltinType::Prefixed(prefix, inner)) => {
+                    let mut inner = inner.clone();
+                    if inner.name.is_none() {
+                        inner.name = Some(name.to_string());
+                    }
+
+                    // Structs carry their own tag, everything else is
+                    // tagged through a newtype.
+                    match &inner.raw_type {
+                        RawType::Builtin(BuiltinType::Sequence(_)) => {
+                            backend.generate_type(&*inner, Some(prefix))?.map(drop)
+                        }
+                        _ => match backend.generate_type(&*inner, None)? {
+                            Some((generated, _)) => {
+                                let alias =
+                                    backend.generate_alias(name, &inner, generated, Some(prefix))?;
+                                write_alias(writer, alias)?;
+                                Some(())
+                            }
+                            None => None,
+                        },
+                    }
                 }
-                RawType::Builtin(BuiltinType::Prefixed(prefix, ty)) => {
+                _ => {
                     let mut ty = ty.clone();
                     if ty.name.is_none() {
                         ty.name = Some(name.to_string());
                     }
 
-                    self.backend.generate_type(&*ty, Some(prefix))?;
-                }
-                _ => {
-                    write!(self.writer, "UNIMPLEMENTED {}\n", &name)?;
+                    match backend.generate_type(&ty, None)? {
+                        Some((generated, _)) => {
+                            write_alias(writer, backend.generate_alias(name, &ty, generated, None)?)?;
+                            Some(())
+                        }
+                        None => None,
+                    }
                 }
+            };
+
+            if generated.is_none() {
+                newly_skipped.insert(name.clone());
             }
         }
 
-        self.backend.write_prelude(self.writer)?;
-        write!(self.writer, "\n\n")?;
-        self.backend.write_footer(self.writer)?;
+        backend.assignment_span(None);
+        backend.write_prelude(writer)?;
+        write!(writer, "\n\n")?;
+        backend.write_footer(writer)?;
 
-        Ok(())
+        Ok((backend.take_diagnostics(), newly_skipped))
     }
 }
+
+fn write_alias<W: Write>(writer: &mut W, alias: Option<String>) -> Result<()> {
+    if let Some(alias) = alias {
+        writeln!(writer, "{}", alias)?;
+    }
+
+    Ok(())
+}
@@ -1,37 +1,559 @@
-use failure::Fallible;
+use std::{collections::BTreeMap, convert::TryFrom};
 
-use super::{imports::Visibility, Backend, Rust};
-use crate::parser::{Type, Value};
+use heck::{CamelCase, ShoutySnakeCase, SnakeCase};
 
+use super::{imports::Visibility, Rust};
+use crate::{diagnostics::Span, parser::*, semantics::resolve_type};
+
+/// A value assignment, which is generated as a `const` when its value can be
+/// built in a constant expression, and otherwise as a function that builds
+/// the value.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Constant {
     visibility: Visibility,
     name: String,
     ty: Type,
     value: Value,
+    span: Option<Span>,
 }
 
 impl Constant {
-    pub fn new(visibility: Visibility, name: String, ty: Type, value: Value) -> Self {
+    pub fn new(
+        visibility: Visibility,
+        name: String,
+        ty: Type,
+        value: Value,
+        span: Option<Span>,
+    ) -> Self {
         Self {
             visibility,
             name,
             ty,
             value,
+            span,
+        }
+    }
+
+    /// Generates the constant, or returns `None` with a warning if its type
+    /// isn't supported yet.
+    pub fn generate(self, backend: &mut Rust) -> Option<String> {
+        let constant = typed_const_value(backend, &self.ty, &self.value)
+            .or_else(|| const_value(backend, &self.ty, &self.value));
+
+        if let Some((ty, value)) = constant {
+            return Some(format!(
+                "{vis}const {name}: {ty} = {value};",
+                vis = self.visibility,
+                name = self.name.to_shouty_snake_case(),
+                ty = ty,
+                value = value,
+            ));
+        }
+
+        let function = value_type(backend, &self.ty).and_then(|ty| {
+            let value = value_expression(backend, &self.ty, &self.value, 0)?;
+
+            Some(format!(
+                "{vis}fn {name}() -> {ty} {{\n\t{value}\n}}",
+                vis = self.visibility,
+                name = self.name.to_snake_case(),
+                ty = ty,
+                value = value,
+            ))
+        });
+
+        if function.is_none() {
+            backend.diagnostics.warning(
+                format!(
+                    "the value `{}` of type `{}` isn't supported yet and was skipped",
+                    self.name, self.ty.raw_type
+                ),
+                self.span,
+            );
+        }
+
+        function
+    }
+}
+
+/// The Rust type and expression of `value` if it can be a `const` of the
+/// type generated for its defined type.
+fn typed_const_value(backend: &mut Rust, ty: &Type, value: &Value) -> Option<(String, String)> {
+    match &ty.raw_type {
+        RawType::Referenced(reference) if reference.is_internal() => Some((
+            reference.item.clone(),
+            const_expression(backend, ty, value, 0)?,
+        )),
+        _ => None,
+    }
+}
+
+/// A constant expression building `value` as a value of the Rust type
+/// generated for `ty`, or `None` if there isn't one.
+fn const_expression(backend: &mut Rust, ty: &Type, value: &Value, depth: usize) -> Option<String> {
+    if depth >= MAX_VALUE_DEPTH {
+        return None;
+    }
+
+    // References that aren't values can still be named numbers.
+    if let Value::Defined(DefinedValue::Simple(reference)) = value {
+        if let Some((_, referenced)) = backend.values.get(&reference.item).cloned() {
+            return const_expression(backend, ty, &referenced, depth + 1);
+        }
+    }
+
+    let expression = match (&ty.raw_type, value) {
+        (RawType::Referenced(reference), _) if reference.is_internal() => {
+            let name = &reference.item;
+            if backend.skipped.contains(name) {
+                return None;
+            }
+
+            let mut definition = backend.types.get(name)?.clone();
+            // Newtypes wrap the tagged type, and carry the tag themselves.
+            if let RawType::Builtin(BuiltinType::Prefixed(_, inner)) = &definition.raw_type {
+                definition = (**inner).clone();
+            }
+
+            if !backend.is_newtype(name) {
+                // Either an alias, or a struct that can't be built in a
+                // constant expression.
+                return match &definition.raw_type {
+                    RawType::Builtin(BuiltinType::Sequence(_))
+                    | RawType::Builtin(BuiltinType::Set(_)) => None,
+                    _ => const_expression(backend, &definition, value, depth + 1),
+                };
+            }
+
+            match (&definition.raw_type, value) {
+                (
+                    RawType::Builtin(BuiltinType::Integer(named_numbers)),
+                    Value::Integer(IntegerValue::Literal(value)),
+                ) if named_numbers
+                    .values()
+                    .any(|n| *n == Number::Literal(*value)) =>
+                {
+                    let (named, _) = named_numbers
+                        .iter()
+                        .find(|(_, n)| **n == Number::Literal(*value))?;
+                    format!("{}::{}", name, named.to_shouty_snake_case())
+                }
+                (
+                    RawType::Builtin(BuiltinType::Integer(named_numbers)),
+                    Value::Defined(DefinedValue::Simple(reference)),
+                ) if named_numbers.contains_key(&reference.item) => {
+                    format!("{}::{}", name, reference.item.to_shouty_snake_case())
+                }
+                _ => format!(
+                    "{}({})",
+                    name,
+                    const_expression(backend, &definition, value, depth + 1)?
+                ),
+            }
+        }
+        (RawType::Builtin(BuiltinType::Prefixed(_, inner)), _) => {
+            const_expression(backend, inner, value, depth + 1)?
+        }
+        (RawType::Builtin(BuiltinType::Boolean), Value::Boolean(value)) => value.to_string(),
+        (
+            RawType::Builtin(BuiltinType::Integer(_)),
+            Value::Integer(IntegerValue::Literal(value)),
+        ) => {
+            backend.import(&["rasn", "types", "Integer"]);
+            format!("Integer::from_i128({})", value)
+        }
+        (
+            RawType::Builtin(BuiltinType::OctetString),
+            Value::BitString(BitString::Literal(bits)),
+        ) => {
+            backend.import(&["rasn", "types", "OctetString"]);
+            format!(
+                "OctetString::from_static({})",
+                bytes(&octets(&parse_bits(bits)))
+            )
+        }
+        _ => return None,
+    };
+
+    Some(expression)
+}
+
+/// The Rust type and expression of `value` if it can be a `const`.
+fn const_value(backend: &mut Rust, ty: &Type, value: &Value) -> Option<(String, String)> {
+    let resolved = resolve_type(ty, &backend.types).raw_type.clone();
+
+    let generated = match (resolved, value) {
+        (RawType::Builtin(BuiltinType::Boolean), Value::Boolean(value)) => {
+            (String::from("bool"), value.to_string())
+        }
+        (
+            RawType::Builtin(BuiltinType::Integer(_)),
+            Value::Integer(IntegerValue::Literal(value)),
+        ) => (String::from("i64"), value.to_string()),
+        (RawType::Builtin(BuiltinType::CharacterString(_)), Value::CharacterString(value)) => {
+            (String::from("&str"), format!("{:?}", value))
+        }
+        (
+            RawType::Builtin(BuiltinType::OctetString),
+            Value::BitString(BitString::Literal(bits)),
+        ) => (String::from("&[u8]"), bytes(&octets(&parse_bits(bits)))),
+        (RawType::Builtin(BuiltinType::ObjectIdentifier), Value::ObjectIdentifier(oid)) => {
+            backend.import(&["rasn", "types", "ConstOid"]);
+            (String::from("ConstOid"), const_oid(oid)?)
+        }
+        // `BitString` can't be built in a constant expression, so its bits
+        // are generated instead.
+        (RawType::Builtin(BuiltinType::BitString(named_bits)), Value::BitString(bits)) => {
+            let bits = match bits {
+                BitString::Literal(literal) => parse_bits(literal),
+                BitString::List(names) => named_bit_list(&named_bits, names)?,
+                BitString::Containing(_) => return None,
+            };
+
+            (
+                String::from("&[bool]"),
+                format!("&[{}]", itertools::join(&bits, ", ")),
+            )
+        }
+        _ => return None,
+    };
+
+    Some(generated)
+}
+
+/// The Rust type of values of `ty` that are built by a function.
+fn value_type(backend: &mut Rust, ty: &Type) -> Option<String> {
+    match &resolve_type(ty, &backend.types).raw_type {
+        RawType::Builtin(BuiltinType::Sequence(_)) => sequence_name(ty),
+        _ => None,
+    }
+}
+
+/// An expression building `value` as a value of the Rust type generated for
+/// `ty`.
+fn value_expression(backend: &mut Rust, ty: &Type, value: &Value, depth: usize) -> Option<String> {
+    if depth >= MAX_VALUE_DEPTH {
+        return None;
+    }
+
+    // Values of newtypes and their aliases are built like constants.
+    if let RawType::Referenced(_) = &ty.raw_type {
+        if let Some(expression) = const_expression(backend, ty, value, depth) {
+            return Some(expression);
+        }
+    }
+
+    // References to other values are replaced with their value, as the
+    // referenced value might not be a `const`.
+    if let Value::Defined(DefinedValue::Simple(reference)) = value {
+        let (_, referenced) = backend.values.get(&reference.item)?.clone();
+        return value_expression(backend, ty, &referenced, depth + 1);
+    }
+
+    let resolved = resolve_type(ty, &backend.types).clone();
+
+    let expression = match (&resolved.raw_type, value) {
+        (RawType::Builtin(BuiltinType::Boolean), Value::Boolean(value)) => value.to_string(),
+        (
+            RawType::Builtin(BuiltinType::Integer(_)),
+            Value::Integer(IntegerValue::Literal(value)),
+        ) => {
+            backend.import(&["rasn", "types", "Integer"]);
+            format!("Integer::from({})", value)
+        }
+        (
+            RawType::Builtin(BuiltinType::OctetString),
+            Value::BitString(BitString::Literal(bits)),
+        ) => {
+            backend.import(&["rasn", "types", "OctetString"]);
+            format!(
+                "OctetString::from_static({})",
+                bytes(&octets(&parse_bits(bits)))
+            )
         }
+        (RawType::Builtin(BuiltinType::ObjectIdentifier), Value::ObjectIdentifier(oid)) => {
+            backend.import(&["rasn", "types", "ConstOid"]);
+            backend.import(&["rasn", "types", "ObjectIdentifier"]);
+            format!("ObjectIdentifier::from({})", const_oid(oid)?)
+        }
+        (RawType::Builtin(BuiltinType::Sequence(list)), Value::Sequence(values)) => {
+            sequence_expression(backend, ty, list, values, depth)?
+        }
+        _ => return None,
+    };
+
+    Some(expression)
+}
+
+/// The maximum nesting of values, to avoid looping on values that are
+/// defined in terms of themselves.
+const MAX_VALUE_DEPTH: usize = 32;
+
+/// A struct expression for a `SEQUENCE` value, or `None` if any of the
+/// components can't be generated.
+fn sequence_expression(
+    backend: &mut Rust,
+    ty: &Type,
+    list: &ComponentTypeList,
+    values: &[NamedValue],
+    depth: usize,
+) -> Option<String> {
+    if !value_type_supported(backend, ty, depth) {
+        return None;
+    }
+
+    // Extension additions aren't generated as fields yet.
+    if values.iter().any(|NamedValue(name, _)| {
+        !list
+            .components
+            .iter()
+            .flatten()
+            .filter_map(ComponentType::as_type)
+            .any(|(ty, _, _)| ty.name.as_ref() == Some(name))
+    }) {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+
+    for component in list.components.iter().flatten() {
+        let (component_ty, optional, default) = component.as_type()?;
+        let name = component_ty.name.as_ref()?;
+        let value = values
+            .iter()
+            .find(|NamedValue(value_name, _)| value_name == name)
+            .map(|NamedValue(_, value)| value);
+
+        let field = match (value, default) {
+            (Some(value), _) if *optional => {
+                format!(
+                    "Some({})",
+                    value_expression(backend, component_ty, value, depth + 1)?
+                )
+            }
+            (Some(value), _) | (None, Some(value)) => {
+                value_expression(backend, component_ty, value, depth + 1)?
+            }
+            (None, None) if *optional => String::from("None"),
+            (None, None) => return None,
+        };
+
+        fields.push(format!("{}: {}", name.to_snake_case(), field));
+    }
+
+    Some(format!(
+        "{} {{ {} }}",
+        sequence_name(ty)?,
+        fields.join(", ")
+    ))
+}
+
+/// Whether values of `ty` can be generated, which is only the case if the
+/// code generator supports `ty`.
+fn value_type_supported(backend: &Rust, ty: &Type, depth: usize) -> bool {
+    if depth >= MAX_VALUE_DEPTH {
+        return false;
+    }
+
+    match &resolve_type(ty, &backend.types).raw_type {
+        RawType::Builtin(BuiltinType::Boolean)
+        | RawType::Builtin(BuiltinType::Integer(_))
+        | RawType::Builtin(BuiltinType::OctetString)
+        | RawType::Builtin(BuiltinType::ObjectIdentifier) => true,
+        RawType::Builtin(BuiltinType::Sequence(list)) => {
+            list.components
+                .iter()
+                .flatten()
+                .all(|component| match component.as_type() {
+                    Some((ty, _, _)) => value_type_supported(backend, ty, depth + 1),
+                    None => false,
+                })
+        }
+        _ => false,
+    }
+}
+
+/// The name of the struct generated for a `SEQUENCE` type, which is the
+/// name of the last type referenced, or the component's name for types that
+/// are defined inline.
+fn sequence_name(ty: &Type) -> Option<String> {
+    match &ty.raw_type {
+        RawType::Referenced(reference) if reference.is_internal() => Some(reference.item.clone()),
+        RawType::Builtin(BuiltinType::Prefixed(_, inner)) if inner.name.is_none() => {
+            sequence_name(inner).or_else(|| ty.name.as_ref().map(|name| name.to_camel_case()))
+        }
+        _ => ty.name.as_ref().map(|name| name.to_camel_case()),
+    }
+}
+
+/// The bits that are set in a list of named bits.
+fn named_bit_list(named_bits: &BTreeMap<String, Number>, names: &[String]) -> Option<Vec<bool>> {
+    let positions = names
+        .iter()
+        .map(|name| match named_bits.get(name)? {
+            Number::Literal(position) => usize::try_from(*position).ok(),
+            Number::DefinedValue(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut bits = vec![false; positions.iter().max().map_or(0, |max| max + 1)];
+    for position in positions {
+        bits[position] = true;
+    }
+
+    Some(bits)
+}
+
+fn parse_bits(literal: &str) -> Vec<bool> {
+    literal.chars().map(|bit| bit == '1').collect()
+}
+
+/// Packs bits into octets, padding the last octet with zeros.
+fn octets(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |octet, (i, &bit)| octet | ((bit as u8) << (7 - i)))
+        })
+        .collect()
+}
+
+fn bytes(octets: &[u8]) -> String {
+    format!(
+        "&[{}]",
+        itertools::join(octets.iter().map(|octet| format!("0x{:02X}", octet)), ", ")
+    )
+}
+
+/// A `ConstOid` expression for an object identifier whose arcs have been
+/// resolved to numbers.
+fn const_oid(oid: &ObjectIdentifier) -> Option<String> {
+    let arcs = oid
+        .iter()
+        .map(|component| match component {
+            ObjIdComponent::Number(Number::Literal(arc)) => Some(arc.to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!("ConstOid(&[{}])", arcs.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::Parser, semantics::SemanticChecker};
+
+    /// Generates each value assignment in `assignments`, in order.
+    fn generate(assignments: &str) -> Vec<Option<String>> {
+        let source = format!("Test DEFINITIONS ::= BEGIN\n{}\nEND\n", assignments);
+        let (module, _) = Parser::parse(&source).unwrap();
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
+        assert!(checker.diagnostics.is_empty());
+
+        let mut backend = Rust {
+            types: checker.table.types.clone(),
+            values: checker.table.values.clone(),
+            ..Rust::default()
+        };
+        let mut names: Vec<_> = checker.table.values.keys().collect();
+        names.sort_by_key(|name| source.find(&format!("\n{} ", name)));
+
+        names
+            .into_iter()
+            .map(|name| {
+                let (ty, value) = checker.table.values[name].clone();
+                Constant::new(Visibility::Public, name.clone(), ty, value, None)
+                    .generate(&mut backend)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            vec![
+                Some(String::from("pub const FLAG: bool = true;")),
+                Some(String::from("pub const MAX_SIZE: i64 = 256;")),
+                Some(String::from("pub const GREETING: &str = \"hi\";")),
+                Some(String::from("pub const MAGIC: &[u8] = &[0xCA, 0xFE];")),
+            ],
+            generate(
+                "flag BOOLEAN ::= TRUE\n\
+                 max-size INTEGER ::= 256\n\
+                 greeting UTF8String ::= \"hi\"\n\
+                 magic OCTET STRING ::= 'CAFE'H"
+            )
+        );
+    }
+
+    #[test]
+    fn object_identifiers() {
+        assert_eq!(
+            vec![
+                Some(String::from(
+                    "pub const ID_PKIX: ConstOid = ConstOid(&[1, 3, 6, 1, 5, 5, 7]);"
+                )),
+                Some(String::from(
+                    "pub const ID_PE: ConstOid = ConstOid(&[1, 3, 6, 1, 5, 5, 7, 1]);"
+                )),
+            ],
+            generate(
+                "id-pkix OBJECT IDENTIFIER ::= { iso(1) identified-organization(3) 6 1 5 5 7 }\n\
+                 id-pe OBJECT IDENTIFIER ::= { id-pkix 1 }"
+            )
+        );
+    }
+
+    #[test]
+    fn sequence_values() {
+        assert_eq!(
+            Some(String::from(
+                "pub fn pair() -> Pair {\n\tPair { a: Integer::from(3), b: None }\n}"
+            )),
+            generate(
+                "Pair ::= SEQUENCE { a INTEGER, b BOOLEAN OPTIONAL }\n\
+                 pair Pair ::= { a 3 }"
+            )
+            .remove(0)
+        );
+    }
+
+    #[test]
+    fn named_bits() {
+        assert_eq!(
+            Some(String::from(
+                "pub const FLAGS: &[bool] = &[true, false, true];"
+            )),
+            generate(
+                "Flags ::= BIT STRING { a(0), b(1), c(2) }\n\
+                 flags Flags ::= { a, c }"
+            )
+            .remove(0)
+        );
     }
 
-    pub fn generate(self, backend: &mut Rust) -> Fallible<String> {
-        use heck::ShoutySnakeCase;
+    #[test]
+    fn unsupported_values_are_skipped() {
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      Color ::= ENUMERATED { red, green }\n\
+                      color Color ::= red\n\
+                      END\n";
+        let (module, _) = Parser::parse(source).unwrap();
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
 
-        let (ty, _) = backend.generate_type(&self.ty, None)?;
+        let mut backend = Rust {
+            types: checker.table.types.clone(),
+            ..Rust::default()
+        };
+        let (ty, value) = checker.table.values["color"].clone();
+        let constant = Constant::new(Visibility::Public, String::from("color"), ty, value, None);
 
-        Ok(format!(
-            "{vis}const {name}: {ty} = {value};",
-            vis = self.visibility,
-            name = self.name.to_shouty_snake_case(),
-            ty = ty,
-            value = backend.generate_value(&self.value)?,
-        ))
+        assert_eq!(None, constant.generate(&mut backend));
+        assert_eq!(1, backend.diagnostics.len());
     }
 }
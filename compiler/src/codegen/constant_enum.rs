@@ -1,5 +1,5 @@
 use failure::Fallible;
-use heck::CamelCase;
+use heck::{CamelCase, ShoutySnakeCase};
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
@@ -63,27 +63,53 @@ impl ConstantEnum {
         }
     }
 
-    pub fn generate(self) -> String {
-        format!(
-            r#"
-#[non_exhaustive]
-{vis}struct {name};
-impl {name} {{
-{variants}
-}}"#,
-            vis = self.visibility,
-            name = self.name.to_camel_case(),
-            variants = self
-                .variants
-                .iter()
-                .map(|triple| format!(
-                    "\t{}{}: {} = {};",
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Generates the named values as associated constants. The constants of
+    /// a `newtype` wrap their value, otherwise a unit struct is generated to
+    /// hold them.
+    pub fn generate(self, newtype: bool) -> String {
+        let name = self.name.to_camel_case();
+        let variants = self
+            .variants
+            .iter()
+            .sorted_by(|a, b| a.key.cmp(&b.key))
+            .map(|triple| {
+                let (ty, value) = if newtype {
+                    (
+                        String::from("Self"),
+                        format!("Self({}::from_i128({}))", triple.value_type, triple.value),
+                    )
+                } else {
+                    (
+                        triple.value_type.clone(),
+                        format!("{}::from_i128({})", triple.value_type, triple.value),
+                    )
+                };
+
+                format!(
+                    "\t{}const {}: {} = {};",
                     self.visibility,
-                    triple.key.to_camel_case(),
-                    triple.value_type.to_camel_case(),
-                    triple.value
-                ))
-                .join("\n"),
+                    triple.key.to_shouty_snake_case(),
+                    ty,
+                    value
+                )
+            })
+            .join("\n");
+
+        let declaration = if newtype {
+            String::new()
+        } else {
+            format!("#[non_exhaustive]\n{}struct {};\n", self.visibility, name)
+        };
+
+        format!(
+            "\n{declaration}impl {name} {{\n{variants}\n}}",
+            declaration = declaration,
+            name = name,
+            variants = variants,
         )
     }
 }
@@ -0,0 +1,438 @@
+use std::cmp;
+
+use crate::parser::*;
+
+use super::structs::Rasn;
+
+/// An inclusive range, where `None` is an unbounded end.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Range {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl Range {
+    fn single(value: i64) -> Self {
+        Self {
+            min: Some(value),
+            max: Some(value),
+        }
+    }
+
+    fn intersect(self, other: Self) -> Self {
+        let min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(cmp::max(a, b)),
+            (a, b) => a.or(b),
+        };
+        let max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, b) => a.or(b),
+        };
+
+        Self { min, max }
+    }
+
+    fn union(self, other: Self) -> Self {
+        let min = self.min.and_then(|a| other.min.map(|b| cmp::min(a, b)));
+        let max = self.max.and_then(|a| other.max.map(|b| cmp::max(a, b)));
+
+        Self { min, max }
+    }
+
+    fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    /// Formats the range in the syntax accepted by `#[rasn(size, value)]`.
+    fn to_attribute(&self) -> String {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => format!("\"{}\"", min),
+            (Some(min), Some(max)) => format!("\"{}..={}\"", min, max),
+            (Some(min), None) => format!("\"{}..\"", min),
+            (None, Some(max)) => format!("\"..={}\"", max),
+            (None, None) => String::from("\"..\""),
+        }
+    }
+}
+
+/// The PER-visible constraints of a type, as used by the `rasn` derive
+/// macros.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EffectiveConstraints {
+    pub value: Option<(Range, bool)>,
+    pub size: Option<(Range, bool)>,
+    pub from: Option<(Vec<(char, char)>, bool)>,
+}
+
+/// The constraints found in a single element set. Sizes and alphabets are
+/// constrained by element sets of their own, and carry whether those are
+/// extensible.
+#[derive(Clone, Debug, Default)]
+struct SetConstraints {
+    value: Option<Range>,
+    size: Option<(Range, bool)>,
+    from: Option<(Vec<(char, char)>, bool)>,
+}
+
+impl SetConstraints {
+    /// An intersection is only extensible if everything in it is.
+    fn intersect(self, other: Self) -> Self {
+        Self {
+            value: merge(self.value, other.value, Range::intersect),
+            size: merge(self.size, other.size, |(a, a_ext), (b, b_ext)| {
+                (a.intersect(b), a_ext && b_ext)
+            }),
+            from: merge(self.from, other.from, |(a, a_ext), (b, b_ext)| {
+                (intersect_alphabets(a, b), a_ext && b_ext)
+            }),
+        }
+    }
+
+    /// A union is extensible if anything in it is.
+    fn union(self, other: Self) -> Self {
+        Self {
+            value: self.value.and_then(|a| other.value.map(|b| a.union(b))),
+            size: self
+                .size
+                .and_then(|(a, a_ext)| other.size.map(|(b, b_ext)| (a.union(b), a_ext || b_ext))),
+            from: self.from.and_then(|(mut a, a_ext)| {
+                other.from.map(|(b, b_ext)| {
+                    a.extend(b);
+                    (a, a_ext || b_ext)
+                })
+            }),
+        }
+    }
+}
+
+/// The characters that are in both `a` and `b`.
+fn intersect_alphabets(a: Vec<(char, char)>, b: Vec<(char, char)>) -> Vec<(char, char)> {
+    a.iter()
+        .flat_map(|&(a_start, a_end)| {
+            b.iter().filter_map(move |&(b_start, b_end)| {
+                let start = cmp::max(a_start, b_start);
+                let end = cmp::min(a_end, b_end);
+
+                (start <= end).then(|| (start, end))
+            })
+        })
+        .collect()
+}
+
+fn merge<T>(a: Option<T>, b: Option<T>, f: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+impl EffectiveConstraints {
+    /// Computes the effective constraints of `constraints` applied serially,
+    /// ignoring any constraints that aren't PER-visible.
+    pub fn new(constraints: &[Constraint]) -> Self {
+        let mut effective = Self::default();
+
+        for constraint in constraints {
+            let (set, extensible) = match element_set(constraint) {
+                Some(set) => set,
+                None => continue,
+            };
+            let set = evaluate_set(set, false);
+
+            // The extension marker of the constraint applies to its values,
+            // and to the sizes and alphabets in it, which can also be
+            // extensible by themselves.
+            if let Some(value) = set.value {
+                effective.value = Some(match effective.value {
+                    Some((range, _)) => (range.intersect(value), extensible),
+                    None => (value, extensible),
+                });
+            }
+
+            if let Some((size, size_extensible)) = set.size {
+                let extensible = extensible || size_extensible;
+                effective.size = Some(match effective.size {
+                    Some((range, _)) => (range.intersect(size), extensible),
+                    None => (size, extensible),
+                });
+            }
+
+            if let Some((from, from_extensible)) = set.from {
+                let extensible = extensible || from_extensible;
+                effective.from = Some(match effective.from {
+                    Some((alphabet, _)) => (intersect_alphabets(alphabet, from), extensible),
+                    None => (from, extensible),
+                });
+            }
+        }
+
+        effective
+    }
+
+    /// The constraints as `#[rasn(..)]` attributes.
+    pub fn attributes(&self) -> Vec<Rasn> {
+        let mut attributes = Vec::new();
+
+        if let Some((range, extensible)) = self.size.filter(|(r, _)| !r.is_unbounded()) {
+            attributes.push(Rasn::Size(range.to_attribute(), extensible));
+        }
+
+        if let Some((range, extensible)) = self.value.filter(|(r, _)| !r.is_unbounded()) {
+            attributes.push(Rasn::Value(range.to_attribute(), extensible));
+        }
+
+        if let Some((ranges, extensible)) = &self.from {
+            let ranges = ranges
+                .iter()
+                .map(|&(start, end)| {
+                    if start == end {
+                        format!("{:?}", start.to_string())
+                    } else {
+                        format!("{:?}", format!("{}..={}", start, end))
+                    }
+                })
+                .collect();
+
+            attributes.push(Rasn::From(ranges, *extensible));
+        }
+
+        attributes
+    }
+}
+
+/// Evaluates a union of intersections of elements. Character values are only
+/// meaningful in the `alphabet` of a `FROM` constraint.
+fn evaluate_set(set: &[Vec<Element>], alphabet: bool) -> SetConstraints {
+    set.iter()
+        .map(|intersections| {
+            intersections
+                .iter()
+                .map(|element| evaluate_element(element, alphabet))
+                .fold(SetConstraints::default(), SetConstraints::intersect)
+        })
+        .fold(None, |acc: Option<SetConstraints>, set| match acc {
+            Some(acc) => Some(acc.union(set)),
+            None => Some(set),
+        })
+        .unwrap_or_default()
+}
+
+fn evaluate_element(element: &Element, alphabet: bool) -> SetConstraints {
+    match element {
+        Element::ElementSet(set) => evaluate_set(set, alphabet),
+        Element::SubType(SubTypeElement::Value(value)) if alphabet => SetConstraints {
+            from: characters(value).map(|alphabet| (alphabet, false)),
+            ..SetConstraints::default()
+        },
+        Element::SubType(SubTypeElement::Value(value)) => SetConstraints {
+            value: integer(value).map(Range::single),
+            ..SetConstraints::default()
+        },
+        Element::SubType(SubTypeElement::Range(low, high)) if alphabet => SetConstraints {
+            from: range_character(low)
+                .and_then(|low| range_character(high).map(|high| (vec![(low, high)], false))),
+            ..SetConstraints::default()
+        },
+        Element::SubType(SubTypeElement::Range(low, high)) => {
+            let min = match low {
+                RangeValue::Value(value, exclusive) => {
+                    integer(value).map(|v| v + *exclusive as i64)
+                }
+                _ => None,
+            };
+            let max = match high {
+                RangeValue::Value(value, exclusive) => {
+                    integer(value).map(|v| v - *exclusive as i64)
+                }
+                _ => None,
+            };
+
+            SetConstraints {
+                value: Some(Range { min, max }),
+                ..SetConstraints::default()
+            }
+        }
+        Element::SubType(SubTypeElement::Size(constraint)) => SetConstraints {
+            size: element_set(constraint).and_then(|(set, extensible)| {
+                evaluate_set(set, false)
+                    .value
+                    .map(|range| (range, extensible))
+            }),
+            ..SetConstraints::default()
+        },
+        Element::SubType(SubTypeElement::PermittedAlphabet(constraint)) => SetConstraints {
+            from: element_set(constraint).and_then(|(set, extensible)| {
+                evaluate_set(set, true)
+                    .from
+                    .map(|(alphabet, _)| (alphabet, extensible))
+            }),
+            ..SetConstraints::default()
+        },
+        _ => SetConstraints::default(),
+    }
+}
+
+/// The element set of `constraint`, and whether it's extensible.
+fn element_set(constraint: &Constraint) -> Option<(&[Vec<Element>], bool)> {
+    match constraint {
+        Constraint::ElementSet(set, extensible) => Some((set, *extensible)),
+        Constraint::General(_) => None,
+    }
+}
+
+fn integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(IntegerValue::Literal(number)) => Some(*number),
+        _ => None,
+    }
+}
+
+fn characters(value: &Value) -> Option<Vec<(char, char)>> {
+    match value {
+        Value::CharacterString(string) => Some(string.chars().map(|c| (c, c)).collect()),
+        _ => None,
+    }
+}
+
+fn range_character(bound: &RangeValue) -> Option<char> {
+    match bound {
+        RangeValue::Value(Value::CharacterString(string), _) if string.chars().count() == 1 => {
+            string.chars().next()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(low: i64, high: i64) -> Element {
+        Element::SubType(SubTypeElement::Range(
+            RangeValue::Value(Value::Integer(IntegerValue::Literal(low)), false),
+            RangeValue::Value(Value::Integer(IntegerValue::Literal(high)), false),
+        ))
+    }
+
+    #[test]
+    fn serial_value_constraints() {
+        let constraints = [
+            Constraint::ElementSet(vec![vec![range(0, 255)]], false),
+            Constraint::ElementSet(vec![vec![range(10, 300)]], true),
+        ];
+
+        let effective = EffectiveConstraints::new(&constraints);
+
+        assert_eq!(
+            Some((
+                Range {
+                    min: Some(10),
+                    max: Some(255)
+                },
+                true
+            )),
+            effective.value
+        );
+        assert_eq!(None, effective.size);
+    }
+
+    #[test]
+    fn union_of_sizes() {
+        let size = |low, high| {
+            Element::SubType(SubTypeElement::Size(Constraint::ElementSet(
+                vec![vec![range(low, high)]],
+                false,
+            )))
+        };
+        let constraints = [Constraint::ElementSet(
+            vec![vec![size(1, 4)], vec![size(8, 16)]],
+            false,
+        )];
+
+        let attributes = EffectiveConstraints::new(&constraints).attributes();
+
+        assert_eq!(
+            "size(\"1..=16\")",
+            attributes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<String>()
+        );
+    }
+
+    #[test]
+    fn extensibility_of_sizes_and_alphabets() {
+        let character = |c: &str| RangeValue::Value(Value::CharacterString(c.to_owned()), false);
+        // SIZE (1..64, ...) ^ FROM ("a".."z")
+        let elements = vec![vec![
+            Element::SubType(SubTypeElement::Size(Constraint::ElementSet(
+                vec![vec![range(1, 64)]],
+                true,
+            ))),
+            Element::SubType(SubTypeElement::PermittedAlphabet(Constraint::ElementSet(
+                vec![vec![Element::SubType(SubTypeElement::Range(
+                    character("a"),
+                    character("z"),
+                ))]],
+                false,
+            ))),
+        ]];
+
+        let attributes = |extensible| {
+            EffectiveConstraints::new(&[Constraint::ElementSet(elements.clone(), extensible)])
+                .attributes()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<String>()
+        };
+
+        assert_eq!(
+            "size(\"1..=64\", extensible)from(\"a..=z\")",
+            attributes(false)
+        );
+        assert_eq!(
+            "size(\"1..=64\", extensible)from(\"a..=z\", extensible)",
+            attributes(true)
+        );
+    }
+
+    #[test]
+    fn intersection_of_alphabets() {
+        let character = |c: &str| RangeValue::Value(Value::CharacterString(c.to_owned()), false);
+        let from = |elements: Vec<Vec<Element>>| {
+            Element::SubType(SubTypeElement::PermittedAlphabet(Constraint::ElementSet(
+                elements, false,
+            )))
+        };
+        let lowercase = from(vec![
+            vec![Element::SubType(SubTypeElement::Range(
+                character("a"),
+                character("z"),
+            ))],
+            vec![Element::SubType(SubTypeElement::Value(
+                Value::CharacterString(String::from("-")),
+            ))],
+        ]);
+        let hex = from(vec![
+            vec![Element::SubType(SubTypeElement::Range(
+                character("0"),
+                character("9"),
+            ))],
+            vec![Element::SubType(SubTypeElement::Range(
+                character("a"),
+                character("f"),
+            ))],
+        ]);
+        let constraints = [Constraint::ElementSet(vec![vec![lowercase, hex]], false)];
+
+        let attributes = EffectiveConstraints::new(&constraints).attributes();
+
+        assert_eq!(
+            "from(\"a..=f\")",
+            attributes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<String>()
+        );
+    }
+}
@@ -7,6 +7,8 @@ pub struct Struct {
     name: String,
     fields: Vec<Field>,
     attributes: Vec<Attribute>,
+    /// The type wrapped by a newtype struct.
+    newtype: Option<String>,
 }
 
 impl Struct {
@@ -25,9 +27,26 @@ impl Struct {
                 Derive::Ord,
                 Derive::Debug,
             ])],
+            newtype: None,
         }
     }
 
+    /// Creates a newtype struct wrapping `ty`.
+    pub fn newtype<I: Into<String>>(visibility: Visibility, name: I, ty: String) -> Self {
+        Self {
+            newtype: Some(ty),
+            ..Self::new(visibility, name)
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_newtype(&self) -> bool {
+        self.newtype.is_some()
+    }
+
     pub fn add_field(&mut self, field: Field) {
         self.fields.push(field);
     }
@@ -36,6 +55,14 @@ impl Struct {
         self.attributes.push(Attribute::Rasn(attributes));
         self
     }
+
+    /// Derives `derives` in addition to the default derives.
+    pub fn add_derives(mut self, derives: &[String]) -> Self {
+        if let Some(Attribute::Derive(existing)) = self.attributes.first_mut() {
+            existing.extend(derives.iter().cloned().map(Derive::Other));
+        }
+        self
+    }
 }
 
 impl fmt::Display for Struct {
@@ -45,6 +72,14 @@ impl fmt::Display for Struct {
             writeln!(f)?;
         }
 
+        if let Some(ty) = &self.newtype {
+            return writeln!(
+                f,
+                "{}struct {}({}{});",
+                self.visibility, self.name, self.visibility, ty
+            );
+        }
+
         writeln!(f, "{}struct {} {{", self.visibility, self.name)?;
 
         if !self.fields.is_empty() {
@@ -186,6 +221,8 @@ pub enum Derive {
     Eq,
     Ord,
     Debug,
+    /// A user supplied derive, e.g. `Clone` or `serde::Serialize`.
+    Other(String),
 }
 
 impl fmt::Display for Derive {
@@ -199,6 +236,7 @@ impl fmt::Display for Derive {
             Derive::Eq => "Eq",
             Derive::Ord => "Ord",
             Derive::Debug => "Debug",
+            Derive::Other(derive) => derive,
         };
 
         derive.fmt(f)
@@ -208,12 +246,22 @@ impl fmt::Display for Derive {
 pub enum Rasn {
     Type(&'static str),
     Prefix(Prefix),
+    /// A `SIZE` constraint's range, and whether it's extensible.
+    Size(String, bool),
+    /// A value constraint's range, and whether it's extensible.
+    Value(String, bool),
+    /// A permitted alphabet's characters and ranges, and whether it's
+    /// extensible.
+    From(Vec<String>, bool),
 }
 
 impl fmt::Display for Rasn {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let rasn = match self {
             Rasn::Type(type_name) => type_name.to_string(),
+            Rasn::Size(range, extensible) => constraint("size", &[range.clone()], *extensible),
+            Rasn::Value(range, extensible) => constraint("value", &[range.clone()], *extensible),
+            Rasn::From(ranges, extensible) => constraint("from", ranges, *extensible),
             Rasn::Prefix(prefix) => match prefix.kind {
                 TagKind::Explicit => format!(
                     "tag(explicit({class}{number}))",
@@ -245,3 +293,9 @@ impl fmt::Display for Rasn {
         rasn.fmt(f)
     }
 }
+
+fn constraint(name: &str, arguments: &[String], extensible: bool) -> String {
+    let extensible = if extensible { ", extensible" } else { "" };
+
+    format!("{}({}{})", name, arguments.join(", "), extensible)
+}
@@ -0,0 +1,230 @@
+//! Errors and warnings found while compiling a module, with their location
+//! in the source.
+
+use std::{fmt, path::Path};
+
+use asn1_pest::Rule;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A range of bytes in the source.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<'a> From<pest::Span<'a>> for Span {
+    fn from(span: pest::Span<'a>) -> Self {
+        Self::new(span.start(), span.end())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    pub fn error<I: Into<String>>(message: I, span: Option<Span>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn warning<I: Into<String>>(message: I, span: Option<Span>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Renders the diagnostic with the line, column and snippet of `source`
+    /// that it points at.
+    pub fn render(&self, path: &Path, source: &str) -> String {
+        let mut output = format!("{}: {}\n", self.severity, self.message);
+
+        let span = match self.span {
+            Some(span) => span,
+            None => {
+                output.push_str(&format!(" --> {}\n", path.display()));
+                return output;
+            }
+        };
+
+        let start = span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let line_number = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count() + 1;
+        let line = source[line_start..line_end].trim_end_matches('\r');
+
+        // Only underline the first line of multi-line spans.
+        let end = span.end.min(line_end).max(start);
+        let width = source[start..end].chars().count().max(1);
+        let gutter = " ".repeat(line_number.to_string().len());
+        let padding: String = source[line_start..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        output.push_str(&format!(
+            "{gutter}--> {path}:{line_number}:{column}\n\
+             {gutter} |\n\
+             {line_number} | {line}\n\
+             {gutter} | {padding}{underline}\n",
+            gutter = gutter,
+            path = path.display(),
+            line_number = line_number,
+            column = column,
+            line = line,
+            padding = padding,
+            underline = "^".repeat(width),
+        ));
+
+        output
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+impl From<pest::error::Error<Rule>> for Diagnostic {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        let span = match error.location {
+            pest::error::InputLocation::Pos(position) => Span::new(position, position),
+            pest::error::InputLocation::Span((start, end)) => Span::new(start, end),
+        };
+
+        let message = match &error.variant {
+            pest::error::ErrorVariant::ParsingError { positives, .. } if !positives.is_empty() => {
+                format!(
+                    "syntax error, expected {}",
+                    itertools::join(positives.iter().map(|r| format!("{:?}", r)), " or ")
+                )
+            }
+            pest::error::ErrorVariant::ParsingError { .. } => String::from("syntax error"),
+            pest::error::ErrorVariant::CustomError { message } => message.clone(),
+        };
+
+        Diagnostic::error(message, Some(span))
+    }
+}
+
+/// A collection of diagnostics.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.0.push(diagnostic);
+    }
+
+    pub fn error<I: Into<String>>(&mut self, message: I, span: Option<Span>) {
+        self.push(Diagnostic::error(message, span));
+    }
+
+    pub fn warning<I: Into<String>>(&mut self, message: I, span: Option<Span>) {
+        self.push(Diagnostic::warning(message, span));
+    }
+
+    pub fn extend<I: IntoIterator<Item = Diagnostic>>(&mut self, diagnostics: I) {
+        self.0.extend(diagnostics);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.0.iter().any(Diagnostic::is_error)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.0.iter()
+    }
+
+    /// Renders every diagnostic, ordered by their position in `source`.
+    pub fn render(&self, path: &Path, source: &str) -> String {
+        let mut diagnostics: Vec<_> = self.0.iter().collect();
+        diagnostics.sort_by_key(|d| d.span.map_or(usize::MAX, |s| s.start));
+
+        itertools::join(diagnostics.iter().map(|d| d.render(path, source)), "\n")
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = std::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_points_at_span() {
+        let source = "Test DEFINITIONS ::= BEGIN\n    A ::= Missing\nEND\n";
+        let start = source.find("Missing").unwrap();
+        let diagnostic = Diagnostic::error(
+            "unresolved reference `Missing`",
+            Some(Span::new(start, start + "Missing".len())),
+        );
+
+        assert_eq!(
+            "error: unresolved reference `Missing`\n \
+             --> test.asn1:2:11\n  \
+             |\n\
+             2 |     A ::= Missing\n  \
+             |           ^^^^^^^\n",
+            diagnostic.render(Path::new("test.asn1"), source)
+        );
+    }
+}
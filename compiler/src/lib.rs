@@ -4,19 +4,30 @@
 extern crate log;
 
 mod codegen;
+pub mod diagnostics;
 mod parser;
 mod registry;
 mod semantics;
 
-use std::{fs, path::PathBuf};
+use std::{fs, io::Write, mem, path::PathBuf};
+
+use heck::SnakeCase;
 
 use self::{codegen::*, parser::Parser, semantics::*};
 
+pub use self::{
+    codegen::Unsupported,
+    diagnostics::{Diagnostic, Diagnostics},
+};
+
 pub type Result<T> = std::result::Result<T, failure::Error>;
 
 pub struct NotationCompiler {
     path: PathBuf,
     dependencies: Option<PathBuf>,
+    unsupported: Unsupported,
+    derives: Vec<String>,
+    feature: Option<String>,
 }
 
 impl NotationCompiler {
@@ -24,6 +35,9 @@ impl NotationCompiler {
         Self {
             path: path.into(),
             dependencies: None,
+            unsupported: Unsupported::default(),
+            derives: Vec::new(),
+            feature: None,
         }
     }
 
@@ -32,17 +46,155 @@ impl NotationCompiler {
         self
     }
 
-    pub fn build(self) -> Result<String> {
+    /// Sets what is generated for types that aren't supported yet. The
+    /// default is to skip them.
+    pub fn unsupported(mut self, unsupported: Unsupported) -> Self {
+        self.unsupported = unsupported;
+        self
+    }
+
+    /// Adds a trait to derive on the generated types, in addition to the
+    /// `rasn` traits.
+    pub fn derive<I: Into<String>>(mut self, derive: I) -> Self {
+        self.derives.push(derive.into());
+        self
+    }
+
+    /// Only compiles the generated module when `feature` is enabled, by
+    /// wrapping it in a `#[cfg(feature = "...")] pub mod name { ... }` named
+    /// after the ASN.1 module, so the output of several modules can be
+    /// concatenated.
+    pub fn feature<I: Into<String>>(mut self, feature: I) -> Self {
+        self.feature = Some(feature.into());
+        self
+    }
+
+    /// Compiles the module, collecting every problem found along the way.
+    /// Only fails if the module couldn't be read.
+    pub fn compile(self) -> Result<Compilation> {
+        self.run(true)
+    }
+
+    /// Parses and checks the module without generating any code.
+    pub fn check(self) -> Result<Compilation> {
+        self.run(false)
+    }
+
+    fn run(self, generate: bool) -> Result<Compilation> {
         let source = fs::read_to_string(&self.path)?;
-        let ast = Parser::parse(&source)?;
+        let mut compilation = Compilation {
+            output: None,
+            diagnostics: Diagnostics::new(),
+            module: None,
+            types: Vec::new(),
+            path: self.path,
+            source: String::new(),
+        };
+
+        let ast = match Parser::parse(&source) {
+            Ok((ast, diagnostics)) => {
+                compilation.diagnostics.extend(diagnostics);
+                ast
+            }
+            Err(error) => {
+                compilation.diagnostics.push(error);
+                compilation.source = source;
+                return Ok(compilation);
+            }
+        };
+        let ast_name = ast.identifier.name.clone();
+        compilation.module = Some(ast_name.clone());
 
         let mut fixed_tree = SemanticChecker::new(ast);
         fixed_tree.build()?;
+        compilation
+            .diagnostics
+            .extend(mem::take(&mut fixed_tree.diagnostics));
+        compilation.types = fixed_tree
+            .table
+            .types
+            .iter()
+            .map(|(name, ty)| (name.clone(), ty.raw_type.to_string()))
+            .collect();
+
+        if generate && !compilation.diagnostics.has_errors() {
+            let mut output = Vec::new();
+
+            let diagnostics = CodeGenerator::<Vec<u8>, Rust>::new(fixed_tree, &mut output)
+                .unsupported(self.unsupported)
+                .derives(self.derives)
+                .generate()?;
 
-        let mut output = Vec::new();
+            let mut output = String::from_utf8(output).unwrap();
+            if let Some(feature) = &self.feature {
+                output = feature_module(feature, &ast_name, &output);
+            }
 
-        CodeGenerator::<Vec<u8>, Rust>::new(fixed_tree, &mut output).generate()?;
+            compilation.diagnostics.extend(diagnostics);
+            compilation.output = Some(output);
+        }
+
+        compilation.source = source;
+        Ok(compilation)
+    }
+
+    /// Compiles the module, logging any warnings, and failing with the
+    /// rendered errors if there were any.
+    pub fn build(self) -> Result<String> {
+        let mut compilation = self.compile()?;
+
+        match compilation.output.take() {
+            Some(output) => {
+                if !compilation.diagnostics.is_empty() {
+                    warn!("{}", compilation.render_diagnostics());
+                }
+
+                Ok(output)
+            }
+            None => Err(failure::err_msg(compilation.render_diagnostics())),
+        }
+    }
+}
+
+/// Wraps `code` in a module named after `module`, that's only compiled when
+/// `feature` is enabled.
+fn feature_module(feature: &str, module: &str, code: &str) -> String {
+    let mut output = format!(
+        "#[cfg(feature = {:?})]\npub mod {} {{\n",
+        feature,
+        module.to_snake_case()
+    );
+
+    for line in code.lines() {
+        if !line.is_empty() {
+            output.push('\t');
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+/// The result of compiling a module.
+pub struct Compilation {
+    /// The generated code, or `None` if the module has errors or was only
+    /// checked.
+    pub output: Option<String>,
+    pub diagnostics: Diagnostics,
+    /// The module's name, if it could be parsed.
+    pub module: Option<String>,
+    /// The name of each type defined in the module, and the type it's
+    /// defined as.
+    pub types: Vec<(String, String)>,
+    path: PathBuf,
+    source: String,
+}
 
-        Ok(String::from_utf8(output).unwrap())
+impl Compilation {
+    /// Renders the diagnostics with the parts of the source they point at.
+    pub fn render_diagnostics(&self) -> String {
+        self.diagnostics.render(&self.path, &self.source)
     }
 }
@@ -1,19 +1,67 @@
-use clap::{clap_app, crate_description, crate_version};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use clap::{clap_app, crate_description, crate_version, ArgMatches, SubCommand};
+use heck::SnakeCase;
 use log::{debug, LevelFilter};
 
-use rasn_compiler::NotationCompiler;
+use rasn_compiler::{Compilation, NotationCompiler, Unsupported};
+
+/// The module had errors, or warnings with `--deny-warnings`.
+const EXIT_DIAGNOSTICS: i32 = 1;
+/// A file couldn't be read or written.
+const EXIT_IO: i32 = 2;
 
 fn main() {
     let matches = clap_app!(casn1 =>
         (version: crate_version!())
         (about: crate_description!())
+        (@setting SubcommandRequiredElseHelp)
+        (@setting VersionlessSubcommands)
         (@arg dependencies: -d --dependencies
             +takes_value
+            +global
             "Specify the dependency directory. Will automatically parse the headers of \
-            the files, and import them if necessary. Default: \"./definitions\"")
-        (@arg input: ... "ASN.1 files to parse.")
+            the files, and import them if necessary. Default: \"./asn1\"")
+        (@arg deny_warnings: -D --("deny-warnings")
+            +global
+            "Exit with an error if there are any warnings.")
         (@arg verbose: -v --verbose ...
+            +global
             "Set log output level")
+        (@subcommand generate =>
+            (about: "Generates Rust code for ASN.1 modules.")
+            (@arg output: -o --output
+                +takes_value
+                "Write each module to a file in this directory, along with a `mod.rs` \
+                declaring them. Default: print to stdout")
+            (@arg derive: --derive
+                +takes_value
+                +multiple
+                number_of_values(1)
+                use_delimiter(true)
+                "Additional traits to derive on generated types, e.g. `Clone,Hash`.")
+            (@arg feature: --feature
+                +takes_value
+                "Only compile the generated modules when this crate feature is enabled.")
+            (@arg rustfmt: --rustfmt
+                "Format the generated code with `rustfmt`.")
+            (@arg unsupported: --unsupported
+                +takes_value
+                possible_value[skip any]
+                "What to generate for types that aren't supported yet: skip them, or \
+                use `Any`. Default: \"skip\"")
+            (@arg input: +required ... "ASN.1 files to compile."))
+        (@subcommand check =>
+            (about: "Parses and checks ASN.1 modules without generating code.")
+            (@arg input: +required ... "ASN.1 files to check."))
+        (subcommand: clap_app!(@app (SubCommand::with_name("list-types"))
+            (about: "Lists the types defined in ASN.1 modules.")
+            (@arg input: +required ... "ASN.1 files to list.")))
     )
     .get_matches();
 
@@ -31,12 +79,196 @@ fn main() {
     debug!("LOG Level: {:?}", filter_level);
     debug!("CLI Config: {:#?}", matches);
 
-    let directory = matches.value_of("dependencies").unwrap_or("./asn1");
+    let status = match matches.subcommand() {
+        ("generate", Some(matches)) => generate(matches),
+        ("check", Some(matches)) => check(matches),
+        ("list-types", Some(matches)) => list_types(matches),
+        _ => unreachable!(),
+    };
+
+    process::exit(status);
+}
+
+fn compiler(matches: &ArgMatches, input: &str) -> NotationCompiler {
+    NotationCompiler::new(input).dependencies(matches.value_of("dependencies").unwrap_or("./asn1"))
+}
+
+/// Prints the diagnostics of `compilation`, returning whether they should
+/// fail the run.
+fn report(matches: &ArgMatches, compilation: &Compilation) -> bool {
+    if !compilation.diagnostics.is_empty() {
+        eprintln!("{}", compilation.render_diagnostics());
+    }
+
+    compilation.diagnostics.has_errors()
+        || (matches.is_present("deny_warnings") && !compilation.diagnostics.is_empty())
+}
+
+fn generate(matches: &ArgMatches) -> i32 {
+    let unsupported = match matches.value_of("unsupported") {
+        Some("any") => Unsupported::Any,
+        _ => Unsupported::Skip,
+    };
+    let output = matches.value_of("output").map(Path::new);
+    let feature = matches.value_of("feature");
+    let mut status = 0;
+    let mut modules = Vec::new();
+
+    for input in matches.values_of("input").unwrap() {
+        let mut compiler = compiler(matches, input).unsupported(unsupported);
+
+        for derive in matches.values_of("derive").into_iter().flatten() {
+            compiler = compiler.derive(derive);
+        }
+
+        // Modules written to a directory are gated by their declaration in
+        // `mod.rs` instead.
+        if let Some(feature) = feature.filter(|_| output.is_none()) {
+            compiler = compiler.feature(feature);
+        }
+
+        let compilation = match compiler.compile() {
+            Ok(compilation) => compilation,
+            Err(error) => {
+                eprintln!("error: couldn't read {}: {}", input, error);
+                status = EXIT_IO;
+                continue;
+            }
+        };
+
+        if report(matches, &compilation) {
+            status = status.max(EXIT_DIAGNOSTICS);
+        }
+
+        let mut code = match compilation.output {
+            Some(code) => code,
+            None => continue,
+        };
+
+        if matches.is_present("rustfmt") {
+            match rustfmt(&code) {
+                Ok(formatted) => code = formatted,
+                Err(error) => eprintln!("warning: couldn't format {}: {}", input, error),
+            }
+        }
+
+        // Each module is written to its own file, or wrapped in its own
+        // module when it's gated by a feature, so their names can't clash.
+        let module = compilation.module.unwrap().to_snake_case();
+        if output.is_some() || feature.is_some() {
+            if let Some((_, other)) = modules.iter().find(|(name, _)| *name == module) {
+                eprintln!(
+                    "error: {} and {} would both generate the module `{}`",
+                    other, input, module
+                );
+                status = status.max(EXIT_DIAGNOSTICS);
+                continue;
+            }
+        }
+
+        let directory = match output {
+            Some(directory) => directory,
+            None => {
+                println!("{}", code);
+                modules.push((module, input));
+                continue;
+            }
+        };
+
+        let path = directory.join(format!("{}.rs", module));
+
+        if let Err(error) = fs::create_dir_all(directory).and_then(|_| fs::write(&path, code)) {
+            eprintln!("error: couldn't write {}: {}", path.display(), error);
+            status = EXIT_IO;
+            continue;
+        }
+
+        modules.push((module, input));
+    }
+
+    if let Some(directory) = output.filter(|_| !modules.is_empty()) {
+        let path = directory.join("mod.rs");
+        let cfg = feature
+            .map(|feature| format!("#[cfg(feature = {:?})]\n", feature))
+            .unwrap_or_default();
+        let declarations: String = modules
+            .iter()
+            .map(|(module, _)| format!("{}pub mod {};\n", cfg, module))
+            .collect();
+
+        if let Err(error) = fs::write(&path, declarations) {
+            eprintln!("error: couldn't write {}: {}", path.display(), error);
+            status = EXIT_IO;
+        }
+    }
+
+    status
+}
+
+fn check(matches: &ArgMatches) -> i32 {
+    let mut status = 0;
+
+    for input in matches.values_of("input").unwrap() {
+        match compiler(matches, input).check() {
+            Ok(compilation) => {
+                if report(matches, &compilation) {
+                    status = status.max(EXIT_DIAGNOSTICS);
+                }
+            }
+            Err(error) => {
+                eprintln!("error: couldn't read {}: {}", input, error);
+                status = EXIT_IO;
+            }
+        }
+    }
+
+    status
+}
+
+fn list_types(matches: &ArgMatches) -> i32 {
+    let mut status = 0;
+
+    for input in matches.values_of("input").unwrap() {
+        let compilation = match compiler(matches, input).check() {
+            Ok(compilation) => compilation,
+            Err(error) => {
+                eprintln!("error: couldn't read {}: {}", input, error);
+                status = EXIT_IO;
+                continue;
+            }
+        };
+
+        if report(matches, &compilation) {
+            status = status.max(EXIT_DIAGNOSTICS);
+        }
+
+        if let Some(module) = &compilation.module {
+            for (name, ty) in &compilation.types {
+                println!("{}.{}\t{}", module, name, ty);
+            }
+        }
+    }
+
+    status
+}
+
+/// Formats `code` by piping it through `rustfmt`.
+fn rustfmt(code: &str) -> io::Result<String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2018", "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    child.stdin.take().unwrap().write_all(code.as_bytes())?;
+    let output = child.wait_with_output()?;
 
-    let module = NotationCompiler::new(matches.value_of("input").unwrap())
-        .dependencies(directory)
-        .build()
-        .unwrap_or_else(|e| panic!("{}", e));
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
 
-    println!("{}", module);
+    String::from_utf8(output.stdout).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
@@ -4,7 +4,7 @@ pub mod oid;
 pub mod types;
 pub mod values;
 
-use std::{collections::BTreeMap, fmt, iter::Peekable};
+use std::{collections::BTreeMap, fmt, iter::Peekable, str::FromStr};
 
 use derefable::Derefable;
 use pest::{
@@ -13,7 +13,10 @@ use pest::{
 };
 use variation::Variation;
 
-use crate::Result;
+use crate::{
+    diagnostics::{Diagnostic, Diagnostics, Span},
+    Result,
+};
 
 pub use self::module::*;
 pub use self::object::*;
@@ -22,16 +25,39 @@ pub use self::types::*;
 pub use self::values::*;
 pub use asn1_pest::{Asn1Parser as Pest, Rule};
 
+type ParseResult<T> = std::result::Result<T, Diagnostic>;
+
 // First Vec is a Vec of Unions, containing a Vec of intersections.
 type ElementSet = Vec<Vec<Element>>;
 
-pub(crate) struct Parser<'a>(Peekable<FlatPairs<'a, Rule>>, &'a str);
+/// The remaining pairs, the source, the diagnostics collected so far, and
+/// where the names used in the assignment being parsed first appear.
+pub(crate) struct Parser<'a>(
+    Peekable<FlatPairs<'a, Rule>>,
+    &'a str,
+    Diagnostics,
+    BTreeMap<String, Span>,
+);
 
 impl<'a> Parser<'a> {
     /// Parse asn1 module into an Abstract Syntax Tree (AST) represented by the
-    /// `Module` struct.
-    pub fn parse(source: &'a str) -> Result<Module> {
-        Self::new(Rule::ModuleDefinition, source)?.parse_module()
+    /// `Module` struct, along with warnings about any constructs that were
+    /// skipped. Returns the syntax error if the module couldn't be parsed.
+    pub fn parse(source: &'a str) -> std::result::Result<(Module, Diagnostics), Diagnostic> {
+        let mut parser = Pest::parse(Rule::ModuleDefinition, source)
+            .map(|pairs| {
+                Self(
+                    pairs.flatten().peekable(),
+                    source,
+                    Diagnostics::new(),
+                    BTreeMap::new(),
+                )
+            })
+            .map_err(Diagnostic::from)?;
+
+        let module = parser.parse_module()?;
+
+        Ok((module, parser.2))
     }
 
     /// Copies the lexer output and parses the module's identifying information
@@ -39,59 +65,87 @@ impl<'a> Parser<'a> {
     /// `ModuleIdentifier` struct.
     pub fn parse_header(source: &'a str) -> Result<ModuleIdentifier> {
         let mut ast = Self::new(Rule::ModuleHeaderOnly, source)?;
-        ast.take(Rule::ModuleHeaderOnly);
+        ast.take(Rule::ModuleHeaderOnly)?;
 
-        ast.parse_module_identifier()
+        Ok(ast.parse_module_identifier()?)
     }
 
     fn new(rule: Rule, source: &'a str) -> Result<Self> {
         let iter = Pest::parse(rule, source)?;
 
-        Ok(Self(iter.flatten().peekable(), source))
+        Ok(Self(
+            iter.flatten().peekable(),
+            source,
+            Diagnostics::new(),
+            BTreeMap::new(),
+        ))
+    }
+
+    /// Skips every remaining pair that starts before `end`.
+    fn skip_to(&mut self, end: usize) {
+        while self.0.peek().map_or(false, |x| x.as_span().start() < end) {
+            self.0.next();
+        }
+    }
+
+    /// Records a warning that the construct in `span` isn't supported, and
+    /// skips over it.
+    fn unsupported(&mut self, span: pest::Span, construct: &str) {
+        self.2.warning(
+            format!("{} aren't supported yet and were ignored", construct),
+            Some(span.clone().into()),
+        );
+        self.skip_to(span.end());
     }
 
-    fn next(&mut self) -> Pair<Rule> {
-        self.0.next().unwrap()
+    fn next(&mut self) -> ParseResult<Pair<'a, Rule>> {
+        match self.0.next() {
+            Some(pair) => Ok(pair),
+            None => Err(self.end_of_input()),
+        }
     }
 
     fn peek(&mut self, rule: Rule) -> bool {
         self.rule_peek() == rule
     }
 
+    /// The `Rule` of the next pair, or `Rule::EOI` if every pair was taken.
     fn rule_peek(&mut self) -> Rule {
-        self.0.peek().map(|x| x.as_rule()).unwrap()
+        self.0.peek().map_or(Rule::EOI, |x| x.as_rule())
     }
 
-    fn next_rule(&mut self) -> Rule {
-        self.0.next().map(|x| x.as_rule()).unwrap()
+    /// The position in the source where the next pair starts.
+    fn peek_start(&mut self) -> usize {
+        let end = self.1.len();
+        self.0.peek().map_or(end, |x| x.as_span().start())
     }
 
-    /// Takes the next pair, and asserts that's its `Rule` matches `rule`.
+    fn next_rule(&mut self) -> ParseResult<Rule> {
+        self.next().map(|x| x.as_rule())
+    }
+
+    /// Takes the next pair, and checks that its `Rule` matches `rule`.
     ///
-    /// # Panics
+    /// # Errors
     /// If `rule` doesn't match the next rule in the iterator.
-    fn take(&mut self, rule: Rule) -> Pair<Rule> {
-        let pair = self.0.next().unwrap();
-        let expected = pair.as_rule();
-        if rule != expected {
-            eprintln!("Parse Error: {:?} != {:?}", expected, rule);
-            eprintln!("===================LINE==================");
-            eprintln!("{}", pair.as_str());
-            eprintln!("===================REST==================");
-            eprintln!("{:#?}", &self.0.clone().collect::<Vec<_>>()[..5]);
-            eprintln!("=========================================");
-            panic!("Parse Error: {:?} != {:?}", expected, rule);
-            //::std::process::exit(-1);
-        }
+    fn take(&mut self, rule: Rule) -> ParseResult<Pair<'a, Rule>> {
+        let pair = self.next()?;
 
-        pair
+        if pair.as_rule() == rule {
+            Ok(pair)
+        } else {
+            Err(Diagnostic::error(
+                format!("expected {:?}, found {:?}", rule, pair.as_rule()),
+                Some(pair.as_span().into()),
+            ))
+        }
     }
 
     /// Look at the next pair and checks if its `Rule` matches `rule`, consumes the pair if it
     /// matches. Useful for checking for optional wrapper rules.
-    fn look(&mut self, rule: Rule) -> Option<Pair<Rule>> {
+    fn look(&mut self, rule: Rule) -> Option<Pair<'a, Rule>> {
         if self.peek(rule) {
-            Some(self.take(rule))
+            self.0.next()
         } else {
             None
         }
@@ -99,32 +153,50 @@ impl<'a> Parser<'a> {
 
     /// Calls `Ast::peek` using the provided `Rule` and calls `parse_fn` and returns `Some(T)`
     /// if `true`, otherwise returns `None`.
-    fn optionally_parse<T>(&mut self, rule: Rule, parse_fn: &dyn Fn(&mut Self) -> T) -> Option<T> {
+    fn optionally_parse<T>(
+        &mut self,
+        rule: Rule,
+        parse_fn: &dyn Fn(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Option<T>> {
         if self.peek(rule) {
-            Some((parse_fn)(self))
+            (parse_fn)(self).map(Some)
         } else {
-            None
+            Ok(None)
         }
     }
 
-    fn parse_module(&mut self) -> Result<Module> {
-        self.take(Rule::ModuleDefinition);
+    /// The error for a `rule` that isn't valid at the next pair's position.
+    fn unexpected(&mut self, rule: Rule) -> Diagnostic {
+        let span = self.0.peek().map(|x| Span::from(x.as_span()));
+
+        Diagnostic::error(format!("unexpected {:?}", rule), span)
+    }
+
+    /// The error for a module that ended while more pairs were expected.
+    fn end_of_input(&self) -> Diagnostic {
+        let end = self.1.len();
+
+        Diagnostic::error("unexpected end of module", Some(Span::new(end, end)))
+    }
+
+    fn parse_module(&mut self) -> ParseResult<Module> {
+        self.take(Rule::ModuleDefinition)?;
 
         let identifier = self.parse_module_identifier()?;
         let tag = self.parse_tag_default();
         //let extension = self.parse_extension_default();
 
         let (exports, imports, assignments) = if self.look(Rule::ModuleBody).is_some() {
-            let exports = self.parse_exports();
-            let imports = self.parse_imports();
-            let assignments = self.parse_assignments();
+            let exports = self.parse_exports()?;
+            let imports = self.parse_imports()?;
+            let assignments = self.parse_assignments()?;
 
             (exports, imports, assignments)
         } else {
             (Exports::All, Vec::new(), Vec::new())
         };
 
-        self.take(Rule::EOI);
+        self.take(Rule::EOI)?;
 
         Ok(Module {
             identifier,
@@ -136,28 +208,28 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_module_identifier(&mut self) -> Result<ModuleIdentifier> {
-        self.take(Rule::ModuleIdentifier);
+    fn parse_module_identifier(&mut self) -> ParseResult<ModuleIdentifier> {
+        self.take(Rule::ModuleIdentifier)?;
 
-        let mut module_identifier = ModuleIdentifier::new(self.parse_reference_identifier());
+        let mut module_identifier = ModuleIdentifier::new(self.parse_reference_identifier()?);
 
         if self.look(Rule::DefinitiveIdentification).is_some() {
-            self.take(Rule::DefinitiveOID);
+            self.take(Rule::DefinitiveOID)?;
 
             while self.look(Rule::DefinitiveObjIdComponent).is_some() {
-                let pair = self.next();
+                let pair = self.next()?;
 
                 let component = match pair.as_rule() {
-                    Rule::NameForm => ObjIdComponent::Name(self.parse_identifier()),
+                    Rule::NameForm => ObjIdComponent::Name(self.parse_identifier()?),
                     Rule::DefinitiveNumberForm => {
-                        ObjIdComponent::Number(Number::Literal(pair.as_str().parse()?))
+                        ObjIdComponent::Number(Number::Literal(parse_int(&pair)?))
                     }
                     Rule::DefinitiveNameAndNumberForm => {
-                        let name = self.parse_identifier();
-                        let number = self.take(Rule::DefinitiveNumberForm).as_str().parse()?;
+                        let name = self.parse_identifier()?;
+                        let number = parse_int(&self.take(Rule::DefinitiveNumberForm)?)?;
                         ObjIdComponent::NameAndNumber(name, Number::Literal(number))
                     }
-                    _ => unreachable!(),
+                    _ => return Err(unexpected_pair(&pair)),
                 };
 
                 module_identifier.identification.push(component);
@@ -183,53 +255,53 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_exports(&mut self) -> Exports {
+    pub fn parse_exports(&mut self) -> ParseResult<Exports> {
         if self.look(Rule::Exports).is_some() && self.peek(Rule::SymbolList) {
-            Exports::Symbols(self.parse_symbol_list())
+            Ok(Exports::Symbols(self.parse_symbol_list()?))
         } else {
-            Exports::All
+            Ok(Exports::All)
         }
     }
 
-    pub fn parse_symbol_list(&mut self) -> Vec<String> {
-        self.take(Rule::SymbolList);
+    pub fn parse_symbol_list(&mut self) -> ParseResult<Vec<String>> {
+        self.take(Rule::SymbolList)?;
         let mut symbols = Vec::new();
 
         while self.look(Rule::Symbol).is_some() {
             match self.rule_peek() {
                 // TODO(Erin): Support parameterization
                 Rule::Reference => {
-                    symbols.push(self.parse_reference());
+                    symbols.push(self.parse_reference()?);
                 }
                 Rule::ParameterizedReference => {
-                    self.take(Rule::ParameterizedReference);
-                    symbols.push(self.parse_reference());
+                    self.take(Rule::ParameterizedReference)?;
+                    symbols.push(self.parse_reference()?);
                 }
-                r => unreachable!("Unexpected rule: {:?}", r),
+                rule => return Err(self.unexpected(rule)),
             }
         }
 
-        symbols
+        Ok(symbols)
     }
 
-    pub fn parse_imports(&mut self) -> Vec<(ModuleReference, Vec<String>)> {
+    pub fn parse_imports(&mut self) -> ParseResult<Vec<(ModuleReference, Vec<String>)>> {
         let mut imports = Vec::new();
 
         if self.look(Rule::Imports).is_some() {
             while self.look(Rule::SymbolsFromModule).is_some() {
-                let symbol_list = self.parse_symbol_list();
-                self.take(Rule::GlobalModuleReference);
-                let module_name = self.parse_reference_identifier();
+                let symbol_list = self.parse_symbol_list()?;
+                self.take(Rule::GlobalModuleReference)?;
+                let module_name = self.parse_reference_identifier()?;
 
                 let identification = if self.look(Rule::AssignedIdentifier).is_some() {
                     let identification = match self.rule_peek() {
                         Rule::ObjectIdentifierValue => AssignedIdentifier::ObjectIdentifier(
-                            self.parse_object_identifier_value(),
+                            self.parse_object_identifier_value()?,
                         ),
                         Rule::DefinedValue => {
-                            AssignedIdentifier::Defined(self.parse_defined_value())
+                            AssignedIdentifier::Defined(self.parse_defined_value()?)
                         }
-                        _ => unreachable!(),
+                        rule => return Err(self.unexpected(rule)),
                     };
 
                     Some(identification)
@@ -244,64 +316,83 @@ impl<'a> Parser<'a> {
             }
         }
 
-        imports
+        Ok(imports)
     }
 
-    pub fn parse_object_identifier_value(&mut self) -> ObjectIdentifier {
-        self.take(Rule::ObjectIdentifierValue);
+    pub fn parse_object_identifier_value(&mut self) -> ParseResult<ObjectIdentifier> {
+        self.take(Rule::ObjectIdentifierValue)?;
         let mut components = Vec::new();
 
         while self.look(Rule::ObjIdComponents).is_some() {
             let component = match self.rule_peek() {
-                Rule::Identifier => ObjIdComponent::Name(self.parse_identifier()),
+                Rule::Identifier => ObjIdComponent::Name(self.parse_name(Rule::Identifier)?),
                 Rule::NumberForm => {
-                    self.take(Rule::NumberForm);
-                    ObjIdComponent::Number(self.parse_number_or_defined_value())
+                    self.take(Rule::NumberForm)?;
+                    ObjIdComponent::Number(self.parse_number_or_defined_value()?)
                 }
                 Rule::NameAndNumberForm => {
-                    self.take(Rule::NameAndNumberForm);
-                    let name = self.parse_identifier();
-                    self.take(Rule::NumberForm);
-                    let number = self.parse_number_or_defined_value();
+                    self.take(Rule::NameAndNumberForm)?;
+                    let name = self.parse_identifier()?;
+                    self.take(Rule::NumberForm)?;
+                    let number = self.parse_number_or_defined_value()?;
 
                     ObjIdComponent::NameAndNumber(name, number)
                 }
-                _ => unreachable!(),
+                rule => return Err(self.unexpected(rule)),
             };
 
             components.push(component)
         }
 
-        ObjectIdentifier::from_components(components)
+        Ok(ObjectIdentifier::from_components(components))
     }
 
-    fn parse_defined_value(&mut self) -> DefinedValue {
-        self.take(Rule::DefinedValue);
+    fn parse_defined_value(&mut self) -> ParseResult<DefinedValue> {
+        self.take(Rule::DefinedValue)?;
 
         match self.rule_peek() {
-            Rule::DefinedTypeReference => DefinedValue::Simple(self.parse_defined_type_reference()),
-            Rule::valuereference => DefinedValue::Simple(self.parse_value_reference()),
-            Rule::ParameterizedValue => unimplemented!(),
-            _ => unreachable!(),
+            Rule::DefinedTypeReference => {
+                Ok(DefinedValue::Simple(self.parse_defined_type_reference()?))
+            }
+            Rule::valuereference => Ok(DefinedValue::Simple(self.parse_value_reference()?)),
+            Rule::ParameterizedValue => {
+                self.take(Rule::ParameterizedValue)?;
+                self.take(Rule::SimpleDefinedValue)?;
+
+                let reference = if self.peek(Rule::ExternalValueReference) {
+                    self.parse_external_value_reference()?
+                } else {
+                    self.parse_value_reference()?
+                };
+
+                Ok(DefinedValue::Parameterized(
+                    reference,
+                    ParameterList(self.parse_actual_parameter_list()?),
+                ))
+            }
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_number_or_defined_value(&mut self) -> Number {
-        self.take(Rule::NumberOrDefinedValue);
+    fn parse_number_or_defined_value(&mut self) -> ParseResult<Number> {
+        self.take(Rule::NumberOrDefinedValue)?;
 
         match self.rule_peek() {
-            Rule::number => Number::Literal(self.parse_number()),
-            Rule::DefinedValue => Number::DefinedValue(self.parse_defined_value()),
-            _ => unreachable!(),
+            Rule::number => Ok(Number::Literal(self.parse_number()?)),
+            Rule::DefinedValue => Ok(Number::DefinedValue(self.parse_defined_value()?)),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_assignments(&mut self) -> Vec<Assignment> {
+    fn parse_assignments(&mut self) -> ParseResult<Vec<Assignment>> {
         let mut assignments = Vec::new();
 
-        while self.look(Rule::Assignment).is_some() {
-            let assignment_type = self.next_rule();
-            let ident = self.parse_reference();
+        while let Some(assignment) = self.look(Rule::Assignment) {
+            let span = Span::from(assignment.as_span());
+            let assignment_type = self.next_rule()?;
+            let name_span = self.0.peek().map_or(span, |x| x.as_span().into());
+            let ident = self.parse_reference()?;
+            self.3.clear();
 
             let parameter_list = if self.look(Rule::ParameterList).is_some() {
                 let mut parameters = Vec::new();
@@ -310,27 +401,27 @@ impl<'a> Parser<'a> {
                     let governor = if self.look(Rule::ParamGovernor).is_some() {
                         match self.rule_peek() {
                             Rule::Governor => {
-                                self.take(Rule::Governor);
+                                self.take(Rule::Governor)?;
                                 let g = match self.rule_peek() {
-                                    Rule::Type => ParamGovernor::Type(self.parse_type()),
+                                    Rule::Type => ParamGovernor::Type(self.parse_type()?),
                                     Rule::DefinedObjectClass => {
-                                        ParamGovernor::Class(self.parse_defined_object_class())
+                                        ParamGovernor::Class(self.parse_defined_object_class()?)
                                     }
-                                    _ => unreachable!(),
+                                    rule => return Err(self.unexpected(rule)),
                                 };
 
                                 Some(g)
                             }
                             Rule::Reference => {
-                                Some(ParamGovernor::Reference(self.parse_reference()))
+                                Some(ParamGovernor::Reference(self.parse_reference()?))
                             }
-                            _ => unreachable!(),
+                            rule => return Err(self.unexpected(rule)),
                         }
                     } else {
                         None
                     };
 
-                    parameters.push((governor, self.parse_reference()));
+                    parameters.push((governor, self.parse_reference()?));
                 }
 
                 Some(parameters)
@@ -339,53 +430,68 @@ impl<'a> Parser<'a> {
             };
 
             let kind = match assignment_type {
-                Rule::TypeAssignment => AssignmentType::Type(self.parse_type()),
+                Rule::TypeAssignment => AssignmentType::Type(self.parse_type()?),
                 Rule::ValueAssignment => {
-                    AssignmentType::Value(self.parse_type(), self.parse_value())
+                    AssignmentType::Value(self.parse_type()?, self.parse_value()?)
                 }
                 Rule::ValueSetAssignment => {
-                    AssignmentType::ValueSet(self.parse_type(), self.parse_value_set())
+                    AssignmentType::ValueSet(self.parse_type()?, self.parse_value_set()?)
                 }
                 Rule::ObjectClassAssignment => {
-                    AssignmentType::ObjectClass(self.parse_object_class())
+                    AssignmentType::ObjectClass(self.parse_object_class()?)
                 }
                 Rule::ObjectAssignment => {
-                    AssignmentType::Object(self.parse_defined_object_class(), self.parse_object())
+                    AssignmentType::Object(self.parse_defined_object_class()?, self.parse_object()?)
                 }
                 Rule::ObjectSetAssignment => AssignmentType::ObjectSet(
-                    self.parse_defined_object_class(),
-                    self.parse_object_set(),
+                    self.parse_defined_object_class()?,
+                    self.parse_object_set()?,
                 ),
-                _ => unreachable!(),
+                rule => return Err(self.unexpected(rule)),
             };
 
-            assignments.push(Assignment::new(ident, kind, parameter_list))
+            let names = std::mem::take(&mut self.3);
+            assignments.push(Assignment::new(
+                ident,
+                kind,
+                parameter_list,
+                span,
+                name_span,
+                names,
+            ))
         }
 
-        assignments
+        Ok(assignments)
     }
 
-    fn parse_type(&mut self) -> Type {
-        self.take(Rule::Type);
+    fn parse_type(&mut self) -> ParseResult<Type> {
+        self.take(Rule::Type)?;
 
         match self.rule_peek() {
-            Rule::UnconstrainedType => self.parse_unconstrained_type().into(),
+            Rule::UnconstrainedType => Ok(self.parse_unconstrained_type()?.into()),
             Rule::ConstrainedType => {
-                self.take(Rule::ConstrainedType);
+                self.take(Rule::ConstrainedType)?;
 
                 if self.peek(Rule::TypeWithConstraint) {
-                    let is_set = self.take(Rule::TypeWithConstraint).as_str().contains("SET");
+                    let is_set = self
+                        .take(Rule::TypeWithConstraint)?
+                        .as_str()
+                        .contains("SET");
 
                     let constraint = if self.peek(Rule::Constraint) {
-                        self.parse_constraint()
+                        self.parse_constraint()?
                     } else {
-                        self.parse_size_constraint()
+                        let size = self.parse_size_constraint()?;
+                        Constraint::ElementSet(
+                            vec![vec![Element::SubType(SubTypeElement::Size(size))]],
+                            false,
+                        )
                     };
 
                     let inner_type = if self.peek(Rule::NamedType) {
-                        self.parse_named_type()
+                        self.parse_named_type()?
                     } else {
-                        self.parse_type()
+                        self.parse_type()?
                     };
 
                     let raw_type = if is_set {
@@ -394,88 +500,136 @@ impl<'a> Parser<'a> {
                         RawType::Builtin(BuiltinType::SequenceOf(Box::new(inner_type)))
                     };
 
-                    Type {
+                    Ok(Type {
                         raw_type,
                         name: None,
                         constraints: Some(vec![constraint]),
-                    }
+                    })
                 } else {
-                    let raw_type = self.parse_unconstrained_type();
+                    let raw_type = self.parse_unconstrained_type()?;
                     let mut constraints = Vec::new();
 
                     while self.peek(Rule::Constraint) {
-                        constraints.push(self.parse_constraint());
+                        constraints.push(self.parse_constraint()?);
                     }
 
-                    Type {
+                    Ok(Type {
                         raw_type,
                         name: None,
                         constraints: Some(constraints),
-                    }
+                    })
                 }
             }
-            _ => unreachable!(),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_constraint(&mut self) -> Constraint {
-        self.take(Rule::Constraint);
-        self.take(Rule::ConstraintSpec);
+    fn parse_constraint(&mut self) -> ParseResult<Constraint> {
+        self.take(Rule::Constraint)?;
+        self.take(Rule::ConstraintSpec)?;
 
         if self.look(Rule::GeneralConstraint).is_some() {
             match self.rule_peek() {
                 Rule::TableConstraint => {
-                    self.take(Rule::TableConstraint);
+                    self.take(Rule::TableConstraint)?;
 
                     if self.look(Rule::ComponentRelationConstraint).is_some() {
-                        let object_set = self.parse_defined_object_set();
+                        let object_set = self.parse_defined_object_set()?;
                         let mut components = Vec::new();
 
-                        while self.look(Rule::AtNotation).is_some() {
+                        while let Some(at) = self.look(Rule::AtNotation) {
                             let mut component_ids = Vec::new();
 
-                            if self.peek(Rule::Level) {
-                                unimplemented!("Leveled constraints currently not supported");
-                            } else {
-                                self.take(Rule::ComponentIdList);
+                            if at.as_str().starts_with("@.") {
+                                component_ids.push(String::from("."));
+                            }
 
-                                while self.peek(Rule::Identifier) {
-                                    component_ids.push(self.parse_identifier());
-                                }
+                            while self.look(Rule::Level).is_some() {
+                                component_ids.push(String::from("."));
+                            }
+
+                            self.take(Rule::ComponentIdList)?;
+
+                            while self.peek(Rule::Identifier) {
+                                component_ids.push(self.parse_identifier()?);
                             }
 
                             components.push(component_ids);
                         }
 
-                        Constraint::General(GeneralConstraint::Table(object_set, components))
+                        Ok(Constraint::General(GeneralConstraint::Table(
+                            object_set, components,
+                        )))
                     } else {
-                        let (set, extendable) = self.parse_object_set();
-                        Constraint::General(GeneralConstraint::ObjectSet(set, extendable))
+                        let (set, extendable) = self.parse_object_set()?;
+                        Ok(Constraint::General(GeneralConstraint::ObjectSet(
+                            set, extendable,
+                        )))
                     }
                 }
-                Rule::ContentsConstraint => unimplemented!(),
-                Rule::UserDefinedConstraint => unimplemented!(),
-                _ => unreachable!(),
+                Rule::ContentsConstraint => {
+                    let end = self.take(Rule::ContentsConstraint)?.as_span().end();
+
+                    let r#type = self.optionally_parse(Rule::Type, &|this| this.parse_type())?;
+                    let encoding = if self.peek(Rule::Value) && self.peek_start() < end {
+                        Some(self.parse_value()?)
+                    } else {
+                        None
+                    };
+
+                    Ok(Constraint::General(GeneralConstraint::Contents(
+                        r#type, encoding,
+                    )))
+                }
+                Rule::UserDefinedConstraint => {
+                    let end = self.take(Rule::UserDefinedConstraint)?.as_span().end();
+                    self.skip_to(end);
+
+                    Ok(Constraint::General(GeneralConstraint::UserDefined))
+                }
+                rule => return Err(self.unexpected(rule)),
             }
         } else {
-            let is_extendable = self.take(Rule::ElementSetSpecs).as_str().contains("...");
+            let is_extendable = self.take_element_set_specs()?;
+            let root = self.parse_element_set_spec()?;
+
+            // Additional elements aren't PER-visible, only the fact that the
+            // constraint is extensible is.
+            if is_extendable && self.peek(Rule::ElementSetSpec) {
+                self.parse_element_set_spec()?;
+            }
 
-            Constraint::ElementSet(self.parse_element_set_spec(), is_extendable)
+            Ok(Constraint::ElementSet(root, is_extendable))
         }
     }
 
-    fn parse_size_constraint(&mut self) -> Constraint {
-        self.take(Rule::SizeConstraint);
-        self.parse_constraint()
+    fn parse_size_constraint(&mut self) -> ParseResult<Constraint> {
+        self.take(Rule::SizeConstraint)?;
+        Ok(self.parse_constraint()?)
     }
 
-    fn parse_element_set_specs(&mut self) -> ElementSetSpec {
-        let has_ellipsis = self.take(Rule::ElementSetSpecs).as_str().contains("...");
-        let set = self.parse_element_set_spec();
+    /// Takes an `ElementSetSpecs`, and returns whether it has an extension
+    /// marker. Markers in nested constraints, as in `SIZE (1..4, ...)`, are
+    /// part of the root set and don't count.
+    fn take_element_set_specs(&mut self) -> ParseResult<bool> {
+        let specs = self.take(Rule::ElementSetSpecs)?;
+        let start = specs.as_span().start();
+        let root_end = specs
+            .clone()
+            .into_inner()
+            .next()
+            .map_or(start, |root| root.as_span().end());
+
+        Ok(specs.as_str()[root_end - start..].contains("..."))
+    }
+
+    fn parse_element_set_specs(&mut self) -> ParseResult<ElementSetSpec> {
+        let has_ellipsis = self.take_element_set_specs()?;
+        let set = self.parse_element_set_spec()?;
 
         let extensible = if has_ellipsis {
             if self.peek(Rule::ElementSetSpec) {
-                let with = self.parse_element_set_spec();
+                let with = self.parse_element_set_spec()?;
 
                 Extensible::YesWith(with)
             } else {
@@ -485,56 +639,63 @@ impl<'a> Parser<'a> {
             Extensible::No
         };
 
-        ElementSetSpec { set, extensible }
+        Ok(ElementSetSpec { set, extensible })
     }
 
-    fn parse_element_set_spec(&mut self) -> ElementSet {
+    fn parse_element_set_spec(&mut self) -> ParseResult<ElementSet> {
         let mut element_set = Vec::new();
 
-        if self.look(Rule::ElementSetSpec).is_none() {
-            return element_set;
-        }
+        let end = match self.look(Rule::ElementSetSpec) {
+            Some(pair) => pair.as_span().end(),
+            None => return Ok(element_set),
+        };
 
-        self.take(Rule::Unions);
+        self.take(Rule::Unions)?;
 
-        while self.look(Rule::Intersections).is_some() {
+        // Nested element sets are flattened into the same stream of pairs,
+        // so only pairs inside this element set's span belong to it.
+        while self.peek_start() < end && self.look(Rule::Intersections).is_some() {
             let mut intersections = Vec::new();
-            while self.look(Rule::IntersectionElements).is_some() {
-                intersections.push(self.parse_elements());
-                self.look(Rule::IntersectionMark);
+            while self.peek_start() < end && self.look(Rule::IntersectionElements).is_some() {
+                intersections.push(self.parse_elements()?);
+                if self.peek_start() < end {
+                    self.look(Rule::IntersectionMark);
+                }
             }
 
             element_set.push(intersections);
-            self.look(Rule::UnionMark);
+            if self.peek_start() < end {
+                self.look(Rule::UnionMark);
+            }
         }
 
-        element_set
+        Ok(element_set)
     }
 
-    fn parse_unconstrained_type(&mut self) -> RawType {
-        self.take(Rule::UnconstrainedType);
+    fn parse_unconstrained_type(&mut self) -> ParseResult<RawType> {
+        self.take(Rule::UnconstrainedType)?;
 
         if self.look(Rule::BuiltinType).is_some() {
-            let pair = self.next();
+            let pair = self.next()?;
             match pair.as_rule() {
-                Rule::BooleanType => RawType::Builtin(BuiltinType::Boolean),
+                Rule::BooleanType => Ok(RawType::Builtin(BuiltinType::Boolean)),
                 Rule::BitStringType => {
                     let mut named_bits = BTreeMap::new();
 
                     if self.look(Rule::NamedBitList).is_some() {
                         while self.look(Rule::NamedBit).is_some() {
                             named_bits.insert(
-                                self.parse_identifier(),
-                                self.parse_number_or_defined_value(),
+                                self.parse_identifier()?,
+                                self.parse_number_or_defined_value()?,
                             );
                         }
                     }
 
-                    RawType::Builtin(BuiltinType::BitString(named_bits))
+                    Ok(RawType::Builtin(BuiltinType::BitString(named_bits)))
                 }
 
                 Rule::CharacterStringType => {
-                    let pair = self.next();
+                    let pair = self.next()?;
                     let char_type = if pair.as_rule() == Rule::UnrestrictedCharacterStringType {
                         CharacterStringType::Unrestricted
                     } else {
@@ -552,52 +713,55 @@ impl<'a> Parser<'a> {
                             "UTF8String" => CharacterStringType::Utf8,
                             "VideotexString" => CharacterStringType::Videotex,
                             "VisibleString" => CharacterStringType::Visible,
-                            _ => unreachable!(),
+                            _ => return Err(unexpected_pair(&pair)),
                         }
                     };
 
-                    RawType::Builtin(BuiltinType::CharacterString(char_type))
+                    Ok(RawType::Builtin(BuiltinType::CharacterString(char_type)))
                 }
 
                 Rule::ChoiceType => {
-                    self.take(Rule::AlternativeTypeLists);
-                    self.take(Rule::AlternativeTypeList);
+                    self.take(Rule::AlternativeTypeLists)?;
+                    self.take(Rule::AlternativeTypeList)?;
                     let mut alternatives = Vec::new();
 
                     while self.peek(Rule::NamedType) {
-                        alternatives.push(self.parse_named_type());
+                        alternatives.push(self.parse_named_type()?);
                     }
 
-                    let extension = self.parse_extension_and_exception();
+                    let extension = self.optionally_parse(
+                        Rule::ExtensionAndException,
+                        &Self::parse_extension_and_exception,
+                    )?;
 
-                    RawType::Builtin(BuiltinType::Choice(ChoiceType {
+                    Ok(RawType::Builtin(BuiltinType::Choice(ChoiceType {
                         alternatives,
                         extension,
-                    }))
+                    })))
                 }
 
                 Rule::EnumeratedType => {
-                    self.take(Rule::Enumerations);
+                    self.take(Rule::Enumerations)?;
 
-                    let enumerations = self.parse_enumeration();
+                    let enumerations = self.parse_enumeration()?;
 
                     let exception_spec = if self.peek(Rule::ExceptionSpec) {
-                        Some(self.parse_exception_spec())
+                        Some(self.parse_exception_spec()?)
                     } else {
                         None
                     };
 
                     let extended_enumerations = if self.peek(Rule::Enumeration) {
-                        Some(self.parse_enumeration())
+                        Some(self.parse_enumeration()?)
                     } else {
                         None
                     };
 
-                    RawType::Builtin(BuiltinType::Enumeration(
+                    Ok(RawType::Builtin(BuiltinType::Enumeration(
                         enumerations,
                         exception_spec,
                         extended_enumerations,
-                    ))
+                    )))
                 }
 
                 Rule::IntegerType => {
@@ -605,180 +769,225 @@ impl<'a> Parser<'a> {
 
                     if self.look(Rule::NamedNumberList).is_some() {
                         while self.look(Rule::NamedNumber).is_some() {
-                            let ident = self.parse_identifier();
+                            let ident = self.parse_identifier()?;
 
                             let value = match self.rule_peek() {
-                                Rule::SignedNumber => Number::Literal(self.parse_signed_number()),
+                                Rule::SignedNumber => Number::Literal(self.parse_signed_number()?),
                                 Rule::DefinedValue => {
-                                    Number::DefinedValue(self.parse_defined_value())
+                                    Number::DefinedValue(self.parse_defined_value()?)
                                 }
-                                _ => unreachable!(),
+                                rule => return Err(self.unexpected(rule)),
                             };
 
                             named_numbers.insert(ident, value);
                         }
                     }
 
-                    RawType::Builtin(BuiltinType::Integer(named_numbers))
+                    Ok(RawType::Builtin(BuiltinType::Integer(named_numbers)))
                 }
 
-                Rule::NullType => RawType::Builtin(BuiltinType::Null),
+                Rule::NullType => Ok(RawType::Builtin(BuiltinType::Null)),
 
                 Rule::ObjectClassFieldType => {
-                    let class = self.parse_defined_object_class();
+                    let class = self.parse_defined_object_class()?;
 
-                    let field_name = self.parse_field_name();
+                    let field_name = self.parse_field_name()?;
 
-                    RawType::Builtin(BuiltinType::ObjectClassField(class, field_name))
+                    Ok(RawType::Builtin(BuiltinType::ObjectClassField(
+                        class, field_name,
+                    )))
                 }
 
-                Rule::ObjectIdentifierType => RawType::Builtin(BuiltinType::ObjectIdentifier),
-                Rule::OctetStringType => RawType::Builtin(BuiltinType::OctetString),
+                Rule::ObjectIdentifierType => Ok(RawType::Builtin(BuiltinType::ObjectIdentifier)),
+                Rule::OctetStringType => Ok(RawType::Builtin(BuiltinType::OctetString)),
 
                 Rule::PrefixedType => {
                     if self.look(Rule::TaggedType).is_some() {
-                        self.take(Rule::Tag);
+                        self.take(Rule::Tag)?;
 
                         let encoding = if self.look(Rule::EncodingReference).is_some() {
-                            Some(self.parse_encoding_reference())
+                            Some(self.parse_encoding_reference()?)
                         } else {
                             None
                         };
 
                         let class = self.look(Rule::Class).and_then(|r| r.as_str().parse().ok());
 
-                        let _pair = self.take(Rule::ClassNumber).as_str().to_owned();
-                        let number = self.parse_number_or_defined_value();
+                        let _pair = self.take(Rule::ClassNumber)?.as_str().to_owned();
+                        let number = self.parse_number_or_defined_value()?;
                         let kind = match self.look(Rule::TagKind).map(|r| r.as_str()) {
                             Some("IMPLICIT") => TagKind::Implicit,
                             Some("EXPLICIT") => TagKind::Explicit,
                             _ => TagKind::Environment,
                         };
-                        let r#type = Box::new(self.parse_type());
+                        let r#type = Box::new(self.parse_type()?);
 
-                        RawType::Builtin(BuiltinType::Prefixed(
+                        Ok(RawType::Builtin(BuiltinType::Prefixed(
                             Prefix::new(encoding, kind, class, number),
                             r#type,
-                        ))
+                        )))
                     } else {
-                        unimplemented!("Encoding prefixed types are not supported currently.")
+                        // Encoding instructions don't change the abstract
+                        // value of the type, so the prefix is skipped.
+                        self.take(Rule::EncodingPrefixedType)?;
+                        self.take(Rule::EncodingPrefix)?;
+                        if self.look(Rule::EncodingReference).is_some() {
+                            self.parse_encoding_reference()?;
+                        }
+                        self.look(Rule::EncodingInstruction);
+
+                        let span = self.0.peek().map(|x| Span::from(x.as_span()));
+                        let r#type = self.parse_type()?;
+                        if r#type.constraints.is_some() {
+                            self.2.warning(
+                                "constraints on encoding prefixed types are not currently \
+                                 supported and were ignored",
+                                span,
+                            );
+                        }
+
+                        Ok(r#type.raw_type)
                     }
                 }
 
-                Rule::SequenceType => {
-                    RawType::Builtin(BuiltinType::Sequence(self.parse_component_type_lists()))
-                }
+                Rule::SequenceType => Ok(RawType::Builtin(BuiltinType::Sequence(
+                    self.parse_component_type_lists()?,
+                ))),
 
                 Rule::SequenceOfType => {
                     if self.peek(Rule::Type) {
-                        RawType::Builtin(BuiltinType::SequenceOf(Box::new(self.parse_type())))
+                        Ok(RawType::Builtin(BuiltinType::SequenceOf(Box::new(
+                            self.parse_type()?,
+                        ))))
                     } else {
-                        RawType::Builtin(BuiltinType::SequenceOf(Box::new(self.parse_named_type())))
+                        Ok(RawType::Builtin(BuiltinType::SequenceOf(Box::new(
+                            self.parse_named_type()?,
+                        ))))
                     }
                 }
 
                 Rule::SetType => {
                     if self.peek(Rule::ExtensionAndException) {
-                        RawType::Builtin(BuiltinType::Set(Set::Extensible(
-                            self.parse_extension_and_exception().unwrap(),
+                        Ok(RawType::Builtin(BuiltinType::Set(Set::Extensible(
+                            self.parse_extension_and_exception()?,
                             self.parse_optional_extension_marker(),
-                        )))
+                        ))))
                     } else if self.peek(Rule::ComponentTypeLists) {
-                        RawType::Builtin(BuiltinType::Set(Set::Concrete(
-                            self.parse_component_type_lists(),
-                        )))
+                        Ok(RawType::Builtin(BuiltinType::Set(Set::Concrete(
+                            self.parse_component_type_lists()?,
+                        ))))
                     } else {
-                        RawType::Builtin(BuiltinType::Set(Set::Concrete(ComponentTypeList::new())))
+                        Ok(RawType::Builtin(BuiltinType::Set(Set::Concrete(
+                            ComponentTypeList::new(),
+                        ))))
                     }
                 }
                 Rule::SetOfType => {
                     if self.peek(Rule::Type) {
-                        RawType::Builtin(BuiltinType::SetOf(Box::new(self.parse_type())))
+                        Ok(RawType::Builtin(BuiltinType::SetOf(Box::new(
+                            self.parse_type()?,
+                        ))))
                     } else {
-                        RawType::Builtin(BuiltinType::SetOf(Box::new(self.parse_named_type())))
+                        Ok(RawType::Builtin(BuiltinType::SetOf(Box::new(
+                            self.parse_named_type()?,
+                        ))))
                     }
                 }
 
-                r => unreachable!("Unexpected rule: {:?}", r),
+                rule => return Err(self.unexpected(rule)),
             }
         } else {
-            self.take(Rule::ReferencedType);
+            self.take(Rule::ReferencedType)?;
 
             match self.rule_peek() {
                 Rule::DefinedType => {
-                    self.take(Rule::DefinedType);
+                    self.take(Rule::DefinedType)?;
 
                     match self.rule_peek() {
-                        Rule::DefinedTypeReference => self.parse_defined_type_reference().into(),
+                        Rule::DefinedTypeReference => {
+                            Ok(self.parse_defined_type_reference()?.into())
+                        }
                         Rule::ParameterizedType => {
-                            self.take(Rule::ParameterizedType);
+                            self.take(Rule::ParameterizedType)?;
 
-                            let reference = self.parse_defined_type_reference();
-                            let parameters = self.parse_actual_parameter_list();
+                            let reference = self.parse_defined_type_reference()?;
+                            let parameters = self.parse_actual_parameter_list()?;
 
-                            RawType::ParameterizedReference(reference, parameters)
+                            Ok(RawType::ParameterizedReference(reference, parameters))
                         }
-                        Rule::ParameterizedValueSet => unimplemented!(),
+                        Rule::ParameterizedValueSet => {
+                            self.take(Rule::ParameterizedValueSet)?;
+
+                            let reference = self.parse_defined_type_reference()?;
+                            let parameters = self.parse_actual_parameter_list()?;
 
-                        r => unreachable!("Unexpected rule: {:?}", r),
+                            Ok(RawType::ParameterizedReference(reference, parameters))
+                        }
+
+                        rule => return Err(self.unexpected(rule)),
                     }
                 }
-                Rule::FromObject => RawType::ReferencedFromObject(self.parse_from_object()),
-                _ => unreachable!(),
+                Rule::FromObject => Ok(RawType::ReferencedFromObject(self.parse_from_object()?)),
+                rule => return Err(self.unexpected(rule)),
             }
         }
     }
 
-    fn parse_actual_parameter_list(&mut self) -> Vec<Parameter> {
-        self.take(Rule::ActualParameterList);
+    fn parse_actual_parameter_list(&mut self) -> ParseResult<Vec<Parameter>> {
+        self.take(Rule::ActualParameterList)?;
         let mut parameters = Vec::new();
 
         while self.look(Rule::ActualParameter).is_some() {
             let parameter = match self.rule_peek() {
-                Rule::Type => Parameter::Type(self.parse_type()),
-                Rule::Value => Parameter::Value(self.parse_value()),
-                Rule::ValueSet => Parameter::ValueSet(self.parse_value_set()),
+                Rule::Type => Parameter::Type(self.parse_type()?),
+                Rule::Value => Parameter::Value(self.parse_value()?),
+                Rule::ValueSet => Parameter::ValueSet(self.parse_value_set()?),
                 Rule::DefinedObjectClass => {
-                    Parameter::ObjectClass(self.parse_defined_object_class())
+                    Parameter::ObjectClass(self.parse_defined_object_class()?)
                 }
-                Rule::Object => Parameter::Object(self.parse_object()),
-                Rule::ObjectSet => Parameter::ObjectSet(self.parse_object_set()),
-                _ => unreachable!(),
+                Rule::Object => Parameter::Object(self.parse_object()?),
+                Rule::ObjectSet => Parameter::ObjectSet(self.parse_object_set()?),
+                rule => return Err(self.unexpected(rule)),
             };
 
             parameters.push(parameter);
         }
 
-        parameters
+        Ok(parameters)
     }
 
-    fn parse_value_set(&mut self) -> ElementSetSpec {
-        self.take(Rule::ValueSet);
+    fn parse_value_set(&mut self) -> ParseResult<ElementSetSpec> {
+        self.take(Rule::ValueSet)?;
 
-        self.parse_element_set_specs()
+        Ok(self.parse_element_set_specs()?)
     }
 
-    fn parse_value(&mut self) -> Value {
-        self.take(Rule::Value);
+    fn parse_value(&mut self) -> ParseResult<Value> {
+        self.take(Rule::Value)?;
 
         match self.rule_peek() {
-            Rule::BuiltinValue => self.parse_builtin_value(),
-            Rule::ReferencedValue => self.parse_referenced_value(),
-            Rule::ObjectClassFieldType => unimplemented!(),
-            _ => unreachable!(),
+            Rule::BuiltinValue => Ok(self.parse_builtin_value()?),
+            Rule::ReferencedValue => Ok(self.parse_referenced_value()?),
+            Rule::ObjectClassFieldType => {
+                let span = self.take(Rule::ObjectClassFieldType)?.as_span();
+                self.unsupported(span, "object class field values");
+
+                Ok(Value::ObjectClassField)
+            }
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_builtin_value(&mut self) -> Value {
-        self.take(Rule::BuiltinValue);
+    fn parse_builtin_value(&mut self) -> ParseResult<Value> {
+        self.take(Rule::BuiltinValue)?;
 
         match self.rule_peek() {
             Rule::BitStringValue => {
-                self.take(Rule::BitStringValue);
+                self.take(Rule::BitStringValue)?;
 
                 let bitstring = match self.rule_peek() {
                     Rule::bstring => {
-                        self.take(Rule::bstring);
+                        self.take(Rule::bstring)?;
 
                         let bitstring = self
                             .look(Rule::bits)
@@ -788,102 +997,180 @@ impl<'a> Parser<'a> {
                         BitString::Literal(bitstring)
                     }
                     Rule::hstring => {
-                        self.take(Rule::hstring);
+                        self.take(Rule::hstring)?;
 
+                        // Each hexadecimal digit is four bits.
                         let bitstring = self
                             .look(Rule::hexes)
-                            .and_then(|hex| u64::from_str_radix(hex.as_str(), 16).ok())
-                            .map(|num| format!("{:b}", num))
+                            .map(|hex| {
+                                hex.as_str()
+                                    .chars()
+                                    .filter_map(|digit| digit.to_digit(16))
+                                    .map(|nibble| format!("{:04b}", nibble))
+                                    .collect()
+                            })
                             .unwrap_or_else(String::new);
 
                         BitString::Literal(bitstring)
                     }
 
                     Rule::IdentifierList => {
-                        self.take(Rule::IdentifierList);
+                        self.take(Rule::IdentifierList)?;
 
                         let mut identifiers = Vec::new();
                         while self.peek(Rule::Identifier) {
-                            identifiers.push(self.parse_identifier());
+                            identifiers.push(self.parse_identifier()?);
                         }
 
                         BitString::List(identifiers)
                     }
 
-                    Rule::Value => {
-                        unimplemented!("BitStrings with 'CONTAINING' aren't currently supported.")
-                    }
+                    Rule::Value => BitString::Containing(Box::new(self.parse_value()?)),
 
                     _ => BitString::Literal(String::new()),
                 };
 
-                Value::BitString(bitstring)
+                Ok(Value::BitString(bitstring))
             }
             Rule::IntegerValue => {
-                self.take(Rule::IntegerValue);
+                self.take(Rule::IntegerValue)?;
 
                 let value = match self.rule_peek() {
-                    Rule::SignedNumber => IntegerValue::Literal(self.parse_signed_number()),
-                    Rule::Identifier => IntegerValue::Identifier(self.parse_identifier()),
-                    _ => unreachable!(),
+                    Rule::SignedNumber => IntegerValue::Literal(self.parse_signed_number()?),
+                    Rule::Identifier => IntegerValue::Identifier(self.parse_identifier()?),
+                    rule => return Err(self.unexpected(rule)),
                 };
 
-                Value::Integer(value)
+                Ok(Value::Integer(value))
             }
 
-            Rule::ObjectIdentifierValue => {
-                Value::ObjectIdentifier(self.parse_object_identifier_value())
+            Rule::ObjectIdentifierValue => Ok(Value::ObjectIdentifier(
+                self.parse_object_identifier_value()?,
+            )),
+
+            Rule::CharacterStringValue => {
+                let span = self.take(Rule::CharacterStringValue)?.as_span();
+
+                if self.look(Rule::RestrictedCharacterStringValue).is_none() {
+                    self.unsupported(span, "CHARACTER STRING values");
+                    return Ok(Value::CharacterString(String::new()));
+                }
+
+                let characters = if self.look(Rule::CharacterStringList).is_some() {
+                    self.take(Rule::CharSyms)?;
+
+                    let mut characters = String::new();
+                    while self.peek_start() < span.end() && self.look(Rule::CharsDefn).is_some() {
+                        characters.push_str(&self.parse_chars_defn()?);
+                    }
+
+                    characters
+                } else {
+                    self.parse_chars_defn()?
+                };
+
+                Ok(Value::CharacterString(characters))
             }
+            Rule::SequenceValue => Ok(self.parse_sequence_value()?),
+            Rule::EnumeratedValue => Ok(self.parse_enumerated_value()?),
+            Rule::BooleanValue => Ok(self.parse_boolean_value()?),
 
-            Rule::SequenceValue => self.parse_sequence_value(),
-            Rule::EnumeratedValue => self.parse_enumerated_value(),
-            Rule::BooleanValue => self.parse_boolean_value(),
+            rule => return Err(self.unexpected(rule)),
+        }
+    }
 
-            e => unreachable!("Unexpected Rule {:?}", e),
+    /// Parses a quoted string or a character identified by its position in
+    /// a character table.
+    fn parse_chars_defn(&mut self) -> ParseResult<String> {
+        let position = |pair: Pair<Rule>| pair.as_str().parse::<u32>().unwrap_or_default();
+
+        match self.rule_peek() {
+            Rule::cstring => {
+                self.take(Rule::cstring)?;
+
+                Ok(self
+                    .look(Rule::characters)
+                    .map(|c| c.as_str().replace("\"\"", "\""))
+                    .unwrap_or_else(String::new))
+            }
+            Rule::Quadruple => {
+                self.take(Rule::Quadruple)?;
+                let group = position(self.take(Rule::Group)?);
+                let plane = position(self.take(Rule::Plane)?);
+                let row = position(self.take(Rule::Row)?);
+                let cell = position(self.take(Rule::Cell)?);
+
+                Ok(
+                    std::char::from_u32(group << 24 | plane << 16 | row << 8 | cell)
+                        .map(String::from)
+                        .unwrap_or_default(),
+                )
+            }
+            Rule::Tuple => {
+                self.take(Rule::Tuple)?;
+                let column = position(self.take(Rule::TableColumn)?);
+                let row = position(self.take(Rule::TableRow)?);
+
+                Ok(std::char::from_u32(column << 4 | row)
+                    .map(String::from)
+                    .unwrap_or_default())
+            }
+            _ => {
+                let span = self.take(Rule::DefinedValue)?.as_span();
+                self.unsupported(span, "value references in character strings");
+
+                Ok(String::new())
+            }
         }
     }
 
-    fn parse_referenced_value(&mut self) -> Value {
-        self.take(Rule::ReferencedValue);
+    fn parse_referenced_value(&mut self) -> ParseResult<Value> {
+        self.take(Rule::ReferencedValue)?;
 
         match self.rule_peek() {
-            Rule::DefinedValue => Value::Defined(self.parse_defined_value()),
-            Rule::FromObject => Value::FromObject(self.parse_from_object()),
-            _ => unreachable!(),
+            Rule::DefinedValue => Ok(Value::Defined(self.parse_defined_value()?)),
+            Rule::FromObject => Ok(Value::FromObject(self.parse_from_object()?)),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_from_object(&mut self) -> FieldReference {
-        self.take(Rule::FromObject);
-        self.take(Rule::ReferencedObjects);
+    fn parse_from_object(&mut self) -> ParseResult<FieldReference> {
+        self.take(Rule::FromObject)?;
+        self.take(Rule::ReferencedObjects)?;
 
         let referenced_object = match self.rule_peek() {
-            Rule::DefinedObject => self.parse_defined_object(),
-            Rule::DefinedObjectSet => self.parse_defined_object_set(),
-            Rule::ParameterizedObjectSet => self.parse_parameterized_object_set(),
-            _ => unreachable!(),
+            Rule::DefinedObject => self.parse_defined_object()?,
+            Rule::DefinedObjectSet => self.parse_defined_object_set()?,
+            Rule::ParameterizedObjectSet => self.parse_parameterized_object_set()?,
+            rule => return Err(self.unexpected(rule)),
         };
 
-        FieldReference::new(referenced_object, self.parse_field_name())
+        Ok(FieldReference::new(
+            referenced_object,
+            self.parse_field_name()?,
+        ))
     }
 
-    fn parse_signed_number(&mut self) -> i64 {
-        self.take(Rule::SignedNumber).as_str().parse().unwrap()
+    fn parse_signed_number(&mut self) -> ParseResult<i64> {
+        parse_int(&self.take(Rule::SignedNumber)?)
     }
 
-    fn parse_number(&mut self) -> i64 {
-        self.take(Rule::number).as_str().parse().unwrap()
+    fn parse_number(&mut self) -> ParseResult<i64> {
+        parse_int(&self.take(Rule::number)?)
     }
 
-    fn parse_identifier(&mut self) -> String {
-        self.parse_to_str(Rule::Identifier)
+    fn parse_identifier(&mut self) -> ParseResult<String> {
+        Ok(self.parse_to_str(Rule::Identifier)?)
     }
 
-    fn parse_value_reference(&mut self) -> ReferenceType {
-        ReferenceType::new(None, self.parse_to_str(Rule::valuereference))
+    fn parse_value_reference(&mut self) -> ParseResult<ReferenceType> {
+        Ok(ReferenceType::new(
+            None,
+            self.parse_name(Rule::valuereference)?,
+        ))
     }
 
-    fn parse_reference(&mut self) -> String {
+    fn parse_reference(&mut self) -> ParseResult<String> {
         const VALID_RULES: [Rule; 8] = [
             Rule::Reference,
             // These rules are also allowed, so that this can be called in parse_assignment.
@@ -896,79 +1183,91 @@ impl<'a> Parser<'a> {
             Rule::objectsetreference,
         ];
 
-        let pair = self.next();
+        let pair = self.next()?;
 
         let is_valid = VALID_RULES.into_iter().any(|rule| pair.as_rule() == *rule);
 
         if is_valid {
-            pair.as_str().to_owned()
+            Ok(pair.as_str().to_owned())
         } else {
-            panic!("{:?} != {:?}", pair.as_rule(), VALID_RULES);
+            Err(unexpected_pair(&pair))
         }
     }
 
-    fn parse_reference_identifier(&mut self) -> String {
-        self.parse_to_str(Rule::ReferenceIdentifier)
+    fn parse_reference_identifier(&mut self) -> ParseResult<String> {
+        Ok(self.parse_to_str(Rule::ReferenceIdentifier)?)
     }
 
-    fn parse_type_reference(&mut self) -> String {
-        self.parse_to_str(Rule::typereference)
+    fn parse_type_reference(&mut self) -> ParseResult<String> {
+        self.parse_name(Rule::typereference)
     }
 
-    fn parse_encoding_identifier(&mut self) -> String {
-        self.parse_to_str(Rule::EncodingIdentifier)
+    fn parse_encoding_identifier(&mut self) -> ParseResult<String> {
+        Ok(self.parse_to_str(Rule::EncodingIdentifier)?)
     }
 
-    fn parse_module_reference(&mut self) -> String {
-        self.parse_to_str(Rule::modulereference)
+    fn parse_module_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_to_str(Rule::modulereference)?)
     }
 
-    fn parse_object_reference(&mut self) -> String {
-        self.parse_to_str(Rule::objectreference)
+    fn parse_object_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_to_str(Rule::objectreference)?)
     }
 
-    fn parse_object_set_reference(&mut self) -> String {
-        self.parse_to_str(Rule::objectsetreference)
+    fn parse_object_set_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_to_str(Rule::objectsetreference)?)
     }
 
-    fn parse_to_str(&mut self, rule: Rule) -> String {
-        self.take(rule).as_str().to_owned()
+    fn parse_to_str(&mut self, rule: Rule) -> ParseResult<String> {
+        Ok(self.take(rule)?.as_str().to_owned())
     }
 
-    fn parse_value_field_reference(&mut self) -> String {
-        self.parse_field_reference(Rule::valuefieldreference)
+    /// Parses a reference or component name, and records where it first
+    /// appears in the assignment being parsed.
+    fn parse_name(&mut self, rule: Rule) -> ParseResult<String> {
+        let pair = self.take(rule)?;
+        let name = pair.as_str().to_owned();
+        self.3
+            .entry(name.clone())
+            .or_insert_with(|| pair.as_span().into());
+
+        Ok(name)
     }
 
-    fn parse_value_set_field_reference(&mut self) -> String {
-        self.parse_field_reference(Rule::valuesetfieldreference)
+    fn parse_value_field_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_field_reference(Rule::valuefieldreference)?)
     }
 
-    fn parse_object_field_reference(&mut self) -> String {
-        self.parse_field_reference(Rule::objectfieldreference)
+    fn parse_value_set_field_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_field_reference(Rule::valuesetfieldreference)?)
     }
 
-    fn parse_object_set_field_reference(&mut self) -> String {
-        self.parse_field_reference(Rule::objectsetfieldreference)
+    fn parse_object_field_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_field_reference(Rule::objectfieldreference)?)
     }
 
-    fn parse_type_field_reference(&mut self) -> String {
-        self.parse_field_reference(Rule::typefieldreference)
+    fn parse_object_set_field_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_field_reference(Rule::objectsetfieldreference)?)
     }
 
-    fn parse_field_reference(&mut self, rule: Rule) -> String {
-        self.take(rule).as_str().trim_matches('&').to_owned()
+    fn parse_type_field_reference(&mut self) -> ParseResult<String> {
+        Ok(self.parse_field_reference(Rule::typefieldreference)?)
     }
 
-    fn parse_encoding_reference(&mut self) -> String {
-        self.take(Rule::encodingreference).as_str().to_owned()
+    fn parse_field_reference(&mut self, rule: Rule) -> ParseResult<String> {
+        Ok(self.take(rule)?.as_str().trim_matches('&').to_owned())
     }
 
-    fn parse_literal(&mut self) -> String {
-        self.take(Rule::Literal).as_str().to_owned()
+    fn parse_encoding_reference(&mut self) -> ParseResult<String> {
+        Ok(self.take(Rule::encodingreference)?.as_str().to_owned())
     }
 
-    fn parse_component_type_lists(&mut self) -> ComponentTypeList {
-        self.take(Rule::ComponentTypeLists);
+    fn parse_literal(&mut self) -> ParseResult<String> {
+        Ok(self.take(Rule::Literal)?.as_str().to_owned())
+    }
+
+    fn parse_component_type_lists(&mut self) -> ParseResult<ComponentTypeList> {
+        self.take(Rule::ComponentTypeLists)?;
 
         let mut component_list = ComponentTypeList {
             components: None,
@@ -976,17 +1275,17 @@ impl<'a> Parser<'a> {
         };
 
         if self.peek(Rule::ComponentTypeList) {
-            component_list.components = Some(self.parse_component_type_list());
+            component_list.components = Some(self.parse_component_type_list()?);
         }
 
         if self.look(Rule::ComponentTypeExtension).is_some() {
-            let exception = match self.parse_extension_and_exception().unwrap() {
+            let exception = match self.parse_extension_and_exception()? {
                 ExtensionAndException::Extension => None,
                 ExtensionAndException::Exception(id) => Some(id),
             };
-            let additions = self.parse_extension_additions();
+            let additions = self.parse_extension_additions()?;
             let marker = if self.look(Rule::ExtensionEndMarker).is_some() {
-                ExtensionMarker::End(self.parse_component_type_list())
+                ExtensionMarker::End(self.parse_component_type_list()?)
             } else {
                 ExtensionMarker::Extensible
             };
@@ -998,139 +1297,155 @@ impl<'a> Parser<'a> {
             });
         }
 
-        component_list
+        Ok(component_list)
     }
 
-    fn parse_component_type_list(&mut self) -> Vec<ComponentType> {
-        self.take(Rule::ComponentTypeList);
+    fn parse_component_type_list(&mut self) -> ParseResult<Vec<ComponentType>> {
+        self.take(Rule::ComponentTypeList)?;
 
         let mut component_types = Vec::new();
 
         while self.peek(Rule::ComponentType) {
-            component_types.push(self.parse_component_type());
+            component_types.push(self.parse_component_type()?);
         }
 
-        component_types
+        Ok(component_types)
     }
 
-    fn parse_component_type(&mut self) -> ComponentType {
-        let raw = self.take(Rule::ComponentType).as_str().to_owned();
+    fn parse_component_type(&mut self) -> ParseResult<ComponentType> {
+        let raw = self.take(Rule::ComponentType)?.as_str().to_owned();
 
         if raw.contains("COMPONENTS") {
-            ComponentType::ComponentsOf(self.parse_type())
+            Ok(ComponentType::ComponentsOf(self.parse_type()?))
         } else {
-            let ty = self.parse_named_type();
+            let ty = self.parse_named_type()?;
             let optional = raw.contains("OPTIONAL");
             let default = match raw.contains("DEFAULT") {
-                true => Some(self.parse_value()),
+                true => Some(self.parse_value()?),
                 false => None,
             };
 
-            ComponentType::Type {
+            Ok(ComponentType::Type {
                 ty,
                 optional,
                 default,
-            }
+            })
         }
     }
 
-    fn parse_named_type(&mut self) -> Type {
-        self.take(Rule::NamedType);
-        let ident = self.parse_identifier();
-        let mut ty = self.parse_type();
+    fn parse_named_type(&mut self) -> ParseResult<Type> {
+        self.take(Rule::NamedType)?;
+        let ident = self.parse_name(Rule::Identifier)?;
+        let mut ty = self.parse_type()?;
         ty.name = Some(ident);
 
-        ty
+        Ok(ty)
     }
 
-    fn parse_elements(&mut self) -> Element {
-        self.take(Rule::Elements);
+    fn parse_elements(&mut self) -> ParseResult<Element> {
+        self.take(Rule::Elements)?;
         match self.rule_peek() {
-            Rule::ElementSetSpec => Element::ElementSet(self.parse_element_set_spec()),
+            Rule::ElementSetSpec => Ok(Element::ElementSet(self.parse_element_set_spec()?)),
             Rule::SubtypeElements => {
-                self.take(Rule::SubtypeElements);
+                self.take(Rule::SubtypeElements)?;
                 let subtype = match self.rule_peek() {
-                    Rule::Value => SubTypeElement::Value(self.parse_value()),
-                    Rule::Type => SubTypeElement::Type(self.parse_type()),
-                    Rule::SizeConstraint => SubTypeElement::Size(self.parse_size_constraint()),
+                    Rule::Value => SubTypeElement::Value(self.parse_value()?),
+                    Rule::Type => SubTypeElement::Type(self.parse_type()?),
+                    Rule::SizeConstraint => SubTypeElement::Size(self.parse_size_constraint()?),
+                    Rule::PermittedAlphabet => {
+                        self.take(Rule::PermittedAlphabet)?;
+                        SubTypeElement::PermittedAlphabet(self.parse_constraint()?)
+                    }
+                    Rule::ContainedSubtype => {
+                        self.take(Rule::ContainedSubtype)?;
+                        SubTypeElement::Type(self.parse_type()?)
+                    }
+                    Rule::PatternConstraint => {
+                        self.take(Rule::PatternConstraint)?;
+                        SubTypeElement::Pattern(self.parse_value()?)
+                    }
                     Rule::ValueRange => {
-                        self.take(Rule::ValueRange);
+                        self.take(Rule::ValueRange)?;
                         let is_low_inclusive =
-                            self.take(Rule::LowerEndpoint).as_str().contains('<');
-                        let low_value = if self.take(Rule::LowerEndValue).as_str().contains("MIN") {
+                            self.take(Rule::LowerEndpoint)?.as_str().contains('<');
+                        let low_value = if self.take(Rule::LowerEndValue)?.as_str().contains("MIN")
+                        {
                             RangeValue::Min(is_low_inclusive)
                         } else {
-                            RangeValue::Value(self.parse_value(), is_low_inclusive)
+                            RangeValue::Value(self.parse_value()?, is_low_inclusive)
                         };
 
                         let is_high_inclusive =
-                            self.take(Rule::UpperEndpoint).as_str().contains('<');
-                        let high_value = if self.take(Rule::UpperEndValue).as_str().contains("MAX")
+                            self.take(Rule::UpperEndpoint)?.as_str().contains('<');
+                        let high_value = if self.take(Rule::UpperEndValue)?.as_str().contains("MAX")
                         {
                             RangeValue::Max(is_high_inclusive)
                         } else {
-                            RangeValue::Value(self.parse_value(), is_high_inclusive)
+                            RangeValue::Value(self.parse_value()?, is_high_inclusive)
                         };
 
                         SubTypeElement::Range(low_value, high_value)
                     }
                     Rule::InnerTypeConstraints => {
-                        self.take(Rule::InnerTypeConstraints);
+                        self.take(Rule::InnerTypeConstraints)?;
 
                         if self.peek(Rule::Constraint) {
-                            SubTypeElement::Constraint(self.parse_constraint())
+                            SubTypeElement::Constraint(self.parse_constraint()?)
                         } else {
-                            self.take(Rule::MultipleTypeConstraints);
+                            self.take(Rule::MultipleTypeConstraints)?;
 
                             if self.look(Rule::FullSpecification).is_some() {
-                                SubTypeElement::FullSpec(self.parse_type_constraints())
+                                SubTypeElement::FullSpec(self.parse_type_constraints()?)
                             } else {
-                                self.take(Rule::PartialSpecification);
-                                SubTypeElement::PartialSpec(self.parse_type_constraints())
+                                self.take(Rule::PartialSpecification)?;
+                                SubTypeElement::PartialSpec(self.parse_type_constraints()?)
                             }
                         }
                     }
-                    e => unreachable!("{:?}", e),
+                    rule => return Err(self.unexpected(rule)),
                 };
 
-                Element::SubType(subtype)
+                Ok(Element::SubType(subtype))
             }
             Rule::ObjectSetElements => {
-                self.take(Rule::ObjectSetElements);
+                self.take(Rule::ObjectSetElements)?;
 
                 match self.rule_peek() {
-                    Rule::Object => Element::Object(self.parse_object()),
-                    Rule::DefinedObjectSet => Element::ObjectSet(self.parse_defined_object_set()),
+                    Rule::Object => Ok(Element::Object(self.parse_object()?)),
+                    Rule::DefinedObjectSet => {
+                        Ok(Element::ObjectSet(self.parse_defined_object_set()?))
+                    }
                     Rule::ParameterizedObjectSet => {
-                        Element::ObjectSet(self.parse_parameterized_object_set())
+                        Ok(Element::ObjectSet(self.parse_parameterized_object_set()?))
                     }
-                    _ => unreachable!(),
+                    rule => return Err(self.unexpected(rule)),
                 }
             }
-            _ => unreachable!(),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_type_constraints(&mut self) -> BTreeMap<String, ComponentConstraint> {
-        self.take(Rule::TypeConstraints);
+    fn parse_type_constraints(&mut self) -> ParseResult<BTreeMap<String, ComponentConstraint>> {
+        self.take(Rule::TypeConstraints)?;
         let mut map = BTreeMap::new();
 
         while self.look(Rule::NamedConstraint).is_some() {
-            let name = self.parse_identifier();
-            self.take(Rule::ComponentConstraint);
+            let name = self.parse_identifier()?;
+            self.take(Rule::ComponentConstraint)?;
 
             let constraint = if self.peek(Rule::Constraint) {
-                Some(self.parse_constraint())
+                Some(self.parse_constraint()?)
             } else {
                 None
             };
 
             let presence = if self.peek(Rule::PresenceConstraint) {
-                let p = match self.next().as_str() {
+                let pair = self.next()?;
+                let p = match pair.as_str() {
                     "PRESENT" => Presence::Present,
                     "ABSENT" => Presence::Absent,
                     "OPTIONAL" => Presence::Optional,
-                    _ => unreachable!(),
+                    _ => return Err(unexpected_pair(&pair)),
                 };
 
                 Some(p)
@@ -1141,53 +1456,53 @@ impl<'a> Parser<'a> {
             map.insert(name, ComponentConstraint::new(constraint, presence));
         }
 
-        map
+        Ok(map)
     }
 
-    fn parse_defined_object_class(&mut self) -> DefinedObjectClass {
-        self.take(Rule::DefinedObjectClass);
+    fn parse_defined_object_class(&mut self) -> ParseResult<DefinedObjectClass> {
+        self.take(Rule::DefinedObjectClass)?;
 
         match self.rule_peek() {
             Rule::ExternalObjectClassReference => {
-                self.take(Rule::ExternalObjectClassReference);
+                self.take(Rule::ExternalObjectClassReference)?;
 
-                DefinedObjectClass::Reference(ReferenceType::new(
-                    Some(self.parse_reference_identifier()),
-                    self.parse_encoding_identifier(),
-                ))
+                Ok(DefinedObjectClass::Reference(ReferenceType::new(
+                    Some(self.parse_reference_identifier()?),
+                    self.parse_encoding_identifier()?,
+                )))
             }
-            Rule::EncodingIdentifier => DefinedObjectClass::Reference(ReferenceType::new(
+            Rule::EncodingIdentifier => Ok(DefinedObjectClass::Reference(ReferenceType::new(
                 None,
-                self.parse_encoding_identifier(),
-            )),
+                self.parse_encoding_identifier()?,
+            ))),
             Rule::UsefulObjectClassReference => {
                 if self
-                    .take(Rule::UsefulObjectClassReference)
+                    .take(Rule::UsefulObjectClassReference)?
                     .as_str()
                     .contains("ABSTRACT-SYNTAX")
                 {
-                    DefinedObjectClass::AbstractSyntax
+                    Ok(DefinedObjectClass::AbstractSyntax)
                 } else {
-                    DefinedObjectClass::TypeIdentifier
+                    Ok(DefinedObjectClass::TypeIdentifier)
                 }
             }
-            _ => unreachable!(),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_field_name(&mut self) -> Vec<Field> {
-        self.take(Rule::FieldName);
+    fn parse_field_name(&mut self) -> ParseResult<Vec<Field>> {
+        self.take(Rule::FieldName)?;
         let mut field_names = Vec::new();
 
         while self.peek(Rule::PrimitiveFieldName) {
-            field_names.push(self.parse_primitive_field_name());
+            field_names.push(self.parse_primitive_field_name()?);
         }
 
-        field_names
+        Ok(field_names)
     }
 
-    fn parse_primitive_field_name(&mut self) -> Field {
-        self.take(Rule::PrimitiveFieldName);
+    fn parse_primitive_field_name(&mut self) -> ParseResult<Field> {
+        self.take(Rule::PrimitiveFieldName)?;
 
         let rule = self.rule_peek();
         let kind = match rule {
@@ -1196,27 +1511,27 @@ impl<'a> Parser<'a> {
             Rule::valuesetfieldreference => FieldType::ValueSet,
             Rule::objectfieldreference => FieldType::Object,
             Rule::objectsetfieldreference => FieldType::ObjectSet,
-            _ => unreachable!(),
+            rule => return Err(self.unexpected(rule)),
         };
 
-        Field::new(self.parse_field_reference(rule), kind)
+        Ok(Field::new(self.parse_field_reference(rule)?, kind))
     }
 
-    fn parse_object_set(&mut self) -> (ElementSet, bool) {
-        self.take(Rule::ObjectSet);
+    fn parse_object_set(&mut self) -> ParseResult<(ElementSet, bool)> {
+        self.take(Rule::ObjectSet)?;
 
-        let is_extendable = self.take(Rule::ObjectSetSpec).as_str().contains("...");
+        let is_extendable = self.take(Rule::ObjectSetSpec)?.as_str().contains("...");
 
-        (self.parse_element_set_spec(), is_extendable)
+        Ok((self.parse_element_set_spec()?, is_extendable))
     }
 
-    fn parse_object(&mut self) -> Object {
-        self.take(Rule::Object);
+    fn parse_object(&mut self) -> ParseResult<Object> {
+        self.take(Rule::Object)?;
 
         match self.rule_peek() {
-            Rule::DefinedObject => Object::Reference(self.parse_defined_object()),
+            Rule::DefinedObject => Ok(Object::Reference(self.parse_defined_object()?)),
             Rule::ObjectDefn => {
-                self.take(Rule::ObjectDefn);
+                self.take(Rule::ObjectDefn)?;
 
                 let mut tokens = Vec::new();
 
@@ -1224,318 +1539,335 @@ impl<'a> Parser<'a> {
                     while self.look(Rule::DefinedSyntaxToken).is_some() {
                         let token = if self.look(Rule::Setting).is_some() {
                             let setting = match self.rule_peek() {
-                                Rule::Type => Setting::Type(self.parse_type()),
-                                Rule::Value => Setting::Value(self.parse_value()),
-                                Rule::ValueSet => Setting::ValueSet(self.parse_value_set()),
-                                Rule::Object => Setting::Object(self.parse_object()),
-                                Rule::ObjectSet => Setting::ObjectSet(self.parse_object_set().0),
-                                _ => unreachable!(),
+                                Rule::Type => Setting::Type(self.parse_type()?),
+                                Rule::Value => Setting::Value(self.parse_value()?),
+                                Rule::ValueSet => Setting::ValueSet(self.parse_value_set()?),
+                                Rule::Object => Setting::Object(self.parse_object()?),
+                                Rule::ObjectSet => Setting::ObjectSet(self.parse_object_set()?.0),
+                                rule => return Err(self.unexpected(rule)),
                             };
 
                             ObjectDefn::Setting(setting)
                         } else {
-                            ObjectDefn::Literal(self.parse_literal())
+                            ObjectDefn::Literal(self.parse_literal()?)
                         };
 
                         tokens.push(token);
                     }
                 } else {
-                    unimplemented!("Default Syntax is not currently suppported")
+                    let span = self.take(Rule::DefaultSyntax)?.as_span();
+                    self.unsupported(span, "objects in the default syntax");
                 }
 
-                Object::Def(tokens)
+                Ok(Object::Def(tokens))
             }
-            _ => unreachable!(),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_defined_object(&mut self) -> ObjectReference {
-        self.take(Rule::DefinedObject);
+    fn parse_defined_object(&mut self) -> ParseResult<ObjectReference> {
+        self.take(Rule::DefinedObject)?;
 
-        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference);
-        let object = self.parse_object_reference();
+        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference)?;
+        let object = self.parse_object_reference()?;
         let parameters = self.optionally_parse(
             Rule::ActualParameterList,
             &Self::parse_actual_parameter_list,
-        );
+        )?;
 
-        ObjectReference::Object(ReferenceType::new(module, object), parameters)
+        Ok(ObjectReference::Object(
+            ReferenceType::new(module, object),
+            parameters,
+        ))
     }
 
-    fn parse_defined_object_set(&mut self) -> ObjectReference {
-        self.take(Rule::DefinedObjectSet);
+    fn parse_defined_object_set(&mut self) -> ParseResult<ObjectReference> {
+        self.take(Rule::DefinedObjectSet)?;
 
-        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference);
-        let set = self.parse_object_set_reference();
+        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference)?;
+        let set = self.parse_object_set_reference()?;
 
-        ObjectReference::Set(ReferenceType::new(module, set), None)
+        Ok(ObjectReference::Set(ReferenceType::new(module, set), None))
     }
 
-    fn parse_parameterized_object_set(&mut self) -> ObjectReference {
-        self.take(Rule::ParameterizedObjectSet);
-        self.take(Rule::DefinedObjectSet);
+    fn parse_parameterized_object_set(&mut self) -> ParseResult<ObjectReference> {
+        self.take(Rule::ParameterizedObjectSet)?;
+        self.take(Rule::DefinedObjectSet)?;
 
-        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference);
-        let set = self.parse_object_set_reference();
+        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference)?;
+        let set = self.parse_object_set_reference()?;
 
-        let parameters = self.parse_actual_parameter_list();
+        let parameters = self.parse_actual_parameter_list()?;
 
-        ObjectReference::Set(ReferenceType::new(module, set), Some(parameters))
+        Ok(ObjectReference::Set(
+            ReferenceType::new(module, set),
+            Some(parameters),
+        ))
     }
 
-    fn parse_sequence_value(&mut self) -> Value {
-        self.take(Rule::SequenceValue);
+    fn parse_sequence_value(&mut self) -> ParseResult<Value> {
+        self.take(Rule::SequenceValue)?;
 
-        Value::Sequence(self.parse_component_value_list())
+        Ok(Value::Sequence(self.parse_component_value_list()?))
     }
 
-    fn parse_component_value_list(&mut self) -> Vec<NamedValue> {
-        self.take(Rule::ComponentValueList);
+    fn parse_component_value_list(&mut self) -> ParseResult<Vec<NamedValue>> {
+        self.take(Rule::ComponentValueList)?;
 
         let mut values = Vec::new();
 
         while self.look(Rule::NamedValue).is_some() {
-            values.push(NamedValue(self.parse_identifier(), self.parse_value()));
+            values.push(NamedValue(self.parse_identifier()?, self.parse_value()?));
         }
 
-        values
+        Ok(values)
     }
 
-    fn parse_enumerated_value(&mut self) -> Value {
-        self.take(Rule::EnumeratedValue);
+    fn parse_enumerated_value(&mut self) -> ParseResult<Value> {
+        self.take(Rule::EnumeratedValue)?;
 
-        Value::Enumerated(self.parse_identifier())
+        Ok(Value::Enumerated(self.parse_identifier()?))
     }
 
-    fn parse_object_class(&mut self) -> ObjectClass {
-        self.take(Rule::ObjectClass);
+    fn parse_object_class(&mut self) -> ParseResult<ObjectClass> {
+        self.take(Rule::ObjectClass)?;
 
         match self.rule_peek() {
-            Rule::ObjectClassDefn => ObjectClass::Def(self.parse_object_class_defn()),
-            Rule::ParameterizedObjectClass => unimplemented!("ParameterizedObjectClass"),
-            Rule::DefinedObjectClass => ObjectClass::Defined(self.parse_defined_object_class()),
-            _ => unreachable!(),
+            Rule::ObjectClassDefn => Ok(ObjectClass::Def(self.parse_object_class_defn()?)),
+            Rule::ParameterizedObjectClass => {
+                self.take(Rule::ParameterizedObjectClass)?;
+
+                let class = self.parse_defined_object_class()?;
+                let parameters = self.parse_actual_parameter_list()?;
+
+                Ok(ObjectClass::Parameterized(class, Some(parameters)))
+            }
+            Rule::DefinedObjectClass => {
+                Ok(ObjectClass::Defined(self.parse_defined_object_class()?))
+            }
+            rule => return Err(self.unexpected(rule)),
         }
     }
 
-    fn parse_object_class_defn(&mut self) -> ClassDefinition {
-        self.take(Rule::ObjectClassDefn);
+    fn parse_object_class_defn(&mut self) -> ParseResult<ClassDefinition> {
+        self.take(Rule::ObjectClassDefn)?;
         let mut fields = Vec::new();
 
         while self.look(Rule::FieldSpec).is_some() {
             let field = match self.rule_peek() {
-                Rule::FixedTypeValueFieldSpec => self.parse_fixed_type_value_field_spec(),
-                Rule::VariableTypeValueFieldSpec => self.parse_variable_type_value_field_spec(),
-                Rule::FixedTypeValueSetFieldSpec => self.parse_fixed_type_value_set_field_spec(),
+                Rule::FixedTypeValueFieldSpec => self.parse_fixed_type_value_field_spec()?,
+                Rule::VariableTypeValueFieldSpec => self.parse_variable_type_value_field_spec()?,
+                Rule::FixedTypeValueSetFieldSpec => self.parse_fixed_type_value_set_field_spec()?,
                 Rule::VariableTypeValueSetFieldSpec => {
-                    self.parse_variable_type_value_set_field_spec()
+                    self.parse_variable_type_value_set_field_spec()?
                 }
-                Rule::ObjectFieldSpec => self.parse_object_field_spec(),
-                Rule::TypeFieldSpec => self.parse_type_field_spec(),
-                Rule::ObjectSetFieldSpec => self.parse_object_set_field_spec(),
-                _ => unreachable!(),
+                Rule::ObjectFieldSpec => self.parse_object_field_spec()?,
+                Rule::TypeFieldSpec => self.parse_type_field_spec()?,
+                Rule::ObjectSetFieldSpec => self.parse_object_set_field_spec()?,
+                rule => return Err(self.unexpected(rule)),
             };
 
             fields.push(field);
         }
 
         let syntax = if self.look(Rule::WithSyntaxSpec).is_some() {
-            self.take(Rule::SyntaxList);
-            Some(self.parse_token_or_group_spec())
+            self.take(Rule::SyntaxList)?;
+            Some(self.parse_token_or_group_spec()?)
         } else {
             None
         };
 
-        ClassDefinition::new(fields, syntax)
+        Ok(ClassDefinition::new(fields, syntax))
     }
 
-    fn parse_token_or_group_spec(&mut self) -> Vec<Token> {
+    fn parse_token_or_group_spec(&mut self) -> ParseResult<Vec<Token>> {
         let mut tokens = Vec::new();
 
         while self.look(Rule::TokenOrGroupSpec).is_some() {
-            match self.next_rule() {
+            let pair = self.next()?;
+            match pair.as_rule() {
                 Rule::RequiredToken => {
                     let token = match self.rule_peek() {
-                        Rule::Literal => Token::Literal(self.parse_literal()),
-                        Rule::PrimitiveFieldName => Token::Field(self.parse_primitive_field_name()),
-                        _ => unreachable!(),
+                        Rule::Literal => Token::Literal(self.parse_literal()?),
+                        Rule::PrimitiveFieldName => {
+                            Token::Field(self.parse_primitive_field_name()?)
+                        }
+                        rule => return Err(self.unexpected(rule)),
                     };
 
                     tokens.push(token);
                 }
                 Rule::OptionalGroup => {
-                    tokens.push(Token::OptionalGroup(self.parse_token_or_group_spec()));
+                    tokens.push(Token::OptionalGroup(self.parse_token_or_group_spec()?));
                 }
-                _ => unreachable!(),
+                _ => return Err(unexpected_pair(&pair)),
             }
         }
 
-        tokens
+        Ok(tokens)
     }
 
-    fn parse_fixed_type_value_field_spec(&mut self) -> FieldSpec {
+    fn parse_fixed_type_value_field_spec(&mut self) -> ParseResult<FieldSpec> {
         let is_unique = self
-            .take(Rule::FixedTypeValueFieldSpec)
+            .take(Rule::FixedTypeValueFieldSpec)?
             .as_str()
             .contains("UNIQUE");
-        let ident = self.parse_value_field_reference();
-        let ty = self.parse_type();
-        let optionality = self.parse_value_optionality_spec();
+        let ident = self.parse_value_field_reference()?;
+        let ty = self.parse_type()?;
+        let optionality = self.parse_value_optionality_spec()?;
 
-        FieldSpec::FixedTypeValue(ident, ty, is_unique, optionality)
+        Ok(FieldSpec::FixedTypeValue(ident, ty, is_unique, optionality))
     }
 
-    fn parse_variable_type_value_field_spec(&mut self) -> FieldSpec {
-        self.take(Rule::VariableTypeValueFieldSpec);
+    fn parse_variable_type_value_field_spec(&mut self) -> ParseResult<FieldSpec> {
+        self.take(Rule::VariableTypeValueFieldSpec)?;
 
-        let ident = self.parse_value_field_reference();
-        let field_name = self.parse_field_name();
-        let optionality = self.parse_value_optionality_spec();
+        let ident = self.parse_value_field_reference()?;
+        let field_name = self.parse_field_name()?;
+        let optionality = self.parse_value_optionality_spec()?;
 
-        FieldSpec::VariableTypeValue(ident, field_name, optionality)
+        Ok(FieldSpec::VariableTypeValue(ident, field_name, optionality))
     }
 
-    fn parse_fixed_type_value_set_field_spec(&mut self) -> FieldSpec {
-        self.take(Rule::FixedTypeValueSetFieldSpec);
+    fn parse_fixed_type_value_set_field_spec(&mut self) -> ParseResult<FieldSpec> {
+        self.take(Rule::FixedTypeValueSetFieldSpec)?;
 
-        let ident = self.parse_value_set_field_reference();
-        let ty = self.parse_type();
-        let optionality = self.parse_value_set_optionality_spec();
+        let ident = self.parse_value_set_field_reference()?;
+        let ty = self.parse_type()?;
+        let optionality = self.parse_value_set_optionality_spec()?;
 
-        FieldSpec::FixedValueSet(ident, ty, optionality)
+        Ok(FieldSpec::FixedValueSet(ident, ty, optionality))
     }
 
-    fn parse_variable_type_value_set_field_spec(&mut self) -> FieldSpec {
-        self.take(Rule::VariableTypeValueSetFieldSpec);
-        let ident = self.parse_value_set_field_reference();
-        let field = self.parse_field_name();
-        let optionality = self.parse_value_optionality_spec();
+    fn parse_variable_type_value_set_field_spec(&mut self) -> ParseResult<FieldSpec> {
+        self.take(Rule::VariableTypeValueSetFieldSpec)?;
+        let ident = self.parse_value_set_field_reference()?;
+        let field = self.parse_field_name()?;
+        let optionality = self.parse_value_optionality_spec()?;
 
-        FieldSpec::VariableTypeValue(ident, field, optionality)
+        Ok(FieldSpec::VariableTypeValue(ident, field, optionality))
     }
 
-    fn parse_object_field_spec(&mut self) -> FieldSpec {
-        self.take(Rule::ObjectFieldSpec);
-        let ident = self.parse_object_field_reference();
-        let class = self.parse_defined_object_class();
-        let optionality = self.parse_object_optionality_spec();
+    fn parse_object_field_spec(&mut self) -> ParseResult<FieldSpec> {
+        self.take(Rule::ObjectFieldSpec)?;
+        let ident = self.parse_object_field_reference()?;
+        let class = self.parse_defined_object_class()?;
+        let optionality = self.parse_object_optionality_spec()?;
 
-        FieldSpec::ObjectField(ident, class, optionality)
+        Ok(FieldSpec::ObjectField(ident, class, optionality))
     }
 
-    fn parse_type_field_spec(&mut self) -> FieldSpec {
-        self.take(Rule::TypeFieldSpec);
-        let ident = self.parse_type_field_reference();
-        let optionality = self.parse_type_optionality_spec();
+    fn parse_type_field_spec(&mut self) -> ParseResult<FieldSpec> {
+        self.take(Rule::TypeFieldSpec)?;
+        let ident = self.parse_type_field_reference()?;
+        let optionality = self.parse_type_optionality_spec()?;
 
-        FieldSpec::Type(ident, optionality)
+        Ok(FieldSpec::Type(ident, optionality))
     }
 
-    fn parse_object_set_field_spec(&mut self) -> FieldSpec {
-        self.take(Rule::ObjectSetFieldSpec);
+    fn parse_object_set_field_spec(&mut self) -> ParseResult<FieldSpec> {
+        self.take(Rule::ObjectSetFieldSpec)?;
 
-        let ident = self.parse_object_set_field_reference();
-        let class = self.parse_defined_object_class();
-        let optionality = self.parse_object_set_optionality_spec();
+        let ident = self.parse_object_set_field_reference()?;
+        let class = self.parse_defined_object_class()?;
+        let optionality = self.parse_object_set_optionality_spec()?;
 
-        FieldSpec::ObjectSet(ident, class, optionality)
+        Ok(FieldSpec::ObjectSet(ident, class, optionality))
     }
 
-    fn parse_value_optionality_spec(&mut self) -> Optionality<Value> {
+    fn parse_value_optionality_spec(&mut self) -> ParseResult<Optionality<Value>> {
         self.parse_optionality_spec(Rule::ValueOptionalitySpec, &Self::parse_value)
     }
 
-    fn parse_value_set_optionality_spec(&mut self) -> Optionality<ElementSetSpec> {
+    fn parse_value_set_optionality_spec(&mut self) -> ParseResult<Optionality<ElementSetSpec>> {
         self.parse_optionality_spec(Rule::ValueSetOptionalitySpec, &Self::parse_value_set)
     }
 
-    fn parse_object_optionality_spec(&mut self) -> Optionality<Object> {
+    fn parse_object_optionality_spec(&mut self) -> ParseResult<Optionality<Object>> {
         self.parse_optionality_spec(Rule::ObjectOptionalitySpec, &Self::parse_object)
     }
 
-    fn parse_type_optionality_spec(&mut self) -> Optionality<Type> {
+    fn parse_type_optionality_spec(&mut self) -> ParseResult<Optionality<Type>> {
         self.parse_optionality_spec(Rule::TypeOptionalitySpec, &Self::parse_type)
     }
 
-    fn parse_object_set_optionality_spec(&mut self) -> Optionality<(ElementSet, bool)> {
+    fn parse_object_set_optionality_spec(
+        &mut self,
+    ) -> ParseResult<Optionality<(ElementSet, bool)>> {
         self.parse_optionality_spec(Rule::ObjectSetOptionalitySpec, &Self::parse_object_set)
     }
 
     fn parse_optionality_spec<T>(
         &mut self,
         rule: Rule,
-        parse_fn: &Fn(&mut Self) -> T,
-    ) -> Optionality<T> {
+        parse_fn: &Fn(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Optionality<T>> {
         if !self.peek(rule) {
-            Optionality::None
+            Ok(Optionality::None)
         } else {
-            let pair = self.take(rule);
+            let pair = self.take(rule)?;
 
             if pair.as_str().contains("OPTIONAL") {
-                Optionality::Optional
+                Ok(Optionality::Optional)
             } else {
-                Optionality::Default((parse_fn)(self))
+                Ok(Optionality::Default((parse_fn)(self)?))
             }
         }
     }
 
-    fn parse_defined_type_reference(&mut self) -> ReferenceType {
-        self.take(Rule::DefinedTypeReference);
+    fn parse_defined_type_reference(&mut self) -> ParseResult<ReferenceType> {
+        self.take(Rule::DefinedTypeReference)?;
 
-        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference);
-        let item = self.parse_type_reference();
+        let module = self.optionally_parse(Rule::modulereference, &Self::parse_module_reference)?;
+        let item = self.parse_type_reference()?;
 
-        ReferenceType::new(module, item)
+        Ok(ReferenceType::new(module, item))
     }
 
-    fn parse_external_value_reference(&mut self) -> ReferenceType {
-        self.take(Rule::ExternalValueReference);
-        let module = self.parse_module_reference();
-        let mut reference = self.parse_value_reference();
+    fn parse_external_value_reference(&mut self) -> ParseResult<ReferenceType> {
+        self.take(Rule::ExternalValueReference)?;
+        let module = self.parse_module_reference()?;
+        let mut reference = self.parse_value_reference()?;
 
         reference.module = Some(module);
 
-        reference
+        Ok(reference)
     }
 
-    fn parse_extension_and_exception(&mut self) -> Option<ExtensionAndException> {
-        if !self.peek(Rule::ExtensionAndException) {
-            return None;
-        }
-
-        self.take(Rule::ExtensionAndException);
+    fn parse_extension_and_exception(&mut self) -> ParseResult<ExtensionAndException> {
+        self.take(Rule::ExtensionAndException)?;
 
         if self.peek(Rule::ExceptionSpec) {
-            Some(ExtensionAndException::Exception(
-                self.parse_exception_spec(),
+            Ok(ExtensionAndException::Exception(
+                self.parse_exception_spec()?,
             ))
         } else {
-            Some(ExtensionAndException::Extension)
+            Ok(ExtensionAndException::Extension)
         }
     }
 
-    fn parse_extension_additions(&mut self) -> Vec<ExtensionAddition> {
-        self.take(Rule::ExtensionAdditions);
-        self.take(Rule::ExtensionAdditionList);
+    fn parse_extension_additions(&mut self) -> ParseResult<Vec<ExtensionAddition>> {
+        self.take(Rule::ExtensionAdditions)?;
+        self.take(Rule::ExtensionAdditionList)?;
         let mut additions = Vec::new();
 
         while self.look(Rule::ExtensionAddition).is_some() {
             if self.peek(Rule::ComponentType) {
-                additions.push(ExtensionAddition::Component(self.parse_component_type()))
+                additions.push(ExtensionAddition::Component(self.parse_component_type()?))
             } else {
                 let version = if self.peek(Rule::VersionNumber) {
-                    Some(self.parse_number())
+                    Some(self.parse_number()?)
                 } else {
                     None
                 };
 
-                let components = self.parse_component_type_list();
+                let components = self.parse_component_type_list()?;
 
                 additions.push(ExtensionAddition::Group(version, components));
             }
         }
 
-        additions
+        Ok(additions)
     }
 
     /// Returns just whether or not the extension marker was present, while
@@ -1544,63 +1876,93 @@ impl<'a> Parser<'a> {
         self.look(Rule::OptionalExtensionMarker).is_some()
     }
 
-    fn parse_boolean_value(&mut self) -> Value {
-        Value::Boolean(self.take(Rule::BooleanValue).as_str().contains("TRUE"))
+    fn parse_boolean_value(&mut self) -> ParseResult<Value> {
+        Ok(Value::Boolean(
+            self.take(Rule::BooleanValue)?.as_str().contains("TRUE"),
+        ))
     }
 
-    fn parse_enumeration(&mut self) -> Vec<Enumeration> {
-        self.take(Rule::Enumeration);
+    fn parse_enumeration(&mut self) -> ParseResult<Vec<Enumeration>> {
+        self.take(Rule::Enumeration)?;
 
         let mut enumerations = Vec::new();
 
         while self.look(Rule::EnumerationItem).is_some() {
             let (name, number) = if self.peek(Rule::NamedNumber) {
-                let (name, number) = self.parse_named_number();
+                let (name, number) = self.parse_named_number()?;
                 (name, Some(number))
             } else {
-                (self.parse_identifier(), None)
+                (self.parse_identifier()?, None)
             };
 
             enumerations.push(Enumeration::new(name, number));
         }
 
-        enumerations
+        Ok(enumerations)
     }
 
-    fn parse_named_number(&mut self) -> (String, Number) {
-        self.take(Rule::NamedNumber);
+    fn parse_named_number(&mut self) -> ParseResult<(String, Number)> {
+        self.take(Rule::NamedNumber)?;
 
-        let name = self.parse_identifier();
+        let name = self.parse_identifier()?;
 
         let number = if self.peek(Rule::SignedNumber) {
-            Number::Literal(self.parse_signed_number())
+            Number::Literal(self.parse_signed_number()?)
         } else {
-            Number::DefinedValue(self.parse_defined_value())
+            Number::DefinedValue(self.parse_defined_value()?)
         };
 
-        (name, number)
+        Ok((name, number))
     }
 
-    fn parse_exception_spec(&mut self) -> ExceptionIdentification {
-        self.take(Rule::ExceptionSpec);
-        self.take(Rule::ExceptionIdentification);
+    fn parse_exception_spec(&mut self) -> ParseResult<ExceptionIdentification> {
+        self.take(Rule::ExceptionSpec)?;
+        self.take(Rule::ExceptionIdentification)?;
 
         match self.rule_peek() {
-            Rule::SignedNumber => ExceptionIdentification::Number(self.parse_signed_number()),
-            Rule::DefinedValue => ExceptionIdentification::Reference(self.parse_defined_value()),
-            Rule::Type => {
-                ExceptionIdentification::Arbitrary(Box::new(self.parse_type()), self.parse_value())
-            }
-            _ => unreachable!(),
+            Rule::SignedNumber => Ok(ExceptionIdentification::Number(self.parse_signed_number()?)),
+            Rule::DefinedValue => Ok(ExceptionIdentification::Reference(
+                self.parse_defined_value()?,
+            )),
+            Rule::Type => Ok(ExceptionIdentification::Arbitrary(
+                Box::new(self.parse_type()?),
+                self.parse_value()?,
+            )),
+            rule => return Err(self.unexpected(rule)),
         }
     }
 }
 
+/// The error for a `pair` that the parser didn't expect.
+fn unexpected_pair(pair: &Pair<Rule>) -> Diagnostic {
+    Diagnostic::error(
+        format!("unexpected {:?}", pair.as_rule()),
+        Some(pair.as_span().into()),
+    )
+}
+
+/// Parses the integer literal in `pair`, which is an error if it's out of
+/// range for `T`.
+fn parse_int<T: FromStr>(pair: &Pair<Rule>) -> ParseResult<T> {
+    pair.as_str().parse().map_err(|_| {
+        Diagnostic::error(
+            format!("`{}` is out of range", pair.as_str()),
+            Some(pair.as_span().into()),
+        )
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct Assignment {
     pub name: String,
     pub kind: AssignmentType,
     pub parameters: Option<Vec<(Option<ParamGovernor>, String)>>,
+    pub span: Span,
+    /// Where the assignment's name is.
+    pub name_span: Span,
+    /// Where the references and component names in the assignment first
+    /// appear.
+    pub names: BTreeMap<String, Span>,
 }
 
 impl Assignment {
@@ -1608,11 +1970,17 @@ impl Assignment {
         name: String,
         kind: AssignmentType,
         parameters: Option<Vec<(Option<ParamGovernor>, String)>>,
+        span: Span,
+        name_span: Span,
+        names: BTreeMap<String, Span>,
     ) -> Self {
         Self {
             name,
             kind,
             parameters,
+            span,
+            name_span,
+            names,
         }
     }
 }
@@ -1636,8 +2004,14 @@ pub enum Constraint {
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Variation)]
 pub enum GeneralConstraint {
+    /// The object set, and the component references. Relative references
+    /// (`@.id`) start with a `"."` for each level.
     Table(ObjectReference, Vec<Vec<String>>),
     ObjectSet(ElementSet, bool),
+    /// `CONSTRAINED BY { ... }`, which is never PER-visible.
+    UserDefined,
+    /// `CONTAINING Type ENCODED BY Value`, where at least one is present.
+    Contents(Option<Type>, Option<Value>),
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Variation)]
@@ -1653,6 +2027,8 @@ pub enum SubTypeElement {
     Value(Value),
     Type(Type),
     Size(Constraint),
+    PermittedAlphabet(Constraint),
+    Pattern(Value),
     Range(RangeValue, RangeValue),
     Constraint(Constraint),
     FullSpec(BTreeMap<String, ComponentConstraint>),
@@ -1710,8 +2086,12 @@ pub enum Token {
 pub struct ParameterList(#[deref(mutable)] Vec<Parameter>);
 
 impl fmt::Display for ParameterList {
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        unimplemented!("fmt::Display for ParameterList")
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{{ {} }}",
+            itertools::join(self.0.iter().map(|p| format!("{:?}", p)), ", ")
+        )
     }
 }
 
@@ -1746,3 +2126,58 @@ impl ComponentConstraint {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(source: &str) -> (String, &str) {
+        let diagnostic = Parser::parse(source).unwrap_err();
+        let span = diagnostic.span.unwrap();
+
+        (diagnostic.message, &source[span.start..span.end])
+    }
+
+    #[test]
+    fn out_of_range_numbers_are_errors() {
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      x INTEGER ::= 99999999999999999999\n\
+                      END\n";
+        assert_eq!(
+            (
+                String::from("`99999999999999999999` is out of range"),
+                "99999999999999999999"
+            ),
+            error(source)
+        );
+
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      A ::= [99999999999999999999] INTEGER\n\
+                      END\n";
+        assert_eq!(
+            (
+                String::from("`99999999999999999999` is out of range"),
+                "99999999999999999999"
+            ),
+            error(source)
+        );
+
+        let source = "Test { 1 99999999999999999999 } DEFINITIONS ::= BEGIN END\n";
+        assert!(Parser::parse_header(source).is_err());
+    }
+
+    #[test]
+    fn unexpected_pairs_are_errors() {
+        let source = "Test DEFINITIONS ::= BEGIN END\n";
+        let mut parser = Parser::new(Rule::ModuleDefinition, source).unwrap();
+
+        let diagnostic = parser.take(Rule::EOI).unwrap_err();
+        assert_eq!("expected EOI, found ModuleDefinition", diagnostic.message);
+        assert_eq!(Some(Span::new(0, source.len())), diagnostic.span);
+
+        while parser.0.next().is_some() {}
+        let diagnostic = parser.take(Rule::EOI).unwrap_err();
+        assert_eq!("unexpected end of module", diagnostic.message);
+        assert!(parser.parse_reference().is_err());
+    }
+}
@@ -11,6 +11,19 @@ pub struct Type {
     pub constraints: Option<Vec<Constraint>>,
 }
 
+impl Type {
+    /// Returns the type and encoding from the type's `CONTAINING` constraint,
+    /// if it has one.
+    pub fn contents_constraint(&self) -> Option<(Option<&Type>, Option<&Value>)> {
+        self.constraints.iter().flatten().find_map(|c| match c {
+            Constraint::General(GeneralConstraint::Contents(ty, encoding)) => {
+                Some((ty.as_ref(), encoding.as_ref()))
+            }
+            _ => None,
+        })
+    }
+}
+
 impl From<RawType> for Type {
     fn from(raw_type: RawType) -> Self {
         Type {
@@ -29,6 +42,17 @@ pub enum RawType {
     ReferencedFromObject(FieldReference),
 }
 
+impl fmt::Display for RawType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RawType::Builtin(builtin) => builtin.fmt(f),
+            RawType::ParameterizedReference(reference, _) => write!(f, "{}{{...}}", reference),
+            RawType::Referenced(reference) => reference.fmt(f),
+            RawType::ReferencedFromObject(_) => f.write_str("information object field"),
+        }
+    }
+}
+
 impl From<BuiltinType> for RawType {
     fn from(builtin: BuiltinType) -> Self {
         RawType::Builtin(builtin)
@@ -64,6 +88,35 @@ pub enum BuiltinType {
     SetOf(Box<Type>),
 }
 
+/// Formats the type's keyword, e.g. `SEQUENCE OF` or `IA5String`.
+impl fmt::Display for BuiltinType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuiltinType::Boolean => f.write_str("BOOLEAN"),
+            BuiltinType::BitString(_) => f.write_str("BIT STRING"),
+            BuiltinType::CharacterString(string) => string.fmt(f),
+            BuiltinType::Choice(_) => f.write_str("CHOICE"),
+            BuiltinType::Enumeration(..) => f.write_str("ENUMERATED"),
+            BuiltinType::Integer(_) => f.write_str("INTEGER"),
+            BuiltinType::Null => f.write_str("NULL"),
+            BuiltinType::ObjectClassField(..) => f.write_str("object class field"),
+            BuiltinType::ObjectIdentifier => f.write_str("OBJECT IDENTIFIER"),
+            BuiltinType::OctetString => f.write_str("OCTET STRING"),
+            BuiltinType::Prefixed(prefix, ty) => {
+                f.write_str("[")?;
+                if let Some(class) = prefix.class {
+                    write!(f, "{} ", class.to_string().to_uppercase())?;
+                }
+                write!(f, "{}] {}", prefix.number, ty.raw_type)
+            }
+            BuiltinType::Sequence(_) => f.write_str("SEQUENCE"),
+            BuiltinType::SequenceOf(_) => f.write_str("SEQUENCE OF"),
+            BuiltinType::Set(_) => f.write_str("SET"),
+            BuiltinType::SetOf(_) => f.write_str("SET OF"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct ReferenceType {
     pub module: Option<String>,
@@ -108,6 +161,29 @@ pub enum CharacterStringType {
     Visible,
 }
 
+impl fmt::Display for CharacterStringType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            CharacterStringType::Bmp => "BMPString",
+            CharacterStringType::General => "GeneralString",
+            CharacterStringType::Graphic => "GraphicString",
+            CharacterStringType::Ia5 => "IA5String",
+            CharacterStringType::Iso646 => "ISO646String",
+            CharacterStringType::Numeric => "NumericString",
+            CharacterStringType::Printable => "PrintableString",
+            CharacterStringType::T61 => "T61String",
+            CharacterStringType::Teletex => "TeletexString",
+            CharacterStringType::Universal => "UniversalString",
+            CharacterStringType::Unrestricted => "CHARACTER STRING",
+            CharacterStringType::Utf8 => "UTF8String",
+            CharacterStringType::Videotex => "VideotexString",
+            CharacterStringType::Visible => "VisibleString",
+        };
+
+        f.write_str(name)
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct ChoiceType {
     pub alternatives: Vec<Type>,
@@ -124,6 +200,14 @@ impl Enumeration {
     pub fn new(name: String, number: Option<Number>) -> Self {
         Self { name, number }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn number(&self) -> Option<&Number> {
+        self.number.as_ref()
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Variation)]
@@ -11,6 +11,7 @@ use super::ParameterList;
 pub enum Value {
     BitString(BitString),
     Boolean(bool),
+    CharacterString(String),
     Defined(DefinedValue),
     Enumerated(String),
     FromObject(FieldReference),
@@ -5,8 +5,6 @@ use std::{
     path::PathBuf,
 };
 
-use unwrap_to::unwrap_to;
-
 use crate::{parser::*, Result};
 
 #[derive(Debug, Default)]
@@ -68,66 +66,6 @@ impl<K: Ord, V> DerefMut for SymbolTable<V, K> {
     }
 }
 
-impl SymbolTable<(Type, Value)> {
-    pub fn resolve_object_identifiers(&mut self) {
-        debug!("Resolving OIDs to full path.");
-        let total_length = self
-            .map
-            .iter()
-            .filter(|(_, (_, v))| v.is_object_identifier())
-            .count();
-
-        trace!("Total number of OIDs: {}", total_length);
-
-        let mut absolute_oids: BTreeMap<String, ObjectIdentifier> = self
-            .map
-            .clone()
-            .into_iter()
-            .filter(|(_, (_, v))| {
-                v.is_object_identifier() && unwrap_to!(v => Value::ObjectIdentifier).is_absolute()
-            })
-            .map(|(k, (_, v))| {
-                (
-                    k,
-                    match v {
-                        Value::ObjectIdentifier(s) => s,
-                        _ => unreachable!(),
-                    },
-                )
-            })
-            .collect();
-
-        trace!("Number of initial Absolute OIDs: {}", absolute_oids.len());
-
-        while total_length > absolute_oids.len() {
-            for (name, object_identifier) in self
-                .get_object_identifiers_mut()
-                .filter(|(_, o)| o.is_relative())
-            {
-                trace!("Attempting to canonicalise {}", object_identifier);
-                object_identifier.replace(&absolute_oids);
-                if object_identifier.is_absolute() {
-                    trace!("{} is now absolute.", object_identifier);
-                    absolute_oids.insert(name.clone(), object_identifier.clone());
-                    trace!(
-                        "New number of Absolute OIDs: {}, remaining {}",
-                        absolute_oids.len(),
-                        total_length - absolute_oids.len()
-                    );
-                }
-            }
-        }
-    }
-
-    fn get_object_identifiers_mut(
-        &mut self,
-    ) -> impl Iterator<Item = (&String, &mut ObjectIdentifier)> {
-        self.map
-            .iter_mut()
-            .filter_map(|(k, (_, v))| v.as_object_identifier_mut().map(|v| (k, v)))
-    }
-}
-
 impl SymbolTable<PathBuf, ModuleIdentifier> {
     pub fn new(dependencies: Option<PathBuf>) -> Result<Self> {
         let mut map = BTreeMap::new();
@@ -1,15 +1,32 @@
-use std::{collections::BTreeMap, mem};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    mem,
+};
 
 use failure::ensure;
 use unwrap_to::unwrap_to;
 
-use crate::{parser::*, registry::*, Result};
+use crate::{
+    diagnostics::{Diagnostics, Span},
+    parser::*,
+    registry::*,
+    Result,
+};
 
 #[derive(Debug)]
 pub struct SemanticChecker {
     pub imports: BTreeMap<ModuleReference, Vec<String>>,
     pub module: Module,
     pub table: GlobalSymbolTable,
+    /// Where each assignment, and the names used in it, are in the source.
+    pub spans: Spans,
+    pub diagnostics: Diagnostics,
+    /// The names of every assignment, including the information object
+    /// assignments that aren't kept in `table`.
+    names: BTreeSet<String>,
+    /// The names of the parameters of each assignment.
+    parameters: BTreeMap<String, BTreeSet<String>>,
     // object_sets: ValueRegistry,
     // objects: ValueRegistry,
     // classes: ValueRegistry,
@@ -24,30 +41,49 @@ impl SemanticChecker {
             imports,
             module,
             table,
+            spans: Spans::default(),
+            diagnostics: Diagnostics::new(),
+            names: BTreeSet::new(),
+            parameters: BTreeMap::new(),
         }
     }
 
+    /// Resolves the module's assignments. Problems found in the module are
+    /// collected in `diagnostics` rather than stopping at the first one.
     pub fn build(&mut self) -> Result<()> {
         debug!("Building {}", self.module.identifier);
         self.resolve_imports()?;
         self.resolve_assignments()?;
-        self.resolve_type_aliases();
-        debug!("Skipping resolving object identifiers");
-        //self.values.resolve_object_identifiers();
+        self.resolve_named_values();
+        self.check_references();
         self.resolve_defined_values();
+        self.resolve_object_identifiers();
+        self.resolve_constraints();
+        self.check_tags();
         Ok(())
     }
 
     pub fn resolve_assignments(&mut self) -> Result<()> {
         debug!("Resolving assignments");
         for assignment in mem::replace(&mut self.module.assignments, Vec::new()) {
-            ensure!(
-                !self.contains_assignment(&assignment.name),
-                "{:?} was already defined.",
-                assignment.name
-            );
+            if !self.names.insert(assignment.name.clone()) {
+                self.diagnostics.error(
+                    format!("`{}` is defined multiple times", assignment.name),
+                    Some(assignment.name_span),
+                );
+                continue;
+            }
 
-            //debug!("ASSIGNMENT KIND: {:#?}", assignment.kind);
+            self.spans.insert(&assignment);
+            self.parameters.insert(
+                assignment.name.clone(),
+                assignment
+                    .parameters
+                    .iter()
+                    .flatten()
+                    .map(|(_, parameter)| parameter.clone())
+                    .collect(),
+            );
 
             match assignment.kind {
                 AssignmentType::Type(ty) => {
@@ -57,11 +93,16 @@ impl SemanticChecker {
                     self.table.insert_value(assignment.name, ty, value);
                 }
                 AssignmentType::ValueSet(ty, elements) => {
-                    ensure!(
-                        elements.set.len() != 0,
-                        "{:?} is empty, empty element sets are not allowed.",
-                        assignment.name
-                    );
+                    if elements.set.is_empty() {
+                        self.diagnostics.error(
+                            format!(
+                                "`{}` is empty, empty element sets are not allowed",
+                                assignment.name
+                            ),
+                            Some(assignment.span),
+                        );
+                        continue;
+                    }
 
                     self.table.insert_value_set(assignment.name, ty, elements);
                 }
@@ -83,29 +124,86 @@ impl SemanticChecker {
         Ok(())
     }
 
-    fn contains_assignment(&self, name: &String) -> bool {
-        self.table.contains_key(name)
-    }
+    /// Reports references to types and values that aren't defined in, or
+    /// imported into, the module.
+    fn check_references(&mut self) {
+        debug!("Checking references");
+        let imported = imported_names(&self.imports);
+        let is_known = |name: &str| self.names.contains(name) || imported.contains(name);
 
-    pub fn resolve_type_aliases(&mut self) {
-        debug!("Resolving type aliases.");
-        for t in self
+        let mut unresolved = Vec::new();
+        let types = self
             .table
-            .values
-            .iter_mut()
-            .map(|(_, (t, _))| t)
-            .filter(|t| t.is_referenced())
-        {
-            let reference = unwrap_to!(t.raw_type => RawType::Referenced);
+            .types
+            .iter()
+            .chain(self.table.values.iter().map(|(name, (ty, _))| (name, ty)))
+            .chain(
+                self.table
+                    .value_sets
+                    .iter()
+                    .map(|(name, (ty, _))| (name, ty)),
+            );
+
+        for (name, ty) in types {
+            let parameters = &self.parameters[name];
+            let mut references = Vec::new();
+            referenced_types(ty, &mut references);
 
-            if reference.is_internal() {
-                if let Some(original_type) = self.table.types.get(&reference.item) {
-                    // TODO: How do constraints work across type alias?
-                    // Might be defined in the spec
-                    *t = original_type.clone();
+            for reference in references.into_iter().filter(|r| r.is_internal()) {
+                if !is_known(&reference.item) && !parameters.contains(&reference.item) {
+                    let message = format!("cannot find type `{}`", reference.item);
+                    unresolved.push((self.spans.name_in(name, &reference.item), message));
+                }
+            }
+        }
+
+        for (name, (_, value)) in self.table.values.iter() {
+            if let Value::Defined(DefinedValue::Simple(reference)) = value {
+                if reference.is_internal() && !is_known(&reference.item) {
+                    let message = format!("cannot find value `{}`", reference.item);
+                    unresolved.push((self.spans.name_in(name, &reference.item), message));
                 }
-            } else {
-                unimplemented!("External reference types not available yet");
+            }
+        }
+
+        for (span, message) in unresolved {
+            self.diagnostics.error(message, span);
+        }
+    }
+
+    /// Replaces identifiers in values of `ENUMERATED` types, or `INTEGER`
+    /// types with named numbers, with the value they name.
+    fn resolve_named_values(&mut self) {
+        debug!("Resolving named values");
+        let types = &self.table.types;
+
+        for (ty, value) in self.table.values.values_mut() {
+            let name = match value {
+                Value::Defined(DefinedValue::Simple(reference)) if reference.is_internal() => {
+                    &reference.item
+                }
+                _ => continue,
+            };
+
+            let named = match &resolve_type(ty, types).raw_type {
+                RawType::Builtin(BuiltinType::Enumeration(root, _, additions)) => root
+                    .iter()
+                    .chain(additions.iter().flatten())
+                    .any(|e| e.name() == name)
+                    .then(|| Value::Enumerated(name.clone())),
+                RawType::Builtin(BuiltinType::Integer(named_numbers)) => {
+                    match named_numbers.get(name) {
+                        Some(Number::Literal(number)) => {
+                            Some(Value::Integer(IntegerValue::Literal(*number)))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some(named) = named {
+                *value = named;
             }
         }
     }
@@ -113,39 +211,56 @@ impl SemanticChecker {
     pub fn resolve_defined_values(&mut self) {
         debug!("Resolving defined values");
         let frozen_map = self.table.values.clone();
-        let get_value = |defined_value: &mut DefinedValue| {
+        let diagnostics = &mut self.diagnostics;
+        let spans = &self.spans;
+        // Unresolved internal references were already reported by
+        // `check_references`, so only unsupported references are reported.
+        let mut get_value = |name: Option<&str>, defined_value: &DefinedValue| {
             let value = match defined_value {
                 DefinedValue::Simple(v) => v,
-                DefinedValue::Parameterized(_, _) => {
-                    unimplemented!("Parameterized defined values are not currently supported")
+                DefinedValue::Parameterized(reference, _) => {
+                    diagnostics.warning(
+                        format!(
+                            "parameterized value `{}` isn't supported yet",
+                            reference.item
+                        ),
+                        name.and_then(|name| spans.name_in(name, &reference.item)),
+                    );
+                    return None;
                 }
             };
+            let span = name.and_then(|name| spans.name_in(name, &value.item));
 
-            let original_value = if value.is_internal() {
-                match frozen_map.get(&*value.item).map(|(_, v)| v) {
-                    Some(v) => v,
-                    None => panic!("Couldn't find {:?} value", value.item),
-                }
+            if value.is_internal() {
+                frozen_map.get(&*value.item).map(|(_, v)| v.clone())
             } else {
-                unimplemented!("External defines are not currently supported")
-            };
-
-            original_value.clone()
+                diagnostics.warning(
+                    format!(
+                        "`{}.{}` is defined in another module, which isn't supported yet",
+                        value.module.as_ref().unwrap(),
+                        value.item
+                    ),
+                    span,
+                );
+                None
+            }
         };
 
-        for value in self
+        for (name, value) in self
             .table
             .values
             .iter_mut()
-            .map(|(_, (_, v))| v)
-            .filter(|v| v.is_defined())
+            .map(|(name, (_, v))| (name, v))
+            .filter(|(_, v)| v.is_defined())
         {
             let def = match value {
                 Value::Defined(def) => def,
                 _ => unreachable!(),
             };
 
-            *value = get_value(def);
+            if let Some(resolved) = get_value(Some(name), def) {
+                *value = resolved;
+            }
         }
 
         let defined_values_imports = self
@@ -157,13 +272,124 @@ impl SemanticChecker {
             .filter(|a| a.is_defined());
 
         for value in defined_values_imports {
-            let mut def = match value {
+            let def = match value {
                 AssignedIdentifier::Defined(def) => def,
                 _ => unreachable!(),
             };
 
-            *value =
-                AssignedIdentifier::ObjectIdentifier(get_value(&mut def).into_object_identifier());
+            if let Some(resolved) = get_value(None, def) {
+                *value = AssignedIdentifier::ObjectIdentifier(resolved.into_object_identifier());
+            }
+        }
+    }
+
+    /// Replaces the components of every `OBJECT IDENTIFIER` value with the
+    /// numbers of its arcs, following references to other values.
+    fn resolve_object_identifiers(&mut self) {
+        debug!("Resolving object identifiers");
+        let types = &self.table.types;
+        for (ty, value) in self.table.values.values_mut() {
+            let sequence = match (&resolve_type(ty, types).raw_type, &*value) {
+                (RawType::Builtin(BuiltinType::Sequence(_)), Value::ObjectIdentifier(oid))
+                | (RawType::Builtin(BuiltinType::Set(_)), Value::ObjectIdentifier(oid)) => {
+                    sequence_value(oid)
+                }
+                _ => None,
+            };
+
+            if let Some(sequence) = sequence {
+                *value = sequence;
+            }
+        }
+
+        let mut resolver = OidResolver {
+            types: &self.table.types,
+            values: &self.table.values,
+            resolved: BTreeMap::new(),
+        };
+
+        let names: Vec<_> = self
+            .table
+            .values
+            .keys()
+            .filter(|name| resolver.is_object_identifier(name))
+            .cloned()
+            .collect();
+        let mut resolved = Vec::new();
+
+        for name in names {
+            match resolver.resolve(&name, 0) {
+                Ok(Some(arcs)) => resolved.push((name, arcs)),
+                // Arcs that reference values from other modules are left as
+                // they are.
+                Ok(None) => {}
+                Err((item, message)) => {
+                    let span = self.spans.name_in(&name, &item);
+                    self.diagnostics.error(message, span);
+                }
+            }
+        }
+
+        for (name, arcs) in resolved {
+            let components = arcs
+                .into_iter()
+                .map(|arc| ObjIdComponent::Number(Number::Literal(i64::from(arc))))
+                .collect();
+
+            self.table.values.get_mut(&name).unwrap().1 =
+                Value::ObjectIdentifier(ObjectIdentifier::from_components(components));
+        }
+    }
+
+    /// Reports components of `SEQUENCE`, `SET` and `CHOICE` types whose tags
+    /// would make the encoding ambiguous (X.680 §25.5, §27.3 and §29.2).
+    fn check_tags(&mut self) {
+        debug!("Checking tags");
+        let automatic = self.module.tag == Tag::Automatic;
+        let mut clashes = Vec::new();
+
+        for (name, ty) in self.table.types.iter() {
+            let mut checker = TagChecker {
+                types: &self.table.types,
+                automatic,
+                clashes: Vec::new(),
+            };
+            checker.check(name, ty);
+
+            clashes.extend(checker.clashes.into_iter().map(|clash| (name, clash)));
+        }
+
+        for (name, (component, clash)) in clashes {
+            self.diagnostics
+                .error(clash, self.spans.name_in(name, &component));
+        }
+    }
+
+    /// Resolves the effective constraints of every type, so that code
+    /// generation doesn't need to look outside of the type itself. Value
+    /// references in constraints are replaced with their values, and
+    /// constraints on a referenced type are applied before the type's own.
+    pub fn resolve_constraints(&mut self) {
+        debug!("Resolving constraints");
+        let frozen_types = self.table.types.clone();
+        let mut scope = ConstraintScope {
+            values: &self.table.values,
+            imported: imported_names(&self.imports),
+            unresolved: BTreeSet::new(),
+        };
+
+        for (name, ty) in self.table.types.iter_mut() {
+            resolve_type_constraints(ty, &frozen_types, &mut scope);
+
+            // Parameters of the type are only known once it's instantiated.
+            let parameters = &self.parameters[name];
+            let unresolved = mem::take(&mut scope.unresolved);
+            for value in unresolved.into_iter().filter(|v| !parameters.contains(v)) {
+                self.diagnostics.error(
+                    format!("cannot find value `{}`", value),
+                    self.spans.name_in(name, &value),
+                );
+            }
         }
     }
 
@@ -181,3 +407,792 @@ impl SemanticChecker {
         Ok(())
     }
 }
+
+/// Where each assignment, and the names used in it, are in the source.
+#[derive(Debug, Default)]
+pub struct Spans {
+    assignments: BTreeMap<String, Span>,
+    names: BTreeMap<String, BTreeMap<String, Span>>,
+}
+
+impl Spans {
+    fn insert(&mut self, assignment: &Assignment) {
+        self.assignments
+            .insert(assignment.name.clone(), assignment.span);
+        self.names
+            .insert(assignment.name.clone(), assignment.names.clone());
+    }
+
+    /// Where the assignment `name` is.
+    pub fn get(&self, name: &str) -> Option<&Span> {
+        self.assignments.get(name)
+    }
+
+    /// Where `item` first appears in the assignment `name`, or where the
+    /// assignment is if `item` doesn't appear in it.
+    pub fn name_in(&self, name: &str, item: &str) -> Option<Span> {
+        self.names
+            .get(name)
+            .and_then(|names| names.get(item))
+            .or_else(|| self.get(name))
+            .copied()
+    }
+}
+
+/// The maximum number of type references followed when resolving
+/// constraints or tags, to avoid looping on recursive definitions.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+/// The names of every symbol imported into the module.
+fn imported_names(imports: &BTreeMap<ModuleReference, Vec<String>>) -> BTreeSet<&str> {
+    imports
+        .values()
+        .flatten()
+        .map(|name| name.trim_end_matches("{}"))
+        .collect()
+}
+
+/// The values that references in constraints can resolve to, and the
+/// references that couldn't be resolved.
+struct ConstraintScope<'a> {
+    values: &'a BTreeMap<String, (Type, Value)>,
+    imported: BTreeSet<&'a str>,
+    unresolved: BTreeSet<String>,
+}
+
+impl ConstraintScope<'_> {
+    /// Replaces a reference to a value with the value, looking in the named
+    /// numbers or items of the constrained type first. References to
+    /// imported values are left as they are.
+    fn resolve(&mut self, value: &mut Value, named: &BTreeMap<String, Value>) {
+        let reference = match value {
+            Value::Defined(DefinedValue::Simple(reference)) if reference.is_internal() => reference,
+            _ => return,
+        };
+
+        if let Some(resolved) = named.get(&reference.item) {
+            *value = resolved.clone();
+        } else if let Some((_, resolved)) = self.values.get(&reference.item) {
+            *value = resolved.clone();
+        } else if !self.imported.contains(&*reference.item) {
+            self.unresolved.insert(reference.item.clone());
+        }
+    }
+}
+
+/// The values that the named numbers of an `INTEGER` type, or the items of
+/// an `ENUMERATED` type, stand for in its constraints.
+fn named_values(ty: &Type, types: &BTreeMap<String, Type>) -> BTreeMap<String, Value> {
+    match &resolve_type(ty, types).raw_type {
+        RawType::Builtin(BuiltinType::Integer(named_numbers)) => named_numbers
+            .iter()
+            .filter_map(|(name, number)| match number {
+                Number::Literal(number) => {
+                    Some((name.clone(), Value::Integer(IntegerValue::Literal(*number))))
+                }
+                Number::DefinedValue(_) => None,
+            })
+            .collect(),
+        RawType::Builtin(BuiltinType::Enumeration(root, _, additions)) => root
+            .iter()
+            .chain(additions.iter().flatten())
+            .map(|e| (e.name().to_owned(), Value::Enumerated(e.name().to_owned())))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+fn resolve_type_constraints(
+    ty: &mut Type,
+    types: &BTreeMap<String, Type>,
+    scope: &mut ConstraintScope,
+) {
+    let named = named_values(ty, types);
+    let mut constraints = match &ty.raw_type {
+        RawType::Referenced(reference) if reference.is_internal() => {
+            referenced_constraints(&reference.item, types, 0)
+        }
+        _ => Vec::new(),
+    };
+    constraints.extend(ty.constraints.take().into_iter().flatten());
+
+    for constraint in &mut constraints {
+        resolve_constraint_values(constraint, &named, scope);
+    }
+
+    if !constraints.is_empty() {
+        ty.constraints = Some(constraints);
+    }
+
+    let mut resolve_component = |component: &mut ComponentType| {
+        if let ComponentType::Type { ty, .. } = component {
+            resolve_type_constraints(ty, types, scope);
+        }
+    };
+
+    match &mut ty.raw_type {
+        RawType::Builtin(BuiltinType::Sequence(list))
+        | RawType::Builtin(BuiltinType::Set(Set::Concrete(list))) => {
+            list.components
+                .iter_mut()
+                .flatten()
+                .for_each(&mut resolve_component);
+
+            if let Some(extension) = &mut list.extension {
+                for addition in &mut extension.additions {
+                    match addition {
+                        ExtensionAddition::Component(component) => resolve_component(component),
+                        ExtensionAddition::Group(_, components) => {
+                            components.iter_mut().for_each(&mut resolve_component)
+                        }
+                    }
+                }
+
+                if let ExtensionMarker::End(components) = &mut extension.marker {
+                    components.iter_mut().for_each(&mut resolve_component);
+                }
+            }
+        }
+        RawType::Builtin(BuiltinType::Choice(choice)) => {
+            for alternative in &mut choice.alternatives {
+                resolve_type_constraints(alternative, types, scope);
+            }
+        }
+        RawType::Builtin(BuiltinType::SequenceOf(inner))
+        | RawType::Builtin(BuiltinType::SetOf(inner))
+        | RawType::Builtin(BuiltinType::Prefixed(_, inner)) => {
+            resolve_type_constraints(inner, types, scope);
+        }
+        _ => {}
+    }
+}
+
+/// Collects the constraints of the type named `name`, including the
+/// constraints of any types it references.
+fn referenced_constraints(
+    name: &str,
+    types: &BTreeMap<String, Type>,
+    depth: usize,
+) -> Vec<Constraint> {
+    let ty = match types.get(name) {
+        Some(ty) if depth < MAX_REFERENCE_DEPTH => ty,
+        _ => return Vec::new(),
+    };
+
+    let mut constraints = match &ty.raw_type {
+        RawType::Referenced(reference) if reference.is_internal() => {
+            referenced_constraints(&reference.item, types, depth + 1)
+        }
+        _ => Vec::new(),
+    };
+    constraints.extend(ty.constraints.iter().flatten().cloned());
+
+    constraints
+}
+
+fn resolve_constraint_values(
+    constraint: &mut Constraint,
+    named: &BTreeMap<String, Value>,
+    scope: &mut ConstraintScope,
+) {
+    let set = match constraint {
+        Constraint::ElementSet(set, _) => set,
+        Constraint::General(_) => return,
+    };
+
+    for element in set.iter_mut().flatten() {
+        resolve_element_values(element, named, scope);
+    }
+}
+
+fn resolve_element_values(
+    element: &mut Element,
+    named: &BTreeMap<String, Value>,
+    scope: &mut ConstraintScope,
+) {
+    match element {
+        Element::ElementSet(set) => {
+            for element in set.iter_mut().flatten() {
+                resolve_element_values(element, named, scope);
+            }
+        }
+        Element::SubType(SubTypeElement::Value(value)) => scope.resolve(value, named),
+        Element::SubType(SubTypeElement::Range(low, high)) => {
+            for bound in [low, high].iter_mut() {
+                if let RangeValue::Value(value, _) = bound {
+                    scope.resolve(value, named);
+                }
+            }
+        }
+        // The bounds of a size constraint aren't values of the constrained
+        // type, so its named values don't apply.
+        Element::SubType(SubTypeElement::Size(constraint)) => {
+            resolve_constraint_values(constraint, &BTreeMap::new(), scope)
+        }
+        Element::SubType(SubTypeElement::PermittedAlphabet(constraint))
+        | Element::SubType(SubTypeElement::Constraint(constraint)) => {
+            resolve_constraint_values(constraint, named, scope)
+        }
+        _ => {}
+    }
+}
+
+/// Collects the types referenced by `ty` and any types nested inside it.
+fn referenced_types<'a>(ty: &'a Type, references: &mut Vec<&'a ReferenceType>) {
+    let component_types = |list: &'a ComponentTypeList| {
+        let extension = list.extension.iter().flat_map(|extension| {
+            let additions = extension
+                .additions
+                .iter()
+                .flat_map(|addition| match addition {
+                    ExtensionAddition::Component(component) => std::slice::from_ref(component),
+                    ExtensionAddition::Group(_, components) => &components[..],
+                });
+            let end = match &extension.marker {
+                ExtensionMarker::End(components) => &components[..],
+                ExtensionMarker::Extensible => &[],
+            };
+
+            additions.chain(end)
+        });
+
+        list.components.iter().flatten().chain(extension)
+    };
+
+    match &ty.raw_type {
+        RawType::Referenced(reference) | RawType::ParameterizedReference(reference, _) => {
+            references.push(reference)
+        }
+        RawType::Builtin(BuiltinType::Sequence(list))
+        | RawType::Builtin(BuiltinType::Set(Set::Concrete(list))) => {
+            for component in component_types(list) {
+                match component {
+                    ComponentType::Type { ty, .. } | ComponentType::ComponentsOf(ty) => {
+                        referenced_types(ty, references)
+                    }
+                }
+            }
+        }
+        RawType::Builtin(BuiltinType::Choice(choice)) => {
+            for alternative in &choice.alternatives {
+                referenced_types(alternative, references);
+            }
+        }
+        RawType::Builtin(BuiltinType::SequenceOf(inner))
+        | RawType::Builtin(BuiltinType::SetOf(inner))
+        | RawType::Builtin(BuiltinType::Prefixed(_, inner)) => referenced_types(inner, references),
+        _ => {}
+    }
+}
+
+/// Finds components that share a tag in the types nested in a type.
+struct TagChecker<'a> {
+    types: &'a BTreeMap<String, Type>,
+    automatic: bool,
+    /// The name of the second component of each clash, and the clash.
+    clashes: Vec<(String, String)>,
+}
+
+impl<'a> TagChecker<'a> {
+    fn check(&mut self, name: &str, ty: &Type) {
+        match &ty.raw_type {
+            RawType::Builtin(BuiltinType::Sequence(list)) => {
+                let components = self.root_components(list);
+
+                // Every optional component must have a tag that differs from
+                // the components up to and including the next required one.
+                for (i, (a, optional)) in components.iter().enumerate() {
+                    if !optional {
+                        continue;
+                    }
+
+                    for (b, optional) in &components[i + 1..] {
+                        self.compare(name, a, b);
+
+                        if !optional {
+                            break;
+                        }
+                    }
+                }
+
+                self.check_nested(list);
+            }
+            RawType::Builtin(BuiltinType::Set(Set::Concrete(list))) => {
+                let components = self.root_components(list);
+                let types: Vec<_> = components.iter().map(|(ty, _)| *ty).collect();
+                self.compare_all(name, &types);
+
+                self.check_nested(list);
+            }
+            RawType::Builtin(BuiltinType::Choice(choice)) => {
+                let alternatives: Vec<_> = choice.alternatives.iter().collect();
+                if !self.automatic || alternatives.iter().any(|ty| is_prefixed(ty)) {
+                    self.compare_all(name, &alternatives);
+                }
+
+                for alternative in alternatives {
+                    self.check(alternative.name.as_deref().unwrap_or(name), alternative);
+                }
+            }
+            RawType::Builtin(BuiltinType::SequenceOf(inner))
+            | RawType::Builtin(BuiltinType::SetOf(inner))
+            | RawType::Builtin(BuiltinType::Prefixed(_, inner)) => self.check(name, inner),
+            _ => {}
+        }
+    }
+
+    fn check_nested(&mut self, list: &ComponentTypeList) {
+        for component in list.components.iter().flatten() {
+            if let ComponentType::Type { ty, .. } = component {
+                if let Some(name) = &ty.name {
+                    self.check(name, ty);
+                }
+            }
+        }
+    }
+
+    /// The components in the root of `list` that are checked, along with
+    /// whether they're optional. Components tagged automatically never
+    /// clash, and nothing can be checked if the list uses `COMPONENTS OF`.
+    fn root_components<'b>(&self, list: &'b ComponentTypeList) -> Vec<(&'b Type, bool)> {
+        let components = match list
+            .components
+            .iter()
+            .flatten()
+            .map(|component| match component {
+                ComponentType::Type {
+                    ty,
+                    optional,
+                    default,
+                } => Some((ty, *optional || default.is_some())),
+                ComponentType::ComponentsOf(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(components) => components,
+            None => return Vec::new(),
+        };
+
+        if self.automatic && !components.iter().any(|(ty, _)| is_prefixed(ty)) {
+            return Vec::new();
+        }
+
+        components
+    }
+
+    fn compare_all(&mut self, name: &str, types: &[&Type]) {
+        for (i, a) in types.iter().enumerate() {
+            for b in &types[i + 1..] {
+                self.compare(name, a, b);
+            }
+        }
+    }
+
+    fn compare(&mut self, name: &str, a: &Type, b: &Type) {
+        let tag = match (self.tag(a, 0), self.tag(b, 0)) {
+            (Some(a), Some(b)) if a == b => a,
+            _ => return,
+        };
+
+        let b_name = b.name.as_deref().unwrap_or("?");
+        let clash = format!(
+            "`{}` and `{}` in `{}` have the same tag [{} {}]",
+            a.name.as_deref().unwrap_or("?"),
+            b_name,
+            name,
+            tag.0.to_string().to_uppercase(),
+            tag.1,
+        );
+        self.clashes.push((b_name.to_owned(), clash));
+    }
+
+    /// The outermost tag of `ty`. Untagged `CHOICE` types and open types
+    /// don't have a single tag, and aren't checked.
+    fn tag(&self, ty: &Type, depth: usize) -> Option<(Class, i64)> {
+        let universal = |number| Some((Class::Universal, number));
+
+        match &ty.raw_type {
+            RawType::Builtin(builtin) => match builtin {
+                BuiltinType::Prefixed(prefix, _) => match prefix.number {
+                    Number::Literal(number) => {
+                        Some((prefix.class.unwrap_or(Class::Context), number))
+                    }
+                    Number::DefinedValue(_) => None,
+                },
+                BuiltinType::Boolean => universal(1),
+                BuiltinType::Integer(_) => universal(2),
+                BuiltinType::BitString(_) => universal(3),
+                BuiltinType::OctetString => universal(4),
+                BuiltinType::Null => universal(5),
+                BuiltinType::ObjectIdentifier => universal(6),
+                BuiltinType::Enumeration(..) => universal(10),
+                BuiltinType::Sequence(_) | BuiltinType::SequenceOf(_) => universal(16),
+                BuiltinType::Set(_) | BuiltinType::SetOf(_) => universal(17),
+                BuiltinType::CharacterString(string) => universal(match string {
+                    CharacterStringType::Utf8 => 12,
+                    CharacterStringType::Numeric => 18,
+                    CharacterStringType::Printable => 19,
+                    CharacterStringType::T61 | CharacterStringType::Teletex => 20,
+                    CharacterStringType::Videotex => 21,
+                    CharacterStringType::Ia5 => 22,
+                    CharacterStringType::Graphic => 25,
+                    CharacterStringType::Visible | CharacterStringType::Iso646 => 26,
+                    CharacterStringType::General => 27,
+                    CharacterStringType::Universal => 28,
+                    CharacterStringType::Unrestricted => 29,
+                    CharacterStringType::Bmp => 30,
+                }),
+                BuiltinType::Choice(_) | BuiltinType::ObjectClassField(..) => None,
+            },
+            RawType::Referenced(reference)
+                if reference.is_internal() && depth < MAX_REFERENCE_DEPTH =>
+            {
+                self.tag(self.types.get(&reference.item)?, depth + 1)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_prefixed(ty: &Type) -> bool {
+    matches!(ty.raw_type, RawType::Builtin(BuiltinType::Prefixed(..)))
+}
+
+/// Follows references to other types from `ty`, and skips any tags, returning
+/// the type that defines `ty`'s values.
+pub fn resolve_type<'a>(ty: &'a Type, types: &'a BTreeMap<String, Type>) -> &'a Type {
+    let mut ty = ty;
+
+    for _ in 0..MAX_REFERENCE_DEPTH {
+        ty = match &ty.raw_type {
+            RawType::Referenced(reference) if reference.is_internal() => {
+                match types.get(&reference.item) {
+                    Some(referenced) => referenced,
+                    None => break,
+                }
+            }
+            RawType::Builtin(BuiltinType::Prefixed(_, inner)) => inner,
+            _ => break,
+        };
+    }
+
+    ty
+}
+
+/// The `SEQUENCE` or `SET` value with a single component that `oid` was
+/// written as, as the grammar parses `{ a 3 }` as an object identifier.
+fn sequence_value(oid: &ObjectIdentifier) -> Option<Value> {
+    let (name, value) = match &oid[..] {
+        [ObjIdComponent::Name(name), value] => (name, value),
+        _ => return None,
+    };
+
+    let value = match value {
+        ObjIdComponent::Number(Number::Literal(number)) => {
+            Value::Integer(IntegerValue::Literal(*number))
+        }
+        ObjIdComponent::Number(Number::DefinedValue(value)) => Value::Defined(value.clone()),
+        ObjIdComponent::Name(value) => Value::Defined(DefinedValue::Simple(ReferenceType::new(
+            None,
+            value.clone(),
+        ))),
+        ObjIdComponent::NameAndNumber(..) => return None,
+    };
+
+    Some(Value::Sequence(vec![NamedValue(name.clone(), value)]))
+}
+
+/// Resolves `OBJECT IDENTIFIER` values to the numbers of their arcs. Values
+/// with arcs that reference values which aren't defined in the module, such
+/// as imported values, resolve to `None`.
+struct OidResolver<'a> {
+    types: &'a BTreeMap<String, Type>,
+    values: &'a BTreeMap<String, (Type, Value)>,
+    resolved: BTreeMap<String, Vec<u32>>,
+}
+
+/// Errors are the name in the value that couldn't be resolved, and why.
+type OidResult<T> = std::result::Result<Option<T>, (String, String)>;
+
+impl<'a> OidResolver<'a> {
+    /// Whether the value `name` is declared as an `OBJECT IDENTIFIER`.
+    fn is_object_identifier(&self, name: &str) -> bool {
+        self.values.get(name).map_or(false, |(ty, _)| {
+            resolve_type(ty, self.types).raw_type == RawType::Builtin(BuiltinType::ObjectIdentifier)
+        })
+    }
+
+    fn resolve(&mut self, name: &str, depth: usize) -> OidResult<Vec<u32>> {
+        if let Some(arcs) = self.resolved.get(name) {
+            return Ok(Some(arcs.clone()));
+        }
+
+        if depth >= MAX_REFERENCE_DEPTH {
+            let message = format!("`{}` is defined in terms of itself", name);
+            return Err((name.to_owned(), message));
+        }
+
+        let components = match self.values.get(name) {
+            Some((_, Value::ObjectIdentifier(oid))) if self.is_object_identifier(name) => oid,
+            Some(_) => {
+                let message = format!("`{}` isn't an OBJECT IDENTIFIER value", name);
+                return Err((name.to_owned(), message));
+            }
+            None => return Ok(None),
+        };
+
+        let mut arcs: Vec<u32> = Vec::new();
+
+        for (i, component) in components.iter().enumerate() {
+            let arc = match component {
+                ObjIdComponent::Number(number) | ObjIdComponent::NameAndNumber(_, number) => {
+                    self.number(number)?
+                }
+                // A reference to another object identifier, which this one
+                // extends.
+                ObjIdComponent::Name(reference) if i == 0 && !component.is_reserved_name_form() => {
+                    // Errors in the referenced value are reported at the
+                    // reference.
+                    let root = self
+                        .resolve(reference, depth + 1)
+                        .map_err(|(_, message)| (reference.clone(), message))?;
+                    match root {
+                        Some(root) => arcs = root,
+                        None => return Ok(None),
+                    }
+                    continue;
+                }
+                ObjIdComponent::Name(arc) => match well_known_arc(&arcs, arc) {
+                    Some(arc) => Some(arc),
+                    None => self.number(&Number::DefinedValue(DefinedValue::Simple(
+                        ReferenceType::new(None, arc.clone()),
+                    )))?,
+                },
+            };
+
+            match arc {
+                Some(arc) => arcs.push(arc),
+                None => return Ok(None),
+            }
+        }
+
+        self.resolved.insert(name.to_owned(), arcs.clone());
+        Ok(Some(arcs))
+    }
+
+    fn number(&self, number: &Number) -> OidResult<u32> {
+        let number = match number {
+            Number::Literal(number) => *number,
+            Number::DefinedValue(DefinedValue::Simple(reference)) if reference.is_internal() => {
+                match self.values.get(&reference.item) {
+                    Some((_, Value::Integer(IntegerValue::Literal(number)))) => *number,
+                    Some(_) => {
+                        let message = format!("`{}` isn't an INTEGER value", reference.item);
+                        return Err((reference.item.clone(), message));
+                    }
+                    None => return Ok(None),
+                }
+            }
+            // A value from another module.
+            Number::DefinedValue(DefinedValue::Simple(_)) => return Ok(None),
+            Number::DefinedValue(value) => {
+                let item = match value {
+                    DefinedValue::Simple(reference) | DefinedValue::Parameterized(reference, _) => {
+                        reference.item.clone()
+                    }
+                };
+                let message = format!("`{}` isn't supported in object identifiers yet", value);
+                return Err((item, message));
+            }
+        };
+
+        u32::try_from(number).map(Some).map_err(|_| {
+            let message = format!("{} is out of range for an object identifier arc", number);
+            (number.to_string(), message)
+        })
+    }
+}
+
+/// The arcs that can be identified by name alone (X.660 Annex A).
+fn well_known_arc(parent: &[u32], name: &str) -> Option<u32> {
+    match (parent, name) {
+        ([], "itu-t") | ([], "ITU-T") | ([], "ccitt") => Some(0),
+        ([], "iso") | ([], "ISO") => Some(1),
+        ([], "joint-iso-itu-t") | ([], "Joint-ISO-ITU-T") | ([], "joint-iso-ccitt") => Some(2),
+        ([0], "recommendation") => Some(0),
+        ([0], "question") => Some(1),
+        ([0], "administration") => Some(2),
+        ([0], "network-operator") => Some(3),
+        ([0], "identified-organization") => Some(4),
+        ([1], "standard") => Some(0),
+        ([1], "registration-authority") => Some(1),
+        ([1], "member-body") => Some(2),
+        ([1], "identified-organization") => Some(3),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_every_error() {
+        let source = "Test DEFINITIONS IMPLICIT TAGS ::= BEGIN\n\
+                      A ::= SEQUENCE { a [0] INTEGER OPTIONAL, b [0] BOOLEAN }\n\
+                      B ::= Missing\n\
+                      A ::= INTEGER\n\
+                      END\n";
+        let (module, diagnostics) = Parser::parse(source).unwrap();
+        assert!(diagnostics.is_empty());
+
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
+
+        let mut errors: Vec<_> = checker
+            .diagnostics
+            .iter()
+            .filter(|d| d.is_error())
+            .map(|d| {
+                let span = d.span.unwrap();
+                (
+                    d.message.as_str(),
+                    span.start,
+                    &source[span.start..span.end],
+                )
+            })
+            .collect();
+        errors.sort();
+
+        let duplicate = source.rfind("A ::=").unwrap();
+        let b = source.find("b [0]").unwrap();
+        let missing = source.find("Missing").unwrap();
+        assert_eq!(
+            vec![
+                ("`A` is defined multiple times", duplicate, "A"),
+                ("`a` and `b` in `A` have the same tag [CONTEXT 0]", b, "b"),
+                ("cannot find type `Missing`", missing, "Missing"),
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn resolves_constraint_values() {
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      Version ::= INTEGER { v3(3) } (v3, ...)\n\
+                      Missing ::= INTEGER (0..unknown)\n\
+                      END\n";
+        let (module, _) = Parser::parse(source).unwrap();
+
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
+
+        let errors: Vec<_> = checker
+            .diagnostics
+            .iter()
+            .map(|d| {
+                let span = d.span.unwrap();
+                (d.message.as_str(), &source[span.start..span.end])
+            })
+            .collect();
+        assert_eq!(vec![("cannot find value `unknown`", "unknown")], errors);
+
+        let constraint = Constraint::ElementSet(
+            vec![vec![Element::SubType(SubTypeElement::Value(
+                Value::Integer(IntegerValue::Literal(3)),
+            ))]],
+            true,
+        );
+        assert_eq!(
+            Some(vec![constraint]),
+            checker.table.types["Version"].constraints
+        );
+    }
+
+    #[test]
+    fn resolves_object_identifiers() {
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      arc INTEGER ::= 7\n\
+                      id-pkix OBJECT IDENTIFIER ::= { iso(1) identified-organization 6 1 5 5 arc }\n\
+                      id-pe OBJECT IDENTIFIER ::= { id-pkix 1 }\n\
+                      END\n";
+        let (module, _) = Parser::parse(source).unwrap();
+
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
+        assert!(checker.diagnostics.is_empty());
+
+        let arcs = |name: &str| match &checker.table.values[name].1 {
+            Value::ObjectIdentifier(oid) => oid
+                .iter()
+                .map(|component| match component {
+                    ObjIdComponent::Number(Number::Literal(arc)) => *arc,
+                    component => panic!("unresolved arc {:?}", component),
+                })
+                .collect::<Vec<_>>(),
+            value => panic!("unexpected value {:?}", value),
+        };
+
+        assert_eq!(vec![1, 3, 6, 1, 5, 5, 7], arcs("id-pkix"));
+        assert_eq!(vec![1, 3, 6, 1, 5, 5, 7, 1], arcs("id-pe"));
+    }
+
+    #[test]
+    fn leaves_object_identifiers_from_other_modules() {
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      IMPORTS id-pkix FROM PKIX1Explicit88;\n\
+                      id-pe OBJECT IDENTIFIER ::= { id-pkix 1 }\n\
+                      Pair ::= SEQUENCE { a INTEGER }\n\
+                      pair Pair ::= { a 3 }\n\
+                      END\n";
+        let (module, _) = Parser::parse(source).unwrap();
+
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
+        assert!(checker.diagnostics.is_empty());
+
+        assert_eq!(
+            &Value::ObjectIdentifier(ObjectIdentifier::from_components(vec![
+                ObjIdComponent::Name(String::from("id-pkix")),
+                ObjIdComponent::Number(Number::Literal(1)),
+            ])),
+            &checker.table.values["id-pe"].1
+        );
+        assert_eq!(
+            &Value::Sequence(vec![NamedValue(
+                String::from("a"),
+                Value::Integer(IntegerValue::Literal(3))
+            )]),
+            &checker.table.values["pair"].1
+        );
+    }
+
+    #[test]
+    fn reports_object_identifier_errors_at_the_arc() {
+        let source = "Test DEFINITIONS ::= BEGIN\n\
+                      flag BOOLEAN ::= TRUE\n\
+                      id-test OBJECT IDENTIFIER ::= { 1 3 flag }\n\
+                      END\n";
+        let (module, _) = Parser::parse(source).unwrap();
+
+        let mut checker = SemanticChecker::new(module);
+        checker.build().unwrap();
+
+        let errors: Vec<_> = checker
+            .diagnostics
+            .iter()
+            .map(|d| {
+                let span = d.span.unwrap();
+                (d.message.as_str(), span.start)
+            })
+            .collect();
+        assert_eq!(
+            vec![(
+                "`flag` isn't an INTEGER value",
+                source.rfind("flag").unwrap()
+            )],
+            errors
+        );
+    }
+}
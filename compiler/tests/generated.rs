@@ -0,0 +1,124 @@
+//! Checks the code generated for the test modules, and that it compiles.
+
+use std::{fs, path::Path, process::Command};
+
+use rasn_compiler::NotationCompiler;
+
+#[test]
+fn generated_code_compiles() {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("generated");
+    fs::create_dir_all(&directory).unwrap();
+
+    let cases = trybuild::TestCases::new();
+    for module in ["constraints", "containing", "values"] {
+        let output = NotationCompiler::new(format!("tests/{}.asn1", module))
+            .build()
+            .unwrap();
+        let path = directory.join(format!("{}.rs", module));
+
+        fs::write(&path, format!("{}\nfn main() {{}}\n", output)).unwrap();
+        cases.pass(path);
+    }
+}
+
+#[test]
+fn constraints_are_emitted_as_attributes() {
+    let output = NotationCompiler::new("tests/constraints.asn1")
+        .build()
+        .unwrap();
+
+    for attributes in [
+        "#[rasn(delegate, value(\"0..=16\"))]\npub struct CellId(",
+        "#[rasn(delegate, value(\"0..=7\", extensible))]\npub struct SmallCellId(",
+        "#[rasn(delegate, size(\"1..=16\"))]\npub struct CellList(",
+        // Only the SIZE constraint has an extension marker.
+        "#[rasn(delegate, size(\"1..=64\", extensible), from(\"a..=z\", \"A..=Z\", \"-\", \".\"))]\npub struct Name(",
+        "#[rasn(delegate, size(\"2..=8\", extensible))]\npub struct Code(",
+        "#[rasn(tag(5), value(\"0..=16\"))]\n\tpub tagged: CellId,",
+        "#[rasn(value(\"-4..=4\"))]\n\tpub neg: Integer,",
+        "#[rasn(value(\"0..\"))]\n\tpub open: Integer,",
+        "#[rasn(size(\"1..=4\"), from(\"a..=z\", \"-\"))]\n\tpub alphabet: Ia5String,",
+    ] {
+        assert!(
+            output.contains(attributes),
+            "expected `{}` in:\n{}",
+            attributes,
+            output
+        );
+    }
+}
+
+#[test]
+fn values_are_constants_of_their_defined_type() {
+    let output = NotationCompiler::new("tests/values.asn1").build().unwrap();
+
+    for constant in [
+        "pub const CURRENT: Version = Version::V3;",
+        "pub const FIRST_CELL: CellId = CellId(Integer::from_i128(0));",
+        "pub const TAGGED: TaggedId = TaggedId(Integer::from_i128(7));",
+        "pub const EMPTY: Hash = OctetString::from_static(&[]);",
+        "pub const FLAGS: &[bool] = &[true, false, true];",
+        "pub const PATTERN: &[bool] = &[true, false, true];",
+        "\tpub const V1: Self = Self(Integer::from_i128(0));",
+        "#[rasn(delegate)]\npub struct Version(pub Integer);",
+        "pub fn cell() -> Cell {\n\tCell { version: Version::V2, id: CellId(Integer::from_i128(5)) }\n}",
+    ] {
+        assert!(
+            output.contains(constant),
+            "expected `{}` in:\n{}",
+            constant,
+            output
+        );
+    }
+}
+
+#[test]
+fn feature_gated_modules_can_be_concatenated() {
+    let output = Command::new(env!("CARGO_BIN_EXE_casn1"))
+        .args(["generate", "--feature", "asn1"])
+        .args(["tests/constraints.asn1", "tests/different.asn1"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let code = String::from_utf8(output.stdout).unwrap();
+    assert!(!code.contains("#!["));
+    assert!(code.starts_with("#[cfg(feature = \"asn1\")]\npub mod test {\n"));
+    assert!(code.contains("#[cfg(feature = \"asn1\")]\npub mod pkcs_12 {\n"));
+
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("generated");
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("features.rs");
+    fs::write(&path, format!("{}\nfn main() {{}}\n", code)).unwrap();
+    trybuild::TestCases::new().pass(path);
+}
+
+#[test]
+fn feature_gated_modules_are_declared_with_the_feature() {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("feature-output");
+    let status = Command::new(env!("CARGO_BIN_EXE_casn1"))
+        .args(["generate", "--feature", "asn1", "--output"])
+        .arg(&directory)
+        .args(["tests/constraints.asn1", "tests/different.asn1"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert_eq!(
+        "#[cfg(feature = \"asn1\")]\npub mod test;\n\
+         #[cfg(feature = \"asn1\")]\npub mod pkcs_12;\n",
+        fs::read_to_string(directory.join("mod.rs")).unwrap()
+    );
+    let module = fs::read_to_string(directory.join("test.rs")).unwrap();
+    assert!(!module.contains("cfg(feature"));
+}
+
+#[test]
+fn skipped_types_are_not_referenced() {
+    let output = NotationCompiler::new("tests/constraints.asn1")
+        .build()
+        .unwrap();
+
+    assert!(!output.contains("Legacy"));
+    assert!(output.contains("pub struct Outer"));
+}
@@ -1,70 +1,72 @@
-use itertools::Itertools;
-
-use crate::config::*;
+use crate::{config::*, ext::GenericsExt};
 
 pub fn derive_struct_impl(
     name: syn::Ident,
     mut generics: syn::Generics,
     container: syn::DataStruct,
     config: &Config,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
     let crate_root = &config.crate_root;
     let tag = config.tag_for_struct(&container.fields);
-    let field_groups = container
-        .fields
-        .iter()
+    let field_groups = FieldConfig::for_fields(&container.fields, config)?
+        .into_iter()
         .enumerate()
-        .map(|(i, f)| (i, FieldConfig::new(f, config)));
+        .collect::<Vec<_>>();
 
     let field_metadata = field_groups
-        .clone()
+        .iter()
         .filter(|(_, field)| field.is_not_extension())
         .map(|(i, field)| {
-            let metadata = field.to_field_metadata(i);
+            let metadata = field.to_field_metadata(*i);
             quote!(#metadata)
         })
         .collect::<Vec<_>>();
 
     let extension_metadata = field_groups
-        .clone()
+        .iter()
         .filter(|(_, field)| field.is_extension())
         .map(|(i, field)| {
-            let metadata = field.to_field_metadata(i);
+            let metadata = field.to_field_metadata(*i);
             quote!(#metadata)
         })
         .collect::<Vec<_>>();
 
-    let all_optional_tags_are_unique: Vec<_> = field_groups
-        .group_by(|(_, config)| config.is_option_or_default_type())
-        .into_iter()
-        .filter_map(|(key, fields)| key.then_some(fields))
-        .map(|fields| {
-            let tag_tree = fields.map(|(i, f)| f.tag_tree(i));
-            let error_message = format!(
-                "{}'s fields is not a valid \
-                        order of ASN.1 tags, ensure that your field's tags and \
-                        OPTIONALs are correct.",
-                name
-            );
-
-            quote!({
-                const LIST: &'static [#crate_root::TagTree] = &[#(#tag_tree),*];
-                const TAG_TREE: #crate_root::TagTree = #crate_root::TagTree::Choice(LIST);
-                const _: () = assert!(TAG_TREE.is_unique(), #error_message);
-            })
-        })
-        .collect::<Vec<_>>();
+    let fields_are_unambiguous = (!config.delegate).then(|| {
+        let (check, check_with_extensions, error_message) = if config.set {
+            (
+                quote!(has_distinct_tags),
+                quote!(has_distinct_tags_with_extensions),
+                format!(
+                    "{}'s fields must have distinct tags, as the fields of a SET \
+                    can be encoded in any order.",
+                    name
+                ),
+            )
+        } else {
+            (
+                quote!(has_unambiguous_sequence_tags),
+                quote!(has_unambiguous_sequence_tags_with_extensions),
+                format!(
+                    "{}'s fields is not a valid \
+                    order of ASN.1 tags, ensure that your field's tags and \
+                    OPTIONALs are correct.",
+                    name
+                ),
+            )
+        };
+
+        quote! {
+            let is_unambiguous = match &extended_fields {
+                Some(extended_fields) => fields.#check_with_extensions(extended_fields),
+                None => fields.#check(),
+            };
+            // `Fields` can't be dropped in a constant expression.
+            core::mem::forget(extended_fields);
+            assert!(is_unambiguous, #error_message);
+        }
+    });
 
-    for param in generics.type_params_mut() {
-        param
-            .bounds
-            .push(syn::TypeParamBound::Trait(syn::TraitBound {
-                paren_token: Some(<_>::default()),
-                modifier: syn::TraitBoundModifier::None,
-                lifetimes: None,
-                path: syn::parse_str("rasn::AsnType").unwrap(),
-            }));
-    }
+    generics.add_trait_bounds(config, &container.fields, quote::format_ident!("AsnType"))?;
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
@@ -80,32 +82,79 @@ pub fn derive_struct_impl(
         quote!(None)
     };
 
+    // A `const` item can't refer to the parameters of generic types, so
+    // they're checked when their fields are evaluated instead.
+    let is_generic = !generics.params.is_empty();
+    let fields_def = quote! {
+        #crate_root::types::fields::Fields::from_static(&[
+            #(#field_metadata),*
+        ])
+    };
+    let fields_def = match &fields_are_unambiguous {
+        Some(check) if is_generic => quote!({
+            let fields = #fields_def;
+            let extended_fields = <Self as #crate_root::types::Constructed>::EXTENDED_FIELDS;
+            #check
+            fields
+        }),
+        _ => fields_def,
+    };
+    let fields_are_unambiguous = fields_are_unambiguous.filter(|_| !is_generic).map(|check| {
+        quote! {
+            const _: () = {
+                let fields = <#name as #crate_root::types::Constructed>::FIELDS;
+                let extended_fields = <#name as #crate_root::types::Constructed>::EXTENDED_FIELDS;
+                #check
+                core::mem::forget(fields);
+            };
+        }
+    });
+
     let constructed_impl = (!config.delegate).then(|| {
         quote! {
             #[automatically_derived]
             impl #impl_generics  #crate_root::types::Constructed for #name #ty_generics #where_clause {
-                const FIELDS: #crate_root::types::fields::Fields = #crate_root::types::fields::Fields::from_static(&[
-                    #(#field_metadata),*
-                ]);
+                const FIELDS: #crate_root::types::fields::Fields = #fields_def;
                 const EXTENDED_FIELDS: Option<#crate_root::types::fields::Fields> = #extended_fields_def;
             }
         }
     });
 
+    // The components of extension addition groups continue the numbering of
+    // the struct's automatic tags. A group that's used more than once has no
+    // single number to continue from, so it isn't numbered.
+    let group_types = field_groups
+        .iter()
+        .filter_map(|(_, field)| field.group_type())
+        .collect::<Vec<_>>();
+    let group_impls = field_groups
+        .iter()
+        .filter(|_| config.automatic_tags)
+        .filter_map(|(i, field)| Some((field.group_type()?, field.automatic_tag_number(*i))))
+        .filter(|(ty, _)| group_types.iter().filter(|other| other == &ty).count() == 1)
+        .map(|(ty, number)| {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics #crate_root::types::ExtensionAdditionGroup for #ty #where_clause {
+                    const FIRST_TAG: u32 = #number;
+                }
+            }
+        });
+
     let constraints_def = config.constraints.const_static_def(crate_root);
 
-    quote! {
+    Ok(quote! {
         #constructed_impl
 
+        #(#group_impls)*
+
+        #fields_are_unambiguous
+
         #[automatically_derived]
         impl #impl_generics  #crate_root::AsnType for #name #ty_generics #where_clause {
-            const TAG: #crate_root::Tag = {
-                #(#all_optional_tags_are_unique)*
-
-                #tag
-            };
+            const TAG: #crate_root::Tag = #tag;
 
             #constraints_def
         }
-    }
+    })
 }
@@ -1,5 +1,5 @@
 use quote::ToTokens;
-use syn::{Lit, NestedMeta, Path};
+use syn::{ext::IdentExt, Lit, NestedMeta, Path};
 
 use crate::{ext::TypeExt, tag::Tag};
 
@@ -204,7 +204,7 @@ impl Constraints {
             .then(|| quote!(#crate_root::types::Constraint::Extensible))
     }
 
-    fn has_constraints(&self) -> bool {
+    pub fn has_constraints(&self) -> bool {
         self.extensible || self.from.is_some() || self.size.is_some() || self.value.is_some()
     }
 }
@@ -216,116 +216,172 @@ pub struct Config {
     pub choice: bool,
     pub set: bool,
     pub automatic_tags: bool,
+    /// Whether the struct is an extension addition group, whose automatic
+    /// tags continue the numbering of the struct containing it.
+    pub extension_addition_group: bool,
     pub option_type: OptionalEnum,
     pub delegate: bool,
     pub tag: Option<Tag>,
     pub constraints: Constraints,
+    /// The casing of the ASN.1 identifiers of fields and variants that
+    /// don't have an `identifier` attribute.
+    pub rename_all: Option<Case>,
+    /// The `where` predicates that replace the inferred trait bounds.
+    pub bound: Bounds,
 }
 
 impl Config {
-    pub fn from_attributes(input: &syn::DeriveInput) -> Self {
-        let mut choice = false;
-        let mut set = false;
+    /// Parses the container's attributes, and checks the attributes of its
+    /// fields and variants, reporting every error that was found.
+    pub fn from_attributes(input: &syn::DeriveInput) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+        let mut choice = None;
+        let mut set = None;
         let mut crate_root = None;
-        let mut enumerated = false;
+        let mut enumerated = None;
         let mut automatic_tags = false;
+        let mut extension_addition_group = None;
+        let mut rename_all = None;
+        let mut bound = Bounds::default();
         let mut tag = None;
         let mut option = None;
         let mut from = None;
         let mut size = None;
         let mut value = None;
-        let mut delegate = false;
+        let mut delegate = None;
         let extensible = input
             .attrs
             .iter()
             .any(|a| a.path.is_ident("non_exhaustive"));
 
-        let mut iter = input
-            .attrs
-            .iter()
-            .filter(|a| a.path.is_ident(crate::CRATE_NAME))
-            .map(|a| a.parse_meta().unwrap());
-
-        while let Some(syn::Meta::List(list)) = iter.next() {
-            for item in list.nested.iter().filter_map(|n| match n {
-                syn::NestedMeta::Meta(m) => Some(m),
-                _ => None,
-            }) {
-                let path = item.path();
-
-                if path.is_ident("crate_root") {
-                    if let syn::Meta::NameValue(nv) = item {
-                        crate_root = match &nv.lit {
-                            syn::Lit::Str(s) => s.parse::<syn::Path>().ok(),
-                            _ => None,
-                        };
-                    }
-                } else if path.is_ident("enumerated") {
-                    enumerated = true;
-                } else if path.is_ident("choice") {
-                    choice = true;
-                } else if path.is_ident("set") {
-                    set = true;
-                } else if path.is_ident("automatic_tags") {
-                    automatic_tags = true;
-                } else if path.is_ident("option_type") {
-                    if let syn::Meta::List(list) = item {
-                        let filter_into_paths = |nm: &_| match nm {
-                            syn::NestedMeta::Meta(meta) => Some(meta.path().clone()),
-                            _ => None,
-                        };
-                        let mut iter = list
-                            .nested
-                            .iter()
-                            .take(3)
-                            .filter_map(filter_into_paths)
-                            .fuse();
-
-                        let path = iter.next();
-                        let some_variant = iter.next();
-                        let none_variant = iter.next();
-                        option = Some((path, some_variant, none_variant));
+        for item in rasn_items(&input.attrs, &mut errors) {
+            let path = item.path();
+
+            if path.is_ident("crate_root") {
+                crate_root = match &item {
+                    syn::Meta::NameValue(syn::MetaNameValue {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }) => errors.push_result(s.parse::<syn::Path>()),
+                    _ => {
+                        errors.push(syn::Error::new_spanned(
+                            &item,
+                            "expected `crate_root = \"path\"`",
+                        ));
+                        None
                     }
-                } else if path.is_ident("tag") {
-                    tag = Tag::from_meta(item);
-                } else if path.is_ident("delegate") {
-                    delegate = true;
-                } else if path.is_ident("from") {
-                    from = Some(StringValue::from_meta(item));
-                } else if path.is_ident("size") {
-                    size = Some(Value::from_meta(item));
-                } else if path.is_ident("value") {
-                    value = Some(Value::from_meta(item));
-                } else {
-                    panic!("unknown input provided: {}", path.to_token_stream());
+                };
+            } else if path.is_ident("enumerated") {
+                enumerated = Some(path.clone());
+            } else if path.is_ident("choice") {
+                choice = Some(path.clone());
+            } else if path.is_ident("set") {
+                set = Some(path.clone());
+            } else if path.is_ident("automatic_tags") {
+                automatic_tags = true;
+            } else if path.is_ident("extension_addition_group") {
+                extension_addition_group = Some(path.clone());
+            } else if path.is_ident("rename_all") {
+                rename_all = errors.push_result(string_value(&item).and_then(Case::from_lit));
+            } else if path.is_ident("option_type") {
+                if let syn::Meta::List(list) = &item {
+                    let filter_into_paths = |nm: &_| match nm {
+                        syn::NestedMeta::Meta(meta) => Some(meta.path().clone()),
+                        _ => None,
+                    };
+                    let mut iter = list
+                        .nested
+                        .iter()
+                        .take(3)
+                        .filter_map(filter_into_paths)
+                        .fuse();
+
+                    let path = iter.next();
+                    let some_variant = iter.next();
+                    let none_variant = iter.next();
+                    option = Some((path, some_variant, none_variant));
                 }
+            } else if path.is_ident("tag") {
+                tag = errors.push_result(Tag::from_meta(&item));
+            } else if path.is_ident("delegate") {
+                delegate = Some(path.clone());
+            } else if path.is_ident("bound") {
+                bound = errors
+                    .push_result(Bounds::from_meta(&item))
+                    .unwrap_or_default();
+            } else if path.is_ident("from") {
+                from = errors.push_result(StringValue::from_meta(&item));
+            } else if path.is_ident("size") {
+                size = errors.push_result(Value::from_meta(&item));
+            } else if path.is_ident("value") {
+                value = errors.push_result(Value::from_meta(&item));
+            } else {
+                errors.push(unknown_attribute(path));
             }
         }
 
-        let is_enum = matches!(input.data, syn::Data::Enum(_));
-
-        if !is_enum && (choice || enumerated) {
-            panic!("Structs cannot be annotated with `#[rasn(choice)]` or `#[rasn(enumerated)]`.");
-        } else if is_enum && set {
-            panic!("Enums cannot be annotated with `#[rasn(set)]`.");
-        } else if is_enum && ((choice && enumerated) || (!choice && !enumerated)) {
-            panic!(
-                "Enums must be annotated with either `#[rasn(choice)]` OR `#[rasn(enumerated)]`."
-            )
-        }
+        match &input.data {
+            syn::Data::Struct(data) => {
+                for path in choice.iter().chain(&enumerated) {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        format!(
+                            "structs cannot be annotated with `#[rasn({})]`",
+                            path.to_token_stream()
+                        ),
+                    ));
+                }
 
-        let mut invalid_delegate = false;
+                if let Some(path) = delegate.as_ref().filter(|_| data.fields.len() != 1) {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        "`#[rasn(delegate)]` is only valid on structs with a single field",
+                    ));
+                }
 
-        if is_enum && delegate {
-            invalid_delegate = true;
-        } else if delegate {
-            if let syn::Data::Struct(data) = &input.data {
-                invalid_delegate = data.fields.len() != 1;
+                // The fields of a `set` are tagged with literals, which a
+                // group's tags aren't.
+                if let (Some(_), Some(path)) = (&set, &extension_addition_group) {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        "`#[rasn(extension_addition_group)]` can't be combined with `#[rasn(set)]`",
+                    ));
+                }
             }
-        }
+            syn::Data::Enum(_) => {
+                for path in set.iter().chain(&extension_addition_group) {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        format!(
+                            "enums cannot be annotated with `#[rasn({})]`",
+                            path.to_token_stream()
+                        ),
+                    ));
+                }
+
+                if let Some(path) = &delegate {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        "`#[rasn(delegate)]` is only valid on structs with a single field",
+                    ));
+                }
 
-        if invalid_delegate {
-            panic!("`#[rasn(delegate)]` is only valid on single-unit structs.");
+                match (&choice, &enumerated) {
+                    (Some(_), Some(path)) => errors.push(syn::Error::new_spanned(
+                        path,
+                        "enums can't be annotated with both `#[rasn(choice)]` and `#[rasn(enumerated)]`",
+                    )),
+                    (None, None) => errors.push(syn::Error::new_spanned(
+                        &input.ident,
+                        "enums must be annotated with either `#[rasn(choice)]` or `#[rasn(enumerated)]`",
+                    )),
+                    _ => {}
+                }
+            }
+            syn::Data::Union(data) => errors.push(syn::Error::new_spanned(
+                data.union_token,
+                "union types are not supported",
+            )),
         }
 
         let option_type = {
@@ -352,13 +408,18 @@ impl Config {
             }
         };
 
-        Self {
-            automatic_tags,
-            choice,
-            delegate,
-            enumerated,
+        let config = Self {
+            // The components of a group are tagged automatically when the
+            // struct containing it is.
+            automatic_tags: automatic_tags || extension_addition_group.is_some(),
+            extension_addition_group: extension_addition_group.is_some(),
+            rename_all,
+            bound,
+            choice: choice.is_some(),
+            delegate: delegate.is_some(),
+            enumerated: enumerated.is_some(),
             option_type,
-            set,
+            set: set.is_some(),
             tag,
             constraints: Constraints {
                 extensible,
@@ -367,11 +428,51 @@ impl Config {
                 value,
             },
             crate_root: crate_root.unwrap_or_else(|| {
-                syn::LitStr::new(crate::CRATE_NAME, proc_macro2::Span::call_site())
-                    .parse()
-                    .unwrap()
+                syn::Ident::new(crate::CRATE_NAME, proc_macro2::Span::call_site()).into()
             }),
+        };
+
+        // Automatic tagging isn't applied if any of the components (including
+        // extension additions) already has a tag (X.680 §25.3).
+        let mut has_tagged_component = false;
+
+        match &input.data {
+            syn::Data::Struct(data) => {
+                for field in &data.fields {
+                    if let Some(field) = errors.push_result(FieldConfig::new(field, &config)) {
+                        has_tagged_component |= field.tag.is_some();
+                    }
+                }
+            }
+            syn::Data::Enum(data) => {
+                let mut has_unknown = false;
+
+                for variant in &data.variants {
+                    if let Some(variant) =
+                        errors.push_result(VariantConfig::new(variant, &input.generics, &config))
+                    {
+                        has_tagged_component |= variant.tag.is_some();
+
+                        if variant.unknown && std::mem::replace(&mut has_unknown, true) {
+                            errors.push(syn::Error::new_spanned(
+                                &variant.variant.ident,
+                                "only one variant can be annotated with `#[rasn(unknown)]`",
+                            ));
+                        }
+                    }
+
+                    for field in &variant.fields {
+                        errors.push_result(FieldConfig::new(field, &config));
+                    }
+                }
+            }
+            syn::Data::Union(_) => {}
         }
+
+        errors.finish().map(|_| Self {
+            automatic_tags: config.automatic_tags && !has_tagged_component,
+            ..config
+        })
     }
 
     fn tag_tree_for_ty(&self, ty: &syn::Type) -> proc_macro2::TokenStream {
@@ -386,6 +487,14 @@ impl Config {
         self.tag.as_ref().map_or(false, |tag| tag.is_explicit())
     }
 
+    /// Applies `rename_all` to the name of a field or variant.
+    fn rename(&self, name: String) -> String {
+        match self.rename_all {
+            Some(case) => case.apply(&name),
+            None => name,
+        }
+    }
+
     pub fn tag_for_struct(&self, fields: &syn::Fields) -> proc_macro2::TokenStream {
         let crate_root = &self.crate_root;
         self.tag
@@ -454,7 +563,11 @@ pub struct VariantConfig<'config> {
     generics: &'config syn::Generics,
     pub tag: Option<Tag>,
     pub extension_addition: bool,
+    /// Whether the variant keeps the discriminants of an enumerated type
+    /// that aren't defined by the other variants.
+    pub unknown: bool,
     pub constraints: Constraints,
+    identifier: Option<String>,
 }
 
 impl<'config> VariantConfig<'config> {
@@ -462,46 +575,78 @@ impl<'config> VariantConfig<'config> {
         variant: &'config syn::Variant,
         generics: &'config syn::Generics,
         container_config: &'config Config,
-    ) -> Self {
+    ) -> syn::Result<Self> {
+        let mut errors = Errors::default();
         let mut extensible = false;
         let mut extension_addition = false;
+        let mut unknown = None;
         let mut from = None;
         let mut size = None;
         let mut tag = None;
         let mut value = None;
+        let mut identifier = None;
+
+        for item in rasn_items(&variant.attrs, &mut errors) {
+            let path = item.path();
+            if path.is_ident("tag") {
+                tag = errors.push_result(Tag::from_meta(&item));
+            } else if path.is_ident("size") {
+                size = errors.push_result(Value::from_meta(&item));
+            } else if path.is_ident("value") {
+                value = errors.push_result(Value::from_meta(&item));
+            } else if path.is_ident("from") {
+                from = errors.push_result(StringValue::from_meta(&item));
+            } else if path.is_ident("extensible") {
+                extensible = true;
+            } else if path.is_ident("extension_addition") {
+                extension_addition = true;
+            } else if path.is_ident("unknown") {
+                unknown = Some(path.clone());
+            } else if path.is_ident("identifier") {
+                identifier = errors
+                    .push_result(string_value(&item))
+                    .map(|lit| lit.value());
+            } else {
+                errors.push(unknown_attribute(path));
+            }
+        }
 
-        let mut iter = variant
-            .attrs
-            .iter()
-            .filter_map(|a| a.parse_meta().ok())
-            .filter(|m| m.path().is_ident(crate::CRATE_NAME));
+        if let Some(path) = &unknown {
+            if !container_config.enumerated {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "`#[rasn(unknown)]` is only valid on variants of enumerated enums",
+                ));
+            } else if variant.fields.len() != 1 || variant.fields.iter().any(|f| f.ident.is_some())
+            {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "`#[rasn(unknown)]` variants must have a single `isize` field, e.g. `Unknown(isize)`",
+                ));
+            } else if extension_addition {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "`#[rasn(unknown)]` variants can't be extension additions",
+                ));
+            }
+        }
 
-        while let Some(syn::Meta::List(list)) = iter.next() {
-            for item in list.nested.iter().filter_map(|n| match n {
-                syn::NestedMeta::Meta(m) => Some(m),
-                _ => None,
-            }) {
-                let path = item.path();
-                if path.is_ident("tag") {
-                    tag = Tag::from_meta(item);
-                } else if path.is_ident("size") {
-                    size = Some(Value::from_meta(item));
-                } else if path.is_ident("value") {
-                    value = Some(Value::from_meta(item));
-                } else if path.is_ident("from") {
-                    from = Some(StringValue::from_meta(item));
-                } else if path.is_ident("extensible") {
-                    extensible = true;
-                } else if path.is_ident("extension_addition") {
-                    extension_addition = true;
-                }
+        if let syn::Fields::Unnamed(fields) = &variant.fields {
+            if fields.unnamed.len() != 1 {
+                errors.push(syn::Error::new_spanned(
+                    fields,
+                    "tuple-style enum variants must contain only a single field, \
+                    switch to struct-style variants for multiple fields",
+                ));
             }
         }
 
-        Self {
+        errors.finish().map(|_| Self {
             container_config,
             extension_addition,
+            unknown: unknown.is_some(),
             generics,
+            identifier,
             tag,
             variant,
             constraints: Constraints {
@@ -510,7 +655,7 @@ impl<'config> VariantConfig<'config> {
                 size,
                 value,
             },
-        }
+        })
     }
 
     pub fn discriminant(&self) -> Option<usize> {
@@ -530,9 +675,21 @@ impl<'config> VariantConfig<'config> {
         self.tag.as_ref().map_or(false, |tag| tag.is_explicit())
     }
 
-    pub fn decode(&self, name: &syn::Ident, context: usize) -> proc_macro2::TokenStream {
+    /// The variant's ASN.1 identifier.
+    pub fn identifier(&self) -> String {
+        self.identifier.clone().unwrap_or_else(|| {
+            self.container_config
+                .rename(self.variant.ident.unraw().to_string())
+        })
+    }
+
+    pub fn decode(
+        &self,
+        name: &syn::Ident,
+        context: usize,
+    ) -> syn::Result<proc_macro2::TokenStream> {
         let crate_root = &self.container_config.crate_root;
-        let tag_tree = self.tag_tree(context);
+        let tag_tree = self.tag_tree(context)?;
         let ident = &self.variant.ident;
         let is_explicit = self.has_explicit_tag();
 
@@ -547,9 +704,6 @@ impl<'config> VariantConfig<'config> {
                 quote!(#decode_op.map(|_| Self::#ident))
             }
             syn::Fields::Unnamed(_) => {
-                if self.variant.fields.len() != 1 {
-                    panic!("Tuple struct variants should contain only a single element.");
-                }
                 let constraints = self
                     .constraints
                     .const_expr(&self.container_config.crate_root);
@@ -557,14 +711,12 @@ impl<'config> VariantConfig<'config> {
                 let field = FieldConfig::new(
                     self.variant.fields.iter().next().unwrap(),
                     self.container_config,
-                );
+                )?;
                 let decode_operation = if is_explicit {
                     quote!(decoder.decode_explicit_prefix(tag))
                 } else if self.container_config.automatic_tags || self.tag.is_some() {
-                    if let Some(path) = field.default {
-                        let path = path
-                            .map(|path| quote!(#path))
-                            .unwrap_or_else(|| quote!(<_>::default));
+                    if let Some(default) = &field.default {
+                        let path = default.function(&field.field.ty);
                         if let Some(constraints) = constraints {
                             quote!(decoder.decode_default_with_tag_and_constraints(tag, #path, #constraints))
                         } else {
@@ -577,10 +729,8 @@ impl<'config> VariantConfig<'config> {
                             quote!(<_>::decode_with_tag(decoder, tag))
                         }
                     }
-                } else if let Some(path) = field.default {
-                    let path = path
-                        .map(|path| quote!(#path))
-                        .unwrap_or_else(|| quote!(<_>::default));
+                } else if let Some(default) = &field.default {
+                    let path = default.function(&field.field.ty);
                     quote!(<_>::decode_default(decoder, <_>::TAG, #path))
                 } else {
                     quote!(<_>::decode(decoder))
@@ -617,11 +767,11 @@ impl<'config> VariantConfig<'config> {
             }
         };
 
-        quote! {
+        Ok(quote! {
             if #crate_root::TagTree::tag_contains(&tag, &[#tag_tree]) {
                 return #decode_op
             }
-        }
+        })
     }
 
     pub fn tag(&self, context: usize) -> crate::tag::Tag {
@@ -639,24 +789,28 @@ impl<'config> VariantConfig<'config> {
         }
     }
 
-    pub fn tag_tree(&self, context: usize) -> proc_macro2::TokenStream {
+    pub fn tag_tree(&self, context: usize) -> syn::Result<proc_macro2::TokenStream> {
         let crate_root = &self.container_config.crate_root;
         if self.tag.is_some() || self.container_config.automatic_tags {
             let tag = self.tag(context).to_tokens(crate_root);
-            quote!(#crate_root::TagTree::Leaf(#tag))
+            Ok(quote!(#crate_root::TagTree::Leaf(#tag)))
         } else {
-            let field_tags = self
-                .variant
-                .fields
-                .iter()
-                .enumerate()
-                .filter(|(_, f)| FieldConfig::new(f, self.container_config).is_option_type())
-                .map(|(i, f)| FieldConfig::new(f, self.container_config).tag_tree(i));
+            let mut field_tags = Vec::new();
+            for (i, field) in self.variant.fields.iter().enumerate() {
+                let field = FieldConfig::new(field, self.container_config)?;
+                if field.is_option_type() {
+                    field_tags.push(field.tag_tree(i));
+                }
+            }
 
-            match self.variant.fields {
+            Ok(match self.variant.fields {
                 syn::Fields::Unit => {
                     quote!(#crate_root::TagTree::Leaf(<() as #crate_root::AsnType>::TAG))
                 }
+                // Generic types can't be checked in `const` items.
+                syn::Fields::Named(_) if self.generics.type_params().next().is_some() => {
+                    quote!(#crate_root::TagTree::Leaf(#crate_root::Tag::SEQUENCE))
+                }
                 syn::Fields::Named(_) => {
                     let error_message = format!(
                         "{}'s fields is not a valid \
@@ -673,16 +827,12 @@ impl<'config> VariantConfig<'config> {
                     })
                 }
                 syn::Fields::Unnamed(_) => {
-                    if self.variant.fields.iter().count() != 1 {
-                        panic!("Tuple-style enum variants must contain only a single field, switch to struct-style variants for multiple fields.");
-                    } else {
-                        let mut ty = self.variant.fields.iter().next().unwrap().ty.clone();
-                        ty.strip_lifetimes();
+                    let mut ty = self.variant.fields.iter().next().unwrap().ty.clone();
+                    ty.strip_lifetimes();
 
-                        quote!(<#ty as #crate_root::AsnType>::TAG_TREE)
-                    }
+                    quote!(<#ty as #crate_root::AsnType>::TAG_TREE)
                 }
-            }
+            })
         }
     }
 }
@@ -692,10 +842,15 @@ pub struct FieldConfig<'a> {
     pub field: &'a syn::Field,
     pub container_config: &'a Config,
     pub tag: Option<Tag>,
-    pub default: Option<Option<syn::Path>>,
+    pub default: Option<DefaultValue>,
     pub extension_addition: bool,
     pub extension_addition_group: bool,
     pub constraints: Constraints,
+    pub bound: Bounds,
+    /// The types of the extension addition groups before the field, whose
+    /// components take automatic tag numbers before the field's.
+    pub preceding_groups: Vec<&'a syn::Type>,
+    identifier: Option<String>,
 }
 
 pub enum FieldType {
@@ -704,77 +859,451 @@ pub enum FieldType {
     Default,
 }
 
+/// The value of a `DEFAULT` field.
+#[derive(Clone, Debug)]
+pub enum DefaultValue {
+    /// `default`, the field type's `Default` implementation.
+    Trait,
+    /// `default = "path"`, a function returning the default value.
+    Function(syn::Path),
+    /// `default = "v1"`, a named value, which is the variant or associated
+    /// constant `V1` of the field's type.
+    Named(syn::Ident),
+    /// `default = 5`, and other literal values, as an expression of the
+    /// field's type.
+    Literal(proc_macro2::TokenStream),
+}
+
+/// The literal value of `default = ...`, before it's checked against the
+/// field's type.
+enum LiteralValue {
+    Integer(i128),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    Bits(Vec<bool>),
+    ObjectIdentifier(Vec<u32>),
+    Text(String),
+}
+
+impl DefaultValue {
+    /// Parses the value of `default = ...` for a field of type `ty`. Strings
+    /// are function paths, unless they're ASN.1 values such as `v1`, `-1`,
+    /// `'0101'B`, `'0A'H`, `{ 1 2 3 }` or `"text"`.
+    ///
+    /// ASN.1 identifiers can't contain `_`, so a string that's a single
+    /// identifier without one, as in `DEFAULT v1`, is a named value. Literal
+    /// values are checked against the field's type here, so that invalid
+    /// values are errors at the attribute.
+    fn from_lit(lit: &syn::Lit, ty: &syn::Type, crate_root: &syn::Path) -> syn::Result<Self> {
+        let value = match lit {
+            syn::Lit::Str(string) => {
+                let value = string.value();
+                if let Some(name) = named_value(value.trim()) {
+                    return Ok(Self::Named(syn::Ident::new(&name, string.span())));
+                }
+
+                match string.parse() {
+                    Ok(path) => return Ok(Self::Function(path)),
+                    Err(_) => LiteralValue::from_asn1(string)?,
+                }
+            }
+            syn::Lit::Int(int) => LiteralValue::Integer(int.base10_parse()?),
+            syn::Lit::Bool(bool) => LiteralValue::Bool(bool.value),
+            syn::Lit::ByteStr(bytes) => LiteralValue::Bytes(bytes.value()),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "expected an integer, boolean, byte string, or string",
+                ))
+            }
+        };
+
+        value
+            .to_expr(ty, crate_root)
+            .map(Self::Literal)
+            .map_err(|message| {
+                syn::Error::new_spanned(
+                    lit,
+                    format!("{} for a field of type `{}`", message, quote!(#ty)),
+                )
+            })
+    }
+
+    /// A function returning the default value of a field of type `ty`.
+    pub fn function(&self, ty: &syn::Type) -> proc_macro2::TokenStream {
+        match self {
+            Self::Trait => quote!(<#ty>::default),
+            Self::Function(path) => quote!(#path),
+            Self::Named(name) => quote!(|| -> #ty { <#ty>::#name }),
+            Self::Literal(value) => quote!(|| -> #ty { #value }),
+        }
+    }
+}
+
+/// The Rust name of a named value, if `value` is an ASN.1 identifier.
+/// `v1` is `V1`, and `key-agreement` is `KeyAgreement`.
+fn named_value(value: &str) -> Option<String> {
+    let is_identifier = value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !value.ends_with('-')
+        && !value.contains("--");
+
+    is_identifier.then(|| {
+        value
+            .split('-')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect()
+    })
+}
+
+impl LiteralValue {
+    /// Parses an ASN.1 value: `-1`, `'0101'B`, `'0A'H`, `{ 1 2 3 }` or
+    /// `"text"`.
+    fn from_asn1(string: &syn::LitStr) -> syn::Result<Self> {
+        let value = string.value();
+        let value = value.trim();
+        let error = |message: &str| syn::Error::new_spanned(string, message);
+
+        if let Ok(number) = value.parse::<i128>() {
+            Ok(Self::Integer(number))
+        } else if let Some(text) = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+        {
+            Ok(Self::Text(text.replace("\"\"", "\"")))
+        } else if let Some(bits) = value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix("'B"))
+        {
+            bits.chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|bit| match bit {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    _ => Err(error("bit strings may only contain `0` and `1`")),
+                })
+                .collect::<syn::Result<_>>()
+                .map(Self::Bits)
+        } else if let Some(hex) = value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix("'H"))
+        {
+            let mut nibbles = hex
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|nibble| {
+                    nibble
+                        .to_digit(16)
+                        .map(|nibble| nibble as u8)
+                        .ok_or_else(|| error("hex strings may only contain hex digits"))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            if nibbles.len() % 2 == 1 {
+                nibbles.push(0);
+            }
+
+            Ok(Self::Bytes(
+                nibbles
+                    .chunks(2)
+                    .map(|pair| pair[0] << 4 | pair[1])
+                    .collect(),
+            ))
+        } else if let Some(components) = value
+            .strip_prefix('{')
+            .and_then(|value| value.strip_suffix('}'))
+        {
+            // Arcs are either numbers, or names with numbers (`iso(1)`).
+            let arcs = components
+                .split_whitespace()
+                .map(|arc| {
+                    let number = arc
+                        .split_once('(')
+                        .and_then(|(_, number)| number.strip_suffix(')'))
+                        .unwrap_or(arc);
+
+                    number.parse::<u32>().map_err(|_| {
+                        error("object identifier arcs must be numbers, or names with numbers")
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            if arcs.len() < 2 || arcs[0] > 2 {
+                return Err(error(
+                    "object identifiers have at least two arcs, and the first is 0, 1 or 2",
+                ));
+            }
+
+            Ok(Self::ObjectIdentifier(arcs))
+        } else {
+            Err(error(
+                "expected a function path, or an ASN.1 value such as `v1`, `-1`, \
+                `'0101'B`, `'0A'H`, `{ 1 2 3 }` or `\"text\"`",
+            ))
+        }
+    }
+
+    /// An expression of type `ty` with the value. Types that aren't known
+    /// here, such as type aliases, are converted to with `From`.
+    fn to_expr(
+        &self,
+        ty: &syn::Type,
+        crate_root: &syn::Path,
+    ) -> Result<proc_macro2::TokenStream, String> {
+        let type_name = match ty {
+            syn::Type::Path(path) if path.qself.is_none() => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+        let from =
+            |value: proc_macro2::TokenStream| quote!(<#ty as core::convert::From<_>>::from(#value));
+
+        match (self, &*type_name) {
+            (Self::Integer(value), integer) if INTEGERS.contains(&integer) => {
+                if !fits_integer(*value, integer) {
+                    return Err(format!("`{}` is out of range", value));
+                }
+
+                let value = proc_macro2::Literal::i128_unsuffixed(*value);
+                Ok(quote!(#value))
+            }
+            (Self::Integer(value), _) if !is_known_type(&type_name) || type_name == "Integer" => {
+                let value = proc_macro2::Literal::i128_suffixed(*value);
+                Ok(from(quote!(#value)))
+            }
+            (Self::Integer(_), _) => Err(String::from("an integer isn't a valid default")),
+            (Self::Bool(value), "bool") => Ok(quote!(#value)),
+            (Self::Bool(value), _) if !is_known_type(&type_name) => Ok(from(quote!(#value))),
+            (Self::Bool(_), _) => Err(String::from("a boolean isn't a valid default")),
+            (Self::Bytes(bytes), "OctetString" | "Bytes") => {
+                Ok(quote!(<#ty>::from_static(&[#(#bytes),*])))
+            }
+            (Self::Bytes(bytes), "Vec") => Ok(quote!(<[u8]>::to_vec(&[#(#bytes),*]))),
+            (Self::Bytes(bytes), "BitString") => {
+                Ok(quote!(#crate_root::types::BitString::from_slice(&[#(#bytes),*])))
+            }
+            (Self::Bytes(bytes), _) if !is_known_type(&type_name) => {
+                Ok(from(quote!(<[u8]>::to_vec(&[#(#bytes),*]))))
+            }
+            (Self::Bytes(_), _) => Err(String::from("an octet string isn't a valid default")),
+            (Self::Bits(bits), "BitString") => Ok(bit_string(bits, crate_root)),
+            (Self::Bits(bits), _) if !is_known_type(&type_name) => {
+                Ok(from(bit_string(bits, crate_root)))
+            }
+            (Self::Bits(_), _) => Err(String::from("a bit string isn't a valid default")),
+            (Self::ObjectIdentifier(arcs), "ConstOid") => {
+                Ok(quote!(#crate_root::types::ConstOid(&[#(#arcs),*])))
+            }
+            (Self::ObjectIdentifier(arcs), _)
+                if type_name == "ObjectIdentifier" || !is_known_type(&type_name) =>
+            {
+                Ok(from(quote!(#crate_root::types::ConstOid(&[#(#arcs),*]))))
+            }
+            (Self::ObjectIdentifier(_), _) => {
+                Err(String::from("an object identifier isn't a valid default"))
+            }
+            (Self::Text(text), "Utf8String" | "String") => Ok(quote!(<#ty>::from(#text))),
+            (Self::Text(text), "Ia5String" | "VisibleString" | "PrintableString") => {
+                let (constructor, is_valid): (_, fn(&char) -> bool) = match &*type_name {
+                    "PrintableString" => (quote!(from_bytes), |c| {
+                        c.is_ascii_alphanumeric() || " '()+,-./:=?".contains(*c)
+                    }),
+                    _ => (quote!(from_iso646_bytes), |c| c.is_ascii()),
+                };
+                if let Some(c) = text.chars().find(|c| !is_valid(c)) {
+                    return Err(format!("`{}` isn't a valid character", c));
+                }
+
+                let bytes = syn::LitByteStr::new(text.as_bytes(), proc_macro2::Span::call_site());
+                Ok(quote! {
+                    match <#ty>::#constructor(#bytes) {
+                        Ok(value) => value,
+                        // The characters are checked when the derive is expanded.
+                        Err(_) => unreachable!(),
+                    }
+                })
+            }
+            (Self::Text(text), _) if !is_known_type(&type_name) => Ok(from(quote!(#text))),
+            (Self::Text(_), _) => Err(String::from("a string isn't a valid default")),
+        }
+    }
+}
+
+/// Rust's primitive integer types.
+const INTEGERS: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Whether `value` is in the range of the primitive integer type `ty`.
+fn fits_integer(value: i128, ty: &str) -> bool {
+    match ty {
+        "i8" => i8::try_from(value).is_ok(),
+        "i16" => i16::try_from(value).is_ok(),
+        "i32" => i32::try_from(value).is_ok(),
+        "i64" | "isize" => i64::try_from(value).is_ok(),
+        "u8" => u8::try_from(value).is_ok(),
+        "u16" => u16::try_from(value).is_ok(),
+        "u32" => u32::try_from(value).is_ok(),
+        "u64" | "usize" => u64::try_from(value).is_ok(),
+        _ => true,
+    }
+}
+
+/// Whether default values of the type named `name` are checked by
+/// `LiteralValue::to_expr`, rather than converted to with `From`.
+fn is_known_type(name: &str) -> bool {
+    INTEGERS.contains(&name)
+        || matches!(
+            name,
+            "Integer"
+                | "bool"
+                | "OctetString"
+                | "Bytes"
+                | "Vec"
+                | "BitString"
+                | "ConstOid"
+                | "ObjectIdentifier"
+                | "Utf8String"
+                | "String"
+                | "Ia5String"
+                | "VisibleString"
+                | "PrintableString"
+        )
+}
+
+/// A `BitString` expression with the value of `bits`.
+fn bit_string(bits: &[bool], crate_root: &syn::Path) -> proc_macro2::TokenStream {
+    let length = bits.len();
+    let octets = bits.chunks(8).map(|chunk| {
+        chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |octet, (i, &bit)| octet | ((bit as u8) << (7 - i)))
+    });
+
+    quote!(#crate_root::types::BitString::from_slice(&[#(#octets),*])[..#length].to_bitvec())
+}
+
 impl<'a> FieldConfig<'a> {
-    pub fn new(field: &'a syn::Field, container_config: &'a Config) -> Self {
+    /// The configs of every field in `fields`.
+    pub fn for_fields(
+        fields: &'a syn::Fields,
+        container_config: &'a Config,
+    ) -> syn::Result<Vec<Self>> {
+        let mut preceding_groups = Vec::new();
+        fields
+            .iter()
+            .map(|field| {
+                let mut config = Self::new(field, container_config)?;
+                config.preceding_groups = preceding_groups.clone();
+                preceding_groups.extend(config.group_type());
+
+                Ok(config)
+            })
+            .collect()
+    }
+
+    /// The type of the group, if the field is an extension addition group.
+    pub fn group_type(&self) -> Option<&'a syn::Type> {
+        let ty = &self.field.ty;
+
+        self.extension_addition_group.then(|| {
+            self.container_config
+                .option_type
+                .map_to_inner_type(ty)
+                .unwrap_or(ty)
+        })
+    }
+
+    pub fn new(field: &'a syn::Field, container_config: &'a Config) -> syn::Result<Self> {
+        let mut errors = Errors::default();
         let mut default = None;
         let mut tag = None;
         let mut size = None;
         let mut from = None;
         let mut value = None;
         let mut extensible = false;
-        let mut extension_addition = false;
-        let mut extension_addition_group = false;
-        let mut iter = field
-            .attrs
-            .iter()
-            .map(|a| a.parse_meta().unwrap())
-            .filter(|m| m.path().is_ident(crate::CRATE_NAME));
-
-        while let Some(syn::Meta::List(list)) = iter.next() {
-            for item in list.nested.iter().filter_map(|n| match n {
-                syn::NestedMeta::Meta(m) => Some(m),
-                _ => None,
-            }) {
-                let path = item.path();
-                if path.is_ident("tag") {
-                    tag = Tag::from_meta(item);
-                } else if path.is_ident("default") {
-                    default = Some(match item {
-                        syn::Meta::NameValue(value) => match &value.lit {
-                            syn::Lit::Str(lit_str) => lit_str.parse().map(Some).unwrap(),
-                            _ => panic!("Unsupported type for default."),
-                        },
-                        _ => None,
-                    });
-                } else if path.is_ident("size") {
-                    size = Some(Value::from_meta(item));
-                } else if path.is_ident("value") {
-                    value = Some(Value::from_meta(item));
-                } else if path.is_ident("from") {
-                    from = Some(StringValue::from_meta(item));
-                } else if path.is_ident("extensible") {
-                    extensible = true;
-                } else if path.is_ident("extension_addition") {
-                    extension_addition = true;
-                } else if path.is_ident("extension_addition_group") {
-                    extension_addition_group = true;
-                } else {
-                    panic!(
-                        "unknown field tag {:?}",
-                        path.get_ident().map(ToString::to_string)
-                    );
-                }
+        let mut extension_addition = None;
+        let mut extension_addition_group = None;
+        let mut identifier = None;
+        let mut bound = Bounds::default();
+
+        for item in rasn_items(&field.attrs, &mut errors) {
+            let path = item.path();
+            if path.is_ident("tag") {
+                tag = errors.push_result(Tag::from_meta(&item));
+            } else if path.is_ident("default") {
+                default = match &item {
+                    syn::Meta::Path(_) => Some(DefaultValue::Trait),
+                    syn::Meta::NameValue(syn::MetaNameValue { lit, .. }) => errors.push_result(
+                        DefaultValue::from_lit(lit, &field.ty, &container_config.crate_root),
+                    ),
+                    _ => {
+                        errors.push(syn::Error::new_spanned(
+                            &item,
+                            "expected `default` or `default = value`",
+                        ));
+                        None
+                    }
+                };
+            } else if path.is_ident("size") {
+                size = errors.push_result(Value::from_meta(&item));
+            } else if path.is_ident("value") {
+                value = errors.push_result(Value::from_meta(&item));
+            } else if path.is_ident("from") {
+                from = errors.push_result(StringValue::from_meta(&item));
+            } else if path.is_ident("extensible") {
+                extensible = true;
+            } else if path.is_ident("extension_addition") {
+                extension_addition = Some(path.clone());
+            } else if path.is_ident("extension_addition_group") {
+                extension_addition_group = Some(path.clone());
+            } else if path.is_ident("identifier") {
+                identifier = errors
+                    .push_result(string_value(&item))
+                    .map(|lit| lit.value());
+            } else if path.is_ident("bound") {
+                bound = errors
+                    .push_result(Bounds::from_meta(&item))
+                    .unwrap_or_default();
+            } else {
+                errors.push(unknown_attribute(path));
             }
         }
 
-        if extension_addition && extension_addition_group {
-            panic!("field cannot be both `extension_addition` and `extension_addition_group`, choose one");
+        if let (Some(_), Some(path)) = (&extension_addition, &extension_addition_group) {
+            errors.push(syn::Error::new_spanned(
+                path,
+                "field cannot be both `extension_addition` and `extension_addition_group`, choose one",
+            ));
         }
 
-        Self {
+        errors.finish().map(|_| Self {
             container_config,
             default,
             field,
             tag,
-            extension_addition,
-            extension_addition_group,
+            extension_addition: extension_addition.is_some(),
+            extension_addition_group: extension_addition_group.is_some(),
+            identifier,
+            bound,
+            preceding_groups: Vec::new(),
             constraints: Constraints {
                 extensible,
                 from,
                 size,
                 value,
             },
-        }
+        })
     }
 
     pub fn encode(&self, context: usize, use_self: bool) -> proc_macro2::TokenStream {
@@ -790,10 +1319,7 @@ impl<'a> FieldConfig<'a> {
         let mut ty = self.field.ty.clone();
         let crate_root = &self.container_config.crate_root;
         ty.strip_lifetimes();
-        let default_fn = self.default.as_ref().map(|default_fn| match default_fn {
-            Some(path) => quote!(#path),
-            None => quote!(<#ty>::default),
-        });
+        let default_fn = self.default.as_ref().map(|default| default.function(&ty));
 
         let encode = if self.tag.is_some() || self.container_config.automatic_tags {
             if self.tag.as_ref().map_or(false, |tag| tag.is_explicit()) {
@@ -805,6 +1331,14 @@ impl<'a> FieldConfig<'a> {
                             #encode
                         }
                     }
+                } else if let Some(default_fn) = &default_fn {
+                    // `DEFAULT` values are never encoded, as required by DER
+                    // and CER.
+                    quote! {
+                        if #this #field != (#default_fn)() {
+                            #encode
+                        }
+                    }
                 } else {
                     encode
                 }
@@ -903,6 +1437,61 @@ impl<'a> FieldConfig<'a> {
         }
     }
 
+    /// Visits the field with its identifier, in the derived
+    /// `Encode::accept_with_context`.
+    pub fn accept(&self, context: usize) -> proc_macro2::TokenStream {
+        let crate_root = &self.container_config.crate_root;
+        let i = syn::Index::from(context);
+        let field = self
+            .field
+            .ident
+            .as_ref()
+            .map(|name| quote!(#name))
+            .unwrap_or_else(|| quote!(#i));
+        let mut ty = self.field.ty.clone();
+        ty.strip_lifetimes();
+        let identifier = self.identifier(context);
+        let tag = self.tag(context);
+        let (tag, explicit_tag) = if self.tag.as_ref().map_or(false, |tag| tag.is_explicit()) {
+            (
+                quote!(<#ty as #crate_root::AsnType>::TAG),
+                quote!(Some(#tag)),
+            )
+        } else {
+            (tag, quote!(None))
+        };
+        let constraints = match self.constraints.const_expr(crate_root) {
+            Some(constraints) => {
+                quote!(<#ty as #crate_root::AsnType>::CONSTRAINTS.override_constraints(#constraints))
+            }
+            None => quote!(<#ty as #crate_root::AsnType>::CONSTRAINTS),
+        };
+        let context = quote! {
+            #crate_root::visit::Context {
+                name: Some(#identifier),
+                tag: #tag,
+                explicit_tag: #explicit_tag,
+                constraints: #constraints,
+            }
+        };
+        let accept =
+            quote!(#crate_root::Encode::accept_with_context(&self.#field, visitor, #context)?;);
+
+        match &self.default {
+            Some(default) => {
+                let default_fn = default.function(&ty);
+                quote! {
+                    if self.#field == (#default_fn)() {
+                        visitor.visit_absent(&#context)?;
+                    } else {
+                        #accept
+                    }
+                }
+            }
+            None => accept,
+        }
+    }
+
     pub fn decode_field_def(&self, name: &syn::Ident, context: usize) -> proc_macro2::TokenStream {
         let lhs = self.field.ident.as_ref().map(|i| quote!(#i :));
         let decode_op = self.decode(name, context);
@@ -923,10 +1512,7 @@ impl<'a> FieldConfig<'a> {
         );
 
         let or_else = quote!(.map_err(|error| #crate_root::de::Error::field_error(#ident, error))?);
-        let default_fn = self.default.as_ref().map(|default_fn| match default_fn {
-            Some(path) => quote!(#path),
-            None => quote!(<#ty>::default),
-        });
+        let default_fn = self.default.as_ref().map(|default| default.function(ty));
 
         let tag = self.tag(context);
         let constraints = self.constraints.const_expr(crate_root);
@@ -944,10 +1530,7 @@ impl<'a> FieldConfig<'a> {
             match (
                 (self.tag.is_some() || self.container_config.automatic_tags)
                     .then(|| self.tag.as_ref().map_or(false, |tag| tag.is_explicit())),
-                self.default.as_ref().map(|path| {
-                    path.as_ref()
-                        .map_or(quote!(<_>::default), |path| quote!(#path))
-                }),
+                self.default.as_ref().map(|default| default.function(ty)),
                 self.constraints.has_constraints(),
             ) {
                 (Some(true), _, _) => {
@@ -1013,8 +1596,19 @@ impl<'a> FieldConfig<'a> {
         };
 
         if self.extension_addition {
+            let decode = if self
+                .tag
+                .as_ref()
+                .map_or(self.container_config.automatic_tags, |tag| {
+                    !tag.is_explicit()
+                }) {
+                quote!(decoder.decode_extension_addition_with_tag(#tag))
+            } else {
+                quote!(decoder.decode_extension_addition())
+            };
+
             quote! {
-                decoder.decode_extension_addition() #or_else #handle_extension
+                #decode #or_else #handle_extension
             }
         } else {
             quote!({
@@ -1025,10 +1619,6 @@ impl<'a> FieldConfig<'a> {
 
     pub fn tag_derive(&self, context: usize) -> proc_macro2::TokenStream {
         if let Some(tag) = &self.tag {
-            if self.container_config.automatic_tags {
-                panic!("You can't use the `#[rasn(tag)]` with `#[rasn(automatic_tags)]`")
-            }
-
             tag.to_attribute_tokens()
         } else if self.container_config.automatic_tags {
             let context = syn::Index::from(context);
@@ -1041,13 +1631,11 @@ impl<'a> FieldConfig<'a> {
     pub fn tag(&self, context: usize) -> proc_macro2::TokenStream {
         let crate_root = &self.container_config.crate_root;
         if let Some(tag) = &self.tag {
-            if self.container_config.automatic_tags {
-                panic!("You can't use the `#[rasn(tag)]` with `#[rasn(automatic_tags)]`")
-            }
             let tag = tag.to_tokens(crate_root);
             quote!(#tag)
         } else if self.container_config.automatic_tags {
-            quote!(#crate_root::Tag::new(#crate_root::types::Class::Context, #context as u32))
+            let number = self.automatic_tag_number(context);
+            quote!(#crate_root::Tag::new(#crate_root::types::Class::Context, #number))
         } else {
             let mut ty = self.field.ty.clone();
             ty.strip_lifetimes();
@@ -1055,6 +1643,23 @@ impl<'a> FieldConfig<'a> {
         }
     }
 
+    /// The number of the field's automatic tag. The components of extension
+    /// addition groups are numbered as if they weren't in a group, so the
+    /// numbering continues after the components of the preceding groups, and
+    /// the components of a group continue the numbering of its container.
+    pub fn automatic_tag_number(&self, context: usize) -> proc_macro2::TokenStream {
+        let crate_root = &self.container_config.crate_root;
+        let first_tag = self.container_config.extension_addition_group.then(
+            || quote!(<Self as #crate_root::types::ExtensionAdditionGroup>::FIRST_TAG as usize +),
+        );
+        let groups = self
+            .preceding_groups
+            .iter()
+            .map(|ty| quote!((<#ty as #crate_root::types::Constructed>::FIELD_COUNT - 1)));
+
+        quote!((#first_tag #context #(+ #groups)*) as u32)
+    }
+
     pub fn tag_tree(&self, context: usize) -> proc_macro2::TokenStream {
         let crate_root = &self.container_config.crate_root;
         let ty = &self.field.ty;
@@ -1067,10 +1672,24 @@ impl<'a> FieldConfig<'a> {
         }
     }
 
+    /// The field's ASN.1 identifier, which is its position for fields of
+    /// tuple structs.
+    pub fn identifier(&self, context: usize) -> String {
+        self.identifier.clone().unwrap_or_else(|| {
+            self.container_config.rename(
+                self.field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| context.to_string(), |ident| ident.unraw().to_string()),
+            )
+        })
+    }
+
     pub fn to_field_metadata(&self, context: usize) -> proc_macro2::TokenStream {
         let crate_root = &self.container_config.crate_root;
         let tag = self.tag(context);
         let tag_tree = self.tag_tree(context);
+        let identifier = self.identifier(context);
 
         let constructor = quote::format_ident!(
             "{}",
@@ -1081,7 +1700,9 @@ impl<'a> FieldConfig<'a> {
             }
         );
 
-        quote!({ #crate_root::types::fields::Field::#constructor(#tag, #tag_tree) })
+        quote!({
+            #crate_root::types::fields::Field::#constructor(#tag, #tag_tree).with_name(#identifier)
+        })
     }
 
     pub fn field_type(&self) -> FieldType {
@@ -1140,7 +1761,7 @@ impl<T> From<T> for Constraint<T> {
 pub struct StringValue(pub Vec<u32>);
 
 impl StringValue {
-    fn from_meta(item: &syn::Meta) -> Constraint<StringValue> {
+    fn from_meta(item: &syn::Meta) -> syn::Result<Constraint<StringValue>> {
         let mut values = Vec::new();
         let mut extensible: Option<_> = None;
 
@@ -1153,43 +1774,35 @@ impl StringValue {
             string.chars().map(u32::from).next()
         }
 
-        let syn::Meta::List(list) = item else {
-            panic!("Unsupported meta item: {:?}", item);
-        };
-
-        for item in &list.nested {
-            let string = match item {
-                NestedMeta::Lit(Lit::Str(string)) => string.value(),
-                NestedMeta::Meta(syn::Meta::Path(path)) => path.get_ident().unwrap().to_string(),
-                item => panic!("Unsupported meta item: {:?}", item),
-            };
+        for item in constraint_items(item)? {
+            let string = constraint_string(item)?;
 
             if string == "extensible" {
                 extensible = Some(Vec::new());
                 continue;
             }
 
-            if string.len() == 1 {
-                values.push(parse_character(&string).map(StringRange::Single).unwrap());
+            if string.chars().count() == 1 {
+                values.push(StringRange::Single(parse_character(&string).unwrap()));
                 continue;
             }
 
-            let Some((start, mut end)) = string.split_once("..") else {
-                panic!("unknown format: {string}, must be a single character or range of characters (`..`, `..=`)")
+            let invalid_range = || {
+                syn::Error::new_spanned(
+                    item,
+                    format!("invalid range `{string}`, must be a single character or range of characters (`..`, `..=`)"),
+                )
             };
 
-            let Some(start) = parse_character(start) else {
-                panic!("start of range was an invalid character: {start}")
-            };
+            let (start, mut end) = string.split_once("..").ok_or_else(invalid_range)?;
+            let start = parse_character(start).ok_or_else(invalid_range)?;
 
             let is_inclusive = end.starts_with('=');
             if is_inclusive {
                 end = &end[1..];
             }
 
-            let Some(end) = parse_character(end) else {
-                panic!("end of range was an invalid character: {end}")
-            };
+            let end = parse_character(end).ok_or_else(invalid_range)?;
 
             if let Some(extensible_values) = extensible.as_mut() {
                 extensible_values.push(StringRange::Range(start, end + is_inclusive as u32));
@@ -1212,10 +1825,10 @@ impl StringValue {
             set
         };
 
-        Constraint {
+        Ok(Constraint {
             constraint: Self((into_flat_set)(values)),
             extensible: extensible.map(|values| vec![Self((into_flat_set)(values))]),
-        }
+        })
     }
 }
 
@@ -1226,7 +1839,7 @@ pub enum Value {
 }
 
 impl Value {
-    fn from_meta(item: &syn::Meta) -> Constraint<Value> {
+    fn from_meta(item: &syn::Meta) -> syn::Result<Constraint<Value>> {
         let mut extensible = None;
         let mut constraint = None;
 
@@ -1234,20 +1847,13 @@ impl Value {
             string.parse().ok()
         }
 
-        let syn::Meta::List(list) = item else {
-            panic!("Unsupported meta item: {:?}", item);
-        };
+        for nested in constraint_items(item)? {
+            if let NestedMeta::Lit(Lit::Int(int)) = nested {
+                constraint = Some(Value::Single(int.base10_parse()?));
+                continue;
+            }
 
-        for item in list.nested.iter() {
-            let string = match item {
-                NestedMeta::Lit(Lit::Str(string)) => string.value(),
-                NestedMeta::Meta(syn::Meta::Path(path)) => path.get_ident().unwrap().to_string(),
-                NestedMeta::Lit(Lit::Int(int)) => {
-                    constraint = Some(int.base10_parse().map(Value::Single).unwrap());
-                    continue;
-                }
-                _ => panic!("Unsupported meta item: {item:?}"),
-            };
+            let string = constraint_string(nested)?;
 
             if string == "extensible" {
                 extensible = Some(Vec::new());
@@ -1257,9 +1863,12 @@ impl Value {
             let value = if let Some(number) = parse_character(&string) {
                 Value::Single(number)
             } else {
-                let Some((start, mut end)) = string.split_once("..") else {
-                    panic!("unknown format: {string}, must be a single character or range of characters (`..`, `..=`)")
-                };
+                let (start, mut end) = string.split_once("..").ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        nested,
+                        format!("invalid value `{string}`, must be a number or range of numbers (`..`, `..=`)"),
+                    )
+                })?;
 
                 let start = parse_character(start);
                 let is_inclusive = end.starts_with('=');
@@ -1276,13 +1885,358 @@ impl Value {
             } else if constraint.is_none() {
                 constraint = Some(value);
             } else {
-                panic!("Multiple non-extensible value constraints are not permitted.");
+                return Err(syn::Error::new_spanned(
+                    nested,
+                    "multiple non-extensible value constraints are not permitted",
+                ));
             }
         }
 
-        Constraint {
-            constraint: constraint.unwrap(),
+        let constraint = constraint
+            .ok_or_else(|| syn::Error::new_spanned(item, "expected a value or range of values"))?;
+
+        Ok(Constraint {
+            constraint,
             extensible,
+        })
+    }
+}
+
+/// The arguments of a constraint attribute such as `size(1..=4)`.
+fn constraint_items(
+    item: &syn::Meta,
+) -> syn::Result<&syn::punctuated::Punctuated<NestedMeta, syn::Token![,]>> {
+    match item {
+        syn::Meta::List(list) => Ok(&list.nested),
+        _ => Err(syn::Error::new_spanned(
+            item,
+            format!("expected `{}(...)`", item.path().to_token_stream()),
+        )),
+    }
+}
+
+/// A constraint argument written as a string literal or an identifier.
+fn constraint_string(item: &NestedMeta) -> syn::Result<String> {
+    match item {
+        NestedMeta::Lit(Lit::Str(string)) => Ok(string.value()),
+        NestedMeta::Meta(syn::Meta::Path(path)) if path.get_ident().is_some() => {
+            Ok(path.get_ident().unwrap().to_string())
+        }
+        _ => Err(syn::Error::new_spanned(
+            item,
+            "expected a string literal or `extensible`",
+        )),
+    }
+}
+
+/// The value of an attribute such as `identifier = "tbsCertificate"`.
+/// The `where` predicates of a `bound` attribute, which replace the bounds
+/// inferred for each derived trait.
+#[derive(Clone, Debug, Default)]
+pub struct Bounds {
+    pub asn_type: Option<Vec<syn::WherePredicate>>,
+    pub encode: Option<Vec<syn::WherePredicate>>,
+    pub decode: Option<Vec<syn::WherePredicate>>,
+}
+
+impl Bounds {
+    /// Parses `bound = "..."`, which applies to every trait, or
+    /// `bound(asn_type = "...", encode = "...", decode = "...")`.
+    fn from_meta(item: &syn::Meta) -> syn::Result<Self> {
+        match item {
+            syn::Meta::NameValue(_) => {
+                let predicates = Self::predicates(&string_value(item)?)?;
+
+                Ok(Self {
+                    asn_type: Some(predicates.clone()),
+                    encode: Some(predicates.clone()),
+                    decode: Some(predicates),
+                })
+            }
+            syn::Meta::List(list) => {
+                let mut bounds = Self::default();
+
+                for item in &list.nested {
+                    let item = match item {
+                        NestedMeta::Meta(item @ syn::Meta::NameValue(_)) => item,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                item,
+                                "expected `asn_type`, `encode`, or `decode = \"...\"`",
+                            ))
+                        }
+                    };
+                    let predicates = Some(Self::predicates(&string_value(item)?)?);
+                    let path = item.path();
+
+                    if path.is_ident("asn_type") {
+                        bounds.asn_type = predicates;
+                    } else if path.is_ident("encode") {
+                        bounds.encode = predicates;
+                    } else if path.is_ident("decode") {
+                        bounds.decode = predicates;
+                    } else {
+                        return Err(unknown_attribute(path));
+                    }
+                }
+
+                Ok(bounds)
+            }
+            syn::Meta::Path(_) => Err(syn::Error::new_spanned(
+                item,
+                "expected `bound = \"...\"` or `bound(encode = \"...\", ...)`",
+            )),
+        }
+    }
+
+    fn predicates(lit: &syn::LitStr) -> syn::Result<Vec<syn::WherePredicate>> {
+        use syn::parse::Parser;
+
+        syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated
+            .parse_str(&lit.value())
+            .map(|predicates| predicates.into_iter().collect())
+            .map_err(|error| syn::Error::new_spanned(lit, error))
+    }
+
+    /// The predicates for the trait named `ident`, if they were given.
+    pub fn for_trait(&self, ident: &syn::Ident) -> Option<&[syn::WherePredicate]> {
+        if ident == "AsnType" {
+            self.asn_type.as_deref()
+        } else if ident == "Encode" {
+            self.encode.as_deref()
+        } else {
+            self.decode.as_deref()
+        }
+    }
+}
+
+fn string_value(item: &syn::Meta) -> syn::Result<syn::LitStr> {
+    match item {
+        syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Ok(lit.clone()),
+        _ => Err(syn::Error::new_spanned(
+            item,
+            format!("expected `{} = \"...\"`", item.path().to_token_stream()),
+        )),
+    }
+}
+
+/// A casing rule for `#[rasn(rename_all = "...")]`.
+#[derive(Clone, Copy, Debug)]
+pub enum Case {
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+}
+
+impl Case {
+    fn from_lit(lit: syn::LitStr) -> syn::Result<Self> {
+        Ok(match &*lit.value() {
+            "camelCase" => Self::Camel,
+            "PascalCase" => Self::Pascal,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "expected `camelCase`, `PascalCase`, `snake_case`, \
+                    `SCREAMING_SNAKE_CASE`, or `kebab-case`",
+                ))
+            }
+        })
+    }
+
+    fn apply(self, name: &str) -> String {
+        use heck::{CamelCase, KebabCase, MixedCase, ShoutySnakeCase, SnakeCase};
+
+        match self {
+            Self::Camel => name.to_mixed_case(),
+            Self::Pascal => name.to_camel_case(),
+            Self::Snake => name.to_snake_case(),
+            Self::ScreamingSnake => name.to_shouty_snake_case(),
+            Self::Kebab => name.to_kebab_case(),
+        }
+    }
+}
+
+/// Collects the errors found in a derive's attributes, so that they can all
+/// be reported at once.
+#[derive(Default)]
+pub struct Errors(Option<syn::Error>);
+
+impl Errors {
+    pub fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Returns the value of `result`, or records its error.
+    pub fn push_result<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    pub fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// The items of every `#[rasn(...)]` attribute in `attrs`.
+fn rasn_items(attrs: &[syn::Attribute], errors: &mut Errors) -> Vec<syn::Meta> {
+    let mut items = Vec::new();
+
+    for attr in attrs.iter().filter(|a| a.path.is_ident(crate::CRATE_NAME)) {
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => {
+                for item in list.nested {
+                    match item {
+                        NestedMeta::Meta(meta) => items.push(meta),
+                        NestedMeta::Lit(lit) => errors.push(syn::Error::new_spanned(
+                            lit,
+                            "expected an attribute, found a literal",
+                        )),
+                    }
+                }
+            }
+            Ok(meta) => errors.push(syn::Error::new_spanned(meta, "expected `#[rasn(...)]`")),
+            Err(error) => errors.push(error),
         }
     }
+
+    items
+}
+
+fn unknown_attribute(path: &syn::Path) -> syn::Error {
+    syn::Error::new_spanned(
+        path,
+        format!("unknown attribute `{}`", path.to_token_stream()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(input: syn::DeriveInput) -> Vec<String> {
+        Config::from_attributes(&input)
+            .unwrap_err()
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn reports_every_error() {
+        assert_eq!(
+            vec![
+                "unknown attribute `sett`",
+                "class must be `universal`, `application`, `context`, or `private`",
+                "unknown attribute `optional`",
+            ],
+            errors(syn::parse_quote! {
+                #[rasn(sett)]
+                struct Foo {
+                    #[rasn(tag(contxt, 0))]
+                    a: bool,
+                    #[rasn(optional)]
+                    b: Option<bool>,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_default_values() {
+        assert_eq!(
+            vec![
+                "bit strings may only contain `0` and `1`",
+                "expected an integer, boolean, byte string, or string",
+                "object identifier arcs must be numbers, or names with numbers",
+            ],
+            errors(syn::parse_quote! {
+                struct Foo {
+                    #[rasn(default = "'102'B")]
+                    a: BitString,
+                    #[rasn(default = 1.5)]
+                    b: Real,
+                    #[rasn(default = "{ iso 2 }")]
+                    c: ObjectIdentifier,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_bounds() {
+        assert_eq!(
+            vec![
+                "expected `bound = \"...\"` or `bound(encode = \"...\", ...)`",
+                "unknown attribute `display`",
+            ],
+            errors(syn::parse_quote! {
+                #[rasn(bound)]
+                struct Foo<T> {
+                    #[rasn(bound(display = "T: Display"))]
+                    a: T,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_unknown_variants() {
+        assert_eq!(
+            vec![
+                "`#[rasn(unknown)]` variants must have a single `isize` field, e.g. `Unknown(isize)`",
+                "`#[rasn(unknown)]` variants can't be extension additions",
+                "only one variant can be annotated with `#[rasn(unknown)]`",
+                "`#[rasn(unknown)]` is only valid on variants of enumerated enums",
+            ],
+            [
+                errors(syn::parse_quote! {
+                    #[rasn(enumerated)]
+                    enum Foo {
+                        A,
+                        #[rasn(unknown)]
+                        B,
+                        #[rasn(unknown)]
+                        C(isize),
+                        #[rasn(unknown, extension_addition)]
+                        D(isize),
+                        #[rasn(unknown)]
+                        E(isize),
+                    }
+                }),
+                errors(syn::parse_quote! {
+                    #[rasn(choice)]
+                    enum Foo {
+                        #[rasn(unknown)]
+                        A(isize),
+                    }
+                }),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn enums_need_choice_or_enumerated() {
+        assert_eq!(
+            vec![
+                "enums must be annotated with either `#[rasn(choice)]` or `#[rasn(enumerated)]`",
+                "tuple-style enum variants must contain only a single field, switch to struct-style variants for multiple fields",
+            ],
+            errors(syn::parse_quote! {
+                enum Foo {
+                    A(bool, bool),
+                }
+            })
+        );
+    }
 }
@@ -7,10 +7,11 @@ pub fn derive_struct_impl(
     mut generics: syn::Generics,
     container: syn::DataStruct,
     config: &Config,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
     let mut list = vec![];
     let crate_root = &config.crate_root;
-    generics.add_trait_bounds(crate_root, quote::format_ident!("Decode"));
+    let field_configs = FieldConfig::for_fields(&container.fields, config)?;
+    generics.add_trait_bounds(config, &container.fields, quote::format_ident!("Decode"))?;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let decode_impl = if config.delegate {
@@ -22,8 +23,17 @@ pub fn derive_struct_impl(
             .map(|tag| tag.is_explicit())
             .unwrap_or_default()
         {
-            quote! {
-                decoder.decode_explicit_prefix::<#ty>(tag).map(Self)
+            if config.constraints.has_constraints() {
+                quote! {
+                    decoder.decode_explicit_prefix_with_constraints::<#ty>(
+                        tag,
+                        <#ty as #crate_root::AsnType>::CONSTRAINTS.override_constraints(constraints),
+                    ).map(Self)
+                }
+            } else {
+                quote! {
+                    decoder.decode_explicit_prefix::<#ty>(tag).map(Self)
+                }
             }
         } else {
             quote! {
@@ -35,52 +45,70 @@ pub fn derive_struct_impl(
             }
         }
     } else if config.set {
+        // The tags of the fields are passed to the derives of their
+        // wrappers below, which need them as literals.
+        if let Some(field) = field_configs.iter().find(|field| {
+            config.automatic_tags && field.tag.is_none() && !field.preceding_groups.is_empty()
+        }) {
+            return Err(syn::Error::new_spanned(
+                field.field,
+                "fields after an `extension_addition_group` in a `set` can't be tagged \
+                automatically, tag the fields with `#[rasn(tag)]` instead",
+            ));
+        }
+
         let field_names = container.fields.iter().map(|field| field.ident.clone());
         let field_names2 = field_names.clone();
-        let required_field_names = container
-            .fields
+        let required_field_names = field_configs
             .iter()
-            .filter(|field| !FieldConfig::new(field, config).is_option_type())
-            .map(|field| field.ident.clone());
-        let (field_type_names, field_type_defs): (Vec<_>, Vec<_>) = container
-            .fields
+            .filter(|config| !config.is_option_type())
+            .map(|config| config.field.ident.clone());
+        let (field_type_names, field_type_defs): (Vec<_>, Vec<_>) = field_configs
             .iter()
             .enumerate()
-            .map(|(i, field)| {
+            .map(|(i, config)| {
+                let field = config.field;
                 let ty = config
+                    .container_config
                     .option_type
                     .map_to_inner_type(&field.ty)
                     .unwrap_or(&field.ty);
-                let config = FieldConfig::new(field, config);
                 let tag_attr = config.tag_derive(i);
                 let constraints = config.constraints.attribute_tokens();
                 let name = quote::format_ident!("Field{}", i);
-                let ty = if config.extension_addition || config.extension_addition_group {
-                    quote!(Option<#ty>)
+                let ty: syn::Type = if config.extension_addition || config.extension_addition_group
+                {
+                    syn::parse_quote!(Option<#ty>)
                 } else {
-                    quote!(#ty)
+                    ty.clone()
                 };
+                let field_generics = generics.used_by(&ty);
+                let (_, field_ty_generics, _) = field_generics.split_for_impl();
 
                 (
-                    name.clone(),
+                    quote!(#name #field_ty_generics),
                     quote! {
                         #[derive(#crate_root::AsnType, #crate_root::Decode, #crate_root::Encode)]
                         #[rasn(delegate)]
                         #tag_attr
                         #constraints
-                        pub struct #name(#ty);
+                        pub struct #name #field_generics (#ty);
                     },
                 )
             })
             .unzip();
+        let field_variant_names: Vec<_> = (0..field_type_names.len())
+            .map(|i| quote::format_ident!("Field{}", i))
+            .collect();
 
         let choice_name = quote::format_ident!("{}Fields", name);
 
+        let choice_params = &generics.params;
         let choice_def = quote! {
             #[derive(#crate_root::AsnType, #crate_root::Decode, #crate_root::Encode)]
             #[rasn(choice)]
-            enum #choice_name {
-                #(#field_type_names(#field_type_names)),*
+            enum #choice_name <#choice_params> {
+                #(#field_variant_names(#field_type_names)),*
             }
         };
 
@@ -90,14 +118,12 @@ pub fn derive_struct_impl(
             syn::Fields::Named(_) => quote!({ #(#field_names2),* }),
         };
 
-        let (field_const_defs, field_match_arms, field_set_arms): (Vec<_>, Vec<_>, Vec<_>) = itertools::multiunzip(container.fields
+        let (field_match_arms, field_set_arms): (Vec<_>, Vec<_>) = field_configs
             .iter()
             .enumerate()
-            .zip(field_type_names)
-            .map(|((context, field), field_name)| {
-                let config = FieldConfig::new(field, config);
+            .zip(&field_variant_names)
+            .map(|((context, config), field_name)| {
                 let tag = config.tag(context);
-                let const_name = quote::format_ident!("{}Const", field_name);
                 let decode_impl = if config.extension_addition {
                     quote!(#field_name(decoder.decode_extension_addition()?))
                 } else if config.extension_addition_group {
@@ -105,7 +131,7 @@ pub fn derive_struct_impl(
                 } else {
                     quote!(<_>::decode(decoder)?)
                 };
-                let ident = &field.ident;
+                let ident = &config.field.ident;
 
                 let set_field_impl = if config.extension_addition || config.extension_addition_group {
                     quote! {
@@ -124,19 +150,17 @@ pub fn derive_struct_impl(
                 };
 
                 (
-                    quote!(const #const_name: Tag = #tag;),
-                    quote!((#context, #const_name) => { #choice_name::#field_name(#decode_impl) }),
+                    quote!((#context, tag) if tag == #tag => { #choice_name::#field_name(#decode_impl) }),
                     quote!(#choice_name::#field_name(value) => { #set_field_impl })
                 )
-            }));
+            })
+            .unzip();
 
         quote! {
             #choice_def
             #(#field_type_defs)*
 
-            decoder.decode_set::<#choice_name, _, _, _>(tag, |decoder, index, tag| {
-                    #(#field_const_defs)*
-
+            decoder.decode_set::<#choice_name #ty_generics, _, _, _>(tag, |decoder, index, tag| {
                     Ok(match (index, tag) {
                         #(#field_match_arms)*
                         _ => return Err(#crate_root::de::Error::custom("Unknown field provided.")),
@@ -158,9 +182,7 @@ pub fn derive_struct_impl(
             )
         }
     } else {
-        for (i, field) in container.fields.iter().enumerate() {
-            let field_config = FieldConfig::new(field, config);
-
+        for (i, field_config) in field_configs.iter().enumerate() {
             list.push(field_config.decode_field_def(&name, i));
         }
 
@@ -194,13 +216,13 @@ pub fn derive_struct_impl(
             decode_impl
         };
 
-    quote! {
+    Ok(quote! {
         impl #impl_generics #crate_root::Decode for #name #ty_generics #where_clause {
             fn decode_with_tag_and_constraints<'constraints, D: #crate_root::Decoder>(decoder: &mut D, tag: #crate_root::Tag, constraints: #crate_root::types::Constraints<'constraints>) -> core::result::Result<Self, D::Error> {
                 #decode_impl
             }
         }
-    }
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -5,16 +5,15 @@ pub fn derive_struct_impl(
     mut generics: syn::Generics,
     container: syn::DataStruct,
     config: &Config,
-) -> proc_macro2::TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
     let crate_root = &config.crate_root;
-    let list: Vec<_> = container
-        .fields
+    let list: Vec<_> = FieldConfig::for_fields(&container.fields, config)?
         .iter()
         .enumerate()
-        .map(|(i, field)| FieldConfig::new(field, config).encode(i, true))
+        .map(|(i, field)| field.encode(i, true))
         .collect();
 
-    generics.add_trait_bounds(crate_root, quote::format_ident!("Encode"));
+    generics.add_trait_bounds(config, &container.fields, quote::format_ident!("Encode"))?;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let encode_impl = if config.delegate {
@@ -22,7 +21,19 @@ pub fn derive_struct_impl(
 
         if let Some(tag) = config.tag.as_ref().filter(|tag| tag.is_explicit()) {
             let tag = tag.to_tokens(crate_root);
-            let encode = quote!(encoder.encode_explicit_prefix(#tag, &self.0).map(drop));
+            // Choices are encoded through `encode`, so the constraints are
+            // only passed down when there are some to add.
+            let encode = if config.constraints.has_constraints() {
+                quote! {
+                    encoder.encode_explicit_prefix_with_constraints(
+                        #tag,
+                        <#ty as #crate_root::AsnType>::CONSTRAINTS.override_constraints(constraints),
+                        &self.0,
+                    ).map(drop)
+                }
+            } else {
+                quote!(encoder.encode_explicit_prefix(#tag, &self.0).map(drop))
+            };
             if config.option_type.is_option_type(ty) {
                 let none_variant = &config.option_type.none_variant;
                 quote! {
@@ -71,16 +82,75 @@ pub fn derive_struct_impl(
         }
     };
 
+    let accept_impl = if config.delegate {
+        let ty = &container.fields.iter().next().unwrap().ty;
+        let context = if config.tag.as_ref().map_or(false, |tag| tag.is_explicit()) {
+            quote! {
+                #crate_root::visit::Context {
+                    tag: <#ty as #crate_root::AsnType>::TAG,
+                    explicit_tag: context.explicit_tag.or(Some(context.tag)),
+                    constraints: <#ty as #crate_root::AsnType>::CONSTRAINTS.override_constraints(context.constraints),
+                    ..context
+                }
+            }
+        } else {
+            quote! {
+                #crate_root::visit::Context {
+                    constraints: <#ty as #crate_root::AsnType>::CONSTRAINTS.override_constraints(context.constraints),
+                    ..context
+                }
+            }
+        };
+
+        quote!(#crate_root::Encode::accept_with_context(&self.0, visitor, #context))
+    } else {
+        let (kind, tag) = if config.set {
+            (quote!(Set), quote!(SET))
+        } else {
+            (quote!(Sequence), quote!(SEQUENCE))
+        };
+        let explicit_context = config
+            .tag
+            .as_ref()
+            .filter(|tag| tag.is_explicit())
+            .map(|_| {
+                quote! {
+                    let context = #crate_root::visit::Context {
+                        tag: #crate_root::Tag::#tag,
+                        explicit_tag: context.explicit_tag.or(Some(context.tag)),
+                        ..context
+                    };
+                }
+            });
+        let fields = container
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| Ok(FieldConfig::new(field, config)?.accept(i)))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        quote! {
+            #explicit_context
+            visitor.enter_constructed(&context, #crate_root::visit::Kind::#kind)?;
+            #(#fields)*
+            visitor.leave_constructed(&context, #crate_root::visit::Kind::#kind)
+        }
+    };
+
     let vars = fields_as_vars(&container.fields);
-    quote! {
+    Ok(quote! {
         impl #impl_generics  #crate_root::Encode for #name #ty_generics #where_clause {
             fn encode_with_tag_and_constraints<'constraints, EN: #crate_root::Encoder>(&self, encoder: &mut EN, tag: #crate_root::Tag, constraints: #crate_root::types::Constraints<'constraints>) -> core::result::Result<(), EN::Error> {
                 #(#vars)*
 
                 #encode_impl
             }
+
+            fn accept_with_context<VI: #crate_root::visit::Visitor>(&self, visitor: &mut VI, context: #crate_root::visit::Context) -> core::result::Result<(), VI::Error> {
+                #accept_impl
+            }
         }
-    }
+    })
 }
 
 pub fn map_to_inner_type(
@@ -90,6 +160,29 @@ pub fn map_to_inner_type(
     generics: &syn::Generics,
     crate_root: &syn::Path,
     is_explicit: bool,
+) -> proc_macro2::TokenStream {
+    let inner = inner_type(name, fields, generics, crate_root);
+    let inner_impl = if is_explicit {
+        let tag = tag.to_tokens(crate_root);
+        quote!(encoder.encode_explicit_prefix(#tag, &inner).map(drop))
+    } else {
+        quote!(inner.encode(encoder))
+    };
+
+    quote! {
+        #inner
+
+        #inner_impl
+    }
+}
+
+/// Defines a struct borrowing `fields`, and binds an instance of it to
+/// `inner`.
+pub fn inner_type(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    generics: &syn::Generics,
+    crate_root: &syn::Path,
 ) -> proc_macro2::TokenStream {
     let inner_name = quote::format_ident!("Inner{}", name);
     let mut inner_generics = generics.clone();
@@ -145,20 +238,11 @@ pub fn map_to_inner_type(
         syn::Fields::Unit => (quote!(;), quote!()),
     };
 
-    let inner_impl = if is_explicit {
-        let tag = tag.to_tokens(crate_root);
-        quote!(encoder.encode_explicit_prefix(#tag, &inner).map(drop))
-    } else {
-        quote!(inner.encode(encoder))
-    };
-
     quote! {
         #[derive(#crate_root::AsnType, #crate_root::Encode)]
         struct #inner_name #inner_generics #field_defs
 
         let inner = #inner_name #init_fields;
-
-        #inner_impl
     }
 }
 
@@ -1,7 +1,9 @@
-use itertools::Itertools;
 use quote::ToTokens;
 
-use crate::{config::*, ext::GenericsExt};
+use crate::{
+    config::*,
+    ext::{GenericsExt, TypeExt},
+};
 
 pub struct Enum {
     pub name: syn::Ident,
@@ -11,8 +13,26 @@ pub struct Enum {
 }
 
 impl Enum {
-    pub fn impl_asntype(&self) -> proc_macro2::TokenStream {
+    /// The fields of every variant.
+    fn fields(&self) -> impl Iterator<Item = &syn::Field> {
+        self.variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+    }
+
+    fn variant_configs<'a>(
+        &'a self,
+        generics: &'a syn::Generics,
+    ) -> syn::Result<Vec<VariantConfig<'a>>> {
+        self.variants
+            .iter()
+            .map(|variant| VariantConfig::new(variant, generics, &self.config))
+            .collect()
+    }
+
+    pub fn impl_asntype(&self) -> syn::Result<proc_macro2::TokenStream> {
         let crate_root = &self.config.crate_root;
+        let variant_configs = self.variant_configs(&self.generics)?;
 
         let tag = self
             .config
@@ -31,19 +51,26 @@ impl Enum {
             self.name
         );
 
+        // The tags of generic types can't be checked in `const` items, as
+        // they can't refer to the type parameters.
+        let is_generic = self.generics.type_params().next().is_some();
         let tag_tree = if self.config.choice {
-            let field_tags = self
-                .variants
+            let field_tags = variant_configs
                 .iter()
                 .enumerate()
-                .map(|(i, v)| VariantConfig::new(v, &self.generics, &self.config).tag_tree(i));
-
-            quote! {
-                {
-                    const VARIANT_LIST: &'static [#crate_root::TagTree] = &[#(#field_tags),*];
-                    const VARIANT_TAG_TREE: #crate_root::TagTree = #crate_root::TagTree::Choice(VARIANT_LIST);
-                    const _: () = assert!(VARIANT_TAG_TREE.is_unique(), #error_message);
-                    VARIANT_TAG_TREE
+                .map(|(i, config)| config.tag_tree(i))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            if is_generic {
+                quote!(#crate_root::TagTree::Choice(&[#(#field_tags),*]))
+            } else {
+                quote! {
+                    {
+                        const VARIANT_LIST: &'static [#crate_root::TagTree] = &[#(#field_tags),*];
+                        const VARIANT_TAG_TREE: #crate_root::TagTree = #crate_root::TagTree::Choice(VARIANT_LIST);
+                        const _: () = assert!(VARIANT_TAG_TREE.is_unique(), #error_message);
+                        VARIANT_TAG_TREE
+                    }
                 }
             }
         } else {
@@ -51,26 +78,38 @@ impl Enum {
         };
 
         let name = &self.name;
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let mut generics = self.generics.clone();
+        generics.add_trait_bounds(&self.config, self.fields(), quote::format_ident!("AsnType"))?;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let return_val = self
             .config
             .tag
             .is_some()
-            .then(|| quote!(#crate_root::types::TagTree::Leaf(Self::TAG)))
-            .unwrap_or_else(|| quote!(TAG_TREE));
+            .then(|| quote!(#crate_root::types::TagTree::Leaf(Self::TAG)));
+        let tag_tree_def = if is_generic {
+            return_val.unwrap_or_else(|| quote!(#crate_root::TagTree::Choice(&[#tag_tree])))
+        } else {
+            let return_val = return_val.unwrap_or_else(|| quote!(TAG_TREE));
+            quote!({
+                const LIST: &'static [#crate_root::TagTree] = &[#tag_tree];
+                const TAG_TREE: #crate_root::TagTree = #crate_root::TagTree::Choice(LIST);
+                const _: () = assert!(TAG_TREE.is_unique(), #error_message);
+                #return_val
+            })
+        };
 
-        let (base_variants, extended_variants): (Vec<_>, Vec<_>) = self
-            .variants
-            .iter()
-            .enumerate()
-            .partition_map(|(i, variant)| {
-                let config = VariantConfig::new(variant, &self.generics, &self.config);
-                let tag_tree = config.tag_tree(i);
-                match config.extension_addition {
-                    false => either::Left(tag_tree),
-                    true => either::Right(tag_tree),
-                }
-            });
+        let mut base_variants = Vec::new();
+        let mut extended_variants = Vec::new();
+        for (i, config) in variant_configs.iter().enumerate() {
+            let variant = (config.tag_tree(i)?, config.identifier());
+            match config.extension_addition {
+                false => base_variants.push(variant),
+                true => extended_variants.push(variant),
+            }
+        }
+        let (base_variants, base_identifiers): (Vec<_>, Vec<_>) = base_variants.into_iter().unzip();
+        let (extended_variants, extended_identifiers): (Vec<_>, Vec<_>) =
+            extended_variants.into_iter().unzip();
 
         let constraints_def = self.config.constraints.const_static_def(crate_root);
 
@@ -82,25 +121,53 @@ impl Enum {
                 const EXTENDED_VARIANTS: &'static [#crate_root::types::TagTree] = &[
                     #(#extended_variants),*
                 ];
+                const IDENTIFIERS: &'static [&'static str] = &[#(#base_identifiers),*];
+                const EXTENDED_IDENTIFIERS: &'static [&'static str] = &[
+                    #(#extended_identifiers),*
+                ];
 
             }
         });
 
         let enumerated_impl = self.config.enumerated.then(|| {
-            let (variants, extended_variants): (Vec<_>, Vec<_>) = self.variants.iter()
-                .map(|variant| VariantConfig::new(variant, &self.generics, &self.config))
+            let (unknown, variants): (Vec<_>, Vec<_>) = variant_configs
+                .into_iter()
+                .partition(|config| config.unknown);
+            let (variants, extended_variants): (Vec<_>, Vec<_>) = variants
+                .into_iter()
                 .partition(|config| !config.extension_addition);
 
-            let discriminants = variants.iter().enumerate().map(|(i, config)| {
-                let discriminant = config.discriminant().unwrap_or(i) as isize;
+            let root_discriminants: Vec<_> = variants.iter().enumerate()
+                .map(|(i, config)| config.discriminant().unwrap_or(i))
+                .collect();
+            let discriminants = variants.iter().zip(&root_discriminants).map(|(config, discriminant)| {
+                let discriminant = *discriminant as isize;
                 let variant = &config.variant.ident;
                 quote!((Self::#variant, #discriminant))
             });
-            let extended_discriminants = extended_variants.iter().enumerate().map(|(i, config)| {
-                let discriminant = config.discriminant().unwrap_or(i) as isize;
+            // Extension additions without a discriminant take the smallest
+            // value that's greater than the previous addition and isn't used
+            // in the root (X.680 §20.3).
+            let mut next_discriminant = 0;
+            let extended_discriminants = extended_variants.iter().map(|config| {
+                let discriminant = config.discriminant().unwrap_or_else(|| {
+                    (next_discriminant..)
+                        .find(|value| !root_discriminants.contains(value))
+                        .unwrap()
+                });
+                next_discriminant = discriminant + 1;
+                let discriminant = discriminant as isize;
                 let variant = &config.variant.ident;
                 quote!((Self::#variant, #discriminant))
-            });
+            }).collect::<Vec<_>>();
+
+            let identifiers = variants.iter().map(VariantConfig::identifier);
+            let extended_identifiers = (!extended_variants.is_empty())
+                .then(|| {
+                    let identifiers = extended_variants.iter().map(VariantConfig::identifier);
+                    quote!(Some(&[#(#identifiers,)*]))
+                })
+                .unwrap_or(quote!(None));
 
             let variants = variants.iter().map(|config| config.variant.ident.clone());
             let extended_variant_idents = extended_variants.iter().map(|config| config.variant.ident.clone());
@@ -111,6 +178,23 @@ impl Enum {
                 .then(|| quote!(Some(&[#(#extended_discriminants,)*])))
                 .unwrap_or(quote!(None));
 
+            let unknown = unknown.first().map(|config| {
+                let variant = &config.variant.ident;
+                quote! {
+                    fn from_unknown_discriminant(value: isize) -> Option<Self> {
+                        Some(Self::#variant(value))
+                    }
+
+                    fn unknown_discriminant(&self) -> Option<isize> {
+                        if let Self::#variant(value) = self {
+                            Some(*value)
+                        } else {
+                            None
+                        }
+                    }
+                }
+            });
+
             quote! {
                 impl #impl_generics #crate_root::types::Enumerated for #name #ty_generics #where_clause {
                     const VARIANTS: &'static [Self] = &[#(Self::#variants,)*];
@@ -118,53 +202,55 @@ impl Enum {
 
                     const DISCRIMINANTS: &'static [(Self, isize)] = &[#(#discriminants,)*];
                     const EXTENDED_DISCRIMINANTS: Option<&'static [(Self, isize)]> = #extended_discriminants;
+
+                    const IDENTIFIERS: &'static [&'static str] = &[#(#identifiers,)*];
+                    const EXTENDED_IDENTIFIERS: Option<&'static [&'static str]> = #extended_identifiers;
+
+                    #unknown
                 }
             }
         });
 
-        quote! {
+        Ok(quote! {
             impl #impl_generics #crate_root::AsnType for #name #ty_generics #where_clause {
                 const TAG: #crate_root::Tag = {
                     #tag
                 };
-                const TAG_TREE: #crate_root::TagTree = {
-                    const LIST: &'static [#crate_root::TagTree] = &[#tag_tree];
-                    const TAG_TREE: #crate_root::TagTree = #crate_root::TagTree::Choice(LIST);
-                    const _: () = assert!(TAG_TREE.is_unique(), #error_message);
-                    #return_val
-                };
+                const TAG_TREE: #crate_root::TagTree = #tag_tree_def;
 
                 #constraints_def
             }
 
             #choice_impl
             #enumerated_impl
-        }
+        })
     }
 
-    pub fn impl_encode(&self) -> proc_macro2::TokenStream {
+    pub fn impl_encode(&self) -> syn::Result<proc_macro2::TokenStream> {
         let crate_root = &self.config.crate_root;
         let mut generics = self.generics.clone();
-        generics.add_trait_bounds(crate_root, quote::format_ident!("Encode"));
+        generics.add_trait_bounds(&self.config, self.fields(), quote::format_ident!("Encode"))?;
 
         let name = &self.name;
-        let encode = self.encode(&generics);
+        let encode = self.encode(&generics)?;
         let encode_with_tag = self.encode_with_tag();
+        let accept = self.accept(&generics)?;
         let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-        quote! {
+        Ok(quote! {
             #[automatically_derived]
             impl #impl_generics #crate_root::Encode for #name #ty_generics #where_clause {
                 #encode
                 #encode_with_tag
+                #accept
             }
-        }
+        })
     }
 
-    pub fn impl_decode(&self) -> proc_macro2::TokenStream {
+    pub fn impl_decode(&self) -> syn::Result<proc_macro2::TokenStream> {
         let crate_root = &self.config.crate_root;
         let mut generics = self.generics.clone();
-        generics.add_trait_bounds(&self.config.crate_root, quote::format_ident!("Decode"));
+        generics.add_trait_bounds(&self.config, self.fields(), quote::format_ident!("Decode"))?;
         let decode_with_tag = if self.config.enumerated {
             quote!(decoder.decode_enumerated(tag))
         } else {
@@ -235,10 +321,16 @@ impl Enum {
         let name = &self.name;
         let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-        let decode_choice_impl = self.config.choice.then(|| {
-            let decode_ops = self.variants.iter()
+        let decode_ops = if self.config.choice {
+            self.variant_configs(&generics)?
+                .iter()
                 .enumerate()
-                .map(|(i, v)| VariantConfig::new(v, &generics, &self.config).decode(&self.name, i));
+                .map(|(i, config)| config.decode(&self.name, i))
+                .collect::<syn::Result<Vec<_>>>()?
+        } else {
+            Vec::new()
+        };
+        let decode_choice_impl = self.config.choice.then(|| {
 
             let str_name = syn::LitStr::new(&self.name.to_string(), proc_macro2::Span::call_site());
             let from_tag = quote! {
@@ -257,7 +349,7 @@ impl Enum {
             }
         });
 
-        quote! {
+        Ok(quote! {
             #decode_choice_impl
 
             #[automatically_derived]
@@ -268,7 +360,7 @@ impl Enum {
 
                 #decode_impl
             }
-        }
+        })
     }
 
     fn encode_with_tag(&self) -> proc_macro2::TokenStream {
@@ -290,20 +382,21 @@ impl Enum {
         }
     }
 
-    fn encode(&self, generics: &syn::Generics) -> Option<proc_macro2::TokenStream> {
+    fn encode(&self, generics: &syn::Generics) -> syn::Result<Option<proc_macro2::TokenStream>> {
         if self.config.choice {
-            Some(self.encode_choice(generics))
+            self.encode_choice(generics).map(Some)
         } else {
-            None
+            Ok(None)
         }
     }
 
-    fn encode_choice(&self, generics: &syn::Generics) -> proc_macro2::TokenStream {
+    fn encode_choice(&self, generics: &syn::Generics) -> syn::Result<proc_macro2::TokenStream> {
         let crate_root = &self.config.crate_root;
-        let variants = self.variants.iter().enumerate().map(|(i, v)| {
+        let variant_configs = self.variant_configs(generics)?;
+        let variants = variant_configs.iter().enumerate().map(|(i, variant_config)| {
+            let v = variant_config.variant;
             let ident = &v.ident;
             let name = &self.name;
-            let variant_config = VariantConfig::new(v, generics, &self.config);
             let variant_tag = variant_config.tag(i);
 
             match &v.fields {
@@ -326,9 +419,6 @@ impl Enum {
                     quote!(#name::#ident { #(#idents),* } => { #encode_impl.map(|_| #tag_tokens) })
                 }
                 syn::Fields::Unnamed(_) => {
-                    if v.fields.iter().count() != 1 {
-                        panic!("Tuple variants must contain only a single element.");
-                    }
                     let constraints = variant_config
                         .constraints
                         .const_expr(&self.config.crate_root);
@@ -441,11 +531,125 @@ impl Enum {
             encode_variants
         };
 
-        quote! {
+        Ok(quote! {
             fn encode<E: #crate_root::Encoder>(&self, encoder: &mut E) -> core::result::Result<(), E::Error> {
                 #encode_impl.map(drop)
             }
+        })
+    }
+}
+
+impl Enum {
+    /// Visits the chosen alternative of a `CHOICE` with its identifier.
+    /// `ENUMERATED` types are walked through their encoding.
+    fn accept(&self, generics: &syn::Generics) -> syn::Result<Option<proc_macro2::TokenStream>> {
+        if !self.config.choice {
+            return Ok(None);
         }
+
+        let crate_root = &self.config.crate_root;
+        let name = &self.name;
+        let variant_configs = self.variant_configs(generics)?;
+        let variants = variant_configs.iter().enumerate().map(|(i, variant_config)| {
+            let v = variant_config.variant;
+            let ident = &v.ident;
+            let identifier = variant_config.identifier();
+            let variant_tag = variant_config.tag(i).to_tokens(crate_root);
+            let is_explicit = variant_config.has_explicit_tag();
+            let explicit_tag = if is_explicit {
+                quote!(Some(#variant_tag))
+            } else {
+                quote!(None)
+            };
+            let context = |tag: proc_macro2::TokenStream, constraints: proc_macro2::TokenStream| {
+                quote! {
+                    #crate_root::visit::Context {
+                        name: Some(#identifier),
+                        tag: #tag,
+                        explicit_tag: #explicit_tag,
+                        constraints: #constraints,
+                    }
+                }
+            };
+
+            match &v.fields {
+                syn::Fields::Named(_) => {
+                    let idents = v.fields.iter().map(|f| {
+                        let ident = f.ident.as_ref().unwrap();
+                        quote!(#ident)
+                    });
+                    let inner = crate::encode::inner_type(ident, &v.fields, generics, crate_root);
+                    let tag = if is_explicit {
+                        quote!(#crate_root::Tag::SEQUENCE)
+                    } else {
+                        variant_tag
+                    };
+                    let context = context(tag, quote!(<_>::default()));
+
+                    quote! {
+                        #name::#ident { #(#idents),* } => {
+                            #inner
+                            #crate_root::Encode::accept_with_context(&inner, visitor, #context)?;
+                        }
+                    }
+                }
+                syn::Fields::Unnamed(_) => {
+                    let mut ty = v.fields.iter().next().unwrap().ty.clone();
+                    ty.strip_lifetimes();
+                    let tag = if is_explicit {
+                        quote!(<#ty as #crate_root::AsnType>::TAG)
+                    } else {
+                        variant_tag
+                    };
+                    let constraints = match variant_config.constraints.const_expr(crate_root) {
+                        Some(constraints) => quote!(<#ty as #crate_root::AsnType>::CONSTRAINTS.override_constraints(#constraints)),
+                        None => quote!(<#ty as #crate_root::AsnType>::CONSTRAINTS),
+                    };
+                    let context = context(tag, constraints);
+
+                    quote! {
+                        #name::#ident(value) => {
+                            #crate_root::Encode::accept_with_context(value, visitor, #context)?;
+                        }
+                    }
+                }
+                syn::Fields::Unit => {
+                    let tag = if is_explicit {
+                        quote!(#crate_root::Tag::NULL)
+                    } else {
+                        variant_tag
+                    };
+                    let context = context(tag, quote!(<_>::default()));
+
+                    quote! {
+                        #name::#ident => {
+                            visitor.visit_primitive(&#context, #crate_root::visit::Primitive::Null)?;
+                        }
+                    }
+                }
+            }
+        });
+
+        let explicit_context = self.config.has_explicit_tag().then(|| {
+            quote! {
+                let context = #crate_root::visit::Context {
+                    tag: #crate_root::Tag::EOC,
+                    explicit_tag: context.explicit_tag.or(Some(context.tag)),
+                    ..context
+                };
+            }
+        });
+
+        Ok(Some(quote! {
+            fn accept_with_context<VI: #crate_root::visit::Visitor>(&self, visitor: &mut VI, context: #crate_root::visit::Context) -> core::result::Result<(), VI::Error> {
+                #explicit_context
+                visitor.enter_constructed(&context, #crate_root::visit::Kind::Choice)?;
+                match self {
+                    #(#variants)*
+                }
+                visitor.leave_constructed(&context, #crate_root::visit::Kind::Choice)
+            }
+        }))
     }
 }
 
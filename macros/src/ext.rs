@@ -1,3 +1,7 @@
+use quote::ToTokens;
+
+use crate::config::{Config, FieldConfig};
+
 pub trait TypeExt {
     fn strip_lifetimes(&mut self);
 }
@@ -11,35 +15,146 @@ impl TypeExt for syn::Type {
 }
 
 pub trait GenericsExt {
-    fn add_trait_bounds(&mut self, crate_root: &syn::Path, r#trait: syn::Ident);
+    /// Adds a bound on the trait named `ident` to each type parameter used
+    /// in `fields`, unless the container or field has a `bound` attribute,
+    /// in which case its predicates are used instead.
+    fn add_trait_bounds<'a>(
+        &mut self,
+        config: &Config,
+        fields: impl IntoIterator<Item = &'a syn::Field>,
+        ident: syn::Ident,
+    ) -> syn::Result<()>;
+
+    /// The parameters that are used in `ty`.
+    fn used_by(&self, ty: &syn::Type) -> syn::Generics;
 }
 
 impl GenericsExt for syn::Generics {
-    fn add_trait_bounds(&mut self, crate_root: &syn::Path, ident: syn::Ident) {
-        for param in self.type_params_mut() {
-            param.colon_token = Some(Default::default());
-            param.bounds = {
-                let mut punct = syn::punctuated::Punctuated::new();
-                punct.push(
-                    syn::TraitBound {
-                        paren_token: None,
-                        modifier: syn::TraitBoundModifier::None,
-                        lifetimes: None,
-                        path: {
-                            let mut path = crate_root.clone();
-                            path.segments.push(syn::PathSegment {
-                                ident: ident.clone(),
-                                arguments: syn::PathArguments::None,
-                            });
-
-                            path
-                        },
+    fn add_trait_bounds<'a>(
+        &mut self,
+        config: &Config,
+        fields: impl IntoIterator<Item = &'a syn::Field>,
+        ident: syn::Ident,
+    ) -> syn::Result<()> {
+        let crate_root = &config.crate_root;
+        let params: Vec<_> = self
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect();
+        let where_clause = self.make_where_clause();
+
+        if let Some(predicates) = config.bound.for_trait(&ident) {
+            where_clause.predicates.extend(predicates.iter().cloned());
+            return Ok(());
+        }
+
+        let mut bounded = Vec::new();
+        for field in fields {
+            match FieldConfig::new(field, config)?.bound.for_trait(&ident) {
+                Some(predicates) => where_clause.predicates.extend(predicates.iter().cloned()),
+                None => bounded_types(&field.ty, &params, &mut bounded),
+            }
+        }
+
+        for ty in bounded {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: #crate_root::#ident));
+        }
+
+        Ok(())
+    }
+
+    fn used_by(&self, ty: &syn::Type) -> syn::Generics {
+        let (mut used_idents, mut used_lifetimes) = (Vec::new(), Vec::new());
+        idents(ty.to_token_stream(), &mut used_idents, &mut used_lifetimes);
+
+        syn::Generics {
+            params: self
+                .params
+                .iter()
+                .filter(|param| match param {
+                    syn::GenericParam::Type(param) => used_idents.contains(&param.ident),
+                    syn::GenericParam::Const(param) => used_idents.contains(&param.ident),
+                    syn::GenericParam::Lifetime(param) => {
+                        used_lifetimes.contains(&param.lifetime.ident)
                     }
-                    .into(),
-                );
+                })
+                .cloned()
+                .collect(),
+            ..<_>::default()
+        }
+    }
+}
 
-                punct
-            };
+/// Collects the identifiers and lifetimes in `tokens`.
+fn idents(
+    tokens: proc_macro2::TokenStream,
+    idents: &mut Vec<proc_macro2::Ident>,
+    lifetimes: &mut Vec<proc_macro2::Ident>,
+) {
+    let mut is_lifetime = false;
+
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Group(group) => self::idents(group.stream(), idents, lifetimes),
+            proc_macro2::TokenTree::Ident(ident) if is_lifetime => lifetimes.push(ident),
+            proc_macro2::TokenTree::Ident(ident) => idents.push(ident),
+            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                is_lifetime = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        is_lifetime = false;
+    }
+}
+
+/// Collects the types in `ty` that need a bound, which are the type
+/// parameters in `params` and their associated types (e.g. `T::Item`).
+fn bounded_types(ty: &syn::Type, params: &[syn::Ident], bounded: &mut Vec<syn::Type>) {
+    match ty {
+        syn::Type::Path(path) => {
+            if let Some(qself) = &path.qself {
+                bounded_types(&qself.ty, params, bounded);
+            }
+
+            let is_param = path.qself.is_none()
+                && path
+                    .path
+                    .segments
+                    .first()
+                    .is_some_and(|segment| params.contains(&segment.ident));
+
+            if is_param {
+                if !bounded.contains(ty) {
+                    bounded.push(ty.clone());
+                }
+                return;
+            }
+
+            for segment in &path.path.segments {
+                if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                    for argument in &arguments.args {
+                        if let syn::GenericArgument::Type(ty) = argument {
+                            bounded_types(ty, params, bounded);
+                        }
+                    }
+                }
+            }
+        }
+        syn::Type::Array(array) => bounded_types(&array.elem, params, bounded),
+        syn::Type::Group(group) => bounded_types(&group.elem, params, bounded),
+        syn::Type::Paren(paren) => bounded_types(&paren.elem, params, bounded),
+        syn::Type::Ptr(ptr) => bounded_types(&ptr.elem, params, bounded),
+        syn::Type::Reference(reference) => bounded_types(&reference.elem, params, bounded),
+        syn::Type::Slice(slice) => bounded_types(&slice.elem, params, bounded),
+        syn::Type::Tuple(tuple) => {
+            for ty in &tuple.elems {
+                bounded_types(ty, params, bounded);
+            }
         }
+        _ => {}
     }
 }
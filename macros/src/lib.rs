@@ -27,7 +27,10 @@ fn __print_stream(stream: proc_macro2::TokenStream) -> proc_macro::TokenStream {
 #[proc_macro_derive(Decode, attributes(rasn))]
 pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    let config = Config::from_attributes(&input);
+    let config = match Config::from_attributes(&input) {
+        Ok(config) => config,
+        Err(error) => return error.into_compile_error().into(),
+    };
     let name = input.ident;
     let generics = input.generics;
 
@@ -40,8 +43,9 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             config,
         }
         .impl_decode(),
-        _ => panic!("Union types are not supported."),
+        syn::Data::Union(_) => unreachable!("unions are rejected by `Config::from_attributes`"),
     }
+    .unwrap_or_else(syn::Error::into_compile_error)
     .into()
 }
 
@@ -54,7 +58,10 @@ pub fn decode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the input tokens into a syntax tree
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    let config = Config::from_attributes(&input);
+    let config = match Config::from_attributes(&input) {
+        Ok(config) => config,
+        Err(error) => return error.into_compile_error().into(),
+    };
 
     let name = input.ident;
     let generics = input.generics;
@@ -68,8 +75,9 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             config,
         }
         .impl_encode(),
-        _ => todo!(),
+        syn::Data::Union(_) => unreachable!("unions are rejected by `Config::from_attributes`"),
     }
+    .unwrap_or_else(syn::Error::into_compile_error)
     .into()
 }
 
@@ -82,20 +90,68 @@ pub fn encode_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// ##### Shared Attributes
 /// These attributes are available on containers, variants, and fields.
 /// - *`tag([class], number)`* — override the default tag with the one
-/// specified with this attribute. E.g. `#[rasn(tag(context, 0))]`, you can also
-/// wrapp `[class], number` in `explicit` to mark it as a explicit tag
-/// (e.g.  `#[rasn(tag(explicit(0)))]`.)
+///   specified with this attribute. E.g. `#[rasn(tag(context, 0))]`, you can also
+///   wrapp `[class], number` in `explicit` to mark it as a explicit tag
+///   (e.g.  `#[rasn(tag(explicit(0)))]`.)
 ///
 /// ##### Container Attributes
 /// - `crate_root` The path to the `rasn` library to use in the macro.
 /// - `enumerated/choice` Use either `#[rasn(choice)]` or `#[rasn(enumerated)]`
 /// - `delegate` Only available for newtype wrappers (e.g. `struct Delegate(T)`);
-/// uses the inner `T` type for implementing the trait.
+///   uses the inner `T` type for implementing the trait. Can be combined
+///   with `size`, `value`, and `from`, which override the matching
+///   constraints of `T`, and with an explicit `tag`.
+/// - `automatic_tags` Tag the fields or variants with context tags numbered
+///   from zero in order of definition, including extension additions, as
+///   with `AUTOMATIC TAGS` in ASN.1. As in X.680 §25.3, automatic tagging
+///   isn't applied if any field or variant already has a `tag` attribute.
+///   The components of an extension addition group are numbered as if they
+///   weren't in a group, and numbering continues after them.
+/// - `extension_addition_group` Marks the struct as the extension addition
+///   group of a struct with `automatic_tags`, so its fields are tagged
+///   automatically with the numbers that follow the preceding fields of that
+///   struct. The group can only be used once, in one struct.
+/// - `bound = "T: MyTrait"` Use these `where` predicates instead of the
+///   inferred bounds, which require each type parameter used in a field to
+///   implement the derived trait. Use `bound(asn_type = "...", encode =
+///   "...", decode = "...")` to give the bounds for each trait separately.
+///   Also available on fields, replacing only the field's inferred bounds.
+/// - `rename_all = "camelCase"` The casing of the ASN.1 identifiers of fields
+///   and variants, which default to their Rust names. One of `camelCase`,
+///   `PascalCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, or `kebab-case`.
+///
+/// ##### Field and Variant Attributes
+/// - `identifier = "tbsCertificate"` Use this ASN.1 identifier for the field
+///   or variant, which is recorded in `Field::name`, `Choice::IDENTIFIERS` and
+///   `Enumerated::IDENTIFIERS`.
+///
+/// ##### Field Attributes
+/// - `default` The field is `DEFAULT`, using the type's `Default`
+///   implementation. The value can also be given as `default = "path"`, a
+///   function returning the value, as a named value (`default = "v1"`, the
+///   variant or associated constant `V1` of the field's type), or as a
+///   literal: an integer (`default = 5`), a boolean, a byte string, or a
+///   string with an ASN.1 value (`default = "-1"`, `"'0101'B"` for bit
+///   strings, `"'0A'H"` for octets, `"{ 1 2 840 }"` for object identifiers,
+///   and `"\"text\""` for character strings). Literals that don't fit the
+///   field's type are compile errors. Functions named by a single word
+///   without `_` are written as a path, such as `default = "self::v1"`.
+///   Fields equal to their default value are never encoded.
+///
+/// ##### Variant Attributes
+/// - `unknown` Only available in `enumerated` enums, on a variant with a
+///   single `isize` field (e.g. `Unknown(isize)`). Discriminants that don't
+///   match any other variant are decoded into this variant, and encoded
+///   again as they were. PER can't encode these values, as it encodes the
+///   index of the variant.
 #[proc_macro_derive(AsnType, attributes(rasn))]
 pub fn asn_type_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the input tokens into a syntax tree
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    let config = Config::from_attributes(&input);
+    let config = match Config::from_attributes(&input) {
+        Ok(config) => config,
+        Err(error) => return error.into_compile_error().into(),
+    };
     let name = input.ident;
     let generics = input.generics;
 
@@ -108,7 +164,8 @@ pub fn asn_type_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
             config,
         }
         .impl_asntype(),
-        _ => panic!("Union types are not supported."),
+        syn::Data::Union(_) => unreachable!("unions are rejected by `Config::from_attributes`"),
     }
+    .unwrap_or_else(syn::Error::into_compile_error)
     .into()
 }
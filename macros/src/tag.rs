@@ -1,3 +1,5 @@
+use quote::ToTokens;
+
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 pub enum Class {
     Universal = 0,
@@ -7,17 +9,19 @@ pub enum Class {
 }
 
 impl Class {
-    fn from_ident(ident: &syn::Ident) -> Self {
-        match &*ident.to_string().to_lowercase() {
+    fn from_ident(ident: &syn::Ident) -> syn::Result<Self> {
+        Ok(match &*ident.to_string().to_lowercase() {
             "universal" => Class::Universal,
             "application" => Class::Application,
             "context" => Class::Context,
             "private" => Class::Private,
-            s => panic!(
-                "Class MUST BE `universal`, `application`, `context`, or `private`. Found: {}",
-                s
-            ),
-        }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "class must be `universal`, `application`, `context`, or `private`",
+                ))
+            }
+        })
     }
 
     pub fn to_ident(self) -> syn::Ident {
@@ -53,99 +57,78 @@ pub enum Tag {
         explicit: bool,
     },
     Delegate {
-        ty: syn::Type,
+        ty: Box<syn::Type>,
     },
 }
 
 impl Tag {
-    pub fn from_meta(item: &syn::Meta) -> Option<Self> {
-        let mut tag = None;
-        let mut explicit = false;
-        match item {
-            syn::Meta::List(list) => match list.nested.iter().count() {
-                1 => match &list.nested[0] {
-                    syn::NestedMeta::Lit(lit) => tag = Some((Class::Context, lit.clone())),
-                    syn::NestedMeta::Meta(meta) => {
-                        let list = match meta {
-                                syn::Meta::List(list) => list,
-                                _ => panic!("Invalid attribute literal provided to `rasn`, expected `rasn(tag(explicit(...)))`."),
-                            };
-
-                        if !list
-                            .path
-                            .is_ident(&syn::Ident::new("explicit", proc_macro2::Span::call_site()))
-                        {
-                            panic!("Invalid attribute literal provided to `rasn`, expected `rasn(tag(explicit(...)))`.");
-                        }
-
-                        let mut iter = list.nested.iter();
-                        let first = iter.next();
-                        let second = iter.next();
-
-                        match (first, second) {
-                            (
-                                Some(syn::NestedMeta::Meta(syn::Meta::Path(path))),
-                                Some(syn::NestedMeta::Lit(lit)),
-                            ) => {
-                                let class = Class::from_ident(
-                                    path.get_ident().expect("Path must be a valid ident."),
-                                );
-                                let value = lit.clone();
-                                explicit = true;
-                                tag = Some((class, value));
-                            }
-                            (Some(syn::NestedMeta::Lit(lit)), None) => {
-                                explicit = true;
-                                tag = Some((Class::Context, lit.clone()));
-                            }
-                            _ => panic!("Expected meta items inside `explicit`"),
-                        }
-                    }
-                },
-                2 => {
-                    let mut iter = list.nested.iter().take(2).fuse();
-                    let class = iter.next().unwrap();
-                    let value = iter.next().unwrap();
-
-                    if let (
-                        syn::NestedMeta::Meta(syn::Meta::Path(path)),
-                        syn::NestedMeta::Lit(value),
-                    ) = (class, value)
-                    {
-                        tag = Some((Class::from_ident(path.get_ident().unwrap()), value.clone()));
-                    }
-                }
-                _ => panic!("The `#[rasn(tag)]`attribute takes a maximum of two arguments."),
-            },
-            _ => panic!("The `#[rasn(tag)]`attribute must be a list."),
-        }
+    pub fn from_meta(item: &syn::Meta) -> syn::Result<Self> {
+        let list = match item {
+            syn::Meta::List(list) => list,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    item,
+                    "expected `tag([class], number)` or `tag(explicit([class], number))`",
+                ))
+            }
+        };
+
+        let (class, value, explicit) = match list.nested.iter().collect::<Vec<_>>()[..] {
+            [syn::NestedMeta::Meta(syn::Meta::List(explicit))]
+                if explicit.path.is_ident("explicit") =>
+            {
+                let (class, value) = Self::class_and_value(explicit)?;
+                (class, value, true)
+            }
+            _ => {
+                let (class, value) = Self::class_and_value(list)?;
+                (class, value, false)
+            }
+        };
 
-        tag.map(|(class, value)| Self::Value {
+        Ok(Self::Value {
             class,
             value,
             explicit,
         })
     }
 
+    /// Parses the `[class], number` arguments of a tag.
+    fn class_and_value(list: &syn::MetaList) -> syn::Result<(Class, syn::Lit)> {
+        match list.nested.iter().collect::<Vec<_>>()[..] {
+            [syn::NestedMeta::Lit(value)] => Ok((Class::Context, value.clone())),
+            [syn::NestedMeta::Meta(syn::Meta::Path(class)), syn::NestedMeta::Lit(value)] => {
+                let class = class
+                    .get_ident()
+                    .ok_or_else(|| syn::Error::new_spanned(class, "expected a tag class"))?;
+
+                Ok((Class::from_ident(class)?, value.clone()))
+            }
+            _ => Err(syn::Error::new_spanned(
+                list,
+                format!(
+                    "expected `{}([class], number)`",
+                    list.path.to_token_stream()
+                ),
+            )),
+        }
+    }
+
     pub fn from_fields(fields: &syn::Fields) -> Self {
         match fields {
             syn::Fields::Unit => Self::Delegate {
-                ty: syn::TypeTuple {
-                    paren_token: <_>::default(),
-                    elems: <_>::default(),
-                }
-                .into(),
+                ty: Box::new(
+                    syn::TypeTuple {
+                        paren_token: <_>::default(),
+                        elems: <_>::default(),
+                    }
+                    .into(),
+                ),
             },
             syn::Fields::Named(_) => Self::SEQUENCE(),
-            syn::Fields::Unnamed(_) => {
-                if fields.iter().count() != 1 {
-                    panic!("Tuple-style enum variants must contain only a single field, switch to struct-style variants for multiple fields.");
-                } else {
-                    let ty = fields.iter().next().cloned().unwrap().ty;
-
-                    Self::Delegate { ty }
-                }
-            }
+            syn::Fields::Unnamed(_) => Self::Delegate {
+                ty: Box::new(fields.iter().next().cloned().unwrap().ty),
+            },
         }
     }
 
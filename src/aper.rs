@@ -17,6 +17,37 @@ pub fn encode<T: crate::Encode>(value: &T) -> Result<alloc::vec::Vec<u8>, crate:
     crate::per::encode(enc::EncoderOptions::aligned(), value)
 }
 
+/// Attempts to encode `value` to APER-CANONICAL, appending the encoding to `buffer`.
+pub fn encode_to<T: crate::Encode>(
+    value: &T,
+    buffer: &mut impl bytes::BufMut,
+) -> Result<(), crate::per::enc::Error> {
+    crate::per::encode_to(enc::EncoderOptions::aligned(), value, buffer)
+}
+
+/// Attempts to encode `value` to APER-CANONICAL at the start of `buffer`,
+/// returning the number of bytes written.
+pub fn encode_into_slice<T: crate::Encode>(
+    value: &T,
+    buffer: &mut [u8],
+) -> Result<usize, crate::per::enc::Error> {
+    crate::per::encode_into_slice(enc::EncoderOptions::aligned(), value, buffer)
+}
+
+/// Attempts to encode `value` to APER-CANONICAL, writing the encoding to `writer`.
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: crate::Encode>(
+    value: &T,
+    writer: impl std::io::Write,
+) -> Result<(), crate::per::enc::Error> {
+    crate::per::encode_to_writer(enc::EncoderOptions::aligned(), value, writer)
+}
+
+/// Returns the length of `value`'s APER-CANONICAL encoding.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::per::enc::Error> {
+    crate::per::encoded_len(enc::EncoderOptions::aligned(), value)
+}
+
 /// Attempts to decode `T` from `input` using APER-BASIC.
 pub fn decode_with_constraints<T: crate::Decode>(
     constraints: Constraints,
@@ -431,4 +462,30 @@ mod tests {
             &[0x01]
         );
     }
+
+    #[test]
+    fn extension_additions() {
+        #[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+        #[rasn(automatic_tags, crate_root = "crate")]
+        #[non_exhaustive]
+        struct Extensible {
+            flag: bool,
+            data: OctetString,
+            #[rasn(extension_addition)]
+            more: Option<OctetString>,
+        }
+
+        // The open type of the extension addition is aligned to the start of
+        // the whole encoding, not to the start of the extension header.
+        round_trip!(
+            aper,
+            Extensible,
+            Extensible {
+                flag: true,
+                data: OctetString::from_static(&[9, 9]),
+                more: Some(OctetString::from_static(&[7])),
+            },
+            &[0xc0, 0x02, 0x09, 0x09, 0x01, 0x02, 0x01, 0x07]
+        );
+    }
 }
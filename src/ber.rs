@@ -2,6 +2,7 @@
 
 pub mod de;
 pub mod enc;
+pub mod frame;
 mod identifier;
 mod rules;
 
@@ -22,6 +23,96 @@ pub fn encode<T: crate::Encode>(value: &T) -> Result<alloc::vec::Vec<u8>, enc::E
     Ok(enc.output())
 }
 
+/// Attempts to encode `value` to BER, appending the encoding to `buffer`.
+pub fn encode_to<T: crate::Encode>(
+    value: &T,
+    buffer: &mut impl bytes::BufMut,
+) -> Result<(), enc::Error> {
+    self::encode_to_with_options(enc::EncoderOptions::ber(), value, buffer)
+}
+
+/// Attempts to encode `value` to BER at the start of `buffer`, returning
+/// the number of bytes written.
+pub fn encode_into_slice<T: crate::Encode>(
+    value: &T,
+    buffer: &mut [u8],
+) -> Result<usize, enc::Error> {
+    self::encode_into_slice_with_options(enc::EncoderOptions::ber(), value, buffer)
+}
+
+/// Attempts to encode `value` to BER, writing the encoding to `writer`.
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: crate::Encode>(
+    value: &T,
+    writer: impl std::io::Write,
+) -> Result<(), enc::Error> {
+    self::encode_to_writer_with_options(enc::EncoderOptions::ber(), value, writer)
+}
+
+/// Returns the length of `value`'s BER encoding, without encoding it.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, enc::Error> {
+    let mut enc = enc::LenEncoder::new(enc::EncoderOptions::ber());
+
+    value.encode(&mut enc)?;
+
+    Ok(enc.len())
+}
+
+/// Encodes `value` straight into `buffer`, checking that it fits first.
+pub(crate) fn encode_to_with_options<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+    buffer: &mut impl bytes::BufMut,
+) -> Result<(), enc::Error> {
+    let mut len_encoder = enc::LenEncoder::new(options);
+    value.encode(&mut len_encoder)?;
+
+    let (needed, available) = (len_encoder.len(), buffer.remaining_mut());
+    if available < needed {
+        return Err(enc::Error::BufferTooSmall { needed, available });
+    }
+
+    let mut enc = enc::Encoder::with_output(
+        options,
+        enc::Sink::new(|bytes: &[u8]| buffer.put_slice(bytes)),
+    );
+    value.encode(&mut enc)
+}
+
+/// Encodes `value` straight into `buffer`, returning the number of bytes
+/// written.
+pub(crate) fn encode_into_slice_with_options<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+    mut buffer: &mut [u8],
+) -> Result<usize, enc::Error> {
+    let available = buffer.len();
+    encode_to_with_options(options, value, &mut buffer)?;
+    Ok(available - buffer.len())
+}
+
+/// Encodes `value` straight into `writer`.
+#[cfg(feature = "std")]
+pub(crate) fn encode_to_writer_with_options<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+    mut writer: impl std::io::Write,
+) -> Result<(), enc::Error> {
+    let mut result = Ok(());
+    let mut enc = enc::Encoder::with_output(
+        options,
+        enc::Sink::new(|bytes: &[u8]| {
+            if result.is_ok() {
+                result = writer.write_all(bytes);
+            }
+        }),
+    );
+    value.encode(&mut enc)?;
+    drop(enc);
+
+    result.map_err(|source| enc::Error::Io { source })
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::borrow::ToOwned;
@@ -57,6 +148,17 @@ mod tests {
         assert_eq!(trailing_expected, encode(&trailing_test).unwrap());
     }
 
+    #[test]
+    fn universal_string() {
+        let name = UniversalString::from("Jo");
+        let encoded = &[0x1C, 0x08, 0, 0, 0, 0x4A, 0, 0, 0, 0x6F];
+
+        assert_eq!(encoded, &*encode(&name).unwrap());
+        assert_eq!(name, decode::<UniversalString>(encoded).unwrap());
+        assert!(decode::<UniversalString>(&[0x1C, 0x03, 0, 0, 0x4A]).is_err());
+        assert!(decode::<UniversalString>(&[0x1C, 0x04, 0, 0x11, 0, 0]).is_err());
+    }
+
     #[test]
     fn implicit_prefix() {
         type MyInteger = Implicit<C0, u64>;
@@ -66,6 +168,50 @@ mod tests {
         assert_eq!(new_int, decode(&encode(&new_int).unwrap()).unwrap());
     }
 
+    #[test]
+    fn encode_to_buffers() {
+        let value = OctetString::from_static(&[1, 2, 3]);
+        let expected = encode(&value).unwrap();
+
+        let mut buffer = bytes::BytesMut::from(&[0xFF][..]);
+        encode_to(&value, &mut buffer).unwrap();
+        assert_eq!(buffer[0], 0xFF);
+        assert_eq!(&buffer[1..], expected);
+
+        let mut slice = [0; 8];
+        assert_eq!(
+            expected.len(),
+            encode_into_slice(&value, &mut slice).unwrap()
+        );
+        assert_eq!(&slice[..expected.len()], expected);
+
+        assert!(matches!(
+            encode_into_slice(&value, &mut [0; 4]),
+            Err(enc::Error::BufferTooSmall {
+                needed: 5,
+                available: 4
+            })
+        ));
+
+        #[cfg(feature = "std")]
+        {
+            let mut writer = Vec::new();
+            encode_to_writer(&value, &mut writer).unwrap();
+            assert_eq!(writer, expected);
+        }
+
+        // Nested values are buffered before the outer value is written.
+        let values = alloc::vec![Integer::from(1), Integer::from(256)];
+
+        let mut der = bytes::BytesMut::new();
+        crate::der::encode_to(&values, &mut der).unwrap();
+        assert_eq!(&der[..], crate::der::encode(&values).unwrap());
+
+        let mut cer = bytes::BytesMut::new();
+        crate::cer::encode_to(&values, &mut cer).unwrap();
+        assert_eq!(&cer[..], crate::cer::encode(&values).unwrap());
+    }
+
     #[test]
     fn explicit_prefix() {
         type MyInteger = Explicit<C0, u64>;
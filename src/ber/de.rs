@@ -1,7 +1,7 @@
 //! # Decoding BER
 
 mod config;
-mod error;
+pub(super) mod error;
 pub(super) mod parser;
 
 use alloc::{borrow::ToOwned, vec::Vec};
@@ -13,8 +13,9 @@ use crate::{
     de::Error as _,
     types::{
         self,
+        integer::PrimitiveBytes,
         oid::{MAX_OID_FIRST_OCTET, MAX_OID_SECOND_OCTET},
-        Constraints, Enumerated, Tag,
+        Constraints, Enumerated, Tag, TagTree,
     },
     Decode,
 };
@@ -30,6 +31,9 @@ pub struct Decoder<'input> {
     input: &'input [u8],
     config: DecoderOptions,
     initial_len: usize,
+    /// Whether the next sequence is an extension addition group, whose
+    /// components are decoded in place of the group.
+    is_extension_addition_group: bool,
 }
 
 impl<'input> Decoder<'input> {
@@ -39,6 +43,7 @@ impl<'input> Decoder<'input> {
             input,
             config,
             initial_len: input.len(),
+            is_extension_addition_group: false,
         }
     }
 
@@ -70,6 +75,13 @@ impl<'input> Decoder<'input> {
         }
     }
 
+    /// Parses a primitive `INTEGER` without going through [`types::Integer`],
+    /// for values which fit in an `i128`.
+    fn decode_primitive_integer(&mut self, tag: Tag) -> Result<i128> {
+        PrimitiveBytes::parse_signed(self.parse_primitive_value(tag)?.1)
+            .context(error::IntegerOverflowSnafu { max_width: 128u32 })
+    }
+
     /// Parses a constructed ASN.1 value, checking the `tag`, and optionally
     /// checking if the identifier is marked as encoded. This should be true
     /// in all cases except explicit prefixes.
@@ -115,6 +127,10 @@ impl<'input> Decoder<'input> {
 impl<'input> crate::Decoder for Decoder<'input> {
     type Error = Error;
 
+    fn codec(&self) -> Option<crate::Codec> {
+        Some(self.config.encoding_rules.codec())
+    }
+
     fn decode_any(&mut self) -> Result<types::Any> {
         let (mut input, (identifier, contents)) =
             self::parser::parse_value(&self.config, self.input, None)?;
@@ -163,6 +179,20 @@ impl<'input> crate::Decoder for Decoder<'input> {
         ))
     }
 
+    fn decode_integer_i64(&mut self, tag: Tag, _: Constraints) -> Result<i64> {
+        self.decode_primitive_integer(tag)?
+            .try_into()
+            .ok()
+            .context(error::IntegerOverflowSnafu { max_width: 64u32 })
+    }
+
+    fn decode_integer_u64(&mut self, tag: Tag, _: Constraints) -> Result<u64> {
+        self.decode_primitive_integer(tag)?
+            .try_into()
+            .ok()
+            .context(error::IntegerOverflowSnafu { max_width: 64u32 })
+    }
+
     fn decode_octet_string(&mut self, tag: Tag, _: Constraints) -> Result<Vec<u8>> {
         let (identifier, contents) = self.parse_value(tag)?;
 
@@ -341,8 +371,22 @@ impl<'input> crate::Decoder for Decoder<'input> {
             .map_err(Error::custom)
     }
 
-    fn decode_bmp_string(&mut self, _: Tag, _constraints: Constraints) -> Result<types::BmpString> {
-        todo!()
+    fn decode_bmp_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<types::BmpString> {
+        types::BmpString::try_from(self.decode_octet_string(tag, constraints)?)
+            .map_err(Error::custom)
+    }
+
+    fn decode_universal_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<types::UniversalString> {
+        types::UniversalString::try_from(self.decode_octet_string(tag, constraints)?)
+            .map_err(Error::custom)
     }
 
     fn decode_utf8_string(
@@ -472,6 +516,10 @@ impl<'input> crate::Decoder for Decoder<'input> {
         tag: Tag,
         decode_fn: F,
     ) -> Result<D> {
+        if core::mem::take(&mut self.is_extension_addition_group) {
+            return (decode_fn)(self);
+        }
+
         self.parse_constructed_contents(tag, true, decode_fn)
     }
 
@@ -544,10 +592,40 @@ impl<'input> crate::Decoder for Decoder<'input> {
         <Option<D>>::decode(self)
     }
 
+    fn decode_extension_addition_with_tag<D>(&mut self, tag: Tag) -> Result<Option<D>, Self::Error>
+    where
+        D: Decode,
+    {
+        self.decode_optional_with_tag(tag)
+    }
+
+    /// Decode an extension addition group, whose components are encoded as
+    /// if they weren't in a group. The group is absent if none of them are
+    /// present.
     fn decode_extension_addition_group<D: Decode + crate::types::Constructed>(
         &mut self,
     ) -> Result<Option<D>, Self::Error> {
-        <Option<D>>::decode(self)
+        // The group is absent unless the next value is one of its components.
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        let (_, identifier) =
+            parser::parse_identifier_octet(self.input).map_err(error::map_nom_err)?;
+        let is_present = D::FIELDS.iter().any(|field| {
+            field.tag.is_choice() && matches!(field.tag_tree, TagTree::Leaf(_))
+                || TagTree::tag_contains(&identifier.tag, &[field.tag_tree])
+        });
+
+        if !is_present {
+            return Ok(None);
+        }
+
+        self.is_extension_addition_group = true;
+        let value = D::decode(self);
+        self.is_extension_addition_group = false;
+
+        value.map(Some)
     }
 }
 
@@ -635,6 +713,31 @@ mod tests {
         assert_eq!(bigint, decode::<num_bigint::BigInt>(&data).unwrap());
     }
 
+    #[test]
+    fn primitive_integer() {
+        let i64_min = [0x02, 0x08, 0x80, 0, 0, 0, 0, 0, 0, 0];
+        let u64_max = [
+            0x02, 0x09, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ];
+        assert_eq!(&i64_min[..], crate::ber::encode(&i64::MIN).unwrap());
+        assert_eq!(&u64_max[..], crate::ber::encode(&u64::MAX).unwrap());
+        assert_eq!(i64::MIN, decode::<i64>(&i64_min).unwrap());
+        assert_eq!(u64::MAX, decode::<u64>(&u64_max).unwrap());
+
+        assert!(matches!(
+            decode::<i64>(&u64_max),
+            Err(Error::IntegerOverflow { max_width: 64 })
+        ));
+        assert!(matches!(
+            decode::<u64>(&[0x02, 0x01, 0xff]),
+            Err(Error::IntegerOverflow { max_width: 64 })
+        ));
+        assert!(matches!(
+            decode::<u64>(&[0x02, 0x11, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(Error::IntegerOverflow { max_width: 128 })
+        ));
+    }
+
     #[test]
     fn octet_string() {
         let octet_string = types::OctetString::from(alloc::vec![1, 2, 3, 4, 5, 6]);
@@ -2,13 +2,16 @@
 
 mod config;
 mod error;
+mod len;
+mod output;
 
-use alloc::{collections::VecDeque, string::ToString, vec::Vec};
+use alloc::{string::ToString, vec::Vec};
 
 use super::Identifier;
 use crate::{
     types::{
         self,
+        integer::PrimitiveBytes,
         oid::{MAX_OID_FIRST_OCTET, MAX_OID_SECOND_OCTET},
         Constraints, Enumerated, Tag,
     },
@@ -17,22 +20,43 @@ use crate::{
 
 pub use config::EncoderOptions;
 pub use error::Error;
+pub use len::LenEncoder;
+pub(crate) use output::Sink;
+
+use output::Output;
 
 const START_OF_CONTENTS: u8 = 0x80;
 const END_OF_CONTENTS: &[u8] = &[0, 0];
+/// The space reserved for the length of a constructed value while its
+/// contents are being encoded, enough for the long form of any `usize`.
+const LENGTH_PLACEHOLDER: usize = 1 + core::mem::size_of::<usize>();
+/// The most bytes a `u32` uses in base 128.
+const MAX_BASE128_LEN: usize = 5;
+const UTC_TIME_FORMAT: &str = "%y%m%d%H%M%SZ";
+const GENERALIZED_TIME_FORMAT: &str = "%Y%m%d%H%M%SZ";
 
 /// A BER and variants encoder. Capable of encoding to BER, CER, and DER.
-pub struct Encoder {
-    output: Vec<u8>,
+pub struct Encoder<O = Vec<u8>> {
+    output: O,
     config: EncoderOptions,
     is_set_encoding: bool,
     set_buffer: alloc::collections::BTreeMap<Tag, Vec<u8>>,
+    /// The definite lengths of constructed values encoded in place, written
+    /// over their placeholders once the encoding is finished.
+    lengths: Vec<LengthFixup>,
+    /// How many bytes the output will shrink by once the lengths in
+    /// `lengths` replace their placeholders.
+    shrink: usize,
+    /// Whether the next sequence is an extension addition group, whose
+    /// components are encoded in place of the group.
+    is_extension_addition_group: bool,
 }
 
-/// A convenience type around results needing to return one or many bytes.
-enum ByteOrBytes {
-    Single(u8),
-    Many(Vec<u8>),
+/// The position of a length placeholder in the output, and the length
+/// that replaces it.
+struct LengthFixup {
+    position: usize,
+    len: usize,
 }
 
 impl Encoder {
@@ -43,6 +67,9 @@ impl Encoder {
             is_set_encoding: false,
             output: <_>::default(),
             set_buffer: <_>::default(),
+            lengths: <_>::default(),
+            shrink: 0,
+            is_extension_addition_group: false,
         }
     }
 
@@ -54,6 +81,9 @@ impl Encoder {
             is_set_encoding: true,
             output: <_>::default(),
             set_buffer: <_>::default(),
+            lengths: <_>::default(),
+            shrink: 0,
+            is_extension_addition_group: false,
         }
     }
 
@@ -68,11 +98,43 @@ impl Encoder {
             config,
             is_set_encoding: false,
             set_buffer: <_>::default(),
+            lengths: <_>::default(),
+            shrink: 0,
+            is_extension_addition_group: false,
         }
     }
 
     /// Consumes the encoder and returns the output of the encoding.
     pub fn output(self) -> Vec<u8> {
+        self.into_contents()
+    }
+}
+
+impl<O: Output> Encoder<O> {
+    /// Creates a new instance from the given `config`, writing the encoding
+    /// to `output`.
+    pub(crate) fn with_output(config: EncoderOptions, output: O) -> Self {
+        Self {
+            output,
+            config,
+            is_set_encoding: false,
+            set_buffer: <_>::default(),
+            lengths: <_>::default(),
+            shrink: 0,
+            is_extension_addition_group: false,
+        }
+    }
+
+    /// Creates an encoder for a value nested in the value being encoded.
+    fn nested(&self, is_set_encoding: bool) -> Self {
+        Self {
+            is_set_encoding,
+            ..Self::with_output(self.config, O::nested())
+        }
+    }
+
+    /// Consumes the encoder and returns the buffered encoding.
+    fn into_contents(mut self) -> Vec<u8> {
         if self.is_set_encoding {
             self.set_buffer
                 .into_values()
@@ -81,109 +143,60 @@ impl Encoder {
                     acc
                 })
         } else {
-            self.output
+            self.write_lengths();
+            core::mem::take(self.output.buffer())
         }
     }
 
-    fn append_byte_or_bytes(&mut self, bytes: ByteOrBytes) {
-        match bytes {
-            ByteOrBytes::Single(b) => self.output.push(b),
-            ByteOrBytes::Many(mut bs) => self.output.append(&mut bs),
+    /// Replaces the length placeholders of constructed values encoded in
+    /// place with their actual lengths, shifting everything after each one
+    /// back in a single pass over the output.
+    fn write_lengths(&mut self) {
+        if self.lengths.is_empty() {
+            return;
         }
-    }
 
-    pub(super) fn encode_as_base128(&self, number: u32, buffer: &mut Vec<u8>) {
-        const WIDTH: u8 = 7;
-        const SEVEN_BITS: u8 = 0x7F;
-        const EIGHTH_BIT: u8 = 0x80;
+        let mut lengths = core::mem::take(&mut self.lengths);
+        lengths.sort_unstable_by_key(|fixup| fixup.position);
+        let output = self.output.buffer();
 
-        if number < EIGHTH_BIT as u32 {
-            buffer.push(number as u8);
-        } else {
-            let mut n: u8;
-            let mut bits_left = 35;
-            let mut cont = false;
-            while bits_left > 0 {
-                bits_left -= WIDTH;
-                n = ((number >> bits_left) as u8) & SEVEN_BITS;
-                if n > 0 || cont {
-                    buffer.push(if bits_left > 0 { EIGHTH_BIT } else { 0 } | (n & SEVEN_BITS));
-                    cont = true;
-                }
-            }
+        let mut read = 0;
+        let mut write = 0;
+        for LengthFixup { position, len } in lengths {
+            output.copy_within(read..position, write);
+            write += position - read;
+
+            let (length, length_len) = definite_length(len);
+            output[write..write + length_len].copy_from_slice(&length[..length_len]);
+            write += length_len;
+            read = position + LENGTH_PLACEHOLDER;
         }
-    }
 
-    /// Encodes the identifier of a type in BER/CER/DER. An identifier consists
-    /// of a "class", encoding bit, and tag number. If our tag number is
-    /// greater than 30 we to encode the number as stream of a 7 bit integers
-    /// in big endian delimited by the leading bit of each byte.
-    ///
-    /// ```text
-    /// ---------------------------------
-    /// | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 |
-    /// ---------------------------------
-    /// | class | E |        Tag        |
-    /// ---------------------------------
-    /// ```
-    fn encode_identifier(
-        &mut self,
-        Identifier {
-            tag,
-            is_constructed,
-        }: Identifier,
-    ) -> ByteOrBytes {
-        const FIVE_BITS: u32 = (1 << 5) - 1;
-        let mut tag_byte = tag.class as u8;
-        let tag_number = tag.value;
-
-        // Constructed is a single bit.
-        tag_byte <<= 1;
-        tag_byte |= match tag {
-            Tag::EXTERNAL | Tag::SEQUENCE | Tag::SET => 1,
-            _ if is_constructed => 1,
-            _ => 0,
-        };
+        let remaining = output.len() - read;
+        output.copy_within(read.., write);
+        output.truncate(write + remaining);
+        self.shrink = 0;
+    }
 
-        tag_byte <<= 5;
+    pub(super) fn encode_as_base128(&self, number: u32, buffer: &mut Vec<u8>) {
+        let (bytes, len) = base128(number);
+        buffer.extend_from_slice(&bytes[..len]);
+    }
 
-        if tag_number >= FIVE_BITS {
-            let mut buffer = alloc::vec![tag_byte | FIVE_BITS as u8];
-            self.encode_as_base128(tag_number, &mut buffer);
-            ByteOrBytes::Many(buffer)
-        } else {
-            tag_byte |= tag_number as u8;
-            ByteOrBytes::Single(tag_byte)
-        }
+    fn encode_identifier(&mut self, identifier: Identifier) {
+        let (bytes, len) = identifier_octets(identifier);
+        self.output.write(&bytes[..len]);
     }
 
     fn encode_length(&mut self, identifier: Identifier, value: &[u8]) {
         if identifier.is_primitive() || !self.config.encoding_rules.is_cer() {
-            let len_bytes = self.encode_definite_length(value.len());
-            self.append_byte_or_bytes(len_bytes);
-            self.output.extend_from_slice(value);
+            let (length, length_len) = definite_length(value.len());
+            self.output.write(&length[..length_len]);
+            self.output.write(value);
         } else {
-            self.output.push(START_OF_CONTENTS);
-            self.output.extend_from_slice(value);
-            self.output.extend_from_slice(END_OF_CONTENTS);
-        }
-    }
-
-    fn encode_definite_length(&mut self, len: usize) -> ByteOrBytes {
-        if len <= 127 {
-            ByteOrBytes::Single(len as u8)
-        } else {
-            let mut length = len;
-            let mut length_buffer = VecDeque::new();
-
-            while length != 0 {
-                length_buffer.push_front((length & 0xff) as u8);
-                length >>= 8;
-            }
-
-            length_buffer.push_front(length_buffer.len() as u8 | 0x80);
-
-            ByteOrBytes::Many(length_buffer.into())
+            self.output.write(&[START_OF_CONTENTS]);
+            self.output.write(value);
+            self.output.write(END_OF_CONTENTS);
         }
     }
 
@@ -198,16 +211,14 @@ impl Encoder {
         let max_string_length = self.config.encoding_rules.max_string_length();
 
         if value.len() > max_string_length {
-            let ident_bytes = self.encode_identifier(Identifier::from_tag(tag, true));
-            self.append_byte_or_bytes(ident_bytes);
-
-            self.output.push(START_OF_CONTENTS);
+            self.encode_identifier(Identifier::from_tag(tag, true));
+            self.output.write(&[START_OF_CONTENTS]);
 
             for chunk in value.chunks(max_string_length) {
                 self.encode_primitive(nested_tag, chunk);
             }
 
-            self.output.extend_from_slice(END_OF_CONTENTS);
+            self.output.write(END_OF_CONTENTS);
             self.encode_to_set(tag);
         } else {
             self.encode_primitive(tag, value);
@@ -224,10 +235,53 @@ impl Encoder {
         self.encode_value(Identifier::from_tag(tag, true), value);
     }
 
+    /// Encodes a constructed value whose contents are written by `encode_fn`
+    /// directly into the output, rather than encoding them separately and
+    /// copying them in. The definite length is reserved up front and written
+    /// by [`Self::output`], so nested values are only copied once.
+    fn encode_constructed_with(
+        &mut self,
+        tag: Tag,
+        encode_fn: impl FnOnce(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let is_cer = self.config.encoding_rules.is_cer();
+        if self.is_set_encoding || (self.output.is_streaming() && !is_cer) {
+            // Each field of a SET is kept in its own buffer to be sorted, and
+            // streamed output can't have definite lengths written afterwards,
+            // so the contents are encoded separately.
+            let mut encoder = self.nested(false);
+            (encode_fn)(&mut encoder)?;
+            self.encode_constructed(tag, &encoder.into_contents());
+            return Ok(());
+        }
+
+        self.encode_identifier(Identifier::from_tag(tag, true));
+
+        if is_cer {
+            self.output.write(&[START_OF_CONTENTS]);
+            (encode_fn)(self)?;
+            self.output.write(END_OF_CONTENTS);
+        } else {
+            let position = self.output.buffer().len();
+            let shrink = self.shrink;
+            self.output
+                .buffer()
+                .resize(position + LENGTH_PLACEHOLDER, 0);
+            (encode_fn)(self)?;
+
+            // Placeholders nested inside this value will shrink as well.
+            let len =
+                self.output.buffer().len() - position - LENGTH_PLACEHOLDER - (self.shrink - shrink);
+            self.shrink += LENGTH_PLACEHOLDER - definite_length(len).1;
+            self.lengths.push(LengthFixup { position, len });
+        }
+
+        Ok(())
+    }
+
     /// Encodes a given ASN.1 BER value with the `identifier`.
     fn encode_value(&mut self, identifier: Identifier, value: &[u8]) {
-        let ident_bytes = self.encode_identifier(identifier);
-        self.append_byte_or_bytes(ident_bytes);
+        self.encode_identifier(identifier);
         self.encode_length(identifier, value);
         self.encode_to_set(identifier.tag);
     }
@@ -237,15 +291,117 @@ impl Encoder {
     fn encode_to_set(&mut self, tag: Tag) {
         if self.is_set_encoding {
             self.set_buffer
-                .insert(tag, core::mem::take(&mut self.output));
+                .insert(tag, core::mem::take(self.output.buffer()));
         }
     }
 }
 
-impl crate::Encoder for Encoder {
+/// Encodes the identifier of a type in BER/CER/DER, returning the buffer and
+/// how many of its bytes are used. An identifier consists of a "class",
+/// encoding bit, and tag number. If our tag number is greater than 30 we to
+/// encode the number as stream of a 7 bit integers in big endian delimited by
+/// the leading bit of each byte.
+///
+/// ```text
+/// ---------------------------------
+/// | 0 | 0 | 0 | 0 | 0 | 0 | 0 | 0 |
+/// ---------------------------------
+/// | class | E |        Tag        |
+/// ---------------------------------
+/// ```
+fn identifier_octets(
+    Identifier {
+        tag,
+        is_constructed,
+    }: Identifier,
+) -> ([u8; 1 + MAX_BASE128_LEN], usize) {
+    const FIVE_BITS: u32 = (1 << 5) - 1;
+    let mut buffer = [0; 1 + MAX_BASE128_LEN];
+    let mut tag_byte = tag.class as u8;
+    let tag_number = tag.value;
+
+    // Constructed is a single bit.
+    tag_byte <<= 1;
+    tag_byte |= match tag {
+        Tag::EXTERNAL | Tag::SEQUENCE | Tag::SET => 1,
+        _ if is_constructed => 1,
+        _ => 0,
+    };
+
+    tag_byte <<= 5;
+
+    if tag_number >= FIVE_BITS {
+        let (number, number_len) = base128(tag_number);
+        buffer[0] = tag_byte | FIVE_BITS as u8;
+        buffer[1..=number_len].copy_from_slice(&number[..number_len]);
+        (buffer, 1 + number_len)
+    } else {
+        buffer[0] = tag_byte | tag_number as u8;
+        (buffer, 1)
+    }
+}
+
+/// Encodes `number` in base 128, returning the buffer and how many of its
+/// bytes are used.
+fn base128(number: u32) -> ([u8; MAX_BASE128_LEN], usize) {
+    const WIDTH: u8 = 7;
+    const SEVEN_BITS: u8 = 0x7F;
+    const EIGHTH_BIT: u8 = 0x80;
+
+    let mut buffer = [0; MAX_BASE128_LEN];
+    if number < EIGHTH_BIT as u32 {
+        buffer[0] = number as u8;
+        return (buffer, 1);
+    }
+
+    let mut len = 0;
+    let mut bits_left = 35;
+    while bits_left > 0 {
+        bits_left -= WIDTH;
+        let n = ((number >> bits_left) as u8) & SEVEN_BITS;
+        if n > 0 || len > 0 {
+            buffer[len] = if bits_left > 0 { EIGHTH_BIT } else { 0 } | n;
+            len += 1;
+        }
+    }
+
+    (buffer, len)
+}
+
+/// The bytes of `value` without its trailing zero bytes, which aren't
+/// encoded.
+fn significant_bytes(value: &types::BitString) -> &[u8] {
+    let bytes = value.as_raw_slice();
+    let trailing_zeros = bytes.iter().rev().take_while(|byte| **byte == 0).count();
+    &bytes[..bytes.len() - trailing_zeros]
+}
+
+/// Encodes `len` as a definite length, returning the buffer and how many of
+/// its bytes are used.
+fn definite_length(len: usize) -> ([u8; LENGTH_PLACEHOLDER], usize) {
+    let mut buffer = [0; LENGTH_PLACEHOLDER];
+
+    if len <= 127 {
+        buffer[0] = len as u8;
+        (buffer, 1)
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = (len.leading_zeros() / 8) as usize;
+        let len_len = bytes.len() - skip;
+        buffer[0] = 0x80 | len_len as u8;
+        buffer[1..=len_len].copy_from_slice(&bytes[skip..]);
+        (buffer, 1 + len_len)
+    }
+}
+
+impl<O: Output> crate::Encoder for Encoder<O> {
     type Ok = ();
     type Error = error::Error;
 
+    fn codec(&self) -> Option<crate::Codec> {
+        Some(self.config.encoding_rules.codec())
+    }
+
     fn encode_any(&mut self, _: Tag, value: &types::Any) -> Result<Self::Ok, Self::Error> {
         if self.is_set_encoding {
             return Err(crate::enc::Error::custom(
@@ -253,7 +409,7 @@ impl crate::Encoder for Encoder {
             ));
         }
 
-        self.output.extend_from_slice(&value.contents);
+        self.output.write(&value.contents);
 
         Ok(())
     }
@@ -268,15 +424,12 @@ impl crate::Encoder for Encoder {
             self.encode_primitive(tag, &[]);
             Ok(())
         } else {
-            let bit_length = value.len();
-            let bytes = value.clone().into_vec();
-            let mut deque = VecDeque::from(bytes);
-            while deque.back().map_or(false, |i| *i == 0) {
-                deque.pop_back();
-            }
-
-            deque.push_front((deque.len() * 8).saturating_sub(bit_length) as u8);
-            self.encode_string(tag, Tag::BIT_STRING, &Vec::from(deque))
+            // The contents start with the number of unused bits.
+            let bytes = significant_bytes(value);
+            let mut contents = Vec::with_capacity(1 + bytes.len());
+            contents.push((bytes.len() * 8).saturating_sub(value.len()) as u8);
+            contents.extend_from_slice(bytes);
+            self.encode_string(tag, Tag::BIT_STRING, &contents)
         }
     }
 
@@ -306,9 +459,29 @@ impl crate::Encoder for Encoder {
         &mut self,
         tag: Tag,
         _constraints: Constraints,
-        value: &num_bigint::BigInt,
+        value: &types::Integer,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_primitive(tag, &value.signed_bytes_be());
+        Ok(())
+    }
+
+    fn encode_integer_i64(
+        &mut self,
+        tag: Tag,
+        _constraints: Constraints,
+        value: i64,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_primitive(tag, &PrimitiveBytes::signed(value.into()));
+        Ok(())
+    }
+
+    fn encode_integer_u64(
+        &mut self,
+        tag: Tag,
+        _constraints: Constraints,
+        value: u64,
     ) -> Result<Self::Ok, Self::Error> {
-        self.encode_primitive(tag, &value.to_signed_bytes_be());
+        self.encode_primitive(tag, &PrimitiveBytes::signed(value.into()));
         Ok(())
     }
 
@@ -413,6 +586,15 @@ impl crate::Encoder for Encoder {
         self.encode_octet_string_(tag, &value.to_bytes())
     }
 
+    fn encode_universal_string(
+        &mut self,
+        tag: Tag,
+        _constraints: Constraints,
+        value: &types::UniversalString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_octet_string_(tag, &value.to_bytes())
+    }
+
     fn encode_utf8_string(
         &mut self,
         tag: Tag,
@@ -431,7 +613,7 @@ impl crate::Encoder for Encoder {
             tag,
             value
                 .naive_utc()
-                .format("%y%m%d%H%M%SZ")
+                .format(UTC_TIME_FORMAT)
                 .to_string()
                 .as_bytes(),
         );
@@ -448,7 +630,7 @@ impl crate::Encoder for Encoder {
             tag,
             value
                 .naive_utc()
-                .format("%Y%m%d%H%M%SZ")
+                .format(GENERALIZED_TIME_FORMAT)
                 .to_string()
                 .as_bytes(),
         );
@@ -491,15 +673,13 @@ impl crate::Encoder for Encoder {
         values: &[E],
         _constraints: Constraints,
     ) -> Result<Self::Ok, Self::Error> {
-        let mut sequence_encoder = Self::new(self.config);
-
-        for value in values {
-            value.encode(&mut sequence_encoder)?;
-        }
-
-        self.encode_constructed(tag, &sequence_encoder.output);
+        self.encode_constructed_with(tag, |encoder| {
+            for value in values {
+                value.encode(encoder)?;
+            }
 
-        Ok(())
+            Ok(())
+        })
     }
 
     fn encode_set_of<E: Encode>(
@@ -508,15 +688,13 @@ impl crate::Encoder for Encoder {
         values: &types::SetOf<E>,
         _constraints: Constraints,
     ) -> Result<Self::Ok, Self::Error> {
-        let mut sequence_encoder = Self::new(self.config);
-
-        for value in values {
-            value.encode(&mut sequence_encoder)?;
-        }
-
-        self.encode_constructed(tag, &sequence_encoder.output);
+        self.encode_constructed_with(tag, |encoder| {
+            for value in values {
+                value.encode(encoder)?;
+            }
 
-        Ok(())
+            Ok(())
+        })
     }
 
     fn encode_explicit_prefix<V: Encode>(
@@ -524,10 +702,7 @@ impl crate::Encoder for Encoder {
         tag: Tag,
         value: &V,
     ) -> Result<Self::Ok, Self::Error> {
-        let mut encoder = Self::new(self.config);
-        value.encode(&mut encoder)?;
-        self.encode_constructed(tag, &encoder.output);
-        Ok(())
+        self.encode_constructed_with(tag, |encoder| value.encode(encoder))
     }
 
     fn encode_sequence<C, F>(&mut self, tag: Tag, encoder_scope: F) -> Result<Self::Ok, Self::Error>
@@ -535,13 +710,11 @@ impl crate::Encoder for Encoder {
         C: crate::types::Constructed,
         F: FnOnce(&mut Self) -> Result<Self::Ok, Self::Error>,
     {
-        let mut encoder = Self::new(self.config);
-
-        (encoder_scope)(&mut encoder)?;
-
-        self.encode_constructed(tag, &encoder.output);
+        if core::mem::take(&mut self.is_extension_addition_group) {
+            return (encoder_scope)(self);
+        }
 
-        Ok(())
+        self.encode_constructed_with(tag, encoder_scope)
     }
 
     fn encode_set<C, F>(&mut self, tag: Tag, encoder_scope: F) -> Result<Self::Ok, Self::Error>
@@ -549,11 +722,11 @@ impl crate::Encoder for Encoder {
         C: crate::types::Constructed,
         F: FnOnce(&mut Self) -> Result<Self::Ok, Self::Error>,
     {
-        let mut encoder = Self::new_set(self.config);
+        let mut encoder = self.nested(true);
 
         (encoder_scope)(&mut encoder)?;
 
-        self.encode_constructed(tag, &encoder.output());
+        self.encode_constructed(tag, &encoder.into_contents());
 
         Ok(())
     }
@@ -567,7 +740,8 @@ impl crate::Encoder for Encoder {
         value.encode_with_tag_and_constraints(self, tag, constraints)
     }
 
-    /// Encode a extension addition group value.
+    /// Encode a extension addition group value, whose components are
+    /// encoded as if they weren't in a group.
     fn encode_extension_addition_group<E>(
         &mut self,
         value: Option<&E>,
@@ -575,6 +749,11 @@ impl crate::Encoder for Encoder {
     where
         E: Encode + crate::types::Constructed,
     {
+        let Some(value) = value else {
+            return Ok(());
+        };
+
+        self.is_extension_addition_group = true;
         value.encode(self)
     }
 }
@@ -605,8 +784,7 @@ mod tests {
     fn identifier() {
         fn ident_to_bytes(ident: Identifier) -> Vec<u8> {
             let mut enc = Encoder::new(EncoderOptions::ber());
-            let bytes = enc.encode_identifier(ident);
-            enc.append_byte_or_bytes(bytes);
+            enc.encode_identifier(ident);
             enc.output
         }
 
@@ -654,6 +832,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_lengths() {
+        use crate::types::OctetString;
+
+        let short = OctetString::from(vec![1; 100]);
+        let long = OctetString::from(vec![2; 200]);
+        let value = vec![vec![short.clone()], vec![short, long]];
+
+        let output = super::super::encode(&value).unwrap();
+        assert_eq!(4 + 104 + 309, output.len());
+        assert_eq!(
+            &[0x30, 0x82, 0x01, 0x9D, 0x30, 0x66, 0x04, 0x64],
+            &output[..8]
+        );
+        assert_eq!(&[0x30, 0x82, 0x01, 0x31, 0x04, 0x64], &output[108..114]);
+        assert_eq!(&[0x04, 0x81, 0xC8, 2], &output[214..218]);
+        assert_eq!(
+            value,
+            crate::ber::decode::<Vec<Vec<OctetString>>>(&output).unwrap()
+        );
+    }
+
     #[test]
     fn base128_test() {
         fn encode(n: u32) -> Vec<u8> {
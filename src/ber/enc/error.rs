@@ -7,6 +7,21 @@ use snafu::*;
 pub enum Error {
     /// `OBJECT IDENTIFIER` must have at least two components.
     InvalidObjectIdentifier,
+    /// The output buffer is too small to hold the encoding.
+    #[snafu(display("buffer too small, needed: {needed}; available: {available}"))]
+    BufferTooSmall {
+        /// The length of the encoding.
+        needed: usize,
+        /// The space left in the output buffer.
+        available: usize,
+    },
+    /// Writing the encoding to the output failed.
+    #[cfg(feature = "std")]
+    #[snafu(display("failed to write encoding: {source}"))]
+    Io {
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
     /// A custom error.
     #[snafu(display("Custom Error:\n{}", msg))]
     Custom {
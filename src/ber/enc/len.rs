@@ -0,0 +1,536 @@
+//! Computing the length of BER encodings without encoding them.
+
+use core::fmt::Write;
+
+use super::{
+    base128, definite_length, identifier_octets, significant_bytes, EncoderOptions, Error,
+    END_OF_CONTENTS, GENERALIZED_TIME_FORMAT, UTC_TIME_FORMAT,
+};
+use crate::{
+    ber::Identifier,
+    enc::Error as _,
+    types::{
+        self,
+        oid::{MAX_OID_FIRST_OCTET, MAX_OID_SECOND_OCTET},
+        Constraints, Enumerated, Tag,
+    },
+    Encode,
+};
+
+// The lengths are those of the encoder's own headers, so that the two can't
+// disagree.
+
+fn definite_length_len(len: usize) -> usize {
+    definite_length(len).1
+}
+
+fn identifier_len(tag: Tag, is_constructed: bool) -> usize {
+    identifier_octets(Identifier::from_tag(tag, is_constructed)).1
+}
+
+fn base128_len(number: u32) -> usize {
+    base128(number).1
+}
+
+/// The number of bytes used by the contents of `value`, encoded as a signed
+/// big endian integer with as few bytes as possible.
+fn integer_len(value: &types::Integer) -> usize {
+    value.signed_bytes_be().len()
+}
+
+/// Counts the bytes written to it.
+#[derive(Default)]
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// An [`Encoder`][crate::Encoder] that computes the length of a value's
+/// BER, CER, or DER encoding without encoding it, so that buffers can be
+/// allocated with the exact size before encoding.
+///
+/// ```
+/// # use rasn::prelude::*;
+/// let mut encoder = rasn::ber::enc::LenEncoder::new(rasn::ber::enc::EncoderOptions::der());
+/// vec![Integer::from(500), Integer::from(-1)].encode(&mut encoder).unwrap();
+/// assert_eq!(9, encoder.len());
+/// ```
+pub struct LenEncoder {
+    len: usize,
+    config: EncoderOptions,
+    is_set_encoding: bool,
+    /// Whether the next sequence is an extension addition group, whose
+    /// components are encoded in place of the group.
+    is_extension_addition_group: bool,
+}
+
+impl LenEncoder {
+    /// Creates a new instance from the given `config`.
+    pub fn new(config: EncoderOptions) -> Self {
+        Self {
+            len: 0,
+            config,
+            is_set_encoding: false,
+            is_extension_addition_group: false,
+        }
+    }
+
+    /// The length of the values encoded so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether nothing has been encoded so far.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn nested(&self, is_set_encoding: bool) -> Self {
+        Self {
+            len: 0,
+            config: self.config,
+            is_set_encoding,
+            is_extension_addition_group: false,
+        }
+    }
+
+    /// Adds a value with `len` bytes of contents.
+    fn value(&mut self, tag: Tag, is_constructed: bool, len: usize) {
+        self.len += identifier_len(tag, is_constructed);
+
+        if is_constructed && self.config.encoding_rules.is_cer() {
+            // Indefinite length, followed by the end of contents.
+            self.len += 1 + len + END_OF_CONTENTS.len();
+        } else {
+            self.len += definite_length_len(len) + len;
+        }
+    }
+
+    fn primitive(&mut self, tag: Tag, len: usize) {
+        self.value(tag, false, len);
+    }
+
+    fn constructed(&mut self, tag: Tag, len: usize) {
+        self.value(tag, true, len);
+    }
+
+    /// Adds a string, which CER splits into chunks of at most 1000 bytes.
+    fn string(&mut self, tag: Tag, nested_tag: Tag, len: usize) {
+        let max_string_length = self.config.encoding_rules.max_string_length();
+
+        if len > max_string_length {
+            self.len += identifier_len(tag, true) + 1 + END_OF_CONTENTS.len();
+
+            for start in (0..len).step_by(max_string_length) {
+                self.primitive(nested_tag, max_string_length.min(len - start));
+            }
+        } else {
+            self.primitive(tag, len);
+        }
+    }
+
+    fn octet_string(&mut self, tag: Tag, len: usize) -> Result<(), Error> {
+        self.string(tag, Tag::OCTET_STRING, len);
+        Ok(())
+    }
+
+    fn time<Tz: chrono::TimeZone>(
+        &mut self,
+        tag: Tag,
+        time: &chrono::DateTime<Tz>,
+        format: &str,
+    ) -> Result<(), Error> {
+        let mut counter = Counter::default();
+        write!(counter, "{}", time.naive_utc().format(format)).map_err(Error::custom)?;
+        self.primitive(tag, counter.0);
+        Ok(())
+    }
+
+    /// Adds the length of the values encoded in `encode_fn` to the length of
+    /// a constructed value.
+    fn scope(
+        &mut self,
+        tag: Tag,
+        is_set_encoding: bool,
+        encode_fn: impl FnOnce(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut encoder = self.nested(is_set_encoding);
+        (encode_fn)(&mut encoder)?;
+        self.constructed(tag, encoder.len);
+        Ok(())
+    }
+}
+
+impl crate::Encoder for LenEncoder {
+    type Ok = ();
+    type Error = Error;
+
+    fn codec(&self) -> Option<crate::Codec> {
+        Some(self.config.encoding_rules.codec())
+    }
+
+    fn encode_any(&mut self, _: Tag, value: &types::Any) -> Result<Self::Ok, Self::Error> {
+        if self.is_set_encoding {
+            return Err(Error::custom("Cannot encode `ANY` types in `SET` fields."));
+        }
+
+        self.len += value.contents.len();
+        Ok(())
+    }
+
+    fn encode_bit_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::BitString,
+    ) -> Result<Self::Ok, Self::Error> {
+        if value.not_any() {
+            self.primitive(tag, 0);
+        } else {
+            // The contents start with the number of unused bits.
+            self.string(tag, Tag::BIT_STRING, 1 + significant_bytes(value).len());
+        }
+
+        Ok(())
+    }
+
+    fn encode_bool(&mut self, tag: Tag, _: bool) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, 1);
+        Ok(())
+    }
+
+    fn encode_choice<E: Encode>(
+        &mut self,
+        _: Constraints,
+        encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
+    ) -> Result<Self::Ok, Self::Error> {
+        (encode_fn)(self).map(drop)
+    }
+
+    fn encode_enumerated<E: Enumerated>(
+        &mut self,
+        tag: Tag,
+        value: &E,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_integer(tag, <_>::default(), &value.discriminant().into())
+    }
+
+    fn encode_integer(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::Integer,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, integer_len(value));
+        Ok(())
+    }
+
+    fn encode_null(&mut self, tag: Tag) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, 0);
+        Ok(())
+    }
+
+    fn encode_object_identifier(&mut self, tag: Tag, oid: &[u32]) -> Result<Self::Ok, Self::Error> {
+        if oid.len() < 2 || oid[0] > MAX_OID_FIRST_OCTET {
+            return Err(Error::InvalidObjectIdentifier);
+        }
+
+        let first = base128_len((oid[0] * (MAX_OID_SECOND_OCTET + 1)) + oid[1]);
+        let rest: usize = oid[2..].iter().map(|arc| base128_len(*arc)).sum();
+        self.primitive(tag, first + rest);
+
+        Ok(())
+    }
+
+    fn encode_octet_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &[u8],
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.len())
+    }
+
+    fn encode_visible_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::VisibleString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.as_iso646_bytes().len())
+    }
+
+    fn encode_ia5_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::Ia5String,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.as_iso646_bytes().len())
+    }
+
+    fn encode_general_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::GeneralString,
+    ) -> Result<Self::Ok, Self::Error> {
+        let value: &[u8] = value;
+        self.octet_string(tag, value.len())
+    }
+
+    fn encode_printable_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::PrintableString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.as_bytes().len())
+    }
+
+    fn encode_numeric_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::NumericString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.as_bytes().len())
+    }
+
+    fn encode_teletex_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::TeletexString,
+    ) -> Result<Self::Ok, Self::Error> {
+        let value: &[u8] = value;
+        self.octet_string(tag, value.len())
+    }
+
+    fn encode_bmp_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::BmpString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.byte_len())
+    }
+
+    fn encode_universal_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &types::UniversalString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.byte_len())
+    }
+
+    fn encode_utf8_string(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.octet_string(tag, value.len())
+    }
+
+    fn encode_utc_time(
+        &mut self,
+        tag: Tag,
+        value: &types::UtcTime,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.time(tag, value, UTC_TIME_FORMAT)
+    }
+
+    fn encode_generalized_time(
+        &mut self,
+        tag: Tag,
+        value: &types::GeneralizedTime,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.time(tag, value, GENERALIZED_TIME_FORMAT)
+    }
+
+    fn encode_some<E: Encode>(&mut self, value: &E) -> Result<Self::Ok, Self::Error> {
+        value.encode(self)
+    }
+
+    fn encode_some_with_tag<E: Encode>(
+        &mut self,
+        tag: Tag,
+        value: &E,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.encode_with_tag(self, tag)
+    }
+
+    fn encode_some_with_tag_and_constraints<E: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &E,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.encode_with_tag_and_constraints(self, tag, constraints)
+    }
+
+    fn encode_none<E: Encode>(&mut self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn encode_none_with_tag(&mut self, _: Tag) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn encode_sequence_of<E: Encode>(
+        &mut self,
+        tag: Tag,
+        values: &[E],
+        _: Constraints,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.scope(tag, false, |encoder| {
+            values.iter().try_for_each(|value| value.encode(encoder))
+        })
+    }
+
+    fn encode_set_of<E: Encode>(
+        &mut self,
+        tag: Tag,
+        values: &types::SetOf<E>,
+        _: Constraints,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.scope(tag, false, |encoder| {
+            values.iter().try_for_each(|value| value.encode(encoder))
+        })
+    }
+
+    fn encode_explicit_prefix<V: Encode>(
+        &mut self,
+        tag: Tag,
+        value: &V,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.scope(tag, false, |encoder| value.encode(encoder))
+    }
+
+    fn encode_sequence<C, F>(&mut self, tag: Tag, encoder_scope: F) -> Result<Self::Ok, Self::Error>
+    where
+        C: types::Constructed,
+        F: FnOnce(&mut Self) -> Result<Self::Ok, Self::Error>,
+    {
+        if core::mem::take(&mut self.is_extension_addition_group) {
+            return (encoder_scope)(self);
+        }
+
+        self.scope(tag, false, encoder_scope)
+    }
+
+    fn encode_set<C, F>(&mut self, tag: Tag, encoder_scope: F) -> Result<Self::Ok, Self::Error>
+    where
+        C: types::Constructed,
+        F: FnOnce(&mut Self) -> Result<Self::Ok, Self::Error>,
+    {
+        self.scope(tag, true, encoder_scope)
+    }
+
+    fn encode_extension_addition<E: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: E,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.encode_with_tag_and_constraints(self, tag, constraints)
+    }
+
+    fn encode_extension_addition_group<E>(
+        &mut self,
+        value: Option<&E>,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        E: Encode + types::Constructed,
+    {
+        let Some(value) = value else {
+            return Ok(());
+        };
+
+        self.is_extension_addition_group = true;
+        value.encode(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[track_caller]
+    fn assert_len<T: Encode>(value: &T) {
+        for options in [
+            EncoderOptions::ber(),
+            EncoderOptions::cer(),
+            EncoderOptions::der(),
+        ] {
+            let mut encoder = super::super::Encoder::new(options);
+            value.encode(&mut encoder).unwrap();
+            let mut len_encoder = LenEncoder::new(options);
+            value.encode(&mut len_encoder).unwrap();
+
+            assert_eq!(encoder.output().len(), len_encoder.len());
+        }
+    }
+
+    #[test]
+    fn integers() {
+        for value in [
+            0i128,
+            1,
+            -1,
+            127,
+            128,
+            -128,
+            -129,
+            255,
+            256,
+            -256,
+            -257,
+            i64::MAX.into(),
+            i64::MIN.into(),
+            i128::MAX,
+            i128::MIN,
+        ] {
+            assert_len(&Integer::from(value));
+        }
+    }
+
+    #[test]
+    fn lengths_and_tags() {
+        assert_len(&OctetString::from(vec![1; 127]));
+        assert_len(&OctetString::from(vec![1; 128]));
+        assert_len(&OctetString::from(vec![1; 256]));
+        assert_len(&OctetString::from(vec![1; 70_000]));
+        assert_len(&Implicit::<Private31, _>::new(true));
+        assert_len(&Explicit::<Private31, _>::new(vec![
+            Utf8String::from("a");
+            1_001
+        ]));
+    }
+
+    #[test]
+    fn strings_and_times() {
+        assert_len(&BitString::from_slice(&[0x0A, 0, 0x80, 0, 0]));
+        assert_len(&BitString::from_slice(&[0; 3]));
+        assert_len(&BmpString::try_from("bmp").unwrap());
+        assert_len(&Ia5String::try_from(String::from("ia5")).unwrap());
+        assert_len(&ObjectIdentifier::new(vec![2, 999, 1, 0x4000]).unwrap());
+        assert_len(&UtcTime::from(chrono::DateTime::<chrono::Utc>::MIN_UTC));
+        assert_len(&GeneralizedTime::from(
+            chrono::DateTime::<chrono::Utc>::MAX_UTC,
+        ));
+    }
+
+    struct Private31;
+
+    impl AsnType for Private31 {
+        const TAG: Tag = Tag::new(Class::Private, 31);
+    }
+}
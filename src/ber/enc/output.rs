@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+
+/// Where an [`Encoder`](super::Encoder) writes its encoding.
+pub trait Output {
+    /// Returns an empty output for the encoding of a nested value.
+    fn nested() -> Self;
+
+    /// Writes `bytes` to the end of the output.
+    fn write(&mut self, bytes: &[u8]);
+
+    /// Returns the buffered encoding.
+    fn buffer(&mut self) -> &mut Vec<u8>;
+
+    /// Whether writes are passed on rather than buffered, so that nothing
+    /// written can be changed afterwards.
+    fn is_streaming(&self) -> bool;
+}
+
+impl Output for Vec<u8> {
+    fn nested() -> Self {
+        Vec::new()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    fn buffer(&mut self) -> &mut Vec<u8> {
+        self
+    }
+
+    fn is_streaming(&self) -> bool {
+        false
+    }
+}
+
+/// An output that passes the encoding of the outermost value straight to
+/// `write`, such as a caller's buffer or writer. Nested values are still
+/// buffered, as their length is written before them.
+pub struct Sink<F> {
+    write: Option<F>,
+    buffer: Vec<u8>,
+}
+
+impl<F: FnMut(&[u8])> Sink<F> {
+    pub fn new(write: F) -> Self {
+        Self {
+            write: Some(write),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl<F: FnMut(&[u8])> Output for Sink<F> {
+    fn nested() -> Self {
+        Self {
+            write: None,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        match &mut self.write {
+            Some(write) => (write)(bytes),
+            None => self.buffer.extend_from_slice(bytes),
+        }
+    }
+
+    fn buffer(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.write.is_some()
+    }
+}
@@ -0,0 +1,311 @@
+//! # Framing BER
+//!
+//! Helpers for finding the boundaries of BER encoded values in a stream of
+//! bytes, such as protocols that send one value after another over TCP.
+
+use core::marker::PhantomData;
+
+use super::de::{self, parser, DecoderOptions};
+use crate::Decode;
+
+const EOC: &[u8] = &[0, 0];
+
+/// Returns the length of the complete tag-length-value encoding at the start
+/// of `input`, or `None` if `input` doesn't contain the whole value yet.
+/// Both definite and indefinite lengths are supported.
+///
+/// ```rust
+/// use rasn::ber::frame::peek_tlv_len;
+///
+/// // An OCTET STRING with a definite length.
+/// assert_eq!(Some(4), peek_tlv_len(&[0x04, 0x02, 1, 2, 0xFF]).unwrap());
+/// assert_eq!(None, peek_tlv_len(&[0x04, 0x02, 1]).unwrap());
+/// // A SEQUENCE with an indefinite length.
+/// assert_eq!(Some(6), peek_tlv_len(&[0x30, 0x80, 0x05, 0x00, 0, 0, 0x05]).unwrap());
+/// ```
+pub fn peek_tlv_len(input: &[u8]) -> Result<Option<usize>, de::Error> {
+    let options = DecoderOptions::ber();
+    let mut offset = 0;
+    // The number of indefinite length values we are currently inside.
+    let mut depth = 0usize;
+
+    loop {
+        let rest = &input[offset..];
+
+        if depth > 0 && rest.starts_with(EOC) {
+            offset += EOC.len();
+            depth -= 1;
+        } else {
+            // Every encoding is at least two octets long, so anything shorter
+            // is either incomplete or the start of an end-of-contents marker.
+            if rest.len() < 2 {
+                return Ok(None);
+            }
+
+            let result = parser::parse_identifier_octet(rest)
+                .and_then(|(i, identifier)| parser::parse_contents(&options, identifier, i));
+
+            match result {
+                Ok((i, contents)) => {
+                    offset = input.len() - i.len();
+
+                    if contents.is_none() {
+                        depth += 1;
+                    }
+                }
+                Err(nom::Err::Incomplete(_)) => return Ok(None),
+                Err(error) => return Err(de::error::map_nom_err(error)),
+            }
+        }
+
+        if depth == 0 {
+            return Ok(Some(offset));
+        }
+    }
+}
+
+/// Returns an iterator decoding consecutive BER encoded `T` values from
+/// `input`.
+pub fn iter<T: Decode>(input: &[u8]) -> Frames<'_, T> {
+    Frames::new(input, DecoderOptions::ber())
+}
+
+/// An iterator decoding consecutive `T` values from a buffer, created with
+/// [`iter`] or [`Frames::new`]. Iteration stops when the buffer is empty, or
+/// after the first error.
+pub struct Frames<'input, T> {
+    input: &'input [u8],
+    options: DecoderOptions,
+    failed: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'input, T> Frames<'input, T> {
+    /// Creates a new iterator decoding values from `input` using `options`.
+    pub fn new(input: &'input [u8], options: DecoderOptions) -> Self {
+        Self {
+            input,
+            options,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the bytes that haven't been decoded yet.
+    pub fn remaining(&self) -> &'input [u8] {
+        self.input
+    }
+}
+
+impl<'input, T: Decode> Iterator for Frames<'input, T> {
+    type Item = Result<T, de::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_empty() || self.failed {
+            return None;
+        }
+
+        let mut decoder = de::Decoder::new(self.input, self.options);
+        match T::decode(&mut decoder) {
+            Ok(value) => {
+                self.input = &self.input[decoder.decoded_len()..];
+                Some(Ok(value))
+            }
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// The default maximum length of a frame, 8 MiB.
+#[cfg(feature = "tokio")]
+pub const DEFAULT_MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+/// A [`tokio_util::codec`] codec splitting a byte stream into complete BER
+/// encoded values. Decoding yields the raw bytes of each value, and
+/// encoding writes already encoded values as they are. Values longer than
+/// the maximum frame length are rejected, including incomplete values once
+/// more than that many bytes are buffered.
+#[cfg(feature = "tokio")]
+#[derive(Clone, Copy, Debug)]
+pub struct FrameCodec {
+    max_frame_len: usize,
+}
+
+#[cfg(feature = "tokio")]
+impl FrameCodec {
+    /// Creates a new codec with a maximum frame length of
+    /// [`DEFAULT_MAX_FRAME_LEN`].
+    pub fn new() -> Self {
+        Self {
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Sets the maximum length of a single encoded value, larger values
+    /// are rejected with [`std::io::ErrorKind::InvalidData`] in both
+    /// directions.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Returns the maximum length of a single encoded value.
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), std::io::Error> {
+        if len > self.max_frame_len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                alloc::format!(
+                    "frame of {} bytes exceeds the maximum frame length of {} bytes",
+                    len,
+                    self.max_frame_len
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Default for FrameCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio_util::codec::Decoder for FrameCodec {
+    type Item = bytes::BytesMut;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match peek_tlv_len(src) {
+            Ok(Some(len)) => {
+                self.check_len(len)?;
+                Ok(Some(src.split_to(len)))
+            }
+            // The value isn't complete yet, so it's at least as long as
+            // what's been buffered.
+            Ok(None) => self.check_len(src.len()).map(|_| None),
+            Err(error) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio_util::codec::Encoder<&[u8]> for FrameCodec {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: &[u8], dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
+        self.check_len(item.len())?;
+        dst.extend_from_slice(item);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::types::*;
+
+    #[test]
+    fn definite_lengths() {
+        let encoded = crate::ber::encode(&OctetString::from_static(&[0; 200])).unwrap();
+        assert_eq!(203, encoded.len());
+
+        for len in 0..encoded.len() {
+            assert_eq!(None, peek_tlv_len(&encoded[..len]).unwrap());
+        }
+        assert_eq!(Some(203), peek_tlv_len(&encoded).unwrap());
+        assert_eq!(Some(2), peek_tlv_len(&[0x05, 0x00, 0x05, 0x00]).unwrap());
+    }
+
+    #[test]
+    fn indefinite_lengths() {
+        // SEQUENCE { SEQUENCE { NULL } } with indefinite lengths throughout.
+        let encoded = [0x30, 0x80, 0x30, 0x80, 0x05, 0x00, 0, 0, 0, 0];
+
+        for len in 0..encoded.len() {
+            assert_eq!(None, peek_tlv_len(&encoded[..len]).unwrap());
+        }
+        assert_eq!(Some(10), peek_tlv_len(&encoded).unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        // Primitive values can't have an indefinite length.
+        assert!(peek_tlv_len(&[0x04, 0x80, 0, 0]).is_err());
+        assert!(peek_tlv_len(&[0x04, 0xFF]).is_err());
+    }
+
+    #[test]
+    fn frames() {
+        let mut input = Vec::new();
+        for value in [1u32, 128, 70000] {
+            input.extend(crate::ber::encode(&value).unwrap());
+        }
+
+        let values = iter::<u32>(&input).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(values, [1, 128, 70000]);
+
+        let mut frames = iter::<u32>(&input[..input.len() - 1]);
+        assert_eq!(Some(1), frames.next().and_then(Result::ok));
+        assert_eq!(Some(128), frames.next().and_then(Result::ok));
+        assert!(frames.next().unwrap().is_err());
+        assert!(frames.next().is_none());
+        assert_eq!(&input[7..input.len() - 1], frames.remaining());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn codec() {
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let mut buffer = bytes::BytesMut::new();
+        let mut codec = FrameCodec::new();
+        codec.encode(&[0x05, 0x00], &mut buffer).unwrap();
+        codec.encode(&[0x02, 0x01], &mut buffer).unwrap();
+
+        assert_eq!(
+            &[0x05, 0x00],
+            &codec.decode(&mut buffer).unwrap().unwrap()[..]
+        );
+        assert_eq!(None, codec.decode(&mut buffer).unwrap());
+
+        buffer.extend_from_slice(&[0x2A]);
+        assert_eq!(
+            &[0x02, 0x01, 0x2A],
+            &codec.decode(&mut buffer).unwrap().unwrap()[..]
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn codec_max_frame_len() {
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let mut codec = FrameCodec::new().with_max_frame_len(4);
+        assert!(codec
+            .encode(&[0x04, 0x03, 1, 2, 3], &mut bytes::BytesMut::new())
+            .is_err());
+
+        // An OCTET STRING claiming to be 16 MiB long, which is rejected once
+        // more than the maximum is buffered, rather than waiting for it all.
+        let mut buffer = bytes::BytesMut::from(&[0x04, 0x84, 0x01, 0x00, 0x00][..]);
+        assert!(codec.decode(&mut buffer).is_err());
+
+        let mut buffer = bytes::BytesMut::from(&[0x04, 0x03, 1, 2, 3][..]);
+        let error = codec.decode(&mut buffer).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+    }
+}
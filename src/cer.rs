@@ -16,3 +16,42 @@ pub fn encode<T: crate::Encode>(value: &T) -> Result<alloc::vec::Vec<u8>, crate:
 
     Ok(enc.output())
 }
+
+/// Attempts to encode `value` to CER, appending the encoding to `buffer`.
+pub fn encode_to<T: crate::Encode>(
+    value: &T,
+    buffer: &mut impl bytes::BufMut,
+) -> Result<(), crate::ber::enc::Error> {
+    crate::ber::encode_to_with_options(crate::ber::enc::EncoderOptions::cer(), value, buffer)
+}
+
+/// Attempts to encode `value` to CER at the start of `buffer`, returning
+/// the number of bytes written.
+pub fn encode_into_slice<T: crate::Encode>(
+    value: &T,
+    buffer: &mut [u8],
+) -> Result<usize, crate::ber::enc::Error> {
+    crate::ber::encode_into_slice_with_options(
+        crate::ber::enc::EncoderOptions::cer(),
+        value,
+        buffer,
+    )
+}
+
+/// Attempts to encode `value` to CER, writing the encoding to `writer`.
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: crate::Encode>(
+    value: &T,
+    writer: impl std::io::Write,
+) -> Result<(), crate::ber::enc::Error> {
+    crate::ber::encode_to_writer_with_options(crate::ber::enc::EncoderOptions::cer(), value, writer)
+}
+
+/// Returns the length of `value`'s CER encoding, without encoding it.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::ber::enc::Error> {
+    let mut enc = crate::ber::enc::LenEncoder::new(crate::ber::enc::EncoderOptions::cer());
+
+    value.encode(&mut enc)?;
+
+    Ok(enc.len())
+}
@@ -1,10 +1,15 @@
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+use alloc::vec::Vec;
+
 use crate::prelude::*;
 
 use snafu::*;
 
 /// A set of supported ASN.1 codecs. Can be used to dynamically encode types
 /// into different codecs at runtime.
-#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[non_exhaustive]
 pub enum Codec {
     /// X.691 — Packed Encoding Rules (Aligned)
@@ -19,7 +24,20 @@ pub enum Codec {
     Uper,
 }
 
+impl core::fmt::Display for Codec {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Aper => write!(f, "APER"),
+            Self::Ber => write!(f, "BER"),
+            Self::Cer => write!(f, "CER"),
+            Self::Der => write!(f, "DER"),
+            Self::Uper => write!(f, "UPER"),
+        }
+    }
+}
+
 impl Codec {
+    /// Encodes `value` using the encoding rules represented by `self`.
     pub fn encode<T: Encode>(self, value: &T) -> Result<Vec<u8>, EncodeError> {
         match self {
             Self::Aper => crate::aper::encode(value).context(AperSnafu),
@@ -29,13 +47,59 @@ impl Codec {
             Self::Uper => crate::uper::encode(value).context(UperSnafu),
         }
     }
+
+    /// Returns the length of `value`'s encoding with the encoding rules
+    /// represented by `self`.
+    pub fn encoded_len<T: Encode>(self, value: &T) -> Result<usize, EncodeError> {
+        match self {
+            Self::Aper => crate::aper::encoded_len(value).context(AperSnafu),
+            Self::Ber => crate::ber::encoded_len(value).context(BerSnafu),
+            Self::Cer => crate::cer::encoded_len(value).context(CerSnafu),
+            Self::Der => crate::der::encoded_len(value).context(DerSnafu),
+            Self::Uper => crate::uper::encoded_len(value).context(UperSnafu),
+        }
+    }
+
+    /// Decodes `input` to `T` using the encoding rules represented by `self`.
+    pub fn decode<T: Decode>(self, input: &[u8]) -> Result<T, DecodeError> {
+        match self {
+            Self::Aper => crate::aper::decode(input).context(AperDecodeSnafu),
+            Self::Ber => crate::ber::decode(input).context(BerDecodeSnafu),
+            Self::Cer => crate::cer::decode(input).context(CerDecodeSnafu),
+            Self::Der => crate::der::decode(input).context(DerDecodeSnafu),
+            Self::Uper => crate::uper::decode(input).context(UperDecodeSnafu),
+        }
+    }
 }
 
+/// An error that occurred while encoding with a dynamically selected [`Codec`].
 #[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
 pub enum EncodeError {
-    Aper{ source: crate::aper::enc::Error },
-    Ber{ source: crate::ber::enc::Error },
-    Cer{ source: crate::der::enc::Error },
-    Der{ source: crate::der::enc::Error },
-    Uper{ source: crate::uper::enc::Error },
+    #[snafu(display("APER encoding error: {source}"))]
+    Aper { source: crate::aper::enc::Error },
+    #[snafu(display("BER encoding error: {source}"))]
+    Ber { source: crate::ber::enc::Error },
+    #[snafu(display("CER encoding error: {source}"))]
+    Cer { source: crate::der::enc::Error },
+    #[snafu(display("DER encoding error: {source}"))]
+    Der { source: crate::der::enc::Error },
+    #[snafu(display("UPER encoding error: {source}"))]
+    Uper { source: crate::uper::enc::Error },
+}
+
+/// An error that occurred while decoding with a dynamically selected [`Codec`].
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum DecodeError {
+    #[snafu(display("APER decoding error: {source}"))]
+    AperDecode { source: crate::aper::de::Error },
+    #[snafu(display("BER decoding error: {source}"))]
+    BerDecode { source: crate::ber::de::Error },
+    #[snafu(display("CER decoding error: {source}"))]
+    CerDecode { source: crate::der::de::Error },
+    #[snafu(display("DER decoding error: {source}"))]
+    DerDecode { source: crate::der::de::Error },
+    #[snafu(display("UPER decoding error: {source}"))]
+    UperDecode { source: crate::uper::de::Error },
 }
@@ -0,0 +1,338 @@
+//! # Tokio Codecs
+//!
+//! [`tokio_util::codec`] adapters for sending and receiving ASN.1 values
+//! over asynchronous byte streams, for use with [`Framed`],
+//! [`FramedRead`], and [`FramedWrite`].
+//!
+//! [`Framed`]: tokio_util::codec::Framed
+//! [`FramedRead`]: tokio_util::codec::FramedRead
+//! [`FramedWrite`]: tokio_util::codec::FramedWrite
+
+use core::marker::PhantomData;
+use std::io;
+
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+use crate::{
+    ber::{
+        self,
+        frame::{FrameCodec, DEFAULT_MAX_FRAME_LEN},
+    },
+    per, Decode, Encode,
+};
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<alloc::boxed::Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn frame_too_large(len: usize, max_frame_len: usize) -> io::Error {
+    invalid_data(alloc::format!(
+        "frame of {len} bytes exceeds the maximum frame length of {max_frame_len} bytes"
+    ))
+}
+
+/// A codec sending and receiving `T` as consecutive BER, CER, or DER encoded
+/// values. BER is self-delimiting, so no extra framing is added.
+pub struct BerCodec<T> {
+    encoder_options: ber::enc::EncoderOptions,
+    decoder_options: ber::de::DecoderOptions,
+    framing: FrameCodec,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> BerCodec<T> {
+    /// Creates a new codec using BER.
+    pub fn new() -> Self {
+        Self::new_with_options(
+            ber::enc::EncoderOptions::ber(),
+            ber::de::DecoderOptions::ber(),
+        )
+    }
+
+    /// Creates a new codec using DER.
+    pub fn der() -> Self {
+        Self::new_with_options(
+            ber::enc::EncoderOptions::der(),
+            ber::de::DecoderOptions::der(),
+        )
+    }
+
+    /// Creates a new codec from the given encoder and decoder options.
+    pub fn new_with_options(
+        encoder_options: ber::enc::EncoderOptions,
+        decoder_options: ber::de::DecoderOptions,
+    ) -> Self {
+        Self {
+            encoder_options,
+            decoder_options,
+            framing: FrameCodec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the maximum length of a single encoded value, larger values
+    /// are rejected with [`io::ErrorKind::InvalidData`] in both directions.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.framing = self.framing.with_max_frame_len(max_frame_len);
+        self
+    }
+
+    /// Returns the maximum length of a single encoded value.
+    pub fn max_frame_len(&self) -> usize {
+        self.framing.max_frame_len()
+    }
+}
+
+impl<T> Default for BerCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for BerCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            encoder_options: self.encoder_options,
+            decoder_options: self.decoder_options,
+            framing: self.framing,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for BerCodec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("BerCodec")
+            .field("encoder_options", &self.encoder_options)
+            .field("decoder_options", &self.decoder_options)
+            .field("max_frame_len", &self.max_frame_len())
+            .finish()
+    }
+}
+
+impl<T: Decode> Decoder for BerCodec<T> {
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        match self.framing.decode(src)? {
+            Some(frame) => T::decode(&mut ber::de::Decoder::new(&frame, self.decoder_options))
+                .map(Some)
+                .map_err(invalid_data),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: Encode> Encoder<T> for BerCodec<T> {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), io::Error> {
+        let mut encoder = ber::enc::Encoder::new(self.encoder_options);
+        item.encode(&mut encoder).map_err(invalid_data)?;
+        self.framing.encode(&encoder.output(), dst)
+    }
+}
+
+/// A codec sending and receiving `T` as APER or UPER encoded values. PER
+/// isn't self-delimiting, so each value is prefixed with its length as a
+/// four byte big endian integer.
+pub struct PerCodec<T> {
+    encoder_options: per::enc::EncoderOptions,
+    decoder_options: per::de::DecoderOptions,
+    framing: LengthDelimitedCodec,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> PerCodec<T> {
+    /// Creates a new codec using APER.
+    pub fn aligned() -> Self {
+        Self::new_with_options(
+            per::enc::EncoderOptions::aligned(),
+            per::de::DecoderOptions::aligned(),
+        )
+    }
+
+    /// Creates a new codec using UPER.
+    pub fn unaligned() -> Self {
+        Self::new_with_options(
+            per::enc::EncoderOptions::unaligned(),
+            per::de::DecoderOptions::unaligned(),
+        )
+    }
+
+    fn new_with_options(
+        encoder_options: per::enc::EncoderOptions,
+        decoder_options: per::de::DecoderOptions,
+    ) -> Self {
+        Self {
+            encoder_options,
+            decoder_options,
+            framing: LengthDelimitedCodec::builder()
+                .max_frame_length(DEFAULT_MAX_FRAME_LEN)
+                .new_codec(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the maximum length of a single encoded value, larger values
+    /// are rejected with [`io::ErrorKind::InvalidData`] in both directions.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.framing.set_max_frame_length(max_frame_len);
+        self
+    }
+
+    /// Returns the maximum length of a single encoded value.
+    pub fn max_frame_len(&self) -> usize {
+        self.framing.max_frame_length()
+    }
+}
+
+impl<T> Clone for PerCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            encoder_options: self.encoder_options,
+            decoder_options: self.decoder_options,
+            framing: self.framing.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for PerCodec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("PerCodec")
+            .field("encoder_options", &self.encoder_options)
+            .field("decoder_options", &self.decoder_options)
+            .field("max_frame_len", &self.max_frame_len())
+            .finish()
+    }
+}
+
+impl<T: Decode> Decoder for PerCodec<T> {
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        match self.framing.decode(src)? {
+            Some(frame) => per::decode(self.decoder_options, &frame)
+                .map(Some)
+                .map_err(invalid_data),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: Encode> Encoder<T> for PerCodec<T> {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), io::Error> {
+        let output = per::encode(self.encoder_options, &item).map_err(invalid_data)?;
+
+        if output.len() > self.max_frame_len() {
+            return Err(frame_too_large(output.len(), self.max_frame_len()));
+        }
+
+        self.framing.encode(bytes::Bytes::from(output), dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{SinkExt, StreamExt};
+    use tokio_util::codec::Framed;
+
+    use super::{BerCodec, PerCodec};
+    use crate as rasn;
+    use crate::prelude::*;
+
+    #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
+    struct Message {
+        id: u32,
+        payload: OctetString,
+    }
+
+    fn message(id: u32) -> Message {
+        Message {
+            id,
+            payload: OctetString::from(alloc::vec![id as u8; (id % 17 + 1) as usize]),
+        }
+    }
+
+    async fn round_trip<C>(codec: C)
+    where
+        C: tokio_util::codec::Decoder<Item = Message, Error = std::io::Error>
+            + tokio_util::codec::Encoder<Message, Error = std::io::Error>
+            + Clone,
+    {
+        // A tiny buffer, so that values arrive over several reads.
+        let (client, server) = ::tokio::io::duplex(3);
+        let mut client = Framed::new(client, codec.clone());
+        let mut server = Framed::new(server, codec);
+
+        let sender = async {
+            for id in 0..20 {
+                client.send(message(id)).await.unwrap();
+            }
+            client
+        };
+        let receiver = async {
+            for id in 0..20 {
+                assert_eq!(message(id), server.next().await.unwrap().unwrap());
+            }
+        };
+
+        let (client, ()) = ::tokio::join!(sender, receiver);
+        drop(client);
+        assert!(server.next().await.is_none());
+    }
+
+    #[::tokio::test]
+    async fn ber() {
+        round_trip(BerCodec::new()).await;
+        round_trip(BerCodec::der()).await;
+    }
+
+    #[::tokio::test]
+    async fn per() {
+        round_trip(PerCodec::aligned()).await;
+        round_trip(PerCodec::unaligned()).await;
+    }
+
+    #[test]
+    fn max_frame_len() {
+        use std::io;
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let mut buffer = bytes::BytesMut::new();
+        let mut codec = BerCodec::<Message>::new().with_max_frame_len(12);
+        assert!(codec.encode(message(2), &mut buffer).is_ok());
+        let error = codec.encode(message(16), &mut buffer).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+
+        // The header announces more contents than the codec accepts.
+        buffer.extend_from_slice(&[0x30, 0x20, 0x02, 0x01]);
+        assert_eq!(Some(message(2)), codec.decode(&mut buffer).unwrap());
+        buffer.extend_from_slice(&[0; 9]);
+        let error = codec.decode(&mut buffer).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+
+        let mut codec = PerCodec::<Message>::unaligned().with_max_frame_len(4);
+        let error = codec.encode(message(16), &mut buffer).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn invalid_data() {
+        use std::io;
+        use tokio_util::codec::Decoder;
+
+        let mut buffer = bytes::BytesMut::from(&[0x04, 0x80, 0, 0][..]);
+        let error = BerCodec::<Message>::new().decode(&mut buffer).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+}
@@ -49,6 +49,14 @@ pub trait Decode: Sized + AsnType {
 pub trait Decoder: Sized {
     type Error: Error;
 
+    /// Returns the variant of `Codec` that the decoder implements, or `None`
+    /// if it doesn't implement one of the standard encoding rules.
+    /// [`types::Containing`] values that inherit the outer encoding rules
+    /// can't be decoded without one.
+    fn codec(&self) -> Option<crate::Codec> {
+        None
+    }
+
     /// Decode a unknown ASN.1 value identified by `tag` from the available input.
     fn decode_any(&mut self) -> Result<types::Any, Self::Error>;
     /// Decode a `BIT STRING` identified by `tag` from the available input.
@@ -67,6 +75,30 @@ pub trait Decoder: Sized {
         tag: Tag,
         constraints: Constraints,
     ) -> Result<types::Integer, Self::Error>;
+    /// Decode a `INTEGER` identified by `tag` that fits in an `i64`. Codecs
+    /// can override this to decode primitive integers without going through
+    /// [`types::Integer`].
+    fn decode_integer_i64(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<i64, Self::Error> {
+        self.decode_integer(tag, constraints)?
+            .try_into()
+            .map_err(Self::Error::custom)
+    }
+    /// Decode a `INTEGER` identified by `tag` that fits in a `u64`. Codecs
+    /// can override this to decode primitive integers without going through
+    /// [`types::Integer`].
+    fn decode_integer_u64(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<u64, Self::Error> {
+        self.decode_integer(tag, constraints)?
+            .try_into()
+            .map_err(Self::Error::custom)
+    }
     /// Decode `NULL` identified by `tag` from the available input.
     fn decode_null(&mut self, tag: Tag) -> Result<(), Self::Error>;
     /// Decode a `OBJECT IDENTIFIER` identified by `tag` from the available input.
@@ -154,8 +186,31 @@ pub trait Decoder: Sized {
         constraints: Constraints,
     ) -> Result<types::BmpString, Self::Error>;
 
+    /// Decode a `UniversalString` identified by `tag` from the available input.
+    /// Fails by default, for decoders which don't support it.
+    fn decode_universal_string(
+        &mut self,
+        _tag: Tag,
+        _constraints: Constraints,
+    ) -> Result<types::UniversalString, Self::Error> {
+        Err(Self::Error::custom(
+            "`UniversalString` isn't supported by this decoder",
+        ))
+    }
+
     /// Decode an ASN.1 value that has been explicitly prefixed with `tag` from the available input.
     fn decode_explicit_prefix<D: Decode>(&mut self, tag: Tag) -> Result<D, Self::Error>;
+    /// Decode an ASN.1 value that has been explicitly prefixed with `tag`,
+    /// using `constraints` in place of the value's own constraints. Encoding
+    /// rules that don't use constraints can rely on the default, which calls
+    /// `decode_explicit_prefix`.
+    fn decode_explicit_prefix_with_constraints<D: Decode>(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+    ) -> Result<D, Self::Error> {
+        self.decode_explicit_prefix(tag)
+    }
     /// Decode a `UtcTime` identified by `tag` from the available input.
     fn decode_utc_time(&mut self, tag: Tag) -> Result<types::UtcTime, Self::Error>;
     /// Decode a `GeneralizedTime` identified by `tag` from the available input.
@@ -229,6 +284,17 @@ pub trait Decoder: Sized {
     where
         D: Decode;
 
+    /// Decode an extension addition that has been implicitly tagged with
+    /// `tag`. Passing the correct tag is required even when used with codecs
+    /// where the tag is not present.
+    fn decode_extension_addition_with_tag<D>(&mut self, tag: Tag) -> Result<Option<D>, Self::Error>
+    where
+        D: Decode,
+    {
+        let _ = tag;
+        self.decode_extension_addition()
+    }
+
     fn decode_extension_addition_group<D: Decode + crate::types::Constructed>(
         &mut self,
     ) -> Result<Option<D>, Self::Error>;
@@ -299,12 +365,12 @@ impl Decode for bool {
 }
 
 macro_rules! impl_integers {
-    ($($int:ty),+ $(,)?) => {
+    ($method:ident: $($int:ty),+ $(,)?) => {
         $(
         impl Decode for $int {
             fn decode_with_tag_and_constraints<D: Decoder>(decoder: &mut D, tag: Tag, constraints: Constraints) -> Result<Self, D::Error> {
                 core::convert::TryInto::try_into(
-                    decoder.decode_integer(
+                    decoder.$method(
                         tag,
                         constraints,
                     )?
@@ -316,11 +382,16 @@ macro_rules! impl_integers {
 }
 
 impl_integers! {
+    decode_integer_i64:
     i8,
     i16,
     i32,
     i64,
     isize,
+}
+
+impl_integers! {
+    decode_integer_u64:
     u8,
     u16,
     u32,
@@ -348,6 +419,16 @@ impl Decode for types::Integer {
     }
 }
 
+impl Decode for num_bigint::BigInt {
+    fn decode_with_tag_and_constraints<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Self, D::Error> {
+        decoder.decode_integer(tag, constraints).map(Self::from)
+    }
+}
+
 impl<T: Decode> Decode for Box<T> {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, D::Error> {
         T::decode(decoder).map(Box::new)
@@ -41,6 +41,23 @@ pub trait Encode: AsnType {
         tag: Tag,
         constraints: Constraints,
     ) -> Result<(), E::Error>;
+
+    /// Calls `visitor` for `self` and every value contained in `self`, where
+    /// `context` describes the component or alternative that `self` is the
+    /// value of. See [`crate::visit`].
+    ///
+    /// **Note for implementors** The derive macros implement this to visit
+    /// every field with its identifier. The default implementation walks the
+    /// value through its [`Encode`] implementation, which visits the
+    /// components of hand written `SEQUENCE`s and `CHOICE`s without their
+    /// identifiers.
+    fn accept_with_context<V: crate::visit::Visitor>(
+        &self,
+        visitor: &mut V,
+        context: crate::visit::Context,
+    ) -> Result<(), V::Error> {
+        crate::visit::walk(self, visitor, context)
+    }
 }
 
 /// A **data format** encode any ASN.1 data type.
@@ -48,6 +65,14 @@ pub trait Encoder {
     type Ok;
     type Error: Error;
 
+    /// Returns the variant of `Codec` that the encoder implements, or `None`
+    /// if it doesn't implement one of the standard encoding rules.
+    /// [`types::Containing`] values that inherit the outer encoding rules
+    /// can't be encoded without one.
+    fn codec(&self) -> Option<crate::Codec> {
+        None
+    }
+
     /// Encode an unknown ASN.1 value.
     fn encode_any(&mut self, tag: Tag, value: &types::Any) -> Result<Self::Ok, Self::Error>;
 
@@ -81,9 +106,31 @@ pub trait Encoder {
         &mut self,
         tag: Tag,
         constraints: Constraints,
-        value: &num_bigint::BigInt,
+        value: &types::Integer,
     ) -> Result<Self::Ok, Self::Error>;
 
+    /// Encode a `INTEGER` value from an `i64`. Codecs can override this to
+    /// encode primitive integers without going through [`types::Integer`].
+    fn encode_integer_i64(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: i64,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_integer(tag, constraints, &value.into())
+    }
+
+    /// Encode a `INTEGER` value from a `u64`. Codecs can override this to
+    /// encode primitive integers without going through [`types::Integer`].
+    fn encode_integer_u64(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: u64,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_integer(tag, constraints, &value.into())
+    }
+
     /// Encode a `NULL` value.
     fn encode_null(&mut self, tag: Tag) -> Result<Self::Ok, Self::Error>;
 
@@ -159,6 +206,19 @@ pub trait Encoder {
         value: &types::BmpString,
     ) -> Result<Self::Ok, Self::Error>;
 
+    /// Encode a `UniversalString` value. Fails by default, for encoders
+    /// which don't support it.
+    fn encode_universal_string(
+        &mut self,
+        _tag: Tag,
+        _constraints: Constraints,
+        _value: &types::UniversalString,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Self::Error::custom(
+            "`UniversalString` isn't supported by this encoder",
+        ))
+    }
+
     /// Encode a `GeneralizedTime` value.
     fn encode_generalized_time(
         &mut self,
@@ -180,6 +240,18 @@ pub trait Encoder {
         value: &V,
     ) -> Result<Self::Ok, Self::Error>;
 
+    /// Encode a explicitly tagged value, using `constraints` in place of the
+    /// value's own constraints. Encoding rules that don't use constraints
+    /// can rely on the default, which calls `encode_explicit_prefix`.
+    fn encode_explicit_prefix_with_constraints<V: Encode>(
+        &mut self,
+        tag: Tag,
+        _: Constraints,
+        value: &V,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.encode_explicit_prefix(tag, value)
+    }
+
     /// Encode a `SEQUENCE` value.
     fn encode_sequence<C, F>(
         &mut self,
@@ -337,6 +409,23 @@ impl Error for core::convert::Infallible {
     }
 }
 
+/// A **data type** whose encoded length can be computed up front, to
+/// allocate the exact size needed for its encoding. Implemented for every
+/// type that implements [`Encode`].
+///
+/// The BER, CER, and DER lengths are computed without encoding the value,
+/// while the PER lengths currently require encoding it.
+pub trait EncodedLen {
+    /// Returns the number of bytes in the encoding of `self` with `codec`.
+    fn encoded_len(&self, codec: crate::Codec) -> Result<usize, crate::codec::EncodeError>;
+}
+
+impl<T: Encode> EncodedLen for T {
+    fn encoded_len(&self, codec: crate::Codec) -> Result<usize, crate::codec::EncodeError> {
+        codec.encoded_len(self)
+    }
+}
+
 impl<E: Encode> Encode for &'_ E {
     fn encode<EN: Encoder>(&self, encoder: &mut EN) -> Result<(), EN::Error> {
         E::encode(self, encoder)
@@ -362,6 +451,14 @@ impl<E: Encode> Encode for &'_ E {
     ) -> Result<(), EN::Error> {
         E::encode_with_tag_and_constraints(self, encoder, tag, constraints)
     }
+
+    fn accept_with_context<V: crate::visit::Visitor>(
+        &self,
+        visitor: &mut V,
+        context: crate::visit::Context,
+    ) -> Result<(), V::Error> {
+        E::accept_with_context(self, visitor, context)
+    }
 }
 
 impl Encode for () {
@@ -419,6 +516,17 @@ impl<E: Encode> Encode for Option<E> {
         }
         .map(drop)
     }
+
+    fn accept_with_context<V: crate::visit::Visitor>(
+        &self,
+        visitor: &mut V,
+        context: crate::visit::Context,
+    ) -> Result<(), V::Error> {
+        match self {
+            Some(value) => value.accept_with_context(visitor, context),
+            None => visitor.visit_absent(&context),
+        }
+    }
 }
 
 impl Encode for bool {
@@ -433,14 +541,14 @@ impl Encode for bool {
 }
 
 macro_rules! impl_integers {
-    ($($int:ty),+) => {
+    ($method:ident as $primitive:ty: $($int:ty),+) => {
         $(
             impl Encode for $int {
                 fn encode_with_tag_and_constraints<E: Encoder>(&self, encoder: &mut E, tag: Tag, constraints: Constraints) -> Result<(), E::Error> {
-                    encoder.encode_integer(
+                    encoder.$method(
                         tag,
                         constraints,
-                        &(*self).into()
+                        *self as $primitive
                     ).map(drop)
                 }
             }
@@ -449,11 +557,16 @@ macro_rules! impl_integers {
 }
 
 impl_integers! {
+    encode_integer_i64 as i64:
     i8,
     i16,
     i32,
     i64,
-    isize,
+    isize
+}
+
+impl_integers! {
+    encode_integer_u64 as u64:
     u8,
     u16,
     u32,
@@ -483,6 +596,19 @@ impl Encode for types::Integer {
     }
 }
 
+impl Encode for num_bigint::BigInt {
+    fn encode_with_tag_and_constraints<E: Encoder>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<(), E::Error> {
+        encoder
+            .encode_integer(tag, constraints, &self.into())
+            .map(drop)
+    }
+}
+
 impl Encode for types::OctetString {
     fn encode_with_tag_and_constraints<E: Encoder>(
         &self,
@@ -609,6 +735,14 @@ impl<E: Encode> Encode for alloc::boxed::Box<E> {
     ) -> Result<(), EN::Error> {
         E::encode_with_tag_and_constraints(self, encoder, tag, constraints)
     }
+
+    fn accept_with_context<V: crate::visit::Visitor>(
+        &self,
+        visitor: &mut V,
+        context: crate::visit::Context,
+    ) -> Result<(), V::Error> {
+        E::accept_with_context(self, visitor, context)
+    }
 }
 
 impl<E: Encode> Encode for alloc::vec::Vec<E> {
@@ -653,6 +787,14 @@ impl<T: AsnType, V: Encode> Encode for types::Implicit<T, V> {
     ) -> Result<(), E::Error> {
         V::encode_with_tag_and_constraints(&self.value, encoder, tag, constraints).map(drop)
     }
+
+    fn accept_with_context<VI: crate::visit::Visitor>(
+        &self,
+        visitor: &mut VI,
+        context: crate::visit::Context,
+    ) -> Result<(), VI::Error> {
+        self.value.accept_with_context(visitor, context)
+    }
 }
 
 impl<T: AsnType, V: Encode> Encode for types::Explicit<T, V> {
@@ -664,4 +806,18 @@ impl<T: AsnType, V: Encode> Encode for types::Explicit<T, V> {
     ) -> Result<(), E::Error> {
         encoder.encode_explicit_prefix(tag, &self.value).map(drop)
     }
+
+    fn accept_with_context<VI: crate::visit::Visitor>(
+        &self,
+        visitor: &mut VI,
+        context: crate::visit::Context,
+    ) -> Result<(), VI::Error> {
+        let context = crate::visit::Context {
+            tag: V::TAG,
+            explicit_tag: Some(context.tag),
+            constraints: V::CONSTRAINTS,
+            ..context
+        };
+        self.value.accept_with_context(visitor, context)
+    }
 }
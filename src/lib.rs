@@ -1,12 +1,14 @@
 #![doc = include_str!("../README.md")]
-// #![no_std]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 extern crate alloc;
 
 mod per;
 
+pub mod codec;
 pub mod de;
 pub mod enc;
 pub mod types;
+pub mod visit;
 
 #[cfg(test)]
 macro_rules! round_trip {
@@ -49,6 +51,7 @@ pub mod uper;
 
 #[doc(inline)]
 pub use self::{
+    codec::Codec,
     de::{Decode, Decoder},
     enc::{Encode, Encoder},
     types::{AsnType, Tag, TagTree},
@@ -141,6 +144,9 @@ mod tests {
         round_trip(&Integer::from(256));
         round_trip(&Integer::from(u64::MAX));
         round_trip(&Integer::from(i64::MIN));
+        round_trip(&Integer::from(i128::MAX));
+        round_trip(&Integer::from(u128::MAX));
+        round_trip(&-Integer::from(u128::MAX));
     }
 
     #[test]
@@ -210,6 +216,16 @@ mod tests {
         round_trip(&crate::types::Utf8String::from("Jones"));
     }
 
+    #[test]
+    fn universal_string() {
+        round_trip(&crate::types::UniversalString::from("J\u{F6}nes \u{1F980}"));
+    }
+
+    #[test]
+    fn teletex_string() {
+        round_trip(&crate::types::TeletexString::from(b"J\xC8ones".to_vec()));
+    }
+
     #[test]
     #[ignore]
     fn long_sequence_of() {
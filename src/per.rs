@@ -1,6 +1,8 @@
 pub mod de;
 pub mod enc;
 
+use alloc::vec::Vec;
+
 use crate::types::Constraints;
 
 pub use self::{de::Decoder, enc::Encoder};
@@ -33,6 +35,63 @@ pub(crate) fn encode<T: crate::Encode>(
     Ok(enc.output())
 }
 
+/// Attempts to encode `value`, appending the encoding to `buffer`.
+pub(crate) fn encode_to<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+    buffer: &mut impl bytes::BufMut,
+) -> Result<(), crate::per::enc::Error> {
+    let mut enc = crate::per::enc::Encoder::new(options);
+
+    value.encode(&mut enc)?;
+
+    enc.write_output(|output| {
+        let (needed, available) = (output.len(), buffer.remaining_mut());
+        if available < needed {
+            return Err(crate::per::enc::Error::BufferTooSmall { needed, available });
+        }
+
+        buffer.put_slice(output);
+        Ok(())
+    })
+}
+
+/// Attempts to encode `value` at the start of `buffer`, returning the number
+/// of bytes written.
+pub(crate) fn encode_into_slice<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+    mut buffer: &mut [u8],
+) -> Result<usize, crate::per::enc::Error> {
+    let available = buffer.len();
+    encode_to(options, value, &mut buffer)?;
+    Ok(available - buffer.len())
+}
+
+/// Attempts to encode `value`, writing the encoding to `writer`.
+#[cfg(feature = "std")]
+pub(crate) fn encode_to_writer<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+    mut writer: impl std::io::Write,
+) -> Result<(), crate::per::enc::Error> {
+    let mut enc = crate::per::enc::Encoder::new(options);
+
+    value.encode(&mut enc)?;
+
+    enc.write_output(|output| writer.write_all(output))
+        .map_err(|source| crate::per::enc::Error::Io { source })
+}
+
+/// Returns the length of `value`'s encoding, which PER can't compute
+/// without encoding the value.
+pub(crate) fn encoded_len<T: crate::Encode>(
+    options: enc::EncoderOptions,
+    value: &T,
+) -> Result<usize, crate::per::enc::Error> {
+    encode(options, value).map(|output| output.len())
+}
+
 /// Attempts to decode `T` from `input` using DER.
 pub(crate) fn decode_with_constraints<T: crate::Decode>(
     options: de::DecoderOptions,
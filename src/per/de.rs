@@ -1,6 +1,6 @@
 mod error;
 
-use alloc::{collections::VecDeque, vec::Vec};
+use alloc::{collections::VecDeque, format, string::String, vec::Vec};
 use bitvec::field::BitField;
 use snafu::*;
 
@@ -11,6 +11,7 @@ use crate::{
         self,
         constraints::{self, Extensible},
         fields::{Field, Fields},
+        integer::PrimitiveBytes,
         strings::StaticPermittedAlphabet,
         Constraints, Enumerated, Tag,
     },
@@ -24,7 +25,6 @@ type InputSlice<'input> = nom_bitvec::BSlice<'input, u8, bitvec::order::Msb0>;
 
 #[derive(Clone, Copy, Debug)]
 pub struct DecoderOptions {
-    #[allow(unused)]
     aligned: bool,
 }
 
@@ -33,6 +33,14 @@ impl DecoderOptions {
         Self { aligned: true }
     }
 
+    fn current_codec(self) -> crate::Codec {
+        if self.aligned {
+            crate::Codec::Aper
+        } else {
+            crate::Codec::Uper
+        }
+    }
+
     pub fn unaligned() -> Self {
         Self { aligned: false }
     }
@@ -322,28 +330,27 @@ impl<'input> Decoder<'input> {
         self.parse_integer(Constraints::new(&[constraints]))
     }
 
-    fn parse_non_negative_binary_integer(&mut self, range: i128) -> Result<types::Integer> {
+    fn parse_non_negative_binary_integer(&mut self, range: i128) -> Result<u128> {
+        use bitvec::field::BitField;
+
         let bits = super::log2(range);
         let (input, data) = nom::bytes::streaming::take(bits)(self.input)?;
         self.input = input;
-        let data = if data.len() < 8 {
-            let mut buffer = types::BitString::repeat(false, 8 - data.len());
-            buffer.extend_from_bitslice(&data);
-            buffer
-        } else {
-            data.to_bitvec()
-        };
 
-        Ok(num_bigint::BigUint::from_bytes_be(&to_vec(&data)).into())
+        if data.is_empty() {
+            Ok(0)
+        } else {
+            Ok(data.load_be::<u128>())
+        }
     }
 
-    fn parse_integer(&mut self, constraints: Constraints) -> Result<types::Integer> {
+    fn parse_integer<I: IntegerValue>(&mut self, constraints: Constraints) -> Result<I> {
         let extensible = self.parse_extensible_bit(&constraints)?;
         let value_constraint = constraints.value();
 
         let Some(value_constraint) = value_constraint.filter(|_| !extensible) else {
             let bytes = to_vec(&self.decode_octets()?);
-            return Ok(num_bigint::BigInt::from_signed_bytes_be(&bytes))
+            return I::from_signed_bytes_be(&bytes);
         };
 
         const K64: i128 = SIXTY_FOUR_K as i128;
@@ -351,14 +358,14 @@ impl<'input> Decoder<'input> {
 
         let number = if let Some(range) = value_constraint.constraint.range() {
             match (self.options.aligned, range) {
-                (_, 0) => return Ok(value_constraint.constraint.minimum().into()),
+                (_, 0) => return I::from_offset(value_constraint.constraint.minimum(), I::zero()),
                 (true, 256) => {
                     self.input = self.parse_padding(self.input)?;
-                    self.parse_non_negative_binary_integer(range)?
+                    I::from_u128(self.parse_non_negative_binary_integer(range)?)?
                 }
                 (true, 257..=K64) => {
                     self.input = self.parse_padding(self.input)?;
-                    self.parse_non_negative_binary_integer(K64)?
+                    I::from_u128(self.parse_non_negative_binary_integer(K64)?)?
                 }
                 (true, OVER_K64..) => {
                     let range_len_in_bytes = num_integer::div_ceil(super::log2(range), 8) as i128;
@@ -367,30 +374,28 @@ impl<'input> Decoder<'input> {
                         .try_into()
                         .map_err(Error::custom)?;
                     self.input = self.parse_padding(self.input)?;
-                    self.parse_non_negative_binary_integer(super::range_from_bits(
-                        (length + 1) * 8,
-                    ))?
+                    I::from_u128(self.parse_non_negative_binary_integer(
+                        super::range_from_bits((length + 1) * 8),
+                    )?)?
                 }
                 (false, OVER_K64..) => {
                     let bytes = to_vec(&self.decode_octets()?);
-                    value_constraint
-                        .constraint
-                        .as_start()
-                        .map(|_| num_bigint::BigUint::from_bytes_be(&bytes).into())
-                        .unwrap_or_else(|| num_bigint::BigInt::from_signed_bytes_be(&bytes))
+                    match value_constraint.constraint.as_start() {
+                        Some(_) => I::from_unsigned_bytes_be(&bytes)?,
+                        None => I::from_signed_bytes_be(&bytes)?,
+                    }
                 }
-                (_, _) => self.parse_non_negative_binary_integer(range)?,
+                (_, _) => I::from_u128(self.parse_non_negative_binary_integer(range)?)?,
             }
         } else {
             let bytes = to_vec(&self.decode_octets()?);
-            value_constraint
-                .constraint
-                .as_start()
-                .map(|_| num_bigint::BigUint::from_bytes_be(&bytes).into())
-                .unwrap_or_else(|| num_bigint::BigInt::from_signed_bytes_be(&bytes))
+            match value_constraint.constraint.as_start() {
+                Some(_) => I::from_unsigned_bytes_be(&bytes)?,
+                None => I::from_signed_bytes_be(&bytes)?,
+            }
         };
 
-        Ok(value_constraint.constraint.minimum() + number)
+        I::from_offset(value_constraint.constraint.minimum(), number)
     }
 
     fn parse_extension_header(&mut self) -> Result<bool> {
@@ -401,7 +406,7 @@ impl<'input> Decoder<'input> {
         }
 
         // The length bitfield has a lower bound of `1..`
-        let extensions_length = self.parse_normally_small_integer()? + 1;
+        let extensions_length = self.parse_normally_small_integer()? + types::Integer::from(1);
         let (input, bitfield) = nom::bytes::streaming::take(
             usize::try_from(extensions_length).map_err(Error::custom)?,
         )(self.input)?;
@@ -537,6 +542,10 @@ impl<'input> Decoder<'input> {
 impl<'input> crate::Decoder for Decoder<'input> {
     type Error = Error;
 
+    fn codec(&self) -> Option<crate::Codec> {
+        Some(self.options.current_codec())
+    }
+
     fn decode_any(&mut self) -> Result<types::Any> {
         let mut octet_string = types::BitString::default();
 
@@ -581,6 +590,18 @@ impl<'input> crate::Decoder for Decoder<'input> {
         self.parse_integer(constraints)
     }
 
+    fn decode_integer_i64(&mut self, _: Tag, constraints: Constraints) -> Result<i64> {
+        self.parse_integer::<i128>(constraints)?
+            .try_into()
+            .map_err(Error::custom)
+    }
+
+    fn decode_integer_u64(&mut self, _: Tag, constraints: Constraints) -> Result<u64> {
+        self.parse_integer::<i128>(constraints)?
+            .try_into()
+            .map_err(Error::custom)
+    }
+
     fn decode_octet_string(&mut self, _: Tag, constraints: Constraints) -> Result<Vec<u8>> {
         let mut octet_string = types::BitString::default();
 
@@ -647,14 +668,23 @@ impl<'input> crate::Decoder for Decoder<'input> {
 
     fn decode_teletex_string(
         &mut self,
-        _: Tag,
-        _constraints: Constraints,
+        tag: Tag,
+        _: Constraints,
     ) -> Result<types::TeletexString> {
-        todo!()
+        self.decode_octet_string(tag, <_>::default())
+            .map(types::TeletexString::from)
+    }
+
+    fn decode_bmp_string(&mut self, _: Tag, constraints: Constraints) -> Result<types::BmpString> {
+        self.parse_fixed_width_string(constraints)
     }
 
-    fn decode_bmp_string(&mut self, _: Tag, _constraints: Constraints) -> Result<types::BmpString> {
-        todo!()
+    fn decode_universal_string(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+    ) -> Result<types::UniversalString> {
+        self.parse_fixed_width_string(constraints)
     }
 
     fn decode_utf8_string(
@@ -757,6 +787,14 @@ impl<'input> crate::Decoder for Decoder<'input> {
         D::decode(self)
     }
 
+    fn decode_explicit_prefix_with_constraints<D: Decode>(
+        &mut self,
+        _: Tag,
+        constraints: Constraints,
+    ) -> Result<D> {
+        D::decode_with_constraints(self, constraints)
+    }
+
     fn decode_set<FIELDS, SET, D, F>(
         &mut self,
         _: Tag,
@@ -884,7 +922,8 @@ impl<'input> crate::Decoder for Decoder<'input> {
             } else {
                 let variance = variants.len();
                 let constraints =
-                    constraints::Value::new(constraints::Bounded::new(0, variance as i128)).into();
+                    constraints::Value::new(constraints::Bounded::new(0, variance as i128 - 1))
+                        .into();
                 self.parse_integer(Constraints::new(&[constraints]))?
             })
             .map_err(|error| {
@@ -956,6 +995,67 @@ impl<'input> crate::Decoder for Decoder<'input> {
     }
 }
 
+/// The types `INTEGER` values are parsed into, which lets primitive integers
+/// be decoded without going through [`types::Integer`].
+trait IntegerValue: Sized {
+    fn zero() -> Self;
+    fn from_u128(value: u128) -> Result<Self>;
+    fn from_signed_bytes_be(bytes: &[u8]) -> Result<Self>;
+    fn from_unsigned_bytes_be(bytes: &[u8]) -> Result<Self>;
+    /// Returns `minimum + offset`.
+    fn from_offset(minimum: i128, offset: Self) -> Result<Self>;
+}
+
+impl IntegerValue for types::Integer {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn from_u128(value: u128) -> Result<Self> {
+        Ok(value.into())
+    }
+
+    fn from_signed_bytes_be(bytes: &[u8]) -> Result<Self> {
+        Ok(Self::from_signed_bytes_be(bytes))
+    }
+
+    fn from_unsigned_bytes_be(bytes: &[u8]) -> Result<Self> {
+        Ok(Self::from_unsigned_bytes_be(bytes))
+    }
+
+    fn from_offset(minimum: i128, offset: Self) -> Result<Self> {
+        Ok(Self::from(minimum) + offset)
+    }
+}
+
+impl IntegerValue for i128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn from_u128(value: u128) -> Result<Self> {
+        value.try_into().map_err(Error::custom)
+    }
+
+    fn from_signed_bytes_be(bytes: &[u8]) -> Result<Self> {
+        PrimitiveBytes::parse_signed(bytes).ok_or_else(integer_overflow)
+    }
+
+    fn from_unsigned_bytes_be(bytes: &[u8]) -> Result<Self> {
+        PrimitiveBytes::parse_unsigned(bytes)
+            .and_then(|value| value.try_into().ok())
+            .ok_or_else(integer_overflow)
+    }
+
+    fn from_offset(minimum: i128, offset: Self) -> Result<Self> {
+        minimum.checked_add(offset).ok_or_else(integer_overflow)
+    }
+}
+
+fn integer_overflow() -> Error {
+    Error::custom("integer larger than 128 bits")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use alloc::string::ToString;
+
 use snafu::*;
 
 use crate::types::variants::Variants;
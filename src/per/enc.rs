@@ -1,6 +1,6 @@
 mod error;
 
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 
 use bitvec::prelude::*;
 use snafu::*;
@@ -12,6 +12,7 @@ use crate::{
         self,
         constraints::{self, Extensible},
         fields::FieldPresence,
+        integer::PrimitiveBytes,
         strings::{DynConstrainedCharacterString, StaticPermittedAlphabet},
         BitString, Constraints, Enumerated, Tag,
     },
@@ -36,6 +37,14 @@ impl EncoderOptions {
         }
     }
 
+    fn current_codec(self) -> crate::Codec {
+        if self.aligned {
+            crate::Codec::Aper
+        } else {
+            crate::Codec::Uper
+        }
+    }
+
     pub fn unaligned() -> Self {
         Self {
             aligned: false,
@@ -49,14 +58,15 @@ impl EncoderOptions {
     }
 }
 
+type FieldBitfield = alloc::collections::BTreeMap<Tag, (FieldPresence, bool)>;
+
 #[derive(Debug)]
 pub struct Encoder {
     options: EncoderOptions,
     output: BitString,
     set_output: alloc::collections::BTreeMap<Tag, BitString>,
-    field_bitfield: alloc::collections::BTreeMap<Tag, (FieldPresence, bool)>,
+    field_bitfield: FieldBitfield,
     extension_fields: Vec<Vec<u8>>,
-    is_extension_sequence: bool,
     parent_output_length: Option<usize>,
 }
 
@@ -67,7 +77,6 @@ impl Encoder {
             output: <_>::default(),
             set_output: <_>::default(),
             field_bitfield: <_>::default(),
-            is_extension_sequence: <_>::default(),
             extension_fields: <_>::default(),
             parent_output_length: <_>::default(),
         }
@@ -77,18 +86,8 @@ impl Encoder {
         let mut options = self.options;
         options.set_encoding = true;
         let mut encoder = Self::new(options);
-        encoder.field_bitfield = dbg!(C::FIELDS
-            .canonised()
-            .iter()
-            .map(|field| (field.tag_tree.smallest_tag(), (field.presence, false)))
-            .collect());
-        encoder.parent_output_length = Some(self.output_length());
-        encoder
-    }
-
-    fn new_sequence_encoder<C: crate::types::Constructed>(&self) -> Self {
-        let mut encoder = Self::new(self.options.without_set_encoding());
         encoder.field_bitfield = C::FIELDS
+            .canonised()
             .iter()
             .map(|field| (field.tag_tree.smallest_tag(), (field.presence, false)))
             .collect();
@@ -102,6 +101,14 @@ impl Encoder {
         super::to_vec(&output)
     }
 
+    /// Consumes the encoder and passes the octets of the encoding to
+    /// `write`, without copying them into a new buffer.
+    pub(crate) fn write_output<R>(self, write: impl FnOnce(&[u8]) -> R) -> R {
+        let mut output = self.bitstring_output();
+        Self::force_pad_to_alignment(&mut output);
+        (write)(output.as_raw_slice())
+    }
+
     pub fn bitstring_output(self) -> BitString {
         self.options
             .set_encoding
@@ -114,14 +121,10 @@ impl Encoder {
         Ok(())
     }
 
+    /// The number of bits preceding the next bit written, which is what
+    /// APER aligns to.
     fn output_length(&self) -> usize {
         let mut output_length = self.output.len();
-        output_length += self.is_extension_sequence as usize;
-        output_length += self
-            .field_bitfield
-            .values()
-            .filter(|(presence, _)| presence.is_optional_or_default())
-            .count();
         output_length += self.parent_output_length.unwrap_or_default();
 
         if self.options.set_encoding {
@@ -135,14 +138,12 @@ impl Encoder {
         output_length
     }
 
-    fn pad_to_alignment(&self, buffer: &mut BitString) {
+    fn pad_to_alignment(&mut self) {
         if self.options.aligned {
-            let mut output_length = self.output_length();
-            output_length += buffer.len();
+            let output_length = self.output_length();
             if output_length % 8 != 0 {
-                for _ in 0..(8 - output_length % 8) {
-                    buffer.push(false);
-                }
+                let padding = 8 - output_length % 8;
+                self.output.resize(self.output.len() + padding, false);
             }
         }
     }
@@ -150,26 +151,59 @@ impl Encoder {
     fn force_pad_to_alignment(buffer: &mut BitString) {
         const BYTE_WIDTH: usize = 8;
         if buffer.len() % BYTE_WIDTH != 0 {
-            let mut string = BitString::new();
-            for _ in 0..BYTE_WIDTH - (buffer.len() % 8) {
-                string.push(false);
-            }
-            buffer.extend(string);
+            let padding = BYTE_WIDTH - buffer.len() % BYTE_WIDTH;
+            buffer.resize(buffer.len() + padding, false);
             debug_assert_eq!(0, buffer.len() % 8);
         }
     }
 
+    /// Encodes the value of a field with `encode_fn`. Values are written
+    /// directly to the output, except for the fields of a SET, which are
+    /// written to their own buffer so that they can be put in canonical
+    /// order once the whole SET has been encoded.
+    fn encode_field(
+        &mut self,
+        tag: Tag,
+        encode_fn: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        if !self.options.set_encoding {
+            return (encode_fn)(self);
+        }
+
+        let mut encoder = Self::new(self.options.without_set_encoding());
+        encoder.parent_output_length = Some(self.output_length());
+        (encode_fn)(&mut encoder)?;
+        self.set_output.insert(tag, encoder.output);
+        Ok(())
+    }
+
+    /// Runs `encode_fn` with its own set of fields, so that values nested in
+    /// the current one don't mark the current value's fields as present,
+    /// returning the nested fields and extension additions.
+    fn encode_nested(
+        &mut self,
+        field_bitfield: FieldBitfield,
+        encode_fn: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<(FieldBitfield, Vec<Vec<u8>>)> {
+        let parent_field_bitfield = core::mem::replace(&mut self.field_bitfield, field_bitfield);
+        let parent_extension_fields = core::mem::take(&mut self.extension_fields);
+        let result = (encode_fn)(self);
+        let field_bitfield = core::mem::replace(&mut self.field_bitfield, parent_field_bitfield);
+        let extension_fields =
+            core::mem::replace(&mut self.extension_fields, parent_extension_fields);
+        result.map(|_| (field_bitfield, extension_fields))
+    }
+
     fn encode_extensible_bit(
         &mut self,
         constraints: &Constraints,
-        buffer: &mut BitString,
         extensible_condition: impl FnOnce() -> bool,
     ) -> bool {
         constraints
             .extensible()
             .then(|| {
                 let is_in_constraints = !(extensible_condition)();
-                buffer.push(is_in_constraints);
+                self.output.push(is_in_constraints);
                 is_in_constraints
             })
             .unwrap_or_default()
@@ -182,79 +216,85 @@ impl Encoder {
         value: &S,
     ) -> Result<()> {
         use crate::types::constraints::Bounded;
-        let mut buffer = BitString::default();
 
-        let is_large_string = if let Some(size) = constraints.size() {
-            let width = match constraints.permitted_alphabet() {
-                Some(alphabet) => {
-                    self.character_width(super::log2(alphabet.constraint.len() as i128))
+        self.encode_field(tag, |encoder| {
+            let is_large_string = if let Some(size) = constraints.size() {
+                let width = match constraints.permitted_alphabet() {
+                    Some(alphabet) => {
+                        encoder.character_width(super::log2(alphabet.constraint.len() as i128))
+                    }
+                    None => encoder.character_width(S::CHARACTER_WIDTH),
+                };
+
+                match *size.constraint {
+                    Bounded::Range {
+                        start: Some(_),
+                        end: Some(_),
+                    } if size.constraint.range().unwrap() * width as usize > 16 => true,
+                    Bounded::Single(max) if max * width as usize > 16 => {
+                        encoder.pad_to_alignment();
+                        true
+                    }
+                    Bounded::Range {
+                        start: None,
+                        end: Some(max),
+                    } if max * width as usize > 16 => {
+                        encoder.pad_to_alignment();
+                        true
+                    }
+                    _ => false,
                 }
-                None => self.character_width(S::CHARACTER_WIDTH),
+            } else {
+                false
             };
 
-            match *size.constraint {
-                Bounded::Range {
-                    start: Some(_),
-                    end: Some(_),
-                } if size.constraint.range().unwrap() * width as usize > 16 => true,
-                Bounded::Single(max) if max * width as usize > 16 => {
-                    self.pad_to_alignment(&mut buffer);
-                    true
+            match constraints.permitted_alphabet() {
+                Some(alphabet)
+                    if S::CHARACTER_WIDTH
+                        > encoder
+                            .character_width(super::log2(alphabet.constraint.len() as i128)) =>
+                {
+                    let alphabet = &alphabet.constraint;
+                    let characters =
+                        &DynConstrainedCharacterString::from_bits(value.chars(), alphabet)
+                            .map_err(Error::custom)?;
+
+                    encoder.encode_length(value.len(), constraints.size(), |encoder, range| {
+                        encoder.output.extend_from_bitslice(&characters[range]);
+                        Ok(())
+                    })
                 }
-                Bounded::Range {
-                    start: None,
-                    end: Some(max),
-                } if max * width as usize > 16 => {
-                    self.pad_to_alignment(&mut buffer);
-                    true
+                _ => {
+                    let char_length = value.len();
+                    let octet_aligned_width = value.octet_aligned_char_width() as usize;
+                    let octet_aligned_value = encoder.options.aligned.then(|| {
+                        if S::CHARACTER_WIDTH <= encoder.character_width(S::CHARACTER_WIDTH) {
+                            value.to_octet_aligned_string()
+                        } else {
+                            value.to_octet_aligned_index_string()
+                        }
+                    });
+                    let value = value.to_index_string();
+                    encoder.encode_string_length(
+                        is_large_string,
+                        char_length,
+                        constraints.size(),
+                        |encoder, range| {
+                            match &octet_aligned_value {
+                                Some(value) => encoder.output.extend_from_bitslice(
+                                    &value.view_bits::<Msb0>()[range.start * octet_aligned_width
+                                        ..range.end * octet_aligned_width],
+                                ),
+                                None => encoder.output.extend_from_bitslice(
+                                    &value[S::char_range_to_bit_range(range)],
+                                ),
+                            }
+                            Ok(())
+                        },
+                    )
                 }
-                _ => false,
-            }
-        } else {
-            false
-        };
-
-        match constraints.permitted_alphabet() {
-            Some(alphabet)
-                if S::CHARACTER_WIDTH
-                    > self.character_width(super::log2(alphabet.constraint.len() as i128)) =>
-            {
-                let alphabet = &alphabet.constraint;
-                let characters = &DynConstrainedCharacterString::from_bits(value.chars(), alphabet)
-                    .map_err(Error::custom)?;
-
-                self.encode_length(&mut buffer, value.len(), constraints.size(), |range| {
-                    Ok(characters[range].to_bitvec())
-                })?;
-            }
-            _ => {
-                let char_length = value.len();
-                let octet_aligned_value = self.options.aligned.then(|| {
-                    if S::CHARACTER_WIDTH <= self.character_width(S::CHARACTER_WIDTH) {
-                        value.to_octet_aligned_string()
-                    } else {
-                        value.to_octet_aligned_index_string()
-                    }
-                });
-                let value = value.to_index_string();
-                let octet_aligned_value = &octet_aligned_value;
-                self.encode_string_length(
-                    &mut buffer,
-                    is_large_string,
-                    char_length,
-                    constraints.size(),
-                    |range| {
-                        Ok(match octet_aligned_value {
-                            Some(value) => types::BitString::from_slice(&value[range]),
-                            None => value[S::char_range_to_bit_range(range)].to_bitvec(),
-                        })
-                    },
-                )?;
             }
-        };
-
-        self.extend(tag, &buffer);
-        Ok(())
+        })
     }
 
     fn character_width(&self, width: u32) -> u32 {
@@ -273,82 +313,69 @@ impl Encoder {
         !extension_fields.iter().all(|vec| vec.is_empty())
     }
 
+    /// Encodes a SEQUENCE or SET in place. Its preamble, the extension bit
+    /// and the presence bits of the optional fields, is reserved up front
+    /// and filled in once `encode_fields` has encoded the fields.
     fn encode_constructed<C: crate::types::Constructed>(
         &mut self,
-        tag: Tag,
-        mut encoder: Self,
+        encode_fields: impl FnOnce(&mut Self) -> Result<(FieldBitfield, Vec<Vec<u8>>)>,
     ) -> Result<()> {
-        self.set_bit(tag, true)?;
-        let mut buffer = BitString::default();
+        let preamble_start = self.output.len();
+        let preamble_length = C::EXTENDED_FIELDS.is_some() as usize
+            + C::FIELDS
+                .iter()
+                .filter(|field| field.presence.is_optional_or_default())
+                .count();
+        self.output.resize(preamble_start + preamble_length, false);
 
-        if C::EXTENDED_FIELDS.is_some() {
-            buffer.push(Self::encoded_extension_addition(&encoder.extension_fields));
-        }
+        let (field_bitfield, extension_fields) = (encode_fields)(self)?;
 
-        for bit in encoder
-            .field_bitfield
+        let extension_bit = C::EXTENDED_FIELDS
+            .is_some()
+            .then(|| Self::encoded_extension_addition(&extension_fields));
+        let presence_bits = field_bitfield
             .values()
             .filter_map(|(presence, is_present)| {
-                presence.is_optional_or_default().then_some(is_present)
-            })
-            .copied()
-        {
-            buffer.push(bit);
-        }
+                presence.is_optional_or_default().then_some(*is_present)
+            });
 
-        let extension_fields = core::mem::take(&mut encoder.extension_fields);
+        for (index, bit) in extension_bit.into_iter().chain(presence_bits).enumerate() {
+            self.output.set(preamble_start + index, bit);
+        }
 
-        if encoder.field_bitfield.values().any(|(_, b)| *b) {
-            buffer.extend(encoder.bitstring_output());
+        if !field_bitfield.values().any(|(_, b)| *b) {
+            self.output.truncate(preamble_start + preamble_length);
         }
 
         if !Self::encoded_extension_addition(&extension_fields) {
-            self.extend(tag, &buffer);
             return Ok(());
         }
 
-        let bitfield_length = extension_fields.len();
-        let mut extension_buffer = {
-            let mut buffer = BitString::new();
-            self.encode_normally_small_length(bitfield_length, &mut buffer)?;
-            buffer
-        };
+        self.encode_normally_small_length(extension_fields.len())?;
 
         for field in &extension_fields {
-            extension_buffer.push(!field.is_empty());
+            self.output.push(!field.is_empty());
         }
 
-        for field in extension_fields
-            .into_iter()
-            .filter(|field| !field.is_empty())
-        {
-            self.encode_length(
-                &mut extension_buffer,
-                field.len(),
-                <_>::default(),
-                |range| Ok(BitString::from_slice(&field[range])),
-            )?;
+        for field in extension_fields.iter().filter(|field| !field.is_empty()) {
+            self.encode_length(field.len(), <_>::default(), |encoder, range| {
+                encoder.extend(&field[range]);
+                Ok(())
+            })?;
         }
 
-        buffer.extend_from_bitslice(&extension_buffer);
-        self.extend(tag, &buffer);
-
         Ok(())
     }
 
-    fn encode_normally_small_length(&mut self, value: usize, buffer: &mut BitString) -> Result<()> {
+    fn encode_normally_small_length(&mut self, value: usize) -> Result<()> {
         debug_assert!(value >= 1);
         let value = if value >= 64 { value } else { value - 1 };
-        self.encode_normally_small_integer(value, buffer)
+        self.encode_normally_small_integer(value)
     }
 
-    fn encode_normally_small_integer(
-        &mut self,
-        value: usize,
-        buffer: &mut BitString,
-    ) -> Result<()> {
+    fn encode_normally_small_integer(&mut self, value: usize) -> Result<()> {
         let is_large = value >= 64;
-        buffer.push(is_large);
+        self.output.push(is_large);
 
         let size_constraints = if is_large {
             constraints::Value::new(constraints::Bounded::start_from(0)).into()
@@ -356,31 +383,26 @@ impl Encoder {
             constraints::Value::new(constraints::Bounded::new(0, 63)).into()
         };
 
-        self.encode_integer_into_buffer(
-            Constraints::new(&[size_constraints]),
-            &value.into(),
-            buffer,
-        )
+        self.encode_integer_value(Constraints::new(&[size_constraints]), &value.into())
     }
 
     fn encode_string_length(
-        &self,
-        buffer: &mut BitString,
+        &mut self,
         is_large_string: bool,
         length: usize,
         constraints: Option<&Extensible<constraints::Size>>,
-        encode_fn: impl Fn(core::ops::Range<usize>) -> Result<BitString>,
+        mut encode_fn: impl FnMut(&mut Self, core::ops::Range<usize>) -> Result<()>,
     ) -> Result<()> {
         let Some(constraints) = constraints else {
-            return self.encode_unconstrained_length(buffer, length, None, encode_fn);
+            return self.encode_unconstrained_length(length, None, encode_fn);
         };
 
         if matches!(constraints.extensible, None) {
             Error::check_length(length, &constraints.constraint)?;
         } else if constraints.constraint.contains(&length) {
-            buffer.push(false);
+            self.output.push(false);
         } else {
-            buffer.push(true);
+            self.output.push(true);
         }
 
         let constraints = constraints.constraint;
@@ -392,8 +414,7 @@ impl Encoder {
                 if range == 0 {
                     Ok(())
                 } else if range == 1 {
-                    buffer.extend((encode_fn)(0..length)?);
-                    Ok(())
+                    (encode_fn)(self, 0..length)
                 } else if range < SIXTY_FOUR_K as usize {
                     let effective_length = constraints.effective_value(length).into_inner();
                     let range = (self.options.aligned && range > 256)
@@ -408,42 +429,39 @@ impl Encoder {
                         })
                         .unwrap_or(range as i128);
                     self.encode_non_negative_binary_integer(
-                        buffer,
                         range,
                         &(effective_length as u32).to_be_bytes(),
                     );
 
                     if is_large_string {
-                        self.pad_to_alignment(buffer);
+                        self.pad_to_alignment();
                     }
 
-                    buffer.extend((encode_fn)(0..length)?);
-                    Ok(())
+                    (encode_fn)(self, 0..length)
                 } else {
-                    self.encode_unconstrained_length(buffer, length, None, encode_fn)
+                    self.encode_unconstrained_length(length, None, encode_fn)
                 }
             }
-            _ => self.encode_unconstrained_length(buffer, length, None, encode_fn),
+            _ => self.encode_unconstrained_length(length, None, encode_fn),
         }
     }
 
     fn encode_length(
-        &self,
-        buffer: &mut BitString,
+        &mut self,
         length: usize,
         constraints: Option<&Extensible<constraints::Size>>,
-        encode_fn: impl Fn(core::ops::Range<usize>) -> Result<BitString>,
+        mut encode_fn: impl FnMut(&mut Self, core::ops::Range<usize>) -> Result<()>,
     ) -> Result<()> {
         let Some(constraints) = constraints else {
-            return self.encode_unconstrained_length(buffer, length, None, encode_fn);
+            return self.encode_unconstrained_length(length, None, encode_fn);
         };
 
         if matches!(constraints.extensible, None) {
             Error::check_length(length, &constraints.constraint)?;
         } else if constraints.constraint.contains(&length) {
-            buffer.push(false);
+            self.output.push(false);
         } else {
-            buffer.push(true);
+            self.output.push(true);
         }
 
         let constraints = constraints.constraint;
@@ -455,8 +473,7 @@ impl Encoder {
                 if range == 0 {
                     Ok(())
                 } else if range == 1 {
-                    buffer.extend((encode_fn)(0..length)?);
-                    Ok(())
+                    (encode_fn)(self, 0..length)
                 } else if range < SIXTY_FOUR_K as usize {
                     let effective_length = constraints.effective_value(length).into_inner();
                     let range = (self.options.aligned && range > 256)
@@ -471,38 +488,34 @@ impl Encoder {
                         })
                         .unwrap_or(range as i128);
                     self.encode_non_negative_binary_integer(
-                        buffer,
                         range,
                         &(effective_length as u32).to_be_bytes(),
                     );
-                    // self.pad_to_alignment(buffer);
-                    buffer.extend((encode_fn)(0..length)?);
-                    Ok(())
+                    (encode_fn)(self, 0..length)
                 } else {
-                    self.encode_unconstrained_length(buffer, length, None, encode_fn)
+                    self.encode_unconstrained_length(length, None, encode_fn)
                 }
             }
-            _ => self.encode_unconstrained_length(buffer, length, None, encode_fn),
+            _ => self.encode_unconstrained_length(length, None, encode_fn),
         }
     }
 
     fn encode_unconstrained_length(
-        &self,
-        buffer: &mut BitString,
+        &mut self,
         mut length: usize,
         min: Option<usize>,
-        encode_fn: impl Fn(core::ops::Range<usize>) -> Result<BitString>,
+        mut encode_fn: impl FnMut(&mut Self, core::ops::Range<usize>) -> Result<()>,
     ) -> Result<()> {
         let mut min = min.unwrap_or_default();
 
-        self.pad_to_alignment(&mut *buffer);
+        self.pad_to_alignment();
         if length <= 127 {
-            buffer.extend((length as u8).to_be_bytes());
-            buffer.extend((encode_fn)(0..length)?);
+            self.extend(length as u8);
+            (encode_fn)(self, min..min + length)?;
         } else if length < SIXTEEN_K.into() {
             const SIXTEENTH_BIT: u16 = 0x8000;
-            buffer.extend((SIXTEENTH_BIT | length as u16).to_be_bytes());
-            buffer.extend((encode_fn)(0..length)?);
+            self.extend(&(SIXTEENTH_BIT | length as u16).to_be_bytes()[..]);
+            (encode_fn)(self, min..min + length)?;
         } else {
             loop {
                 // Hack to get around no exclusive syntax.
@@ -519,24 +532,19 @@ impl Encoder {
                     K32..=K48_MAX => (2, K32),
                     K16..=K32_MAX => (1, K16),
                     _ => {
-                        break self.encode_unconstrained_length(
-                            buffer,
-                            length,
-                            Some(min),
-                            encode_fn,
-                        )?;
+                        break self.encode_unconstrained_length(length, Some(min), encode_fn)?;
                     }
                 };
 
                 const FRAGMENT_MARKER: u8 = 0xC0;
-                buffer.extend(&[FRAGMENT_MARKER | fragment_index]);
+                self.extend(FRAGMENT_MARKER | fragment_index);
 
-                buffer.extend((encode_fn)(min..min + amount)?);
+                (encode_fn)(self, min..min + amount)?;
                 min += amount;
 
                 if length == SIXTEEN_K as usize {
                     // Add final fragment in the frame.
-                    buffer.extend(&[0]);
+                    self.extend(0);
                     break;
                 } else {
                     length = length.saturating_sub(amount);
@@ -547,52 +555,35 @@ impl Encoder {
         Ok(())
     }
 
-    fn extend<'input>(&mut self, tag: Tag, input: impl Into<Input<'input>>) {
-        use bitvec::field::BitField;
-        let mut set_buffer = <_>::default();
-        let buffer = if self.options.set_encoding {
-            &mut set_buffer
-        } else {
-            &mut self.output
-        };
-
+    fn extend<'input>(&mut self, input: impl Into<Input<'input>>) {
         match input.into() {
             Input::Bits(bits) => {
-                buffer.extend_from_bitslice(bits);
+                self.output.extend_from_bitslice(bits);
             }
             Input::Bit(bit) => {
-                buffer.push(bit);
+                self.output.push(bit);
             }
             Input::Byte(byte) => {
-                buffer.store_be(byte);
+                self.output.extend_from_bitslice(byte.view_bits::<Msb0>());
             }
             Input::Bytes(bytes) => {
-                buffer.extend(bytes);
+                self.output.extend_from_bitslice(bytes.view_bits::<Msb0>());
             }
         }
-
-        if self.options.set_encoding {
-            self.set_output.insert(tag, set_buffer);
-        }
     }
 
-    fn encode_octet_string_into_buffer(
-        &mut self,
-        constraints: Constraints,
-        value: &[u8],
-        buffer: &mut BitString,
-    ) -> Result<()> {
-        let extensible_is_present = self.encode_extensible_bit(&constraints, buffer, || todo!());
+    fn encode_octet_string_value(&mut self, constraints: Constraints, value: &[u8]) -> Result<()> {
+        let encode_fn = |encoder: &mut Self, range| {
+            encoder.extend(&value[range]);
+            Ok(())
+        };
+        let extensible_is_present = self.encode_extensible_bit(&constraints, || todo!());
         let Some(constraints) = constraints.size() else {
-            return self.encode_length(buffer, value.len(), <_>::default(), |range| {
-                Ok(BitString::from_slice(&value[range]))
-            });
+            return self.encode_length(value.len(), <_>::default(), encode_fn);
         };
 
         if extensible_is_present {
-            self.encode_length(buffer, value.len(), <_>::default(), |range| {
-                Ok(BitString::from_slice(&value[range]))
-            })?;
+            self.encode_length(value.len(), <_>::default(), encode_fn)?;
         } else if 0
             == constraints
                 .constraint
@@ -601,42 +592,66 @@ impl Encoder {
         {
             // NO-OP
         } else {
-            self.encode_length(buffer, value.len(), Some(constraints), |range| {
-                Ok(BitString::from_slice(&value[range]))
-            })?;
+            self.encode_length(value.len(), Some(constraints), encode_fn)?;
         }
 
         Ok(())
     }
 
-    fn encode_integer_into_buffer(
+    fn encode_integer_value(
         &mut self,
         constraints: Constraints,
-        value: &num_bigint::BigInt,
-        buffer: &mut BitString,
+        value: &types::Integer,
     ) -> Result<()> {
-        self.encode_extensible_bit(&constraints, buffer, || {
+        if let Some(value) = value.as_i128() {
+            return self.encode_primitive_integer_value(constraints, value);
+        }
+
+        self.encode_extensible_bit(&constraints, || {
             constraints.value().map_or(false, |value_range| {
                 value_range.extensible.is_some() && value_range.constraint.bigint_contains(value)
             })
         });
+        // Values outside of the range of `i128` can't be in a value constraint.
+        if constraints.value().is_some() {
+            return i128::try_from(value).map(drop).map_err(Error::custom);
+        }
+
+        let bytes = value.to_signed_bytes_be();
+        self.encode_length(bytes.len(), constraints.size(), |encoder, range| {
+            encoder.extend(&bytes[range]);
+            Ok(())
+        })
+    }
+
+    /// Encodes an `INTEGER` value without going through [`types::Integer`].
+    fn encode_primitive_integer_value(
+        &mut self,
+        constraints: Constraints,
+        value: i128,
+    ) -> Result<()> {
+        self.encode_extensible_bit(&constraints, || {
+            constraints.value().map_or(false, |value_range| {
+                value_range.extensible.is_some() && value_range.constraint.contains(&value)
+            })
+        });
         let Some(value_range) = constraints.value() else {
-            let bytes = value.to_signed_bytes_be();
-            self.encode_length(buffer, bytes.len(), constraints.size(), |range| {
-                Ok(BitString::from_slice(&bytes[range]))
+            let bytes = PrimitiveBytes::signed(value);
+            self.encode_length(bytes.len(), constraints.size(), |encoder, range| {
+                encoder.extend(&bytes[range]);
+                Ok(())
             })?;
-            return Ok(())
+            return Ok(());
         };
 
-        let bytes = match value_range.constraint.effective_bigint_value(value.clone()) {
-            either::Left(offset) => offset.to_biguint().unwrap().to_bytes_be(),
-            either::Right(value) => value.to_signed_bytes_be(),
+        let effective_value = value_range.constraint.effective_value(value);
+        let bytes = match effective_value {
+            either::Left(offset) => {
+                PrimitiveBytes::unsigned(u128::try_from(offset).map_err(Error::custom)?)
+            }
+            either::Right(value) => PrimitiveBytes::signed(value),
         };
-
-        let effective_value: i128 = value_range
-            .constraint
-            .effective_value(value.try_into().map_err(Error::custom)?)
-            .either_into();
+        let effective_value: i128 = effective_value.either_into();
 
         const K64: i128 = SIXTY_FOUR_K as i128;
         const OVER_K64: i128 = K64 + 1;
@@ -644,80 +659,70 @@ impl Encoder {
         if let Some(range) = value_range.constraint.range() {
             match (self.options.aligned, range) {
                 (true, 256) => {
-                    self.pad_to_alignment(buffer);
-                    self.encode_non_negative_binary_integer(buffer, range, &bytes)
+                    self.pad_to_alignment();
+                    self.encode_non_negative_binary_integer(range, &bytes)
                 }
                 (true, 257..=K64) => {
-                    self.pad_to_alignment(buffer);
-                    self.encode_non_negative_binary_integer(buffer, K64, &bytes);
+                    self.pad_to_alignment();
+                    self.encode_non_negative_binary_integer(K64, &bytes);
                 }
                 (true, OVER_K64..) => {
                     let range_len_in_bytes = num_integer::div_ceil(super::log2(range), 8) as i128;
 
                     if effective_value == 0 {
-                        self.encode_non_negative_binary_integer(
-                            &mut *buffer,
-                            range_len_in_bytes,
-                            &[0],
-                        );
-                        self.pad_to_alignment(&mut *buffer);
-                        self.encode_non_negative_binary_integer(&mut *buffer, 255, &bytes);
+                        self.encode_non_negative_binary_integer(range_len_in_bytes, &[0]);
+                        self.pad_to_alignment();
+                        self.encode_non_negative_binary_integer(255, &bytes);
                     } else {
                         let range_value_in_bytes =
                             num_integer::div_ceil(super::log2(effective_value + 1), 8) as i128;
                         self.encode_non_negative_binary_integer(
-                            buffer,
                             range_len_in_bytes,
                             &(range_value_in_bytes - 1).to_be_bytes(),
                         );
-                        self.pad_to_alignment(&mut *buffer);
+                        self.pad_to_alignment();
                         self.encode_non_negative_binary_integer(
-                            &mut *buffer,
                             super::range_from_bits(range_value_in_bytes as u32 * 8),
                             &bytes,
                         );
                     }
                 }
                 (false, OVER_K64..) => {
-                    self.encode_length(buffer, bytes.len(), <_>::default(), |range| {
-                        Ok(BitString::from_slice(&bytes[range]))
+                    self.encode_length(bytes.len(), <_>::default(), |encoder, range| {
+                        encoder.extend(&bytes[range]);
+                        Ok(())
                     })?;
                 }
-                (_, _) => self.encode_non_negative_binary_integer(buffer, range, &bytes),
+                (_, _) => self.encode_non_negative_binary_integer(range, &bytes),
             }
         } else {
-            self.encode_length(buffer, bytes.len(), <_>::default(), |range| {
-                Ok(BitString::from_slice(&bytes[range]))
+            self.encode_length(bytes.len(), <_>::default(), |encoder, range| {
+                encoder.extend(&bytes[range]);
+                Ok(())
             })?;
         }
 
         Ok(())
     }
 
-    fn encode_non_negative_binary_integer(
-        &self,
-        buffer: &mut BitString,
-        range: i128,
-        bytes: &[u8],
-    ) {
+    /// Writes the lowest bits of the big endian `bytes` needed to represent
+    /// every value in `range`.
+    fn encode_non_negative_binary_integer(&mut self, range: i128, bytes: &[u8]) {
         use core::cmp::Ordering;
         let total_bits = super::log2(range) as usize;
-        let bits = BitVec::<u8, Msb0>::from_slice(bytes);
-        let bits = match total_bits.cmp(&bits.len()) {
+        let bits = bytes.view_bits::<Msb0>();
+
+        match total_bits.cmp(&bits.len()) {
             Ordering::Greater => {
-                let mut padding = types::BitString::repeat(false, total_bits - bits.len());
-                padding.extend(bits);
-                padding
+                let padding = total_bits - bits.len();
+                self.output.resize(self.output.len() + padding, false);
+                self.output.extend_from_bitslice(bits);
             }
-            Ordering::Less => bits[bits.len() - total_bits..].to_owned(),
-            Ordering::Equal => bits,
-        };
-
-        // if !self.options.aligned && range >= super::TWO_FIFTY_SIX.into() {
-        //     self.pad_to_alignment(buffer);
-        // }
-
-        buffer.extend(bits);
+            Ordering::Less => self
+                .output
+                .extend_from_bitslice(&bits[bits.len() - total_bits..]),
+            Ordering::Equal => self.output.extend_from_bitslice(bits),
+        }
     }
 }
 
@@ -725,6 +730,10 @@ impl crate::Encoder for Encoder {
     type Ok = ();
     type Error = Error;
 
+    fn codec(&self) -> Option<crate::Codec> {
+        Some(self.options.current_codec())
+    }
+
     fn encode_any(&mut self, tag: Tag, value: &types::Any) -> Result<Self::Ok, Self::Error> {
         self.set_bit(tag, true)?;
         self.encode_octet_string(tag, <_>::default(), &value.contents)
@@ -737,33 +746,34 @@ impl crate::Encoder for Encoder {
         value: &BitString,
     ) -> Result<Self::Ok, Self::Error> {
         self.set_bit(tag, true)?;
-        let mut buffer = BitString::default();
-        let extensible_is_present =
-            self.encode_extensible_bit(&constraints, &mut buffer, || todo!());
-        let size = constraints.size();
-
-        if extensible_is_present || size.is_none() {
-            self.encode_length(&mut buffer, value.len(), <_>::default(), |range| {
-                Ok(BitString::from(&value[range]))
-            })?;
-        } else if size.map(|size| size.constraint.effective_value(value.len()).into_inner())
-            == Some(0)
-        {
-            // NO-OP
-        } else {
-            self.encode_length(&mut buffer, value.len(), constraints.size(), |range| {
-                Ok(BitString::from(&value[range]))
-            })?;
-        }
+        self.encode_field(tag, |encoder| {
+            let encode_fn = |encoder: &mut Self, range| {
+                encoder.output.extend_from_bitslice(&value[range]);
+                Ok(())
+            };
+            let extensible_is_present = encoder.encode_extensible_bit(&constraints, || todo!());
+            let size = constraints.size();
 
-        self.extend(tag, &buffer);
-        Ok(())
+            if extensible_is_present || size.is_none() {
+                encoder.encode_length(value.len(), <_>::default(), encode_fn)?;
+            } else if size.map(|size| size.constraint.effective_value(value.len()).into_inner())
+                == Some(0)
+            {
+                // NO-OP
+            } else {
+                encoder.encode_length(value.len(), constraints.size(), encode_fn)?;
+            }
+
+            Ok(())
+        })
     }
 
     fn encode_bool(&mut self, tag: Tag, value: bool) -> Result<Self::Ok, Self::Error> {
         self.set_bit(tag, true)?;
-        self.extend(tag, value);
-        Ok(())
+        self.encode_field(tag, |encoder| {
+            encoder.extend(value);
+            Ok(())
+        })
     }
 
     fn encode_enumerated<E: Enumerated>(
@@ -771,50 +781,79 @@ impl crate::Encoder for Encoder {
         tag: Tag,
         value: &E,
     ) -> Result<Self::Ok, Self::Error> {
-        self.set_bit(tag, true)?;
-        let mut buffer = BitString::default();
-        let index = value.enumeration_index();
-        if E::EXTENDED_VARIANTS.is_some() {
-            buffer.push(value.is_extended_variant());
+        // PER encodes the index of the variant, which unknown discriminants
+        // don't have.
+        if let Some(discriminant) = value.unknown_discriminant() {
+            return Err(Error::custom(format!(
+                "unknown discriminant {} can't be encoded with PER",
+                discriminant
+            )));
         }
 
-        if value.is_extended_variant() {
-            self.encode_normally_small_integer(index, &mut buffer)?;
-        } else if std::mem::size_of::<usize>() == 4 {
-            self.encode_non_negative_binary_integer(
-                &mut buffer,
-                E::variance() as i128,
-                &u32::try_from(index).unwrap().to_be_bytes(),
-            );
-        } else if std::mem::size_of::<usize>() == 2 {
-            self.encode_non_negative_binary_integer(
-                &mut buffer,
-                E::variance() as i128,
-                &u16::try_from(index).unwrap().to_be_bytes(),
-            );
-        } else {
-            self.encode_non_negative_binary_integer(
-                &mut buffer,
-                E::variance() as i128,
-                &usize::to_be_bytes(index)[..],
-            );
-        }
+        self.set_bit(tag, true)?;
+        self.encode_field(tag, |encoder| {
+            let index = value.enumeration_index();
+            if E::EXTENDED_VARIANTS.is_some() {
+                encoder.output.push(value.is_extended_variant());
+            }
 
-        self.extend(tag, &buffer);
-        Ok(())
+            if value.is_extended_variant() {
+                encoder.encode_normally_small_integer(index)?;
+            } else if core::mem::size_of::<usize>() == 4 {
+                encoder.encode_non_negative_binary_integer(
+                    E::variance() as i128,
+                    &u32::try_from(index).unwrap().to_be_bytes(),
+                );
+            } else if core::mem::size_of::<usize>() == 2 {
+                encoder.encode_non_negative_binary_integer(
+                    E::variance() as i128,
+                    &u16::try_from(index).unwrap().to_be_bytes(),
+                );
+            } else {
+                encoder.encode_non_negative_binary_integer(
+                    E::variance() as i128,
+                    &usize::to_be_bytes(index)[..],
+                );
+            }
+
+            Ok(())
+        })
     }
 
     fn encode_integer(
         &mut self,
         tag: Tag,
         constraints: Constraints,
-        value: &num_bigint::BigInt,
+        value: &types::Integer,
     ) -> Result<Self::Ok, Self::Error> {
         self.set_bit(tag, true)?;
-        let mut buffer = BitString::new();
-        self.encode_integer_into_buffer(constraints, value, &mut buffer)?;
-        self.extend(tag, &buffer);
-        Ok(())
+        self.encode_field(tag, |encoder| {
+            encoder.encode_integer_value(constraints, value)
+        })
+    }
+
+    fn encode_integer_i64(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: i64,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.set_bit(tag, true)?;
+        self.encode_field(tag, |encoder| {
+            encoder.encode_primitive_integer_value(constraints, value.into())
+        })
+    }
+
+    fn encode_integer_u64(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: u64,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.set_bit(tag, true)?;
+        self.encode_field(tag, |encoder| {
+            encoder.encode_primitive_integer_value(constraints, value.into())
+        })
     }
 
     fn encode_null(&mut self, tag: Tag) -> Result<Self::Ok, Self::Error> {
@@ -836,10 +875,9 @@ impl crate::Encoder for Encoder {
         value: &[u8],
     ) -> Result<Self::Ok, Self::Error> {
         self.set_bit(tag, true)?;
-        let mut buffer = BitString::default();
-        self.encode_octet_string_into_buffer(constraints, value, &mut buffer)?;
-        self.extend(tag, &buffer);
-        Ok(())
+        self.encode_field(tag, |encoder| {
+            encoder.encode_octet_string_value(constraints, value)
+        })
     }
 
     fn encode_visible_string(
@@ -912,6 +950,16 @@ impl crate::Encoder for Encoder {
         self.encode_known_multipler_string(tag, &constraints, value)
     }
 
+    fn encode_universal_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::UniversalString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.set_bit(tag, true)?;
+        self.encode_known_multipler_string(tag, &constraints, value)
+    }
+
     fn encode_utf8_string(
         &mut self,
         tag: Tag,
@@ -954,23 +1002,19 @@ impl crate::Encoder for Encoder {
         values: &[E],
         constraints: Constraints,
     ) -> Result<Self::Ok, Self::Error> {
-        let mut buffer = BitString::default();
-        let options = self.options;
         self.set_bit(tag, true)?;
-
-        self.encode_length(&mut buffer, values.len(), constraints.size(), |range| {
-            let mut buffer = BitString::default();
-            for value in &values[range] {
-                let mut encoder = Self::new(options);
-                E::encode(value, &mut encoder)?;
-                buffer.extend(encoder.bitstring_output());
-            }
-            Ok(buffer)
-        })?;
-
-        self.extend(tag, &buffer);
-
-        Ok(())
+        self.encode_field(tag, |encoder| {
+            encoder
+                .encode_nested(<_>::default(), |encoder| {
+                    encoder.encode_length(values.len(), constraints.size(), |encoder, range| {
+                        for value in &values[range] {
+                            E::encode(value, encoder)?;
+                        }
+                        Ok(())
+                    })
+                })
+                .map(drop)
+        })
     }
 
     fn encode_set_of<E: Encode>(
@@ -991,6 +1035,16 @@ impl crate::Encoder for Encoder {
         value.encode_with_tag(self, tag)
     }
 
+    fn encode_explicit_prefix_with_constraints<V: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &V,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.set_bit(tag, true)?;
+        value.encode_with_tag_and_constraints(self, tag, constraints)
+    }
+
     fn encode_some<E: Encode>(&mut self, value: &E) -> Result<Self::Ok, Self::Error> {
         self.set_bit(E::TAG, true)?;
         value.encode(self)
@@ -1030,9 +1084,17 @@ impl crate::Encoder for Encoder {
         C: crate::types::Constructed,
         F: FnOnce(&mut Self) -> Result<Self::Ok, Self::Error>,
     {
-        let mut encoder = self.new_sequence_encoder::<C>();
-        (encoder_scope)(&mut encoder)?;
-        self.encode_constructed::<C>(tag, encoder)
+        self.set_bit(tag, true)?;
+        let field_bitfield = C::FIELDS
+            .iter()
+            .map(|field| (field.tag_tree.smallest_tag(), (field.presence, false)))
+            .collect();
+
+        self.encode_field(tag, |encoder| {
+            encoder.encode_constructed::<C>(|encoder| {
+                encoder.encode_nested(field_bitfield, encoder_scope)
+            })
+        })
     }
 
     fn encode_set<C, F>(&mut self, tag: Tag, encoder_scope: F) -> Result<Self::Ok, Self::Error>
@@ -1040,11 +1102,19 @@ impl crate::Encoder for Encoder {
         C: crate::types::Constructed,
         F: FnOnce(&mut Self) -> Result<Self::Ok, Self::Error>,
     {
-        let mut set = self.new_set_encoder::<C>();
+        self.set_bit(tag, true)?;
+        self.encode_field(tag, |encoder| {
+            encoder.encode_constructed::<C>(|encoder| {
+                let mut set = encoder.new_set_encoder::<C>();
+                (encoder_scope)(&mut set)?;
 
-        (encoder_scope)(&mut set)?;
+                for field in set.set_output.values() {
+                    encoder.output.extend_from_bitslice(field);
+                }
 
-        self.encode_constructed::<C>(tag, set)
+                Ok((set.field_bitfield, set.extension_fields))
+            })
+        })
     }
 
     fn encode_choice<E: Encode + crate::types::Choice>(
@@ -1052,12 +1122,10 @@ impl crate::Encoder for Encoder {
         constraints: Constraints,
         encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
     ) -> Result<Self::Ok, Self::Error> {
-        let mut buffer = BitString::new();
         let mut choice_encoder = Self::new(self.options.without_set_encoding());
         let tag = (encode_fn)(&mut choice_encoder)?;
         let is_root_extension = crate::TagTree::tag_contains(&tag, E::VARIANTS);
 
-        self.encode_extensible_bit(&constraints, &mut buffer, || is_root_extension);
         let variants = crate::types::variants::Variants::from_static(if is_root_extension {
             E::VARIANTS
         } else {
@@ -1082,35 +1150,37 @@ impl crate::Encoder for Encoder {
             Some(None)
         };
 
-        match (index, bounds) {
-            (index, Some(Some(variance))) => {
-                self.encode_integer_into_buffer(
-                    Constraints::new(&[constraints::Value::new(constraints::Bounded::new(
-                        0,
-                        variance as i128,
-                    ))
-                    .into()]),
-                    &index.into(),
-                    &mut buffer,
-                )?;
-
-                buffer.extend(choice_encoder.output);
-            }
-            (index, Some(None)) => {
-                self.encode_normally_small_integer(index, &mut buffer)?;
-                let mut output = choice_encoder.output();
-
-                if output.is_empty() {
-                    output.push(0);
+        self.encode_field(tag, |encoder| {
+            encoder.encode_extensible_bit(&constraints, || is_root_extension);
+
+            match (index, bounds) {
+                (index, Some(Some(variance))) => {
+                    encoder.encode_integer_value(
+                        Constraints::new(&[constraints::Value::new(constraints::Bounded::new(
+                            0,
+                            variance as i128 - 1,
+                        ))
+                        .into()]),
+                        &index.into(),
+                    )?;
+
+                    encoder.output.extend_from_bitslice(&choice_encoder.output);
                 }
+                (index, Some(None)) => {
+                    encoder.encode_normally_small_integer(index)?;
+                    let mut output = choice_encoder.output();
 
-                self.encode_octet_string_into_buffer(<_>::default(), &output, &mut buffer)?;
+                    if output.is_empty() {
+                        output.push(0);
+                    }
+
+                    encoder.encode_octet_string_value(<_>::default(), &output)?;
+                }
+                (_, None) => {}
             }
-            (_, None) => {}
-        }
 
-        self.extend(tag, &buffer);
-        Ok(())
+            Ok(())
+        })
     }
 
     fn encode_extension_addition<E: Encode>(
@@ -1148,8 +1218,7 @@ impl crate::Encoder for Encoder {
         };
 
         self.set_bit(E::TAG, true)?;
-        let mut encoder = self.new_sequence_encoder::<E>();
-        encoder.is_extension_sequence = true;
+        let mut encoder = Self::new(self.options.without_set_encoding());
         value.encode(&mut encoder)?;
 
         let output = encoder.output();
@@ -1230,19 +1299,17 @@ mod tests {
 
     #[test]
     fn length() {
-        let encoder = Encoder::new(EncoderOptions::unaligned());
-        let mut buffer = types::BitString::new();
+        let mut encoder = Encoder::new(EncoderOptions::unaligned());
         encoder
             .encode_length(
-                &mut buffer,
                 4,
                 Some(&Extensible::new(constraints::Size::new(
                     constraints::Bounded::new(1, 64),
                 ))),
-                |_| Ok(<_>::default()),
+                |_, _| Ok(()),
             )
             .unwrap();
-        assert_eq!(&[0xC], buffer.as_raw_slice());
+        assert_eq!(&[0xC], encoder.output.as_raw_slice());
     }
 
     #[test]
@@ -1258,12 +1325,9 @@ mod tests {
     #[test]
     fn normally_small_integer() {
         let mut encoder = Encoder::new(EncoderOptions::unaligned());
-        let mut buffer = types::BitString::new();
-        encoder
-            .encode_normally_small_integer(2, &mut buffer)
-            .unwrap();
-        assert_eq!(buffer.len(), 7);
-        assert_eq!(bitvec::bits![0, 0, 0, 0, 0, 1, 0], buffer);
+        encoder.encode_normally_small_integer(2).unwrap();
+        assert_eq!(encoder.output.len(), 7);
+        assert_eq!(bitvec::bits![0, 0, 0, 0, 0, 1, 0], encoder.output);
     }
 
     #[test]
@@ -1,3 +1,5 @@
+use alloc::string::ToString;
+
 use crate::types::constraints::{Bounded, Size};
 use snafu::*;
 
@@ -11,6 +13,11 @@ pub enum Error {
     },
     #[snafu(display("wrapped der encoding error: {source}"))]
     Der { source: crate::der::enc::Error },
+    #[snafu(display("buffer too small, needed: {needed}; available: {available}"))]
+    BufferTooSmall { needed: usize, available: usize },
+    #[cfg(feature = "std")]
+    #[snafu(display("failed to write encoding: {source}"))]
+    Io { source: std::io::Error },
     #[snafu(display("custom error:\n{}", msg))]
     Custom { msg: alloc::string::String },
 }
@@ -7,12 +7,15 @@ mod any;
 mod instance;
 mod open;
 mod prefix;
+mod registry;
 mod tag;
 
 pub mod constraints;
+pub mod containing;
 pub mod fields;
 pub mod variants;
 
+pub(crate) mod integer;
 pub(crate) mod oid;
 pub(crate) mod strings;
 
@@ -22,18 +25,20 @@ pub use {
     self::{
         any::Any,
         constraints::{Constraint, Constraints, Extensible},
+        containing::Containing,
         instance::InstanceOf,
+        integer::{Integer, TryFromIntegerError},
         oid::{ConstOid, ObjectIdentifier, Oid},
         open::Open,
         prefix::{Explicit, Implicit},
+        registry::{OpenTypeRegistry, OpenValue},
         strings::{
             BmpString, GeneralString, Ia5String, NumericString, PrintableString, TeletexString,
-            Utf8String, VisibleString,
+            UniversalString, Utf8String, VisibleString,
         },
         tag::{Class, Tag, TagTree},
     },
     bytes::Bytes as OctetString,
-    num_bigint::BigInt as Integer,
     rasn_derive::AsnType,
 };
 
@@ -43,8 +48,6 @@ pub type BitString = bitvec::vec::BitVec<u8, bitvec::order::Msb0>;
 pub type BitStr = bitvec::slice::BitSlice<u8, bitvec::order::Msb0>;
 ///  The `SET OF` type.
 pub type SetOf<T> = alloc::collections::BTreeSet<T>;
-///  The `UniversalString` type.
-pub type UniversalString = Implicit<tag::UNIVERSAL_STRING, Utf8String>;
 ///  The `UTCTime` type.
 pub type UtcTime = chrono::DateTime<chrono::Utc>;
 ///  The `GeneralizedTime` type.
@@ -73,6 +76,20 @@ pub trait Constructed {
     const FIELDS: self::fields::Fields;
     /// Fields contained in the list of extensions.
     const EXTENDED_FIELDS: Option<self::fields::Fields> = None;
+    /// The number of fields in `FIELDS`.
+    const FIELD_COUNT: usize = {
+        let fields = Self::FIELDS;
+        let len = fields.len();
+        core::mem::forget(fields);
+        len
+    };
+}
+
+/// An extension addition group whose components are tagged automatically,
+/// continuing the numbering of the `SEQUENCE` or `SET` that contains it.
+pub trait ExtensionAdditionGroup: Constructed {
+    /// The number of the context tag of the group's first component.
+    const FIRST_TAG: u32;
 }
 
 /// A `CHOICE` value.
@@ -81,6 +98,10 @@ pub trait Choice: Sized {
     const VARIANTS: &'static [TagTree];
     /// Variants contained in the list of extensions.
     const EXTENDED_VARIANTS: &'static [TagTree] = &[];
+    /// The ASN.1 identifiers of `VARIANTS`, in the same order.
+    const IDENTIFIERS: &'static [&'static str] = &[];
+    /// The ASN.1 identifiers of `EXTENDED_VARIANTS`, in the same order.
+    const EXTENDED_IDENTIFIERS: &'static [&'static str] = &[];
 }
 
 /// A `CHOICE` value.
@@ -102,6 +123,12 @@ pub trait Enumerated: Sized + 'static + PartialEq + Copy + core::fmt::Debug {
     /// present.
     const EXTENDED_DISCRIMINANTS: Option<&'static [(Self, isize)]>;
 
+    /// The ASN.1 identifiers of `VARIANTS`, in the same order.
+    const IDENTIFIERS: &'static [&'static str] = &[];
+    /// The ASN.1 identifiers of `EXTENDED_VARIANTS`, in the same order, if
+    /// present.
+    const EXTENDED_IDENTIFIERS: Option<&'static [&'static str]> = None;
+
     /// Returns the number of "root" variants for a given type.
     fn variance() -> usize {
         Self::VARIANTS.len()
@@ -139,8 +166,24 @@ pub trait Enumerated: Sized + 'static + PartialEq + Copy + core::fmt::Debug {
         }
     }
 
+    /// Returns the variant that keeps discriminants which aren't defined by
+    /// the type, holding `value`. Types without such a variant return `None`.
+    fn from_unknown_discriminant(_: isize) -> Option<Self> {
+        None
+    }
+
+    /// Returns the discriminant kept by `self`, if it's the variant for
+    /// discriminants which aren't defined by the type.
+    fn unknown_discriminant(&self) -> Option<isize> {
+        None
+    }
+
     /// Returns the discriminant value of `self`.
     fn discriminant(&self) -> isize {
+        if let Some(value) = self.unknown_discriminant() {
+            return value;
+        }
+
         Self::DISCRIMINANTS
             .iter()
             .chain(
@@ -162,6 +205,37 @@ pub trait Enumerated: Sized + 'static + PartialEq + Copy + core::fmt::Debug {
                     .flat_map(|array| array.iter()),
             )
             .find_map(|(variant, discriminant)| (value == *discriminant).then_some(*variant))
+            .or_else(|| Self::from_unknown_discriminant(value))
+    }
+
+    /// Returns the ASN.1 identifier of `self`, if it's known. Unknown
+    /// discriminants have no identifier.
+    fn identifier(&self) -> Option<&'static str> {
+        if self.unknown_discriminant().is_some() {
+            return None;
+        }
+
+        let index = self.enumeration_index();
+
+        if self.is_extended_variant() {
+            Self::EXTENDED_IDENTIFIERS?.get(index).copied()
+        } else {
+            Self::IDENTIFIERS.get(index).copied()
+        }
+    }
+
+    /// Returns a variant, if the ASN.1 identifier matches any variant.
+    fn from_identifier(identifier: &str) -> Option<Self> {
+        Self::IDENTIFIERS
+            .iter()
+            .zip(Self::VARIANTS)
+            .chain(
+                Self::EXTENDED_IDENTIFIERS
+                    .into_iter()
+                    .flatten()
+                    .zip(Self::EXTENDED_VARIANTS.into_iter().flatten()),
+            )
+            .find_map(|(name, variant)| (*name == identifier).then_some(*variant))
     }
 
     /// Returns a variant, if the index matches any "root" variant.
@@ -216,6 +290,7 @@ macro_rules! asn_type {
 asn_type! {
     bool: BOOL,
     Integer: INTEGER,
+    num_bigint::BigInt: INTEGER,
     OctetString: OCTET_STRING,
     ObjectIdentifier: OBJECT_IDENTIFIER,
     Oid: OBJECT_IDENTIFIER,
@@ -300,3 +375,32 @@ impl AsnType for Any {
     const TAG: Tag = Tag::EOC;
     const TAG_TREE: TagTree = TagTree::Choice(&[]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Colour {
+        Red,
+        Green,
+    }
+
+    impl AsnType for Colour {
+        const TAG: Tag = Tag::ENUMERATED;
+    }
+
+    impl Enumerated for Colour {
+        const VARIANTS: &'static [Self] = &[Self::Red, Self::Green];
+        const EXTENDED_VARIANTS: Option<&'static [Self]> = None;
+        const DISCRIMINANTS: &'static [(Self, isize)] = &[(Self::Red, 0), (Self::Green, 1)];
+        const EXTENDED_DISCRIMINANTS: Option<&'static [(Self, isize)]> = None;
+    }
+
+    #[test]
+    fn enumerated_without_identifiers() {
+        assert_eq!(None, Colour::Green.identifier());
+        assert_eq!(None, Colour::from_identifier("green"));
+        assert_eq!(Some(Colour::Green), Colour::from_discriminant(1));
+    }
+}
@@ -0,0 +1,266 @@
+//! Types for values whose contents are constrained to contain the encoding of
+//! another type, e.g. `OCTET STRING (CONTAINING T)`.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{
+    de::Error as _,
+    enc::Error as _,
+    types::{AsnType, BitString, Constraints, OctetString, Tag},
+    Codec, Decode, Decoder, Encode, Encoder,
+};
+
+/// An `OCTET STRING` or `BIT STRING` whose contents are the encoding of a
+/// value of type `T` (X.682 §11 — `CONTAINING T ENCODED BY C`).
+///
+/// `C` selects the encoding rules used for the contained value. The default,
+/// [`Inherited`], uses the same rules as the encoder or decoder of the outer
+/// value. `S` selects the outer string type, and is either [`OctetString`] or
+/// [`BitString`].
+///
+/// Encoders and decoders that don't implement one of the standard encoding
+/// rules have no rules to inherit, so encoding or decoding an [`Inherited`]
+/// value with them is an error. Visitors (see [`crate::visit`]) are the
+/// exception, and visit the contained value itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Containing<T, C = Inherited, S = OctetString> {
+    _codec: PhantomData<C>,
+    _string: PhantomData<S>,
+    /// The contained value.
+    pub value: T,
+}
+
+impl<T, C, S> Containing<T, C, S> {
+    /// Create a wrapper from `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            _codec: PhantomData,
+            _string: PhantomData,
+        }
+    }
+
+    /// Consumes the wrapper, returning the contained value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, C, S> From<T> for Containing<T, C, S> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, C, S> core::ops::Deref for Containing<T, C, S> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, C, S> core::ops::DerefMut for Containing<T, C, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T, C, S: AsnType> AsnType for Containing<T, C, S> {
+    const TAG: Tag = S::TAG;
+}
+
+const NO_INHERITED_CODEC: &str =
+    "the contained value inherits the encoding rules of the outer value, which has none";
+
+impl<T: Encode, C: ContainedEncoding, S: ContentsString> Encode for Containing<T, C, S> {
+    fn encode_with_tag_and_constraints<E: Encoder>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<(), E::Error> {
+        let codec = C::CODEC
+            .or_else(|| encoder.codec())
+            .ok_or_else(|| E::Error::custom(NO_INHERITED_CODEC))?;
+        let contents = codec.encode(&self.value).map_err(E::Error::custom)?;
+
+        S::encode_contents(encoder, tag, constraints, contents)
+    }
+
+    fn accept_with_context<V: crate::visit::Visitor>(
+        &self,
+        visitor: &mut V,
+        context: crate::visit::Context,
+    ) -> Result<(), V::Error> {
+        if C::CODEC.is_some() {
+            return crate::visit::walk(self, visitor, context);
+        }
+
+        let context = crate::visit::Context {
+            tag: T::TAG,
+            constraints: T::CONSTRAINTS,
+            ..context
+        };
+        self.value.accept_with_context(visitor, context)
+    }
+}
+
+impl<T: Decode, C: ContainedEncoding, S: ContentsString> Decode for Containing<T, C, S> {
+    fn decode_with_tag_and_constraints<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Self, D::Error> {
+        let codec = C::CODEC
+            .or_else(|| decoder.codec())
+            .ok_or_else(|| D::Error::custom(NO_INHERITED_CODEC))?;
+        let contents = S::decode_contents(decoder, tag, constraints)?;
+
+        codec
+            .decode(&contents)
+            .map(Self::new)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// The encoding rules used for the value inside a [`Containing`].
+pub trait ContainedEncoding {
+    /// The codec to use, or `None` to use the codec of the outer value.
+    const CODEC: Option<Codec>;
+}
+
+macro_rules! contained_encodings {
+    ($($(#[$doc:meta])* $name:ident = $codec:expr),+ $(,)?) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+            pub struct $name;
+
+            impl ContainedEncoding for $name {
+                const CODEC: Option<Codec> = $codec;
+            }
+        )+
+    }
+}
+
+contained_encodings! {
+    /// Encode the contained value with the same rules as the outer value.
+    Inherited = None,
+    /// Encode the contained value with the Aligned Packed Encoding Rules.
+    Aper = Some(Codec::Aper),
+    /// Encode the contained value with the Basic Encoding Rules.
+    Ber = Some(Codec::Ber),
+    /// Encode the contained value with the Canonical Encoding Rules.
+    Cer = Some(Codec::Cer),
+    /// Encode the contained value with the Distinguished Encoding Rules.
+    Der = Some(Codec::Der),
+    /// Encode the contained value with the Unaligned Packed Encoding Rules.
+    Uper = Some(Codec::Uper),
+}
+
+/// A string type that can hold the encoding of a contained value.
+pub trait ContentsString: AsnType {
+    /// Encode `contents` as a value of this string type.
+    fn encode_contents<E: Encoder>(
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+        contents: Vec<u8>,
+    ) -> Result<(), E::Error>;
+
+    /// Decode a value of this string type, returning its contents.
+    fn decode_contents<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Vec<u8>, D::Error>;
+}
+
+impl ContentsString for OctetString {
+    fn encode_contents<E: Encoder>(
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+        contents: Vec<u8>,
+    ) -> Result<(), E::Error> {
+        encoder
+            .encode_octet_string(tag, constraints, &contents)
+            .map(drop)
+    }
+
+    fn decode_contents<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Vec<u8>, D::Error> {
+        decoder.decode_octet_string(tag, constraints)
+    }
+}
+
+impl ContentsString for BitString {
+    fn encode_contents<E: Encoder>(
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+        contents: Vec<u8>,
+    ) -> Result<(), E::Error> {
+        encoder
+            .encode_bit_string(tag, constraints, &BitString::from_vec(contents))
+            .map(drop)
+    }
+
+    fn decode_contents<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Vec<u8>, D::Error> {
+        let bits = decoder.decode_bit_string(tag, constraints)?;
+
+        if bits.len() % 8 != 0 {
+            return Err(D::Error::custom(
+                "BIT STRING containing an encoded value must be a multiple of eight bits",
+            ));
+        }
+
+        Ok(bits.into_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Integer;
+
+    #[test]
+    fn inherited_octet_string() {
+        let value = <Containing<Integer>>::new(Integer::from(255));
+
+        let ber = crate::ber::encode(&value).unwrap();
+        assert_eq!(&[0x04, 0x04, 0x02, 0x02, 0x00, 0xFF][..], &*ber);
+        assert_eq!(value, crate::ber::decode(&ber).unwrap());
+
+        let uper = crate::uper::encode(&value).unwrap();
+        assert_eq!(&[0x03, 0x02, 0x00, 0xFF][..], &*uper);
+        assert_eq!(value, crate::uper::decode(&uper).unwrap());
+    }
+
+    #[test]
+    fn explicit_codec() {
+        let value = <Containing<bool, Ber>>::new(true);
+
+        let uper = crate::uper::encode(&value).unwrap();
+        assert_eq!(&[0x03, 0x01, 0x01, 0xFF][..], &*uper);
+        assert_eq!(value, crate::uper::decode(&uper).unwrap());
+    }
+
+    #[test]
+    fn bit_string() {
+        let value = <Containing<bool, Der, BitString>>::new(true);
+
+        let der = crate::der::encode(&value).unwrap();
+        assert_eq!(&[0x03, 0x04, 0x00, 0x01, 0x01, 0xFF][..], &*der);
+        assert_eq!(value, crate::der::decode(&der).unwrap());
+    }
+}
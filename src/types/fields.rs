@@ -22,12 +22,12 @@ impl Fields {
         }
     }
 
-    pub fn len(&self) -> usize {
-        self.fields.len()
+    pub const fn len(&self) -> usize {
+        self.as_slice().len()
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.fields.is_empty()
+    pub const fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
     }
 
     pub fn is_not_empty(&self) -> bool {
@@ -55,11 +55,104 @@ impl Fields {
             .sort_by(|a, b| a.tag_tree.smallest_tag().cmp(&b.tag_tree.smallest_tag()));
     }
 
+    /// Whether no two fields have the same tag, as is required for the
+    /// components of a `SET` (X.680 §27.3).
+    pub const fn has_distinct_tags(&self) -> bool {
+        has_distinct_tags(self.as_slice(), &[])
+    }
+
+    /// Whether no two fields or extension additions in `extended` have the
+    /// same tag.
+    pub const fn has_distinct_tags_with_extensions(&self, extended: &Fields) -> bool {
+        has_distinct_tags(self.as_slice(), extended.as_slice())
+    }
+
+    /// Whether the fields can be decoded unambiguously as the components of
+    /// a `SEQUENCE`, which requires every run of consecutive `OPTIONAL` or
+    /// `DEFAULT` fields, and the field following it, to have distinct tags
+    /// (X.680 §25.6).
+    pub const fn has_unambiguous_sequence_tags(&self) -> bool {
+        has_unambiguous_sequence_tags(self.as_slice(), &[])
+    }
+
+    /// Whether the fields followed by the extension additions in `extended`
+    /// can be decoded unambiguously as the components of a `SEQUENCE`.
+    pub const fn has_unambiguous_sequence_tags_with_extensions(&self, extended: &Fields) -> bool {
+        has_unambiguous_sequence_tags(self.as_slice(), extended.as_slice())
+    }
+
+    const fn as_slice(&self) -> &[Field] {
+        match &self.fields {
+            Cow::Borrowed(fields) => fields,
+            Cow::Owned(fields) => fields.as_slice(),
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = Field> + '_ {
         self.fields.iter().cloned()
     }
 }
 
+/// The `i`th field of `root` followed by `extended`.
+const fn field<'a>(root: &'a [Field], extended: &'a [Field], i: usize) -> &'a Field {
+    if i < root.len() {
+        &root[i]
+    } else {
+        &extended[i - root.len()]
+    }
+}
+
+const fn has_distinct_tags(root: &[Field], extended: &[Field]) -> bool {
+    let len = root.len() + extended.len();
+    let mut i = 0;
+
+    while i < len {
+        let mut j = i + 1;
+        while j < len {
+            if field(root, extended, i)
+                .tag_tree
+                .overlaps(&field(root, extended, j).tag_tree)
+            {
+                return false;
+            }
+
+            j += 1;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+const fn has_unambiguous_sequence_tags(root: &[Field], extended: &[Field]) -> bool {
+    let len = root.len() + extended.len();
+    let mut i = 0;
+
+    while i < len {
+        let current = field(root, extended, i);
+        if current.is_optional_or_default() {
+            let mut j = i + 1;
+            while j < len {
+                let next = field(root, extended, j);
+                if current.tag_tree.overlaps(&next.tag_tree) {
+                    return false;
+                }
+
+                if next.is_not_optional_or_default() {
+                    break;
+                }
+
+                j += 1;
+            }
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
 impl From<Cow<'static, [Field]>> for Fields {
     fn from(fields: Cow<'static, [Field]>) -> Self {
         Self::new(fields)
@@ -71,6 +164,8 @@ pub struct Field {
     pub tag: Tag,
     pub tag_tree: TagTree,
     pub presence: FieldPresence,
+    /// The field's ASN.1 identifier, if it's known.
+    pub name: Option<&'static str>,
 }
 
 impl Field {
@@ -79,6 +174,7 @@ impl Field {
             tag,
             tag_tree,
             presence: FieldPresence::Required,
+            name: None,
         }
     }
 
@@ -87,6 +183,7 @@ impl Field {
             tag,
             tag_tree,
             presence: FieldPresence::Optional,
+            name: None,
         }
     }
 
@@ -95,9 +192,16 @@ impl Field {
             tag,
             tag_tree,
             presence: FieldPresence::Default,
+            name: None,
         }
     }
 
+    /// Sets the field's ASN.1 identifier.
+    pub const fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
     pub const fn is_optional_or_default(&self) -> bool {
         self.presence.is_optional_or_default()
     }
@@ -120,3 +224,66 @@ impl FieldPresence {
         matches!(self, Self::Optional | Self::Default)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOL: TagTree = TagTree::Leaf(Tag::BOOL);
+    const INTEGER: TagTree = TagTree::Leaf(Tag::INTEGER);
+
+    #[test]
+    fn sequence_tags() {
+        const UNAMBIGUOUS: Fields = Fields::from_static(&[
+            Field::new_optional(Tag::BOOL, BOOL),
+            Field::new_required(Tag::INTEGER, INTEGER),
+            Field::new_required(Tag::BOOL, BOOL),
+        ]);
+        const CLASHES_WITH_NEXT: Fields = Fields::from_static(&[
+            Field::new_default(Tag::BOOL, BOOL),
+            Field::new_optional(Tag::INTEGER, INTEGER),
+            Field::new_required(Tag::BOOL, BOOL),
+        ]);
+
+        assert!(UNAMBIGUOUS.has_unambiguous_sequence_tags());
+        assert!(!UNAMBIGUOUS.has_distinct_tags());
+        assert!(!CLASHES_WITH_NEXT.has_unambiguous_sequence_tags());
+    }
+
+    #[test]
+    fn set_tags() {
+        const DISTINCT: Fields = Fields::from_static(&[
+            Field::new_required(Tag::BOOL, BOOL),
+            Field::new_optional(
+                Tag::EOC,
+                TagTree::Choice(&[INTEGER, TagTree::Leaf(Tag::new_context(0))]),
+            ),
+        ]);
+        const CHOICE_CLASH: Fields = Fields::from_static(&[
+            Field::new_required(Tag::INTEGER, INTEGER),
+            Field::new_required(Tag::EOC, TagTree::Choice(&[BOOL, INTEGER])),
+        ]);
+
+        assert!(DISTINCT.has_distinct_tags());
+        assert!(!CHOICE_CLASH.has_distinct_tags());
+    }
+
+    #[test]
+    fn extension_tags() {
+        const ROOT: Fields = Fields::from_static(&[
+            Field::new_required(Tag::INTEGER, INTEGER),
+            Field::new_optional(Tag::BOOL, BOOL),
+        ]);
+        const CLASHES_WITH_ROOT: Fields =
+            Fields::from_static(&[Field::new_optional(Tag::BOOL, BOOL)]);
+        const DISTINCT: Fields = Fields::from_static(&[Field::new_optional(
+            Tag::new_context(0),
+            TagTree::Leaf(Tag::new_context(0)),
+        )]);
+
+        assert!(ROOT.has_unambiguous_sequence_tags_with_extensions(&DISTINCT));
+        assert!(ROOT.has_distinct_tags_with_extensions(&DISTINCT));
+        assert!(!ROOT.has_unambiguous_sequence_tags_with_extensions(&CLASHES_WITH_ROOT));
+        assert!(!ROOT.has_distinct_tags_with_extensions(&CLASHES_WITH_ROOT));
+    }
+}
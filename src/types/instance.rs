@@ -52,11 +52,13 @@ impl<T: AsnType> crate::types::Constructed for InstanceOf<T> {
             tag: ObjectIdentifier::TAG,
             tag_tree: ObjectIdentifier::TAG_TREE,
             presence: FieldPresence::Required,
+            name: Some("type-id"),
         },
         Field {
             tag: T::TAG,
             tag_tree: T::TAG_TREE,
             presence: FieldPresence::Required,
+            name: Some("value"),
         },
     ]);
 }
@@ -0,0 +1,441 @@
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt, ops, str::FromStr};
+
+use num_bigint::{BigInt, Sign};
+use num_traits::{Signed, ToPrimitive};
+
+/// The `INTEGER` type, an integer of arbitrary size. Values fitting in an
+/// [`i128`] are stored inline, so only values larger than that allocate.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Integer(Repr);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    Primitive(i128),
+    /// Only used for values which don't fit in an `i128`.
+    Variable(BigInt),
+}
+
+impl Integer {
+    /// Creates a new integer from `value`.
+    pub const fn from_i128(value: i128) -> Self {
+        Self(Repr::Primitive(value))
+    }
+
+    /// Creates a new integer from its big endian two's complement
+    /// representation.
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> Self {
+        match PrimitiveBytes::parse_signed(bytes) {
+            Some(value) => Self::from_i128(value),
+            None => BigInt::from_signed_bytes_be(bytes).into(),
+        }
+    }
+
+    /// Creates a new non-negative integer from its big endian
+    /// representation.
+    pub fn from_unsigned_bytes_be(bytes: &[u8]) -> Self {
+        match PrimitiveBytes::parse_unsigned(bytes) {
+            Some(value) => value.into(),
+            None => BigInt::from_bytes_be(Sign::Plus, bytes).into(),
+        }
+    }
+
+    /// Returns the value as an `i128` if it fits in one.
+    pub fn as_i128(&self) -> Option<i128> {
+        match &self.0 {
+            Repr::Primitive(value) => Some(*value),
+            Repr::Variable(_) => None,
+        }
+    }
+
+    /// Returns the value as a [`BigInt`].
+    pub fn to_bigint(&self) -> BigInt {
+        match &self.0 {
+            Repr::Primitive(value) => BigInt::from(*value),
+            Repr::Variable(value) => value.clone(),
+        }
+    }
+
+    /// Returns the big endian two's complement representation of the value,
+    /// using as few bytes as possible.
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        self.signed_bytes_be().to_vec()
+    }
+
+    /// Like [`Integer::to_signed_bytes_be`], without allocating for values
+    /// fitting in an `i128`.
+    pub(crate) fn signed_bytes_be(&self) -> either::Either<PrimitiveBytes, Vec<u8>> {
+        match &self.0 {
+            Repr::Primitive(value) => either::Left(PrimitiveBytes::signed(*value)),
+            Repr::Variable(value) => either::Right(value.to_signed_bytes_be()),
+        }
+    }
+
+    /// Returns the number of bits needed to represent the magnitude of the
+    /// value.
+    pub fn bits(&self) -> u64 {
+        match &self.0 {
+            Repr::Primitive(value) => (128 - value.unsigned_abs().leading_zeros()).into(),
+            Repr::Variable(value) => value.bits(),
+        }
+    }
+
+    /// Returns whether the value is less than zero.
+    pub fn is_negative(&self) -> bool {
+        match &self.0 {
+            Repr::Primitive(value) => value.is_negative(),
+            Repr::Variable(value) => value.is_negative(),
+        }
+    }
+}
+
+impl Default for Integer {
+    fn default() -> Self {
+        Self::from_i128(0)
+    }
+}
+
+impl From<BigInt> for Integer {
+    fn from(value: BigInt) -> Self {
+        match value.to_i128() {
+            Some(value) => Self::from_i128(value),
+            None => Self(Repr::Variable(value)),
+        }
+    }
+}
+
+impl From<&BigInt> for Integer {
+    fn from(value: &BigInt) -> Self {
+        match value.to_i128() {
+            Some(value) => Self::from_i128(value),
+            None => Self(Repr::Variable(value.clone())),
+        }
+    }
+}
+
+impl From<num_bigint::BigUint> for Integer {
+    fn from(value: num_bigint::BigUint) -> Self {
+        BigInt::from(value).into()
+    }
+}
+
+impl From<Integer> for BigInt {
+    fn from(value: Integer) -> Self {
+        match value.0 {
+            Repr::Primitive(value) => BigInt::from(value),
+            Repr::Variable(value) => value,
+        }
+    }
+}
+
+impl From<&Integer> for BigInt {
+    fn from(value: &Integer) -> Self {
+        value.to_bigint()
+    }
+}
+
+impl From<u128> for Integer {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(value) => Self::from_i128(value),
+            Err(_) => Self(Repr::Variable(value.into())),
+        }
+    }
+}
+
+macro_rules! primitive_conversions {
+    ($($int:ty),+ $(,)?) => {
+        $(
+            impl From<$int> for Integer {
+                fn from(value: $int) -> Self {
+                    Self::from_i128(value as i128)
+                }
+            }
+
+            impl TryFrom<&Integer> for $int {
+                type Error = TryFromIntegerError;
+
+                fn try_from(value: &Integer) -> Result<Self, Self::Error> {
+                    let converted = match &value.0 {
+                        Repr::Primitive(value) => <$int>::try_from(*value).ok(),
+                        Repr::Variable(_) => None,
+                    };
+
+                    converted.ok_or_else(|| TryFromIntegerError { original: value.clone() })
+                }
+            }
+
+            impl TryFrom<Integer> for $int {
+                type Error = TryFromIntegerError;
+
+                fn try_from(value: Integer) -> Result<Self, Self::Error> {
+                    Self::try_from(&value)
+                }
+            }
+        )+
+    }
+}
+
+primitive_conversions!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl TryFrom<&Integer> for u128 {
+    type Error = TryFromIntegerError;
+
+    fn try_from(value: &Integer) -> Result<Self, Self::Error> {
+        let converted = match &value.0 {
+            Repr::Primitive(value) => u128::try_from(*value).ok(),
+            Repr::Variable(value) => value.to_u128(),
+        };
+
+        converted.ok_or_else(|| TryFromIntegerError {
+            original: value.clone(),
+        })
+    }
+}
+
+impl TryFrom<Integer> for u128 {
+    type Error = TryFromIntegerError;
+
+    fn try_from(value: Integer) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// The error returned when an [`Integer`] doesn't fit in a primitive integer
+/// type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryFromIntegerError {
+    original: Integer,
+}
+
+impl TryFromIntegerError {
+    /// Returns the integer that failed to convert.
+    pub fn into_original(self) -> Integer {
+        self.original
+    }
+}
+
+impl fmt::Display for TryFromIntegerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "integer {} is out of range for the target type",
+            self.original
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromIntegerError {}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Integer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Repr::Primitive(lhs), Repr::Primitive(rhs)) => lhs.cmp(rhs),
+            (Repr::Variable(lhs), Repr::Variable(rhs)) => lhs.cmp(rhs),
+            // Variable values are always outside of the range of `i128`.
+            (Repr::Primitive(_), Repr::Variable(rhs)) if rhs.is_negative() => Ordering::Greater,
+            (Repr::Primitive(_), Repr::Variable(_)) => Ordering::Less,
+            (Repr::Variable(lhs), Repr::Primitive(_)) if lhs.is_negative() => Ordering::Less,
+            (Repr::Variable(_), Repr::Primitive(_)) => Ordering::Greater,
+        }
+    }
+}
+
+impl ops::Add for Integer {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        match (&self.0, &rhs.0) {
+            (Repr::Primitive(lhs), Repr::Primitive(rhs)) => match lhs.checked_add(*rhs) {
+                Some(sum) => Self::from_i128(sum),
+                None => (BigInt::from(*lhs) + *rhs).into(),
+            },
+            _ => (BigInt::from(self) + BigInt::from(rhs)).into(),
+        }
+    }
+}
+
+impl ops::Sub for Integer {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        match (&self.0, &rhs.0) {
+            (Repr::Primitive(lhs), Repr::Primitive(rhs)) => match lhs.checked_sub(*rhs) {
+                Some(difference) => Self::from_i128(difference),
+                None => (BigInt::from(*lhs) - *rhs).into(),
+            },
+            _ => (BigInt::from(self) - BigInt::from(rhs)).into(),
+        }
+    }
+}
+
+impl ops::Neg for Integer {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self.0 {
+            Repr::Primitive(value) => match value.checked_neg() {
+                Some(value) => Self::from_i128(value),
+                None => (-BigInt::from(value)).into(),
+            },
+            Repr::Variable(value) => (-value).into(),
+        }
+    }
+}
+
+impl FromStr for Integer {
+    type Err = num_bigint::ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<i128>() {
+            Ok(value) => Ok(Self::from_i128(value)),
+            Err(_) => s.parse::<BigInt>().map(Self::from),
+        }
+    }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Repr::Primitive(value) => fmt::Display::fmt(value, f),
+            Repr::Variable(value) => fmt::Display::fmt(value, f),
+        }
+    }
+}
+
+impl fmt::Debug for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// The big endian representation of a primitive integer, without any
+/// redundant leading bytes.
+#[derive(Clone, Copy)]
+pub(crate) struct PrimitiveBytes {
+    bytes: [u8; 16],
+    start: usize,
+}
+
+impl PrimitiveBytes {
+    /// The two's complement representation of `value`.
+    pub(crate) fn signed(value: i128) -> Self {
+        let bytes = value.to_be_bytes();
+        let redundant = |(byte, next): (&u8, &u8)| {
+            (*byte == 0 && next & 0x80 == 0) || (*byte == 0xFF && next & 0x80 != 0)
+        };
+        let start = bytes
+            .iter()
+            .zip(&bytes[1..])
+            .take_while(|pair| redundant(*pair))
+            .count();
+
+        Self { bytes, start }
+    }
+
+    /// The representation of the non-negative `value`.
+    pub(crate) fn unsigned(value: u128) -> Self {
+        let bytes = value.to_be_bytes();
+        let start = (value.leading_zeros() as usize / 8).min(15);
+
+        Self { bytes, start }
+    }
+
+    /// Parses big endian two's complement `bytes`, returning `None` if the
+    /// value doesn't fit in an `i128`.
+    pub(crate) fn parse_signed(bytes: &[u8]) -> Option<i128> {
+        match bytes.first() {
+            None => Some(0),
+            Some(first) if bytes.len() <= 16 => {
+                let fill = if first & 0x80 == 0 { 0 } else { 0xFF };
+                let mut buffer = [fill; 16];
+                buffer[16 - bytes.len()..].copy_from_slice(bytes);
+                Some(i128::from_be_bytes(buffer))
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Parses big endian `bytes` as a non-negative value, returning `None`
+    /// if it doesn't fit in a `u128`.
+    pub(crate) fn parse_unsigned(bytes: &[u8]) -> Option<u128> {
+        if bytes.len() <= 16 {
+            let mut buffer = [0; 16];
+            buffer[16 - bytes.len()..].copy_from_slice(bytes);
+            Some(u128::from_be_bytes(buffer))
+        } else {
+            None
+        }
+    }
+}
+
+impl ops::Deref for PrimitiveBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[self.start..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_bytes() {
+        for value in [
+            0,
+            1,
+            -1,
+            127,
+            128,
+            -128,
+            -129,
+            255,
+            256,
+            i128::MAX,
+            i128::MIN,
+        ] {
+            let integer = Integer::from(value);
+            assert_eq!(
+                BigInt::from(value).to_signed_bytes_be(),
+                integer.to_signed_bytes_be()
+            );
+            assert_eq!(
+                integer,
+                Integer::from_signed_bytes_be(&integer.to_signed_bytes_be())
+            );
+        }
+
+        assert_eq!(&[0], &*PrimitiveBytes::unsigned(0));
+        assert_eq!(&[1, 0], &*PrimitiveBytes::unsigned(256));
+        assert_eq!(Integer::from(255), Integer::from_unsigned_bytes_be(&[0xFF]));
+    }
+
+    #[test]
+    fn variable() {
+        let big = Integer::from(u128::MAX);
+        assert_eq!(None, big.as_i128());
+        assert_eq!(128, big.bits());
+        assert_eq!(
+            Integer::from(i128::MAX),
+            big.clone() - Integer::from(1u128 << 127)
+        );
+        assert!(Integer::from(i128::MAX) < big);
+        assert!(-big.clone() < Integer::from(i128::MIN));
+        assert_eq!(Ok(u128::MAX), u128::try_from(&big));
+        assert!(u64::try_from(&big).is_err());
+        assert_eq!(big, big.to_string().parse().unwrap());
+        assert_eq!(
+            big,
+            Integer::from_signed_bytes_be(&big.to_signed_bytes_be())
+        );
+    }
+}
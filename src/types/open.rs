@@ -2,22 +2,103 @@ use super::*;
 use crate::{Decode, Encode};
 
 /// An "open" type representing any valid ASN.1 type.
+///
+/// Every untagged universal type can be decoded into `Open`, including
+/// constructed `SEQUENCE` and `SET` values, whose components are decoded as
+/// nested `Open` values in their original order so that they re-encode
+/// faithfully.
 #[derive(AsnType, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Decode, Encode)]
 #[rasn(crate_root = "crate")]
 #[rasn(choice)]
 pub enum Open {
     BitString(BitString),
-    // BmpString(BmpString),
+    BmpString(BmpString),
     Bool(bool),
+    Enumerated(Implicit<tag::ENUMERATED, Integer>),
     GeneralizedTime(GeneralizedTime),
-    // Ia5String(Ia5String),
+    GeneralString(GeneralString),
+    Ia5String(Ia5String),
     Integer(Integer),
     Null,
+    NumericString(NumericString),
     ObjectIdentifier(ObjectIdentifier),
     OctetString(OctetString),
-    // PrintableString(PrintableString),
+    PrintableString(PrintableString),
+    /// The contents octets of a `REAL` value, which are kept as-is.
+    Real(Implicit<tag::REAL, OctetString>),
+    Sequence(SequenceOf<Open>),
+    Set(Implicit<tag::SET, SequenceOf<Open>>),
+    TeletexString(TeletexString),
     UniversalString(UniversalString),
+    Utf8String(Utf8String),
     UtcTime(UtcTime),
     VisibleString(VisibleString),
     InstanceOf(alloc::boxed::Box<InstanceOf<Open>>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn universal_types() {
+        let encoded = &[
+            0x30, 0x51, // SEQUENCE
+            0x01, 0x01, 0xFF, // BOOLEAN
+            0x02, 0x02, 0x01, 0x00, // INTEGER
+            0x03, 0x02, 0x00, 0x80, // BIT STRING
+            0x04, 0x02, 0xAB, 0xCD, // OCTET STRING
+            0x05, 0x00, // NULL
+            0x06, 0x03, 0x2A, 0x03, 0x04, // OBJECT IDENTIFIER
+            0x09, 0x03, 0x80, 0xFB, 0x05, // REAL
+            0x0A, 0x01, 0x03, // ENUMERATED
+            0x0C, 0x02, 0x68, 0x69, // UTF8String
+            0x12, 0x02, 0x31, 0x32, // NumericString
+            0x13, 0x02, 0x68, 0x69, // PrintableString
+            0x14, 0x02, 0x68, 0x69, // TeletexString
+            0x16, 0x02, 0x68, 0x69, // IA5String
+            0x1A, 0x02, 0x68, 0x69, // VisibleString
+            0x1B, 0x02, 0x68, 0x69, // GeneralString
+            0x1E, 0x04, 0x00, 0x68, 0x00, 0x69, // BMPString
+            0x31, 0x08, // SET
+            0x02, 0x01, 0x02, // INTEGER
+            0x02, 0x01, 0x01, // INTEGER
+            0x30, 0x00, // SEQUENCE
+            0x30, 0x05, // SEQUENCE
+            0x30, 0x03, // SEQUENCE
+            0x0A, 0x01, 0x00, // ENUMERATED
+        ][..];
+
+        let open: Open = crate::ber::decode(encoded).unwrap();
+        let Open::Sequence(values) = &open else {
+            panic!("expected a SEQUENCE, found {open:?}");
+        };
+
+        assert_eq!(Open::Bool(true), values[0]);
+        assert_eq!(Open::Integer(256.into()), values[1]);
+        assert_eq!(Open::Null, values[4]);
+        assert_eq!(
+            Open::Real(Implicit::new(OctetString::from_static(&[0x80, 0xFB, 0x05]))),
+            values[6]
+        );
+        assert_eq!(Open::Enumerated(Implicit::new(3.into())), values[7]);
+        assert_eq!(Open::Utf8String("hi".into()), values[8]);
+        assert_eq!(
+            Open::BmpString(BmpString::try_from("hi").unwrap()),
+            values[15]
+        );
+        assert_eq!(
+            Open::Set(Implicit::new(alloc::vec![
+                Open::Integer(2.into()),
+                Open::Integer(1.into()),
+                Open::Sequence(alloc::vec![]),
+            ])),
+            values[16]
+        );
+        assert_eq!(encoded, crate::ber::encode(&open).unwrap());
+        assert_eq!(
+            open,
+            crate::der::decode(&crate::der::encode(&open).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+use super::{Any, ObjectIdentifier, Oid};
+use crate::{
+    codec::{DecodeError, EncodeError},
+    Codec, Decode, Encode,
+};
+
+type DecodeFn<T> = Box<dyn Fn(Codec, &[u8]) -> Result<T, DecodeError> + Send + Sync>;
+type EncodeFn<T> = Box<dyn Fn(Codec, &T) -> Option<Result<Vec<u8>, EncodeError>> + Send + Sync>;
+
+struct Entry<T> {
+    decode: DecodeFn<T>,
+    encode: EncodeFn<T>,
+}
+
+/// A runtime mapping from an [`ObjectIdentifier`] to the concrete type of an
+/// open type value (such as [`Any`]) that it identifies.
+///
+/// Each registered type is converted into a common value type `T`, which is
+/// usually either an enum with a variant per registered type, or a
+/// `Box<dyn OpenValue>` (see [`OpenTypeRegistry::register_boxed`]).
+/// ```
+/// use rasn::{prelude::*, Codec};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Value {
+///     Flag(bool),
+///     Count(u32),
+/// }
+///
+/// const FLAG: ConstOid = ConstOid(&[1, 3, 6, 1, 4, 1, 1]);
+/// const COUNT: ConstOid = ConstOid(&[1, 3, 6, 1, 4, 1, 2]);
+///
+/// let mut registry = OpenTypeRegistry::new();
+/// registry
+///     .register(FLAG, Value::Flag, |value| match value {
+///         Value::Flag(flag) => Some(flag),
+///         _ => None,
+///     })
+///     .register(COUNT, Value::Count, |value| match value {
+///         Value::Count(count) => Some(count),
+///         _ => None,
+///     });
+///
+/// let any = Any::new(rasn::der::encode(&5u32).unwrap());
+/// let value = registry.decode_any(Codec::Der, COUNT.as_ref(), &any).unwrap();
+/// assert_eq!(Some(Value::Count(5)), value);
+/// ```
+pub struct OpenTypeRegistry<T> {
+    entries: BTreeMap<ObjectIdentifier, Entry<T>>,
+}
+
+impl<T> OpenTypeRegistry<T> {
+    /// Creates a new empty registry.
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `V` as the type identified by `oid`. `wrap` converts a
+    /// decoded `V` into `T`, and `unwrap` returns the `V` contained in a `T`,
+    /// or `None` if the value holds a different type. Registering an already
+    /// registered `oid` replaces its previous entry.
+    pub fn register<V, W, U>(
+        &mut self,
+        oid: impl Into<ObjectIdentifier>,
+        wrap: W,
+        unwrap: U,
+    ) -> &mut Self
+    where
+        V: Decode + Encode,
+        W: Fn(V) -> T + Send + Sync + 'static,
+        U: Fn(&T) -> Option<&V> + Send + Sync + 'static,
+    {
+        let entry = Entry {
+            decode: Box::new(move |codec, input| codec.decode::<V>(input).map(&wrap)),
+            encode: Box::new(move |codec, value| unwrap(value).map(|value| codec.encode(value))),
+        };
+
+        self.entries.insert(oid.into(), entry);
+        self
+    }
+
+    /// Returns whether a type has been registered for `oid`.
+    pub fn contains(&self, oid: &Oid) -> bool {
+        self.entries.contains_key(oid)
+    }
+
+    /// Returns an iterator over every registered object identifier, in order.
+    pub fn oids(&self) -> impl Iterator<Item = &ObjectIdentifier> + '_ {
+        self.entries.keys()
+    }
+
+    /// Returns the number of registered types.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no types have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Decodes `input` with `codec` as the type registered for `oid`.
+    ///
+    /// Returns `Ok(None)` if no type has been registered for `oid`.
+    pub fn decode(&self, codec: Codec, oid: &Oid, input: &[u8]) -> Result<Option<T>, DecodeError> {
+        self.entries
+            .get(oid)
+            .map(|entry| (entry.decode)(codec, input))
+            .transpose()
+    }
+
+    /// Decodes the contents of `value` with `codec` as the type registered
+    /// for `oid`.
+    ///
+    /// Returns `Ok(None)` if no type has been registered for `oid`.
+    pub fn decode_any(
+        &self,
+        codec: Codec,
+        oid: &Oid,
+        value: &Any,
+    ) -> Result<Option<T>, DecodeError> {
+        self.decode(codec, oid, value.as_bytes())
+    }
+
+    /// Encodes `value` with `codec` as the type registered for `oid`.
+    ///
+    /// Returns `Ok(None)` if no type has been registered for `oid`, or if
+    /// `value` does not hold the registered type.
+    pub fn encode(
+        &self,
+        codec: Codec,
+        oid: &Oid,
+        value: &T,
+    ) -> Result<Option<Vec<u8>>, EncodeError> {
+        self.entries
+            .get(oid)
+            .and_then(|entry| (entry.encode)(codec, value))
+            .transpose()
+    }
+
+    /// Encodes `value` with `codec` as the type registered for `oid` into an
+    /// [`Any`].
+    ///
+    /// Returns `Ok(None)` if no type has been registered for `oid`, or if
+    /// `value` does not hold the registered type.
+    pub fn encode_any(
+        &self,
+        codec: Codec,
+        oid: &Oid,
+        value: &T,
+    ) -> Result<Option<Any>, EncodeError> {
+        self.encode(codec, oid, value)
+            .map(|bytes| bytes.map(Any::new))
+    }
+}
+
+impl OpenTypeRegistry<Box<dyn OpenValue>> {
+    /// Registers `V` as the type identified by `oid`, with decoded values
+    /// being returned as a `Box<dyn OpenValue>`.
+    /// ```
+    /// use rasn::{prelude::*, Codec};
+    ///
+    /// const FLAG: ConstOid = ConstOid(&[1, 3, 6, 1, 4, 1, 1]);
+    ///
+    /// let mut registry = OpenTypeRegistry::new();
+    /// registry.register_boxed::<bool>(FLAG);
+    ///
+    /// let value = registry.decode(Codec::Der, FLAG.as_ref(), &[0x01, 0x01, 0xff]).unwrap().unwrap();
+    /// assert_eq!(Some(&true), value.downcast_ref::<bool>());
+    /// ```
+    pub fn register_boxed<V>(&mut self, oid: impl Into<ObjectIdentifier>) -> &mut Self
+    where
+        V: Decode + Encode + OpenValue,
+    {
+        self.register::<V, _, _>(
+            oid,
+            |value| Box::new(value) as Box<dyn OpenValue>,
+            |value| value.downcast_ref::<V>(),
+        )
+    }
+}
+
+impl<T> Default for OpenTypeRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> core::fmt::Debug for OpenTypeRegistry<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_set().entries(self.entries.keys()).finish()
+    }
+}
+
+/// A value of a type resolved at runtime through an [`OpenTypeRegistry`].
+pub trait OpenValue: core::any::Any + core::fmt::Debug + Send + Sync {
+    /// Returns `self` as a [`core::any::Any`], for downcasting to the
+    /// concrete type.
+    fn as_any(&self) -> &dyn core::any::Any;
+}
+
+impl<T: core::any::Any + core::fmt::Debug + Send + Sync> OpenValue for T {
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+impl dyn OpenValue {
+    /// Returns whether the value's concrete type is `T`.
+    pub fn is<T: OpenValue>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Returns a reference to the value if its concrete type is `T`.
+    pub fn downcast_ref<T: OpenValue>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConstOid;
+
+    const FLAG: ConstOid = ConstOid(&[1, 3, 6, 1, 4, 1, 1]);
+    const COUNT: ConstOid = ConstOid(&[1, 3, 6, 1, 4, 1, 2]);
+    const UNKNOWN: ConstOid = ConstOid(&[1, 3, 6, 1, 4, 1, 3]);
+
+    #[test]
+    fn boxed() {
+        let mut registry = OpenTypeRegistry::new();
+        registry
+            .register_boxed::<bool>(FLAG)
+            .register_boxed::<u32>(COUNT);
+
+        for codec in [Codec::Ber, Codec::Der, Codec::Uper, Codec::Aper] {
+            let encoded = codec.encode(&500u32).unwrap();
+            let value = registry
+                .decode(codec, COUNT.as_ref(), &encoded)
+                .unwrap()
+                .unwrap();
+
+            assert!(value.is::<u32>());
+            assert_eq!(Some(&500), value.downcast_ref::<u32>());
+            assert_eq!(None, value.downcast_ref::<bool>());
+            assert_eq!(
+                Some(encoded),
+                registry.encode(codec, COUNT.as_ref(), &value).unwrap()
+            );
+            assert_eq!(None, registry.encode(codec, FLAG.as_ref(), &value).unwrap());
+        }
+
+        assert!(registry
+            .decode(Codec::Der, FLAG.as_ref(), &[0x02, 0x01, 0x05])
+            .is_err());
+    }
+
+    #[test]
+    fn unregistered() {
+        let registry = OpenTypeRegistry::<Box<dyn OpenValue>>::new();
+        let value: Box<dyn OpenValue> = Box::new(true);
+
+        assert!(!registry.contains(UNKNOWN.as_ref()));
+        assert!(registry
+            .decode(Codec::Der, UNKNOWN.as_ref(), &[0x01, 0x01, 0xff])
+            .unwrap()
+            .is_none());
+        assert!(registry
+            .encode(Codec::Der, UNKNOWN.as_ref(), &value)
+            .unwrap()
+            .is_none());
+    }
+}
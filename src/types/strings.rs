@@ -5,14 +5,17 @@ mod ia5;
 mod numeric;
 mod printable;
 mod teletex;
+mod universal;
 mod visible;
 
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+
 use crate::prelude::*;
 
 pub use {
     alloc::string::String as Utf8String, bmp::BmpString, general::GeneralString, ia5::Ia5String,
     numeric::NumericString, printable::PrintableString, teletex::TeletexString,
-    visible::VisibleString,
+    universal::UniversalString, visible::VisibleString,
 };
 
 pub(crate) use constrained::{
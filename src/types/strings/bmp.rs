@@ -10,6 +10,11 @@ impl BmpString {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.iter().flat_map(|ch| ch.to_be_bytes()).collect()
     }
+
+    /// The number of bytes in the string's big endian encoding.
+    pub(crate) fn byte_len(&self) -> usize {
+        self.0.len() * 2
+    }
 }
 
 impl StaticPermittedAlphabet for BmpString {
@@ -66,6 +71,32 @@ impl TryFrom<&'_ str> for BmpString {
     }
 }
 
+impl TryFrom<&'_ [u8]> for BmpString {
+    type Error = InvalidBmpString;
+
+    /// Converts a set of big endian bytes into a string.
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() & 1 != 0 {
+            return Err(InvalidBmpString);
+        }
+
+        Ok(Self(
+            (0..value.len())
+                .step_by(2)
+                .map(|i| u16::from_be_bytes([value[i], value[i + 1]]))
+                .collect(),
+        ))
+    }
+}
+
+impl TryFrom<Vec<u8>> for BmpString {
+    type Error = InvalidBmpString;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from(&*value)
+    }
+}
+
 impl AsnType for BmpString {
     const TAG: Tag = Tag::BMP_STRING;
 }
@@ -63,6 +63,15 @@ impl TryFrom<String> for GeneralString {
     }
 }
 
+impl core::fmt::Display for GeneralString {
+    /// Formats the string's ISO/IEC 8859-1 (Latin-1) bytes as characters.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|&byte| core::fmt::Write::write_char(f, char::from(byte)))
+    }
+}
+
 impl core::ops::Deref for GeneralString {
     type Target = Vec<u8>;
 
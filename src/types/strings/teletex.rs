@@ -8,6 +8,184 @@ impl TeletexString {
     pub fn new(vec: Vec<u8>) -> Self {
         Self(vec)
     }
+
+    /// Decodes the T.61 encoded string into a `String`, returning an error
+    /// if it contains a byte that is not assigned in T.61, or a non-spacing
+    /// diacritical mark that is not followed by a character.
+    pub fn to_string(&self) -> Result<String, InvalidTeletexString> {
+        self.decode(|index| Err(InvalidTeletexString { index }))
+    }
+
+    /// Decodes the T.61 encoded string into a `String`, replacing any
+    /// invalid byte with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn to_string_lossy(&self) -> String {
+        self.decode(|_| Ok::<_, core::convert::Infallible>(char::REPLACEMENT_CHARACTER))
+            .unwrap_or_else(|never| match never {})
+    }
+
+    fn decode<E>(&self, mut invalid: impl FnMut(usize) -> Result<char, E>) -> Result<String, E> {
+        let mut string = String::with_capacity(self.0.len());
+        let mut bytes = self.0.iter().copied().enumerate().peekable();
+
+        while let Some((index, byte)) = bytes.next() {
+            if let Some(diacritic) = Diacritic::from_byte(byte) {
+                let base = bytes
+                    .peek()
+                    .filter(|(_, base)| base.is_ascii_graphic() || *base == b' ')
+                    .and_then(|(_, base)| t61_char(*base));
+
+                match base {
+                    Some(base) => {
+                        bytes.next();
+                        diacritic.apply(base, &mut string);
+                    }
+                    None => string.push(invalid(index)?),
+                }
+            } else {
+                match t61_char(byte) {
+                    Some(ch) => string.push(ch),
+                    None => string.push(invalid(index)?),
+                }
+            }
+        }
+
+        Ok(string)
+    }
+}
+
+#[derive(snafu::Snafu, Debug)]
+#[snafu(visibility(pub(crate)))]
+#[snafu(display("Invalid teletex string byte at index {index}"))]
+pub struct InvalidTeletexString {
+    index: usize,
+}
+
+/// Returns the character represented by a single T.61 byte, or `None` if the
+/// byte is unassigned or a non-spacing diacritical mark.
+fn t61_char(byte: u8) -> Option<char> {
+    let ch = match byte {
+        // Primary set positions that differ from ASCII are unassigned.
+        0x23 | 0x24 | 0x5C | 0x5E | 0x60 | 0x7B | 0x7D | 0x7E => return None,
+        // C0 controls and the primary graphic set.
+        0x00..=0x7E => byte as char,
+        // C1 controls and no-break space.
+        0x80..=0xA0 => byte as char,
+        0xA1 => '¡',
+        0xA2 => '¢',
+        0xA3 => '£',
+        0xA4 => '$',
+        0xA5 => '¥',
+        0xA6 => '#',
+        0xA7 => '§',
+        0xA8 => '¤',
+        0xAB => '«',
+        0xB0 => '°',
+        0xB1 => '±',
+        0xB2 => '²',
+        0xB3 => '³',
+        0xB4 => '×',
+        0xB5 => 'µ',
+        0xB6 => '¶',
+        0xB7 => '·',
+        0xB8 => '÷',
+        0xBB => '»',
+        0xBC => '¼',
+        0xBD => '½',
+        0xBE => '¾',
+        0xBF => '¿',
+        0xE0 => 'Ω',
+        0xE1 => 'Æ',
+        0xE2 => 'Đ',
+        0xE3 => 'ª',
+        0xE4 => 'Ħ',
+        0xE6 => 'Ĳ',
+        0xE7 => 'Ŀ',
+        0xE8 => 'Ł',
+        0xE9 => 'Ø',
+        0xEA => 'Œ',
+        0xEB => 'º',
+        0xEC => 'Þ',
+        0xED => 'Ŧ',
+        0xEE => 'Ŋ',
+        0xEF => 'ŉ',
+        0xF0 => 'ĸ',
+        0xF1 => 'æ',
+        0xF2 => 'đ',
+        0xF3 => 'ð',
+        0xF4 => 'ħ',
+        0xF5 => 'ı',
+        0xF6 => 'ĳ',
+        0xF7 => 'ŀ',
+        0xF8 => 'ł',
+        0xF9 => 'ø',
+        0xFA => 'œ',
+        0xFB => 'ß',
+        0xFC => 'þ',
+        0xFD => 'ŧ',
+        0xFE => 'ŋ',
+        _ => return None,
+    };
+
+    Some(ch)
+}
+
+/// A T.61 non-spacing diacritical mark, which precedes the character it
+/// modifies.
+struct Diacritic {
+    /// The equivalent Unicode combining character.
+    combining: char,
+    /// The base characters with a precomposed Unicode form.
+    bases: &'static str,
+    /// The precomposed form of each character in `bases`.
+    composed: &'static str,
+}
+
+impl Diacritic {
+    fn from_byte(byte: u8) -> Option<Self> {
+        let (combining, bases, composed) = match byte {
+            0xC1 => ('\u{300}', "AEIOUaeiou", "ÀÈÌÒÙàèìòù"),
+            0xC2 => (
+                '\u{301}',
+                "ACEILNORSUYZacegilnorsuyz",
+                "ÁĆÉÍĹŃÓŔŚÚÝŹáćéǵíĺńóŕśúýź",
+            ),
+            0xC3 => (
+                '\u{302}',
+                "ACEGHIJOSUWYaceghijosuwy",
+                "ÂĈÊĜĤÎĴÔŜÛŴŶâĉêĝĥîĵôŝûŵŷ",
+            ),
+            0xC4 => ('\u{303}', "AINOUainou", "ÃĨÑÕŨãĩñõũ"),
+            0xC5 => ('\u{304}', "AEIOUaeiou", "ĀĒĪŌŪāēīōū"),
+            0xC6 => ('\u{306}', "AGUagu", "ĂĞŬăğŭ"),
+            0xC7 => ('\u{307}', "CEGIZcegz", "ĊĖĠİŻċėġż"),
+            0xC8 | 0xC9 => ('\u{308}', "AEIOUYaeiouy", "ÄËÏÖÜŸäëïöüÿ"),
+            0xCA => ('\u{30A}', "AUau", "ÅŮåů"),
+            0xCB => ('\u{327}', "CGKLNRSTcgklnrst", "ÇĢĶĻŅŖŞŢçģķļņŗşţ"),
+            0xCC => ('\u{332}', "", ""),
+            0xCD => ('\u{30B}', "OUou", "ŐŰőű"),
+            0xCE => ('\u{328}', "AEIUaeiu", "ĄĘĮŲąęįų"),
+            0xCF => ('\u{30C}', "CDELNRSTZcdelnrstz", "ČĎĚĽŇŘŠŤŽčďěľňřšťž"),
+            _ => return None,
+        };
+
+        Some(Self {
+            combining,
+            bases,
+            composed,
+        })
+    }
+
+    /// Pushes `base` modified by the diacritical mark onto `string`, using
+    /// the precomposed character if there is one.
+    fn apply(&self, base: char, string: &mut String) {
+        match self.bases.chars().position(|ch| ch == base) {
+            Some(index) => string.extend(self.composed.chars().nth(index)),
+            None => {
+                string.push(base);
+                string.push(self.combining);
+            }
+        }
+    }
 }
 
 impl From<Vec<u8>> for TeletexString {
@@ -50,3 +228,40 @@ impl Decode for TeletexString {
         decoder.decode_teletex_string(tag, constraints)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diacritics() {
+        for byte in 0xC1..=0xCF {
+            if let Some(diacritic) = Diacritic::from_byte(byte) {
+                assert_eq!(
+                    diacritic.bases.chars().count(),
+                    diacritic.composed.chars().count()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn t61() {
+        let string = TeletexString::from(b"M\xC8unchen \xA3\xA65 \xE8\xC2odz".to_vec());
+        assert_eq!("München £#5 Łódz", string.to_string().unwrap());
+
+        let string = TeletexString::from(b"\xC2Q\xE9".to_vec());
+        assert_eq!("Q\u{301}Ø", string.to_string().unwrap());
+    }
+
+    #[test]
+    fn invalid_t61() {
+        let string = TeletexString::from(b"a$b\xC3".to_vec());
+        assert_eq!(1, string.to_string().unwrap_err().index);
+        assert_eq!("a\u{FFFD}b\u{FFFD}", string.to_string_lossy());
+
+        let string = TeletexString::from(b"\xC1\xC2e".to_vec());
+        assert!(string.to_string().is_err());
+        assert_eq!("\u{FFFD}é", string.to_string_lossy());
+    }
+}
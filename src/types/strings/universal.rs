@@ -0,0 +1,183 @@
+use super::*;
+
+/// A string of ISO/IEC 10646 characters, encoded as UCS-4 (four big endian
+/// octets per character).
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UniversalString(String);
+
+impl UniversalString {
+    /// Converts the string into a set of big endian UCS-4 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .chars()
+            .flat_map(|ch| u32::from(ch).to_be_bytes())
+            .collect()
+    }
+
+    /// The number of bytes in the string's UCS-4 encoding.
+    pub(crate) fn byte_len(&self) -> usize {
+        self.0.chars().count() * 4
+    }
+
+    /// Returns the string as a `str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts `self` into a `String`.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl StaticPermittedAlphabet for UniversalString {
+    // Every character is encoded as its own 32-bit value, rather than as an
+    // index into a character set.
+    const CHARACTER_SET: &'static [u32] = &[];
+    const CHARACTER_WIDTH: u32 = u32::BITS;
+
+    fn push_char(&mut self, ch: u32) {
+        debug_assert!(char::from_u32(ch).is_some(), "{} is not a character", ch);
+        self.0.extend(char::from_u32(ch));
+    }
+
+    fn chars(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        Box::from(self.0.chars().map(u32::from))
+    }
+
+    fn to_index_string(&self) -> crate::types::BitString {
+        self.to_bit_string()
+    }
+
+    fn to_octet_aligned_index_string(&self) -> Vec<u8> {
+        self.to_octet_aligned_string()
+    }
+
+    fn character_width() -> u32 {
+        Self::CHARACTER_WIDTH
+    }
+
+    fn try_from_permitted_alphabet(
+        input: &crate::types::BitStr,
+        alphabet: Option<&alloc::collections::BTreeMap<u32, u32>>,
+    ) -> Result<Self, FromPermittedAlphabetError> {
+        match alphabet {
+            Some(alphabet) => constrained::try_from_permitted_alphabet(input, alphabet),
+            None => Self::try_from_bits(input.to_bitvec(), Self::CHARACTER_WIDTH as usize),
+        }
+    }
+
+    fn try_from_bits(
+        bits: crate::types::BitString,
+        character_width: usize,
+    ) -> Result<Self, FromPermittedAlphabetError> {
+        use bitvec::field::BitField;
+
+        if bits.len() % character_width != 0 {
+            return Err(FromPermittedAlphabetError::InvalidData {
+                length: bits.len(),
+                width: character_width,
+            });
+        }
+
+        bits.chunks_exact(character_width)
+            .map(|ch| {
+                let index = ch.load_be();
+                char::from_u32(index).ok_or(FromPermittedAlphabetError::IndexNotFound { index })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[derive(snafu::Snafu, Debug)]
+#[snafu(visibility(pub(crate)))]
+#[snafu(display("Invalid universal string"))]
+pub struct InvalidUniversalString;
+
+impl TryFrom<&'_ [u8]> for UniversalString {
+    type Error = InvalidUniversalString;
+
+    /// Converts a set of big endian UCS-4 bytes into a string.
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() & 3 != 0 {
+            return Err(InvalidUniversalString);
+        }
+
+        (0..value.len())
+            .step_by(4)
+            .map(|i| {
+                let ch = u32::from_be_bytes([value[i], value[i + 1], value[i + 2], value[i + 3]]);
+                char::from_u32(ch).ok_or(InvalidUniversalString)
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl TryFrom<Vec<u8>> for UniversalString {
+    type Error = InvalidUniversalString;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from(&*value)
+    }
+}
+
+impl From<String> for UniversalString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&'_ str> for UniversalString {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl From<UniversalString> for String {
+    fn from(value: UniversalString) -> Self {
+        value.0
+    }
+}
+
+impl core::ops::Deref for UniversalString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl core::fmt::Display for UniversalString {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsnType for UniversalString {
+    const TAG: Tag = Tag::UNIVERSAL_STRING;
+}
+
+impl Encode for UniversalString {
+    fn encode_with_tag_and_constraints<E: Encoder>(
+        &self,
+        encoder: &mut E,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<(), E::Error> {
+        encoder
+            .encode_universal_string(tag, constraints, self)
+            .map(drop)
+    }
+}
+
+impl Decode for UniversalString {
+    fn decode_with_tag_and_constraints<D: Decoder>(
+        decoder: &mut D,
+        tag: Tag,
+        constraints: Constraints,
+    ) -> Result<Self, D::Error> {
+        decoder.decode_universal_string(tag, constraints)
+    }
+}
@@ -251,8 +251,13 @@ impl TagTree {
         true
     }
 
+    /// Whether any tag in `self` is also in `other`.
+    pub const fn overlaps(&self, other: &TagTree) -> bool {
+        Self::tree_contains(self, core::slice::from_ref(other))
+    }
+
     /// Whether any `Leaf` in `needle` matches any `Leaf`s in `nodes`.
-    const fn tree_contains(needle: &TagTree, nodes: &'static [TagTree]) -> bool {
+    const fn tree_contains(needle: &TagTree, nodes: &[TagTree]) -> bool {
         match needle {
             TagTree::Choice(inner_tags) => {
                 let mut inner_index = 0;
@@ -18,6 +18,37 @@ pub fn encode<T: crate::Encode>(value: &T) -> Result<alloc::vec::Vec<u8>, crate:
     crate::per::encode(enc::EncoderOptions::unaligned(), value)
 }
 
+/// Attempts to encode `value` to UPER-CANONICAL, appending the encoding to `buffer`.
+pub fn encode_to<T: crate::Encode>(
+    value: &T,
+    buffer: &mut impl bytes::BufMut,
+) -> Result<(), crate::per::enc::Error> {
+    crate::per::encode_to(enc::EncoderOptions::unaligned(), value, buffer)
+}
+
+/// Attempts to encode `value` to UPER-CANONICAL at the start of `buffer`,
+/// returning the number of bytes written.
+pub fn encode_into_slice<T: crate::Encode>(
+    value: &T,
+    buffer: &mut [u8],
+) -> Result<usize, crate::per::enc::Error> {
+    crate::per::encode_into_slice(enc::EncoderOptions::unaligned(), value, buffer)
+}
+
+/// Attempts to encode `value` to UPER-CANONICAL, writing the encoding to `writer`.
+#[cfg(feature = "std")]
+pub fn encode_to_writer<T: crate::Encode>(
+    value: &T,
+    writer: impl std::io::Write,
+) -> Result<(), crate::per::enc::Error> {
+    crate::per::encode_to_writer(enc::EncoderOptions::unaligned(), value, writer)
+}
+
+/// Returns the length of `value`'s UPER-CANONICAL encoding.
+pub fn encoded_len<T: crate::Encode>(value: &T) -> Result<usize, crate::per::enc::Error> {
+    crate::per::encoded_len(enc::EncoderOptions::unaligned(), value)
+}
+
 /// Attempts to decode `T` from `input` using UPER-BASIC.
 pub fn decode_with_constraints<T: crate::Decode>(
     constraints: Constraints,
@@ -47,6 +78,40 @@ mod tests {
         round_trip!(uper, bool, false, &[0]);
     }
 
+    #[test]
+    fn encode_to_buffers() {
+        use crate::uper::{encode, encode_into_slice, encode_to};
+
+        let value = Integer::from(32768);
+        let expected = encode(&value).unwrap();
+
+        let mut buffer = bytes::BytesMut::new();
+        encode_to(&value, &mut buffer).unwrap();
+        assert_eq!(&buffer[..], expected);
+
+        let mut slice = [0; 8];
+        assert_eq!(
+            expected.len(),
+            encode_into_slice(&value, &mut slice).unwrap()
+        );
+        assert_eq!(&slice[..expected.len()], expected);
+
+        assert!(matches!(
+            encode_into_slice(&value, &mut [0; 2]),
+            Err(crate::per::enc::Error::BufferTooSmall {
+                needed: 4,
+                available: 2
+            })
+        ));
+
+        #[cfg(feature = "std")]
+        {
+            let mut writer = Vec::new();
+            crate::uper::encode_to_writer(&value, &mut writer).unwrap();
+            assert_eq!(writer, expected);
+        }
+    }
+
     #[test]
     fn integer() {
         round_trip!(uper, Integer, 32768.into(), &[0x03, 0x00, 0x80, 0x00]);
@@ -81,6 +146,33 @@ mod tests {
         round_trip!(uper, E, Integer::from(1000).into(), &[]);
     }
 
+    #[test]
+    fn primitive_integer() {
+        round_trip!(uper, u8, 200, &[0xc8]);
+        round_trip!(uper, i8, -1, &[0x7f]);
+        round_trip!(uper, i64, i64::MIN, &[0x01, 0x00]);
+        round_trip!(
+            uper,
+            i64,
+            i64::MAX,
+            &[0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+        round_trip!(
+            uper,
+            u64,
+            u64::MAX,
+            &[0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+
+        // Primitive integers are encoded the same way as `INTEGER` values.
+        assert_eq!(
+            crate::uper::encode(&i64::MIN).unwrap(),
+            crate::uper::encode_with_constraints(i64::CONSTRAINTS, &Integer::from(i64::MIN))
+                .unwrap()
+        );
+        assert!(crate::uper::decode::<u32>(&[0x05, 0x01, 0, 0, 0, 0]).is_err());
+    }
+
     #[test]
     fn sequence_of() {
         round_trip!(uper, Vec<u8>, vec![1; 5], &[0b00000101, 1, 1, 1, 1, 1]);
@@ -218,6 +310,33 @@ mod tests {
         round_trip!(aper, Choice, Choice::Medium, &[0x80, 1, 0]);
     }
 
+    #[test]
+    fn choice_index() {
+        use crate as rasn;
+        // The index of the alternative is constrained to `0..=1`, and
+        // `0..=3`, so it takes one and two bits respectively.
+        #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
+        #[rasn(choice, automatic_tags)]
+        enum Pair {
+            A,
+            B,
+        }
+
+        #[derive(AsnType, Decode, Debug, Encode, PartialEq)]
+        #[rasn(choice, automatic_tags)]
+        enum Quad {
+            A,
+            B,
+            C,
+            D,
+        }
+
+        round_trip!(uper, Pair, Pair::B, &[0x80]);
+        round_trip!(uper, Quad, Quad::C, &[0x80]);
+        round_trip!(uper, Quad, Quad::D, &[0xC0]);
+        round_trip!(aper, Quad, Quad::D, &[0xC0]);
+    }
+
     #[test]
     fn enumerated() {
         #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
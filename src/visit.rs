@@ -0,0 +1,756 @@
+//! Walking the values of ASN.1 types.
+//!
+//! A [`Visitor`] is called for every value in a type that implements
+//! [`Encode`], along with the ASN.1 identifier, tag and constraints of the
+//! value. This makes it possible to write tools such as pretty printers or
+//! redaction filters once for every type, rather than for each codec.
+//!
+//! Types that derive [`Encode`] visit their fields and alternatives through
+//! [`Encode::accept_with_context`], which the derive generates. Other types
+//! are walked through their encoding, so the components of hand written
+//! `SEQUENCE` and `CHOICE` types are visited without identifiers.
+//!
+//! ```
+//! use rasn::{prelude::*, visit::{Context, Primitive, Visit, Visitor}};
+//!
+//! #[derive(AsnType, Encode)]
+//! #[rasn(rename_all = "camelCase")]
+//! struct Account {
+//!     user_name: Utf8String,
+//!     password: Utf8String,
+//! }
+//!
+//! /// Collects the names of every string field.
+//! struct Names(Vec<&'static str>);
+//!
+//! impl Visitor for Names {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn visit_primitive(&mut self, context: &Context, value: Primitive) -> Result<(), Self::Error> {
+//!         if let (Some(name), Primitive::Utf8String(_)) = (context.name, value) {
+//!             self.0.push(name);
+//!         }
+//!
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let account = Account { user_name: "alice".into(), password: "hunter2".into() };
+//! let mut names = Names(Vec::new());
+//! account.accept(&mut names).unwrap();
+//!
+//! assert_eq!(vec!["userName", "password"], names.0);
+//! ```
+
+use crate::{
+    enc::Encoder,
+    types::{self, Constraints, Enumerated, Tag},
+    Encode,
+};
+
+/// A value that can be walked by a [`Visitor`], which is every type that
+/// implements [`Encode`].
+pub trait Visit {
+    /// Calls `visitor` for `self` and every value contained in `self`.
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> Result<(), V::Error>;
+}
+
+impl<T: Encode> Visit for T {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> Result<(), V::Error> {
+        let context = Context {
+            name: None,
+            tag: T::TAG,
+            explicit_tag: None,
+            constraints: T::CONSTRAINTS,
+        };
+        self.accept_with_context(visitor, context)
+    }
+}
+
+/// Receives the values of a type as it's walked by [`Visit::accept`].
+pub trait Visitor {
+    type Error: crate::enc::Error;
+
+    /// Visits a value that contains no other values.
+    fn visit_primitive(&mut self, context: &Context, value: Primitive) -> Result<(), Self::Error>;
+
+    /// Called before the values in a constructed value are visited.
+    fn enter_constructed(&mut self, context: &Context, kind: Kind) -> Result<(), Self::Error> {
+        let _ = (context, kind);
+        Ok(())
+    }
+
+    /// Called after the values in a constructed value have been visited.
+    fn leave_constructed(&mut self, context: &Context, kind: Kind) -> Result<(), Self::Error> {
+        let _ = (context, kind);
+        Ok(())
+    }
+
+    /// Visits an `OPTIONAL` component that is absent, or a `DEFAULT`
+    /// component whose value is the default.
+    fn visit_absent(&mut self, context: &Context) -> Result<(), Self::Error> {
+        let _ = context;
+        Ok(())
+    }
+}
+
+/// Where a value is in the type being visited.
+#[derive(Debug, Clone)]
+pub struct Context<'a> {
+    /// The ASN.1 identifier of the component or alternative, or `None` for
+    /// the outermost value and the elements of `SEQUENCE OF` and `SET OF`.
+    pub name: Option<&'static str>,
+    /// The tag of the value.
+    pub tag: Tag,
+    /// The explicit tag around the value, if it's explicitly tagged.
+    pub explicit_tag: Option<Tag>,
+    /// The constraints on the value.
+    pub constraints: Constraints<'a>,
+}
+
+/// The kinds of constructed values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Sequence,
+    SequenceOf,
+    Set,
+    SetOf,
+    Choice,
+}
+
+/// A value that contains no other values.
+#[derive(Debug, Clone, Copy)]
+pub enum Primitive<'a> {
+    Any(&'a types::Any),
+    Bool(bool),
+    BitString(&'a types::BitStr),
+    /// An `ENUMERATED` value, with its ASN.1 identifier, if it's known, and
+    /// discriminant.
+    Enumerated(Option<&'static str>, isize),
+    ObjectIdentifier(&'a [u32]),
+    Integer(&'a types::Integer),
+    Null,
+    OctetString(&'a [u8]),
+    GeneralString(&'a types::GeneralString),
+    Utf8String(&'a str),
+    VisibleString(&'a types::VisibleString),
+    Ia5String(&'a types::Ia5String),
+    PrintableString(&'a types::PrintableString),
+    NumericString(&'a types::NumericString),
+    TeletexString(&'a types::TeletexString),
+    BmpString(&'a types::BmpString),
+    UniversalString(&'a types::UniversalString),
+    GeneralizedTime(&'a types::GeneralizedTime),
+    UtcTime(&'a types::UtcTime),
+}
+
+/// The name and explicit tag of the value being walked, which are used by
+/// the first value the walker is given.
+#[derive(Clone, Copy, Default)]
+struct Pending {
+    name: Option<&'static str>,
+    explicit_tag: Option<Tag>,
+}
+
+/// Walks `value` through its [`Encode`] implementation, which is the
+/// default implementation of [`Encode::accept_with_context`].
+pub(crate) fn walk<T: Encode + ?Sized, V: Visitor>(
+    value: &T,
+    visitor: &mut V,
+    context: Context,
+) -> Result<(), V::Error> {
+    let Context {
+        name,
+        tag,
+        explicit_tag,
+        constraints,
+    } = context;
+    let mut walker = Walker {
+        visitor,
+        pending: Some(Pending { name, explicit_tag }),
+    };
+
+    // `CHOICE` types can't be implicitly tagged, and are only encoded
+    // through `encode`.
+    if T::TAG == Tag::EOC {
+        value.encode(&mut walker)
+    } else {
+        value.encode_with_tag_and_constraints(&mut walker, tag, constraints)
+    }
+}
+
+/// An encoder that calls a [`Visitor`] rather than encoding values.
+///
+/// The walker doesn't implement any of the standard encoding rules, so
+/// [`Encoder::codec`] is `None`. Values the walker is given, such as the
+/// elements of a `SEQUENCE OF`, are visited through their own
+/// [`Encode::accept_with_context`].
+struct Walker<'v, V> {
+    visitor: &'v mut V,
+    pending: Option<Pending>,
+}
+
+impl<'v, V: Visitor> Walker<'v, V> {
+    fn context<'c>(&mut self, tag: Tag, constraints: Constraints<'c>) -> Context<'c> {
+        let Pending { name, explicit_tag } = self.pending.take().unwrap_or_default();
+
+        Context {
+            name,
+            tag,
+            explicit_tag,
+            constraints,
+        }
+    }
+
+    fn primitive(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: Primitive,
+    ) -> Result<(), V::Error> {
+        let context = self.context(tag, constraints);
+        self.visitor.visit_primitive(&context, value)
+    }
+
+    fn constructed(
+        &mut self,
+        context: Context,
+        kind: Kind,
+        visit: impl FnOnce(&mut Self) -> Result<(), V::Error>,
+    ) -> Result<(), V::Error> {
+        self.visitor.enter_constructed(&context, kind)?;
+        (visit)(self)?;
+        self.visitor.leave_constructed(&context, kind)
+    }
+
+    /// Visits a value inside the value being walked.
+    fn nested<E: Encode + ?Sized>(
+        &mut self,
+        tag: Tag,
+        explicit_tag: Option<Tag>,
+        constraints: Constraints,
+        value: &E,
+    ) -> Result<(), V::Error> {
+        let mut context = self.context(tag, constraints);
+        context.explicit_tag = explicit_tag.or(context.explicit_tag);
+        value.accept_with_context(self.visitor, context)
+    }
+
+    /// Visits the elements of a `SEQUENCE OF` or `SET OF`.
+    fn elements<'e, E: Encode + 'e>(
+        &mut self,
+        context: Context,
+        kind: Kind,
+        elements: impl IntoIterator<Item = &'e E>,
+    ) -> Result<(), V::Error> {
+        self.constructed(context, kind, |walker| {
+            elements
+                .into_iter()
+                .try_for_each(|element| walker.nested(E::TAG, None, E::CONSTRAINTS, element))
+        })
+    }
+}
+
+impl<V: Visitor> Encoder for Walker<'_, V> {
+    type Ok = ();
+    type Error = V::Error;
+
+    fn encode_any(&mut self, tag: Tag, value: &types::Any) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, <_>::default(), Primitive::Any(value))
+    }
+
+    fn encode_bool(&mut self, tag: Tag, value: bool) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, <_>::default(), Primitive::Bool(value))
+    }
+
+    fn encode_bit_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::BitString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::BitString(value))
+    }
+
+    fn encode_enumerated<E: Enumerated>(
+        &mut self,
+        tag: Tag,
+        value: &E,
+    ) -> Result<Self::Ok, Self::Error> {
+        let value = Primitive::Enumerated(value.identifier(), value.discriminant());
+        self.primitive(tag, <_>::default(), value)
+    }
+
+    fn encode_object_identifier(
+        &mut self,
+        tag: Tag,
+        value: &[u32],
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, <_>::default(), Primitive::ObjectIdentifier(value))
+    }
+
+    fn encode_integer(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::Integer,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::Integer(value))
+    }
+
+    fn encode_null(&mut self, tag: Tag) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, <_>::default(), Primitive::Null)
+    }
+
+    fn encode_octet_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &[u8],
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::OctetString(value))
+    }
+
+    fn encode_general_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::GeneralString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::GeneralString(value))
+    }
+
+    fn encode_utf8_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::Utf8String(value))
+    }
+
+    fn encode_visible_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::VisibleString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::VisibleString(value))
+    }
+
+    fn encode_ia5_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::Ia5String,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::Ia5String(value))
+    }
+
+    fn encode_printable_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::PrintableString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::PrintableString(value))
+    }
+
+    fn encode_numeric_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::NumericString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::NumericString(value))
+    }
+
+    fn encode_teletex_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::TeletexString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::TeletexString(value))
+    }
+
+    fn encode_bmp_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::BmpString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::BmpString(value))
+    }
+
+    fn encode_universal_string(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &types::UniversalString,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, constraints, Primitive::UniversalString(value))
+    }
+
+    fn encode_generalized_time(
+        &mut self,
+        tag: Tag,
+        value: &types::GeneralizedTime,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, <_>::default(), Primitive::GeneralizedTime(value))
+    }
+
+    fn encode_utc_time(
+        &mut self,
+        tag: Tag,
+        value: &types::UtcTime,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.primitive(tag, <_>::default(), Primitive::UtcTime(value))
+    }
+
+    fn encode_explicit_prefix<E: Encode>(
+        &mut self,
+        tag: Tag,
+        value: &E,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.nested(E::TAG, Some(tag), E::CONSTRAINTS, value)
+    }
+
+    fn encode_sequence<C, F>(&mut self, tag: Tag, encoder_scope: F) -> Result<Self::Ok, Self::Error>
+    where
+        C: types::Constructed,
+        F: FnOnce(&mut Self) -> Result<(), Self::Error>,
+    {
+        let context = self.context(tag, <_>::default());
+        self.constructed(context, Kind::Sequence, encoder_scope)
+    }
+
+    fn encode_sequence_of<E: Encode>(
+        &mut self,
+        tag: Tag,
+        value: &[E],
+        constraints: Constraints,
+    ) -> Result<Self::Ok, Self::Error> {
+        let context = self.context(tag, constraints);
+        self.elements(context, Kind::SequenceOf, value)
+    }
+
+    fn encode_set<C, F>(&mut self, tag: Tag, value: F) -> Result<Self::Ok, Self::Error>
+    where
+        C: types::Constructed,
+        F: FnOnce(&mut Self) -> Result<(), Self::Error>,
+    {
+        let context = self.context(tag, <_>::default());
+        self.constructed(context, Kind::Set, value)
+    }
+
+    fn encode_set_of<E: Encode>(
+        &mut self,
+        tag: Tag,
+        value: &types::SetOf<E>,
+        constraints: Constraints,
+    ) -> Result<Self::Ok, Self::Error> {
+        let context = self.context(tag, constraints);
+        self.elements(context, Kind::SetOf, value)
+    }
+
+    fn encode_some<E: Encode>(&mut self, value: &E) -> Result<Self::Ok, Self::Error> {
+        self.nested(E::TAG, None, E::CONSTRAINTS, value)
+    }
+
+    fn encode_some_with_tag_and_constraints<E: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: &E,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.nested(tag, None, constraints, value)
+    }
+
+    fn encode_none<E: Encode>(&mut self) -> Result<Self::Ok, Self::Error> {
+        self.encode_none_with_tag(E::TAG)
+    }
+
+    fn encode_none_with_tag(&mut self, tag: Tag) -> Result<Self::Ok, Self::Error> {
+        let context = self.context(tag, <_>::default());
+        self.visitor.visit_absent(&context)
+    }
+
+    fn encode_choice<E: Encode + types::Choice>(
+        &mut self,
+        constraints: Constraints,
+        encode_fn: impl FnOnce(&mut Self) -> Result<Tag, Self::Error>,
+    ) -> Result<Self::Ok, Self::Error> {
+        let context = self.context(E::TAG, constraints);
+        self.constructed(context, Kind::Choice, |walker| {
+            (encode_fn)(walker).map(drop)
+        })
+    }
+
+    fn encode_extension_addition<E: Encode>(
+        &mut self,
+        tag: Tag,
+        constraints: Constraints,
+        value: E,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.nested(tag, None, constraints, &value)
+    }
+
+    fn encode_extension_addition_group<E>(
+        &mut self,
+        value: Option<&E>,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        E: Encode + types::Constructed,
+    {
+        match value {
+            Some(value) => self.nested(E::TAG, None, E::CONSTRAINTS, value),
+            None => self.encode_none::<E>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec, vec::Vec};
+
+    use super::*;
+    use crate as rasn;
+    use crate::prelude::*;
+
+    /// Records every value as a line of text.
+    #[derive(Default)]
+    struct Printer(Vec<String>);
+
+    impl Visitor for Printer {
+        type Error = core::convert::Infallible;
+
+        fn visit_primitive(
+            &mut self,
+            context: &Context,
+            value: Primitive,
+        ) -> Result<(), Self::Error> {
+            let value = match value {
+                Primitive::Bool(value) => format!("{}", value),
+                Primitive::Integer(value) => format!("{}", value),
+                Primitive::Enumerated(identifier, discriminant) => {
+                    identifier.map_or_else(|| discriminant.to_string(), String::from)
+                }
+                Primitive::Utf8String(value) => format!("{:?}", value),
+                value => format!("{:?}", value),
+            };
+
+            self.0
+                .push(format!("{} {}", context.name.unwrap_or("-"), value));
+            Ok(())
+        }
+
+        fn enter_constructed(&mut self, context: &Context, kind: Kind) -> Result<(), Self::Error> {
+            self.0
+                .push(format!("{} {:?} {{", context.name.unwrap_or("-"), kind));
+            Ok(())
+        }
+
+        fn leave_constructed(&mut self, _: &Context, _: Kind) -> Result<(), Self::Error> {
+            self.0.push(String::from("}"));
+            Ok(())
+        }
+
+        fn visit_absent(&mut self, context: &Context) -> Result<(), Self::Error> {
+            self.0
+                .push(format!("{} absent", context.name.unwrap_or("-")));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn names_every_value() {
+        #[derive(AsnType, Clone, Copy, Debug, Encode, PartialEq)]
+        #[rasn(enumerated, rename_all = "camelCase")]
+        enum Status {
+            Good,
+            Revoked,
+        }
+
+        #[derive(AsnType, Encode)]
+        #[rasn(choice)]
+        enum Holder {
+            #[rasn(tag(0), identifier = "name")]
+            Name(Utf8String),
+            #[rasn(tag(explicit(1)), identifier = "serial")]
+            Serial(Integer),
+        }
+
+        #[derive(AsnType, Encode)]
+        #[rasn(rename_all = "camelCase")]
+        struct Record {
+            status: Status,
+            holder: Holder,
+            #[rasn(tag(explicit(0)))]
+            serial_numbers: Vec<Integer>,
+            comment: Option<Utf8String>,
+            is_valid: Option<bool>,
+        }
+
+        let record = Record {
+            status: Status::Revoked,
+            holder: Holder::Serial(5.into()),
+            serial_numbers: vec![1.into(), 2.into()],
+            comment: None,
+            is_valid: Some(true),
+        };
+
+        let mut printer = Printer::default();
+        record.accept(&mut printer).unwrap();
+
+        assert_eq!(
+            vec![
+                "- Sequence {",
+                "status revoked",
+                "holder Choice {",
+                "serial 5",
+                "}",
+                "serialNumbers SequenceOf {",
+                "- 1",
+                "- 2",
+                "}",
+                "comment absent",
+                "isValid true",
+                "}",
+            ],
+            printer.0
+        );
+
+        let mut printer = Printer::default();
+        Holder::Name("alice".into()).accept(&mut printer).unwrap();
+
+        assert_eq!(vec!["- Choice {", "name \"alice\"", "}"], printer.0);
+    }
+
+    #[test]
+    fn visits_absent_explicitly_tagged_fields() {
+        #[derive(AsnType, Encode)]
+        #[rasn(rename_all = "camelCase")]
+        struct Certificate {
+            #[rasn(tag(explicit(0)), default)]
+            version: Integer,
+            #[rasn(tag(explicit(1)))]
+            issuer_id: Option<Utf8String>,
+            serial_number: Integer,
+        }
+
+        let certificate = Certificate {
+            version: 0.into(),
+            issuer_id: None,
+            serial_number: 5.into(),
+        };
+
+        let mut printer = Printer::default();
+        certificate.accept(&mut printer).unwrap();
+
+        assert_eq!(
+            vec![
+                "- Sequence {",
+                "version absent",
+                "issuerId absent",
+                "serialNumber 5",
+                "}",
+            ],
+            printer.0
+        );
+    }
+
+    #[test]
+    fn walks_hand_written_types() {
+        /// A `SEQUENCE` without a derived `Encode`.
+        struct Point {
+            x: Integer,
+            y: Integer,
+        }
+
+        impl AsnType for Point {
+            const TAG: Tag = Tag::SEQUENCE;
+        }
+
+        impl types::Constructed for Point {
+            const FIELDS: types::fields::Fields = types::fields::Fields::from_static(&[
+                types::fields::Field::new_required(Integer::TAG, Integer::TAG_TREE),
+                types::fields::Field::new_required(Integer::TAG, Integer::TAG_TREE),
+            ]);
+        }
+
+        impl Encode for Point {
+            fn encode_with_tag_and_constraints<E: Encoder>(
+                &self,
+                encoder: &mut E,
+                tag: Tag,
+                _: Constraints,
+            ) -> Result<(), E::Error> {
+                encoder
+                    .encode_sequence::<Self, _>(tag, |encoder| {
+                        self.x.encode(encoder)?;
+                        self.y.encode(encoder)?;
+                        Ok(())
+                    })
+                    .map(drop)
+            }
+        }
+
+        #[derive(AsnType, Encode)]
+        struct Line {
+            start: Point,
+            end: Option<Point>,
+        }
+
+        let line = Line {
+            start: Point {
+                x: 1.into(),
+                y: 2.into(),
+            },
+            end: None,
+        };
+
+        let mut printer = Printer::default();
+        line.accept(&mut printer).unwrap();
+
+        assert_eq!(
+            vec![
+                "- Sequence {",
+                "start Sequence {",
+                "- 1",
+                "- 2",
+                "}",
+                "end absent",
+                "}",
+            ],
+            printer.0
+        );
+    }
+
+    #[test]
+    fn visits_contained_values() {
+        #[derive(AsnType, Encode)]
+        struct Wrapper {
+            contents: types::Containing<Utf8String>,
+            encoded: types::Containing<bool, types::containing::Der>,
+        }
+
+        let wrapper = Wrapper {
+            contents: types::Containing::new("alice".into()),
+            encoded: types::Containing::new(true),
+        };
+
+        let mut printer = Printer::default();
+        wrapper.accept(&mut printer).unwrap();
+
+        assert_eq!(
+            vec![
+                "- Sequence {",
+                "contents \"alice\"",
+                "encoded OctetString([1, 1, 255])",
+                "}",
+            ],
+            printer.0
+        );
+    }
+}
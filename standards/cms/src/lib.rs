@@ -7,6 +7,7 @@ pub mod algorithms;
 pub mod authenticode;
 pub mod firmware_wrapper;
 pub mod pkcs7_compat;
+pub mod registry;
 
 use alloc::boxed::Box;
 
@@ -101,6 +102,18 @@ pub struct ContentInfo {
     pub content: Any,
 }
 
+impl ContentInfo {
+    /// Decodes `content` as the type registered for `content_type` in
+    /// `registry`, returning `Ok(None)` if the content type is unknown to
+    /// `registry`.
+    pub fn decode_content<T>(
+        &self,
+        registry: &OpenTypeRegistry<T>,
+    ) -> Result<Option<T>, rasn::codec::DecodeError> {
+        registry.decode_any(rasn::Codec::Der, &self.content_type, &self.content)
+    }
+}
+
 /// SignedData represents a signed-data content type
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SignedData {
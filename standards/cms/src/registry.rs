@@ -0,0 +1,71 @@
+//! # Open Type Registries
+//! Pre-populated [`OpenTypeRegistry`]s for the content types and attribute
+//! types defined in this crate, for resolving the content of a
+//! [`ContentInfo`] or the values of an [`Attribute`] to their concrete types.
+//!
+//! [`ContentInfo`]: crate::ContentInfo
+//! [`Attribute`]: crate::Attribute
+
+use rasn::prelude::*;
+pub use rasn_pkix::registry::Registry;
+
+use crate::{authenticode::*, firmware_wrapper::*, *};
+
+/// Returns a registry of every content type defined in this crate.
+pub fn content_types() -> Registry {
+    let mut registry = Registry::new();
+    register_content_types(&mut registry);
+    registry
+}
+
+/// Returns a registry of every attribute type defined in this crate and in
+/// [`rasn_pkix`].
+pub fn attributes() -> Registry {
+    let mut registry = rasn_pkix::registry::attributes();
+    register_attributes(&mut registry);
+    registry
+}
+
+/// Registers every content type defined in this crate with `registry`.
+pub fn register_content_types(registry: &mut Registry) -> &mut Registry {
+    registry
+        // RFC 5652 content types.
+        .register_boxed::<OctetString>(CONTENT_DATA)
+        .register_boxed::<SignedData>(CONTENT_SIGNED_DATA)
+        .register_boxed::<EnvelopedData>(CONTENT_ENVELOPED_DATA)
+        .register_boxed::<DigestedData>(CONTENT_DIGESTED_DATA)
+        .register_boxed::<EncryptedData>(CONTENT_ENCRYPTED_DATA)
+        .register_boxed::<AuthenticatedData>(CONTENT_AUTHENTICATED_DATA)
+        .register_boxed::<AuthEnvelopedData>(
+            Oid::ISO_MEMBER_BODY_US_RSADSI_PKCS9_SMIME_CT_AUTH_ENVELOPED_DATA,
+        )
+        // RFC 4108 content types.
+        .register_boxed::<FirmwarePackageData>(FIRMWARE_PACKAGE)
+        .register_boxed::<FirmwarePackageLoadReceipt>(FIRMWARE_LOAD_RECEIPT)
+        .register_boxed::<FirmwarePackageLoadError>(FIRMWARE_LOAD_ERROR)
+        // Authenticode content types.
+        .register_boxed::<SpcIndirectDataContent>(SPC_INDIRECT_DATA_OBJID)
+}
+
+/// Registers every attribute type defined in this crate with `registry`.
+pub fn register_attributes(registry: &mut Registry) -> &mut Registry {
+    registry
+        // RFC 5652 attributes.
+        .register_boxed::<ContentType>(CONTENT_TYPE)
+        .register_boxed::<Digest>(MESSAGE_DIGEST)
+        .register_boxed::<rasn_pkix::Time>(SIGNING_TIME)
+        .register_boxed::<SignerInfo>(COUNTER_SIGNATURE)
+        // RFC 5755 attribute certificate attributes.
+        .register_boxed::<ContentInfo>(rasn_pkix::attribute_certificate::ENC_ATTRIBUTES)
+        // RFC 4108 attributes.
+        .register_boxed::<FirmwarePackageIdentifier>(FIRMWARE_PACKAGE_ID)
+        .register_boxed::<TargetHardwareIdentifiers>(TARGET_HARDWARE_IDS)
+        .register_boxed::<DecryptKeyIdentifier>(DECRYPT_KEY_ID)
+        .register_boxed::<ImplementedCryptoAlgorithms>(CRYPTO_ALGORITHMS)
+        .register_boxed::<ImplementedCompressAlgorithms>(COMPRESS_ALGORITHMS)
+        .register_boxed::<CommunityIdentifiers>(COMMUNITY_IDENTIFIERS)
+        .register_boxed::<FirmwarePackageInfo>(FIRMWARE_PACKAGE_INFO)
+        .register_boxed::<WrappedFirmwareKey>(WRAPPED_FIRMWARE_KEY)
+        // Authenticode attributes.
+        .register_boxed::<SpcSpOpusInfo>(SPC_SP_OPUS_INFO_OBJID)
+}
@@ -62,3 +62,29 @@ fn test_authenticode() {
         _ => panic!("Unexpected SpcUuid value"),
     }
 }
+
+#[test]
+fn test_cms_registry() {
+    let info = decode::<ContentInfo>(SIGNED_DATA).unwrap();
+    let content = info
+        .decode_content(&registry::content_types())
+        .unwrap()
+        .unwrap();
+    let data = content.downcast_ref::<SignedData>().unwrap();
+    assert_eq!(CONTENT_DATA, data.encap_content_info.content_type);
+
+    let attributes = registry::attributes();
+    for signer_info in &data.signer_infos {
+        let SignerIdentifier::IssuerAndSerialNumber(issuer) = &signer_info.sid else {
+            panic!("Unexpected SignerIdentifier value");
+        };
+        let Name::RdnSequence(rdns) = &issuer.issuer;
+
+        for attribute in rdns.iter().flat_map(|rdn| rdn.iter()) {
+            let value = attribute.decode_value(&attributes).unwrap().unwrap();
+            if attribute.r#type == rasn::types::Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_COMMON_NAME {
+                assert!(value.is::<rasn_pkix::X520CommonName>());
+            }
+        }
+    }
+}
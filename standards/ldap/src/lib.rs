@@ -62,7 +62,9 @@ pub type AttributeList = SequenceOf<Attribute>;
 /// The envelope for all LDAP operations.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct LdapMessage {
+    #[rasn(identifier = "messageID")]
     pub message_id: MessageId,
     pub protocol_op: ProtocolOp,
     #[rasn(tag(0))]
@@ -82,7 +84,7 @@ impl LdapMessage {
 
 /// The kind of operation in the [`LdapMessage`].
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum ProtocolOp {
     BindRequest(BindRequest),
@@ -98,7 +100,9 @@ pub enum ProtocolOp {
     AddResponse(AddResponse),
     DelRequest(DelRequest),
     DelResponse(DelResponse),
+    #[rasn(identifier = "modDNRequest")]
     ModDnRequest(ModifyDnRequest),
+    #[rasn(identifier = "modDNResponse")]
     ModDnResponse(ModifyDnResponse),
     CompareRequest(CompareRequest),
     CompareResponse(CompareResponse),
@@ -110,6 +114,7 @@ pub enum ProtocolOp {
 
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct AttributeValueAssertion {
     pub attribute_desc: AttributeDescription,
     pub assertion_value: AssertionValue,
@@ -127,6 +132,7 @@ impl AttributeValueAssertion {
 
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct PartialAttribute {
     pub r#type: AttributeDescription,
     pub vals: SetOf<AttributeValue>,
@@ -141,6 +147,7 @@ impl PartialAttribute {
 
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct Attribute {
     pub r#type: AttributeDescription,
     pub vals: SetOf<AttributeValue>,
@@ -156,9 +163,11 @@ impl Attribute {
 /// The envelope for the result of any operation.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct LdapResult {
     /// The code indicating the status of the operation.
     pub result_code: ResultCode,
+    #[rasn(identifier = "matchedDN")]
     pub matched_dn: LdapDn,
     pub diagnostic_message: LdapString,
     #[rasn(tag(3))]
@@ -182,8 +191,9 @@ impl LdapResult {
 }
 
 #[derive(AsnType, Encode, Decode, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(enumerated)]
+#[rasn(enumerated, rename_all = "camelCase")]
 #[non_exhaustive]
+#[repr(isize)]
 pub enum ResultCode {
     Success = 0,
     OperationsError = 1,
@@ -207,6 +217,7 @@ pub enum ResultCode {
     InvalidAttributeSyntax = 21,
     NoSuchObject = 32,
     AliasProblem = 33,
+    #[rasn(identifier = "invalidDNSyntax")]
     InvalidDnSyntax = 34,
     AliasDereferencingProblem = 36,
     InappropriateAuthentication = 48,
@@ -219,15 +230,22 @@ pub enum ResultCode {
     NamingViolation = 64,
     ObjectClassViolation = 65,
     NotAllowedOnNonLeaf = 66,
+    #[rasn(identifier = "notAllowedOnRDN")]
     NotAllowedOnRdn = 67,
     EntryAlreadyExists = 68,
     ObjectClassModsProhibited = 69,
+    #[rasn(identifier = "affectsMultipleDSAs")]
     AffectsMultipleDsas = 71,
     Other = 80,
+    /// A result code that isn't defined by RFC 4511, such as those used by
+    /// extensions or specific servers.
+    #[rasn(unknown)]
+    Unknown(isize),
 }
 
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct Control {
     pub control_type: LdapOid,
     #[rasn(default)]
@@ -258,7 +276,7 @@ impl Control {
 /// operation are given in [RFC 4513].
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
-#[rasn(tag(application, 0))]
+#[rasn(tag(application, 0), rename_all = "camelCase")]
 pub struct BindRequest {
     /// A version number indicating the version of the protocol to be used at
     /// the LDAP message layer. This document describes version 3 of the
@@ -294,7 +312,7 @@ impl BindRequest {
 /// by a client return a [`BindResponse`]
 /// with [`ResultCode::AuthMethodNotSupported`].
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum AuthenticationChoice {
     #[rasn(tag(0))]
@@ -306,6 +324,7 @@ pub enum AuthenticationChoice {
 
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct SaslCredentials {
     pub mechanism: LdapString,
     pub credentials: Option<OctetString>,
@@ -327,9 +346,10 @@ impl SaslCredentials {
 ///
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
-#[rasn(tag(application, 1))]
+#[rasn(tag(application, 1), rename_all = "camelCase")]
 pub struct BindResponse {
     pub result_code: ResultCode,
+    #[rasn(identifier = "matchedDN")]
     pub matched_dn: LdapDn,
     pub diagnostic_message: LdapString,
     #[rasn(tag(3))]
@@ -371,7 +391,7 @@ pub struct UnbindRequest;
 /// be used to read attributes from a single entry, from entries immediately
 /// subordinate to a particular entry, or from a whole subtree of entries.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 3))]
+#[rasn(tag(application, 3), rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct SearchRequest {
     /// The name of the base object entry (or possibly the root) relative to
@@ -439,7 +459,7 @@ impl SearchRequest {
 
 /// The scope of the search to be performed.
 #[derive(AsnType, Encode, Decode, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(enumerated)]
+#[rasn(enumerated, rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum SearchRequestScope {
     ///  The scope is constrained to the entry named by `base_object`.
@@ -461,7 +481,7 @@ pub enum SearchRequestScope {
 /// Servers **must** detect looping while dereferencing aliases in order to
 /// prevent denial-of-service attacks of this nature.
 #[derive(AsnType, Encode, Decode, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(enumerated)]
+#[rasn(enumerated, rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum SearchRequestDerefAliases {
     /// Do not dereference aliases in searching or in locating the base object
@@ -492,7 +512,7 @@ pub enum SearchRequestDerefAliases {
 /// Defines the conditions that must be fulfilled in order for the search to
 /// match a given entry.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum Filter {
     /// All matching rules must evaluate to `true`.
@@ -551,6 +571,7 @@ pub enum Filter {
 /// The SUBSTR matching rule for the attribute type or subtype.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct SubstringFilter {
     /// The type to match against.
     pub r#type: AttributeDescription,
@@ -567,7 +588,7 @@ impl SubstringFilter {
 
 /// Which part of the substring to match against.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 #[non_exhaustive]
 pub enum SubstringChoice {
     /// The start of a substrings filter, **must** be the first element.
@@ -585,6 +606,7 @@ pub enum SubstringChoice {
 /// Extensible match rule assertion.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 #[non_exhaustive]
+#[rasn(rename_all = "camelCase")]
 pub struct MatchingRuleAssertion {
     /// If the `matching_rule` is absent, the `type` field **MUST** be
     /// present, and an equality match is performed for that type.
@@ -635,7 +657,7 @@ impl MatchingRuleAssertion {
 
 /// An entry found during the search.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 4))]
+#[rasn(tag(application, 4), rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct SearchResultEntry {
     /// The name of the object found.
@@ -669,7 +691,7 @@ pub struct SearchResultDone(pub LdapResult);
 /// Allows a client to request that a modification of an entry be performed on
 /// its behalf by a server.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 6))]
+#[rasn(tag(application, 6), rename_all = "camelCase")]
 pub struct ModifyRequest {
     /// The name of the entry to be modified.
     pub object: LdapDn,
@@ -686,6 +708,7 @@ pub struct ModifyRequest {
 
 /// Modifications to be performed on an LDAP entry.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct ModifyRequestChanges {
     /// The type of modification being performed.
     pub operation: ChangeOperation,
@@ -695,7 +718,7 @@ pub struct ModifyRequestChanges {
 
 /// The type of modification being performed on an LDAP entry.
 #[derive(AsnType, Encode, Decode, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(enumerated)]
+#[rasn(enumerated, rename_all = "camelCase")]
 pub enum ChangeOperation {
     /// Add values listed to the modification attribute, creating the attribute
     /// if necessary.
@@ -727,7 +750,7 @@ pub struct ModifyResponse(pub LdapResult);
 
 /// Allows a client to request the addition of LDAP an entry into the directory.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 8))]
+#[rasn(tag(application, 8), rename_all = "camelCase")]
 pub struct AddRequest {
     /// The name of the entry to be added.
     pub entry: LdapDn,
@@ -765,7 +788,7 @@ pub struct DelResponse(pub LdapResult);
 /// entry in the directory and/or to move a subtree of entries to a new location
 /// in the directory.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 12))]
+#[rasn(tag(application, 12), rename_all = "camelCase")]
 pub struct ModifyDnRequest {
     /// The name of the entry to be changed.
     pub entry: LdapDn,
@@ -773,9 +796,11 @@ pub struct ModifyDnRequest {
     /// moving the entry to a new superior without changing its RDN. Attribute
     /// values of the new RDN not matching any attribute value of the entry are
     /// added to the entry, and an appropriate error is returned if this fails.
+    #[rasn(identifier = "newrdn")]
     pub new_rdn: RelativeLdapDn,
     /// Controls whether the old RDN attribute values are to be retained as
     /// attributes of the entry or deleted from the entry.
+    #[rasn(identifier = "deleteoldrdn")]
     pub delete_old_rdn: bool,
     /// If present, this is the name of an existing object entry that becomes
     /// the immediate superior (parent) of the existing entry.
@@ -791,7 +816,7 @@ pub struct ModifyDnResponse(pub LdapResult);
 /// Allows a client to compare an assertion value with the values of a
 /// particular attribute in a particular entry in the directory.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 14))]
+#[rasn(tag(application, 14), rename_all = "camelCase")]
 pub struct CompareRequest {
     /// The name of the entry to be compared.
     pub entry: LdapDn,
@@ -858,7 +883,7 @@ pub struct AbandonRequest(pub MessageId);
 /// with predefined syntaxes and semantics.  These may be defined in RFCs or be
 /// private to particular implementations.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 23))]
+#[rasn(tag(application, 23), rename_all = "camelCase")]
 pub struct ExtendedRequest {
     /// The unique [`LdapOid`] corresponding to the extended operation. Where
     /// the request name is not recognized, the server
@@ -873,9 +898,10 @@ pub struct ExtendedRequest {
 /// The result of a [`CompareResponse`] operation.
 ///
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 24))]
+#[rasn(tag(application, 24), rename_all = "camelCase")]
 pub struct ExtendedResponse {
     pub result_code: ResultCode,
+    #[rasn(identifier = "matchedDN")]
     pub matched_dn: LdapDn,
     pub diagnostic_message: LdapString,
     #[rasn(tag(3))]
@@ -900,7 +926,7 @@ pub struct ExtendedResponse {
 /// conjunction with a control when extending existing LDAP operations in a way
 /// that requires them to return [`IntermediateResponse`] information.
 #[derive(AsnType, Encode, Decode, Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
-#[rasn(tag(application, 25))]
+#[rasn(tag(application, 25), rename_all = "camelCase")]
 pub struct IntermediateResponse {
     /// The unique [`LdapOid`] corresponding to the extended operation.
     #[rasn(tag(0))]
@@ -142,7 +142,7 @@ pub struct BasicOcspResponse {
 /// The body of [BasicOcspResponse].
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ResponseData {
-    #[rasn(tag(explicit(0), default))]
+    #[rasn(tag(explicit(0)), default)]
     pub version: Version,
     pub responder_id: ResponderId,
     pub produced_at: GeneralizedTime,
@@ -210,3 +210,28 @@ pub struct CrlId {
     #[rasn(tag(explicit(2)))]
     pub time: Option<GeneralizedTime>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_data_version() {
+        // ResponseData with the default v1 version omitted, and with an
+        // explicit v2 version.
+        let body = [
+            0xA2, 0x06, 0x04, 0x04, 0x01, 0x02, 0x03, 0x04, 0x18, 0x0F, b'2', b'0', b'2', b'0',
+            b'0', b'1', b'0', b'1', b'0', b'0', b'0', b'0', b'0', b'0', b'Z', 0x30, 0x00,
+        ];
+        let v1 = [&[0x30, 0x1B][..], &body].concat();
+        let v2 = [&[0x30, 0x20, 0xA0, 0x03, 0x02, 0x01, 0x01][..], &body].concat();
+
+        let data: ResponseData = rasn::der::decode(&v1).unwrap();
+        assert_eq!(Version::from(0), data.version);
+        assert_eq!(v1, rasn::der::encode(&data).unwrap());
+
+        let data: ResponseData = rasn::der::decode(&v2).unwrap();
+        assert_eq!(Version::from(1), data.version);
+        assert_eq!(v2, rasn::der::encode(&data).unwrap());
+    }
+}
@@ -30,13 +30,16 @@ pub const ROLE: ConstOid = Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_ROLE;
 pub const CLEARANCE: ConstOid = Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_CLEARANCE;
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AttributeCertificate {
+    #[rasn(identifier = "acinfo")]
     pub info: AttributeCertificateInfo,
     pub signature_algorithm: AlgorithmIdentifier,
     pub signature_value: BitString,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AttributeCertificateInfo {
     pub version: AttributeCertificateVersion,
     pub holder: Holder,
@@ -45,6 +48,7 @@ pub struct AttributeCertificateInfo {
     pub serial_number: CertificateSerialNumber,
     pub attr_cert_validity_period: AttributeCertificateValidityPeriod,
     pub attributes: SequenceOf<Attribute>,
+    #[rasn(identifier = "issuerUniqueID")]
     pub issuer_unique_id: Option<UniqueIdentifier>,
     pub extensions: Option<Extensions>,
 }
@@ -58,9 +62,10 @@ impl AttributeCertificateVersion {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct Holder {
     /// The issuer and serial number of the holder's public key certificate.
-    #[rasn(tag(0))]
+    #[rasn(tag(0), identifier = "baseCertificateID")]
     pub base_certificate_id: Option<IssuerSerial>,
     /// The name of the claimant or role.
     #[rasn(tag(1))]
@@ -71,15 +76,17 @@ pub struct Holder {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct ObjectDigestInfo {
     pub digested_object_type: DisgestedObjectType,
+    #[rasn(identifier = "otherObjectTypeID")]
     pub other_object_type_id: Option<ObjectIdentifier>,
     pub digest_algorithm: AlgorithmIdentifier,
     pub object_digest: BitString,
 }
 
 #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(enumerated)]
+#[rasn(enumerated, rename_all = "camelCase")]
 pub enum DisgestedObjectType {
     PublicKey = 0,
     PublicKeyCert = 1,
@@ -87,47 +94,55 @@ pub enum DisgestedObjectType {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum Issuer {
+    #[rasn(identifier = "v1Form")]
     V1(GeneralNames),
-    #[rasn(tag(0))]
+    #[rasn(tag(0), identifier = "v2Form")]
     V2(V2Form),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct V2Form {
     pub issuer_name: Option<GeneralNames>,
-    #[rasn(tag(0))]
+    #[rasn(tag(0), identifier = "baseCertificateID")]
     pub base_certificate_id: Option<IssuerSerial>,
     #[rasn(tag(1))]
     pub object_digest_info: Option<ObjectDigestInfo>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct IssuerSerial {
     pub issuer: GeneralNames,
     pub serial: CertificateSerialNumber,
+    #[rasn(identifier = "issuerUID")]
     pub issuer_uid: Option<UniqueIdentifier>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AttributeCertificateValidityPeriod {
+    #[rasn(identifier = "notBeforeTime")]
     pub not_before: GeneralizedTime,
+    #[rasn(identifier = "notAfterTime")]
     pub not_after: GeneralizedTime,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum Target {
-    #[rasn(tag(0))]
+    #[rasn(tag(0), identifier = "targetName")]
     Name(GeneralName),
-    #[rasn(tag(1))]
+    #[rasn(tag(1), identifier = "targetGroup")]
     Group(GeneralName),
-    #[rasn(tag(2))]
+    #[rasn(tag(2), identifier = "targetCert")]
     Cert(TargetCert),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct TargetCert {
     pub target_certificate: IssuerSerial,
     pub target_name: Option<GeneralName>,
@@ -135,6 +150,7 @@ pub struct TargetCert {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct IetfAttrSyntax {
     #[rasn(tag(0))]
     pub policy_authority: Option<GeneralNames>,
@@ -142,7 +158,7 @@ pub struct IetfAttrSyntax {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum IetfAttrSyntaxValue {
     Octets(OctetString),
     Oid(ObjectIdentifier),
@@ -150,6 +166,7 @@ pub enum IetfAttrSyntaxValue {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct SvceAuthInfo {
     pub service: GeneralName,
     pub ident: GeneralName,
@@ -157,6 +174,7 @@ pub struct SvceAuthInfo {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct RoleSyntax {
     #[rasn(tag(0))]
     role_authority: Option<GeneralNames>,
@@ -165,6 +183,7 @@ pub struct RoleSyntax {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct Clearance {
     policy_id: ObjectIdentifier,
     #[rasn(default = "ClassList::unclassified")]
@@ -203,6 +222,7 @@ impl ClassList {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct SecurityCategory {
     #[rasn(tag(0))]
     r#type: ObjectIdentifier,
@@ -211,13 +231,14 @@ pub struct SecurityCategory {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AaControls {
     path_len_constraint: Option<Integer>,
     #[rasn(tag(0))]
     permitted_attrs: Option<AttrSpec>,
     #[rasn(tag(1))]
     excluded_attrs: Option<AttrSpec>,
-    #[rasn(default = "true_bool")]
+    #[rasn(default = "true_bool", identifier = "permitUnSpecified")]
     permit_unspecified: bool,
 }
 
@@ -226,8 +247,11 @@ fn true_bool() -> bool {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AttributeCertificateClearAttributes {
+    #[rasn(identifier = "acIssuer")]
     pub issuer: GeneralName,
+    #[rasn(identifier = "acSerial")]
     pub serial: Integer,
     pub attrs: SequenceOf<Attribute>,
 }
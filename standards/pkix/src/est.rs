@@ -22,13 +22,14 @@ pub const ASYMMETRIC_DECRYPT_KEY_OID: ConstOid =
 /// Either an OID pointing a specific signature scheme or an attribute for
 /// a particular crypto system.
 #[derive(AsnType, Decode, Encode, Debug, PartialEq, Clone)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum AttrOrOid {
     Oid(ObjectIdentifier),
     Attribute(Attribute),
 }
 
 #[derive(AsnType, Decode, Encode, Debug, PartialEq, Clone)]
+#[rasn(rename_all = "camelCase")]
 pub struct Attribute {
     pub r#type: ObjectIdentifier,
     pub values: SetOf<Any>,
@@ -5,6 +5,7 @@ extern crate alloc;
 
 pub mod attribute_certificate;
 pub mod est;
+pub mod registry;
 
 use rasn::{types::*, Decode, Encode};
 
@@ -79,6 +80,7 @@ macro_rules! derefable {
 
 /// An X.509 certificate
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct Certificate {
     /// Certificate information.
     pub tbs_certificate: TbsCertificate,
@@ -97,9 +99,10 @@ pub struct Certificate {
 /// Information associated with the subject of the certificate and the CA that
 /// issued it.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct TbsCertificate {
     /// The version of the encoded certificate.
-    #[rasn(tag(explicit(0)), default)]
+    #[rasn(tag(explicit(0)), default = "v1")]
     pub version: Version,
     /// The serial number MUST be a positive integer assigned by the CA to each
     /// certificate.  It MUST be unique for each certificate issued by a given
@@ -137,9 +140,9 @@ pub struct TbsCertificate {
     /// The public key and identifies the algorithm with which the key is used
     /// (e.g., RSA, DSA, or Diffie-Hellman).
     pub subject_public_key_info: SubjectPublicKeyInfo,
-    #[rasn(tag(1))]
+    #[rasn(tag(1), identifier = "issuerUniqueID")]
     pub issuer_unique_id: Option<UniqueIdentifier>,
-    #[rasn(tag(2))]
+    #[rasn(tag(2), identifier = "subjectUniqueID")]
     pub subject_unique_id: Option<UniqueIdentifier>,
     /// Extensions to the certificate.
     #[rasn(tag(explicit(3)))]
@@ -188,6 +191,7 @@ impl core::fmt::Display for Version {
 /// standard format for representing trust anchor information.  The RFC-5914
 /// document describes the TrustAnchorInfo structure.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct TrustAnchorInfo {
     /// version identifies the version of TrustAnchorInfo.  Defaults to 1.
     #[rasn(tag(explicit(1)), default)]
@@ -229,6 +233,7 @@ pub struct TrustAnchorInfo {
 /// CertPathControls provides the controls needed to initialize an X.509
 // certification path validation algorithm implementation
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct CertPathControls {
     /// taName provides the X.500 distinguished name associated with the
     /// trust anchor, and this distinguished name is used to construct and
@@ -278,17 +283,18 @@ pub struct CertPathControls {
 /// The taInfo option allows for use of the TrustAnchorInfo structure defined
 /// in RFC-5914.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum TrustAnchorChoice {
     Certificate(Certificate),
-    #[rasn(tag(explicit(1)))]
+    #[rasn(tag(explicit(1)), identifier = "tbsCert")]
     TbsCertificate(TbsCertificate),
-    #[rasn(tag(explicit(2)))]
+    #[rasn(tag(explicit(2)), identifier = "taInfo")]
     TrustAnchorInfo(TrustAnchorInfo),
 }
 
 /// The validity period of the certificate.
 #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct Validity {
     pub not_before: Time,
     pub not_after: Time,
@@ -296,14 +302,17 @@ pub struct Validity {
 
 /// A general time type.
 #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum Time {
+    #[rasn(identifier = "utcTime")]
     Utc(UtcTime),
+    #[rasn(identifier = "generalTime")]
     General(GeneralizedTime),
 }
 
 /// The subject's public key, and the algorithm used to encode it.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct SubjectPublicKeyInfo {
     pub algorithm: AlgorithmIdentifier,
     pub subject_public_key: BitString,
@@ -317,6 +326,7 @@ pub struct SubjectPublicKeyInfo {
 /// MAY be based on either the key identifier (the subject key identifier in the
 /// issuer's certificate) or the issuer name and serial number.
 #[derive(AsnType, Default, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AuthorityKeyIdentifier {
     #[rasn(tag(0))]
     pub key_identifier: Option<KeyIdentifier>,
@@ -328,23 +338,41 @@ pub struct AuthorityKeyIdentifier {
 
 /// Extension to an X.509 certificate.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct Extension {
+    #[rasn(identifier = "extnID")]
     pub extn_id: ObjectIdentifier,
     #[rasn(default)]
     pub critical: bool,
     pub extn_value: OctetString,
 }
 
+impl Extension {
+    /// Decodes `extn_value` as the type registered for `extn_id` in
+    /// `registry`, returning `Ok(None)` if the extension is unknown to
+    /// `registry`.
+    pub fn decode_extn_value<T>(
+        &self,
+        registry: &OpenTypeRegistry<T>,
+    ) -> Result<Option<T>, rasn::codec::DecodeError> {
+        registry.decode(rasn::Codec::Der, &self.extn_id, &self.extn_value)
+    }
+}
+
 /// A signed list of revoked certificates.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct CertificateList {
     pub tbs_cert_list: TbsCertList,
+    #[rasn(identifier = "signatureAlgorithm")]
     pub signature_algorithim: AlgorithmIdentifier,
+    #[rasn(identifier = "signatureValue")]
     pub signature: BitString,
 }
 
 /// The list of revoked certificates along with associated metadata.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct TbsCertList {
     /// The version of the list.
     pub version: Version,
@@ -366,6 +394,7 @@ pub struct TbsCertList {
 
 /// Identifies a revoked certificate.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct RevokedCerificate {
     /// The ID of the certificate being revoked.
     pub user_certificate: CertificateSerialNumber,
@@ -377,6 +406,7 @@ pub struct RevokedCerificate {
 
 /// Identifies what algorithm was used, along with any parameters used as input.
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AlgorithmIdentifier {
     /// The identifier for the algorithm.
     pub algorithm: ObjectIdentifier,
@@ -385,6 +415,7 @@ pub struct AlgorithmIdentifier {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "kebab-case")]
 pub struct OrAddress {
     pub built_in_standard_attributes: BuiltInStandardAttributes,
     pub built_in_domain_defined_attributes: Option<BuiltInDomainDefinedAttributes>,
@@ -392,8 +423,10 @@ pub struct OrAddress {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "kebab-case")]
 pub struct BuiltInStandardAttributes {
     pub country_name: Option<CountryName>,
+    #[rasn(identifier = "administration-domain-name")]
     pub administraion_domain_name: Option<AdministrationDomainName>,
     #[rasn(tag(0))]
     pub network_address: Option<NetworkAddress>,
@@ -401,13 +434,13 @@ pub struct BuiltInStandardAttributes {
     pub terminal_identifier: Option<TerminalIdentifier>,
     #[rasn(tag(2))]
     pub private_domain_name: Option<PrivateDomainName>,
-    #[rasn(tag(3))]
+    #[rasn(tag(3), identifier = "organization-name")]
     pub organisation_name: Option<OrganisationName>,
     #[rasn(tag(4))]
     pub numeric_user_identifier: Option<NumericUserIdentifier>,
     #[rasn(tag(5))]
     pub personal_name: Option<PersonalName>,
-    #[rasn(tag(6))]
+    #[rasn(tag(6), identifier = "organizational-unit-names")]
     pub organisational_unit_name: Option<OrganisationalUnitNames>,
 }
 
@@ -420,6 +453,7 @@ derefable!(
 );
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "kebab-case")]
 pub struct BuiltInDomainDefinedAttribute {
     #[rasn(size("1..=8"))]
     pub r#type: PrintableString,
@@ -429,16 +463,16 @@ pub struct BuiltInDomainDefinedAttribute {
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(tag(explicit(application, 1)))]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "kebab-case")]
 pub enum CountryName {
-    #[rasn(size(3))]
+    #[rasn(size(3), identifier = "x121-dcc-code")]
     X121DccCode(NumericString),
-    #[rasn(size(2))]
+    #[rasn(size(2), identifier = "iso-3166-alpha2-code")]
     Iso3166Alpha2Code(PrintableString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "kebab-case")]
 pub enum PrivateDomainName {
     #[rasn(size("1..=16"))]
     Numeric(NumericString),
@@ -447,7 +481,7 @@ pub enum PrivateDomainName {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(tag(explicit(application, 2)), choice)]
+#[rasn(tag(explicit(application, 2)), choice, rename_all = "kebab-case")]
 pub enum AdministrationDomainName {
     #[rasn(size("0..=16"))]
     Numeric(NumericString),
@@ -456,7 +490,7 @@ pub enum AdministrationDomainName {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(set)]
+#[rasn(set, rename_all = "kebab-case")]
 pub struct PersonalName {
     #[rasn(tag(0))]
     pub surname: PrintableString,
@@ -494,6 +528,7 @@ pub struct ExtensionAttributes(SetOf<ExtensionAttribute>);
 derefable!(ExtensionAttributes, SetOf<ExtensionAttribute>);
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "kebab-case")]
 pub struct ExtensionAttribute {
     #[rasn(tag(0), value("0..=256"))]
     pub extension_attribute_type: u16,
@@ -502,7 +537,7 @@ pub struct ExtensionAttribute {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(set)]
+#[rasn(set, rename_all = "kebab-case")]
 pub struct TeletexPersonalName {
     #[rasn(tag(0), size("1..=40"))]
     pub surname: TeletexString,
@@ -515,20 +550,20 @@ pub struct TeletexPersonalName {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "kebab-case")]
 pub enum PhysicalDeliveryCountryName {
-    #[rasn(size(3))]
+    #[rasn(size(3), identifier = "x121-dcc-code")]
     X121DccCode(NumericString),
-    #[rasn(size(2))]
+    #[rasn(size(2), identifier = "iso-3166-alpha2-code")]
     Iso3166Alpha2Code(PrintableString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "kebab-case")]
 pub enum PostalCode {
-    #[rasn(size("1..=16"))]
+    #[rasn(size("1..=16"), identifier = "numeric-code")]
     Numeric(NumericString),
-    #[rasn(size("1..=16"))]
+    #[rasn(size("1..=16"), identifier = "printable-code")]
     Printable(PrintableString),
 }
 
@@ -576,7 +611,7 @@ pub struct TeletexAddress(TeletexString);
 derefable!(TeletexAddress, TeletexString);
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(set)]
+#[rasn(set, rename_all = "kebab-case")]
 pub struct UnformattedPostalAddress {
     #[rasn(size("1..=6"))]
     pub printable_address: Option<SequenceOf<PrintableAddress>>,
@@ -584,7 +619,7 @@ pub struct UnformattedPostalAddress {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(set)]
+#[rasn(set, rename_all = "kebab-case")]
 pub struct PdsParameter {
     #[rasn(size("1..=30"))]
     pub printable_string: Option<PrintableString>,
@@ -593,14 +628,16 @@ pub struct PdsParameter {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "kebab-case")]
 pub enum ExtendedNetworkAddress {
+    #[rasn(identifier = "e163-4-address")]
     E1634Address(E1634Address),
     #[rasn(tag(0))]
     PsapAddress(PresentationAddress),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "kebab-case")]
 pub struct E1634Address {
     #[rasn(tag(0), size("1..=15"))]
     pub number: NumericString,
@@ -609,6 +646,7 @@ pub struct E1634Address {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct PresentationAddress {
     #[rasn(tag(explicit(0)))]
     pub p_selector: Option<OctetString>,
@@ -625,6 +663,7 @@ pub struct PresentationAddress {
 pub struct TeletexDomainDefinedAttributes(SequenceOf<TeletexDomainDefinedAttribute>);
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "kebab-case")]
 pub struct TeletexDomainDefinedAttribute {
     #[rasn(size("1..=8"))]
     pub r#type: TeletexString,
@@ -633,49 +672,87 @@ pub struct TeletexDomainDefinedAttribute {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum Name {
     RdnSequence(RdnSequence),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct Attribute {
     pub r#type: AttributeType,
     pub values: SetOf<AttributeValue>,
 }
 
+impl Attribute {
+    /// Decodes each of `values` as the type registered for `type` in
+    /// `registry`, returning `Ok(None)` if the attribute type is unknown to
+    /// `registry`.
+    pub fn decode_values<T>(
+        &self,
+        registry: &OpenTypeRegistry<T>,
+    ) -> Result<Option<alloc::vec::Vec<T>>, rasn::codec::DecodeError> {
+        if !registry.contains(&self.r#type) {
+            return Ok(None);
+        }
+
+        self.values
+            .iter()
+            .map(|value| registry.decode_any(rasn::Codec::Der, &self.r#type, value))
+            .collect()
+    }
+}
+
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialOrd, Ord, PartialEq, Eq, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AttributeTypeAndValue {
     pub r#type: AttributeType,
     pub value: AttributeValue,
 }
 
+impl AttributeTypeAndValue {
+    /// Decodes `value` as the type registered for `type` in `registry`,
+    /// returning `Ok(None)` if the attribute type is unknown to `registry`.
+    pub fn decode_value<T>(
+        &self,
+        registry: &OpenTypeRegistry<T>,
+    ) -> Result<Option<T>, rasn::codec::DecodeError> {
+        registry.decode_any(rasn::Codec::Der, &self.r#type, &self.value)
+    }
+}
+
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct PolicyInformation {
     pub policy_identifier: CertPolicyId,
     pub policy_qualifiers: Option<SequenceOf<PolicyQualifierInfo>>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct PolicyQualifierInfo {
+    #[rasn(identifier = "policyQualifierId")]
     pub id: PolicyQualifierId,
     pub qualifier: Any,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct UserNotice {
     pub notice_ref: Option<NoticeReference>,
     pub explicit_text: Option<DisplayText>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct NoticeReference {
+    #[rasn(identifier = "organization")]
     pub organisation: DisplayText,
     pub notice_numbers: SequenceOf<Integer>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum DisplayText {
     Ia5String(Ia5String),
     VisibleString(VisibleString),
@@ -684,19 +761,20 @@ pub enum DisplayText {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct PolicyMapping {
     pub issuer_domain_policy: CertPolicyId,
     pub subject_domain_policy: CertPolicyId,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum GeneralName {
     #[rasn(tag(0))]
     OtherName(InstanceOf<OctetString>),
     #[rasn(tag(1))]
     Rfc822Name(Ia5String),
-    #[rasn(tag(2))]
+    #[rasn(tag(2), identifier = "dNSName")]
     DnsName(Ia5String),
     // Boxed because it's 368 bytes, and the next largest enum variant is 64.
     #[rasn(tag(3))]
@@ -705,15 +783,16 @@ pub enum GeneralName {
     DirectoryName(Name),
     #[rasn(tag(5))]
     EdiPartyName(EdiPartyName),
-    #[rasn(tag(6))]
+    #[rasn(tag(6), identifier = "uniformResourceIdentifier")]
     Uri(Ia5String),
-    #[rasn(tag(7))]
+    #[rasn(tag(7), identifier = "iPAddress")]
     IpAddress(OctetString),
-    #[rasn(tag(8))]
+    #[rasn(tag(8), identifier = "registeredID")]
     RegisteredId(ObjectIdentifier),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct EdiPartyName {
     #[rasn(tag(0))]
     pub name_assigner: Option<DirectoryString>,
@@ -734,120 +813,120 @@ derefable!(X520SerialNumber, PrintableString);
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520StateOrProvinceName {
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520OrganisationName {
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520OrganisationalUnitName {
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520Title {
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520Pseudonym {
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520LocalityName {
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=128"))]
+    #[rasn(size("1..=128"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520Name {
-    #[rasn(size("1..=32768"))]
+    #[rasn(size("1..=32768"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=32768"))]
+    #[rasn(size("1..=32768"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=32768"))]
+    #[rasn(size("1..=32768"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=32768"))]
+    #[rasn(size("1..=32768"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=32768"))]
+    #[rasn(size("1..=32768"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum X520CommonName {
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1..=64"))]
+    #[rasn(size("1..=64"), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
@@ -896,26 +975,28 @@ directory_string_compat! {
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rasn(choice)]
 pub enum DirectoryString {
-    #[rasn(size("1.."))]
+    #[rasn(size("1.."), identifier = "teletexString")]
     Teletex(TeletexString),
-    #[rasn(size("1.."))]
+    #[rasn(size("1.."), identifier = "printableString")]
     Printable(PrintableString),
-    #[rasn(size("1.."))]
+    #[rasn(size("1.."), identifier = "universalString")]
     Universal(UniversalString),
-    #[rasn(size("1.."))]
+    #[rasn(size("1.."), identifier = "utf8String")]
     Utf8(Utf8String),
-    #[rasn(size("1.."))]
+    #[rasn(size("1.."), identifier = "bmpString")]
     Bmp(BmpString),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct BasicConstraints {
-    #[rasn(default)]
+    #[rasn(default, identifier = "cA")]
     pub ca: bool,
     pub path_len_constraint: Option<Integer>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct NameConstraints {
     #[rasn(tag(0))]
     pub permitted_subtrees: Option<GeneralSubtrees>,
@@ -924,6 +1005,7 @@ pub struct NameConstraints {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct GeneralSubtree {
     pub base: GeneralName,
     #[rasn(tag(0), default)]
@@ -933,6 +1015,7 @@ pub struct GeneralSubtree {
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct PolicyConstraints {
     #[rasn(tag(0))]
     pub require_explicit_policy: Option<SkipCerts>,
@@ -941,56 +1024,62 @@ pub struct PolicyConstraints {
 }
 
 #[derive(AsnType, Clone, Debug, Default, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct DistributionPoint {
     #[rasn(tag(0))]
     pub distribution_point: Option<DistributionPointName>,
     #[rasn(tag(1))]
     pub reasons: Option<ReasonFlags>,
-    #[rasn(tag(2))]
+    #[rasn(tag(2), identifier = "cRLIssuer")]
     pub crl_issuer: Option<GeneralNames>,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(choice)]
+#[rasn(choice, rename_all = "camelCase")]
 pub enum DistributionPointName {
     #[rasn(tag(0))]
     FullName(GeneralNames),
-    #[rasn(tag(1))]
+    #[rasn(tag(1), identifier = "nameRelativeToCRLIssuer")]
     NameRelativeToCrlIssuer(RelativeDistinguishedName),
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct AccessDescription {
     pub access_method: ObjectIdentifier,
     pub access_location: GeneralName,
 }
 
 #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[rasn(enumerated)]
+#[rasn(enumerated, rename_all = "camelCase")]
 pub enum CrlReason {
     Unspecified = 0,
     KeyCompromise = 1,
+    #[rasn(identifier = "cACompromise")]
     CaCompromise = 2,
     AffiliationChanged = 3,
     Superseded = 4,
     CessationOfOperation = 5,
     CertificateHold = 6,
+    #[rasn(identifier = "removeFromCRL")]
     RemoveFromCRL = 8,
     PrivilegeWithdrawn = 9,
+    #[rasn(identifier = "aACompromise")]
     AaCompromise = 10,
 }
 
 #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[rasn(rename_all = "camelCase")]
 pub struct IssuingDistributionPoint {
     #[rasn(tag(0))]
     pub distribution_point: Option<DistributionPointName>,
     #[rasn(tag(1), default)]
     pub only_contains_user_certs: bool,
-    #[rasn(tag(2), default)]
+    #[rasn(tag(2), default, identifier = "onlyContainsCACerts")]
     pub only_contains_ca_certs: bool,
     #[rasn(tag(3))]
     pub only_some_reasons: Option<ReasonFlags>,
-    #[rasn(tag(4), default)]
+    #[rasn(tag(4), default, identifier = "indirectCRL")]
     pub indirect_crl: bool,
     #[rasn(tag(5), default)]
     pub only_contains_attribute_certs: bool,
@@ -1069,4 +1158,69 @@ mod tests {
             rasn::der::decode::<CertificatePolicies>(&expected_enc).unwrap()
         );
     }
+
+    #[test]
+    fn identifiers() {
+        use rasn::types::{Choice, Constructed, Enumerated};
+
+        let names = |fields: rasn::types::fields::Fields| {
+            fields
+                .iter()
+                .map(|field| field.name.unwrap())
+                .collect::<alloc::vec::Vec<_>>()
+        };
+
+        assert_eq!(
+            ["tbsCertificate", "signatureAlgorithm", "signatureValue"],
+            &*names(Certificate::FIELDS)
+        );
+        assert_eq!(
+            ["tbsCertList", "signatureAlgorithm", "signatureValue"],
+            &*names(CertificateList::FIELDS)
+        );
+        assert_eq!(
+            [
+                "country-name",
+                "administration-domain-name",
+                "network-address",
+                "terminal-identifier",
+                "private-domain-name",
+                "organization-name",
+                "numeric-user-identifier",
+                "personal-name",
+                "organizational-unit-names",
+            ],
+            &*names(BuiltInStandardAttributes::FIELDS)
+        );
+        assert_eq!(
+            [
+                "otherName",
+                "rfc822Name",
+                "dNSName",
+                "x400Address",
+                "directoryName",
+                "ediPartyName",
+                "uniformResourceIdentifier",
+                "iPAddress",
+                "registeredID",
+            ],
+            GeneralName::IDENTIFIERS
+        );
+        assert_eq!(
+            [
+                "teletexString",
+                "printableString",
+                "universalString",
+                "utf8String",
+                "bmpString",
+            ],
+            DirectoryString::IDENTIFIERS
+        );
+        assert_eq!(
+            ["ia5String", "visibleString", "bmpString", "utf8String"],
+            DisplayText::IDENTIFIERS
+        );
+        assert_eq!(Some("removeFromCRL"), CrlReason::RemoveFromCRL.identifier());
+        assert_eq!(Some("aACompromise"), CrlReason::AaCompromise.identifier());
+    }
 }
@@ -0,0 +1,213 @@
+//! # Open Type Registries
+//! Pre-populated [`OpenTypeRegistry`]s for the certificate extensions and
+//! attribute types defined in this crate, for resolving the values of
+//! [`Extension`]s and [`AttributeTypeAndValue`]s to their concrete types.
+//!
+//! ```
+//! use rasn_pkix::{registry, Extension, KeyUsage};
+//!
+//! let extension = Extension {
+//!     extn_id: rasn::types::Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_KEY_USAGE.into(),
+//!     critical: true,
+//!     extn_value: vec![0x03, 0x02, 0x05, 0xa0].into(),
+//! };
+//!
+//! let value = extension.decode_extn_value(&registry::extensions()).unwrap().unwrap();
+//! assert!(value.downcast_ref::<KeyUsage>().is_some());
+//! ```
+//!
+//! [`Extension`]: crate::Extension
+//! [`AttributeTypeAndValue`]: crate::AttributeTypeAndValue
+
+use alloc::boxed::Box;
+
+use rasn::prelude::*;
+
+use crate::{attribute_certificate::*, est, *};
+
+/// The registry of values decoded as a `Box<dyn OpenValue>`.
+pub type Registry = OpenTypeRegistry<Box<dyn OpenValue>>;
+
+/// Returns a registry of every certificate and CRL extension defined in this
+/// crate.
+pub fn extensions() -> Registry {
+    let mut registry = Registry::new();
+    register_extensions(&mut registry);
+    registry
+}
+
+/// Returns a registry of every attribute type defined in this crate.
+pub fn attributes() -> Registry {
+    let mut registry = Registry::new();
+    register_attributes(&mut registry);
+    registry
+}
+
+/// Registers every certificate and CRL extension defined in this crate with
+/// `registry`.
+pub fn register_extensions(registry: &mut Registry) -> &mut Registry {
+    registry
+        // RFC 5280 certificate extensions.
+        .register_boxed::<AuthorityKeyIdentifier>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_AUTHORITY_KEY_IDENTIFIER,
+        )
+        .register_boxed::<SubjectKeyIdentifier>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_SUBJECT_KEY_IDENTIFIER,
+        )
+        .register_boxed::<KeyUsage>(Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_KEY_USAGE)
+        .register_boxed::<CertificatePolicies>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_CERTIFICATE_POLICIES,
+        )
+        .register_boxed::<PolicyMappings>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_POLICY_MAPPINGS,
+        )
+        .register_boxed::<SubjectAltName>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_SUBJECT_ALT_NAME,
+        )
+        .register_boxed::<GeneralNames>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_ISSUER_ALT_NAME,
+        )
+        .register_boxed::<SubjectDirectoryAttributes>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_SUBJECT_DIRECTORY_ATTRIBUTES,
+        )
+        .register_boxed::<BasicConstraints>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_BASIC_CONSTRAINTS,
+        )
+        .register_boxed::<NameConstraints>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_AUTHORITY_CRL_NAME_CONSTRAINTS,
+        )
+        .register_boxed::<PolicyConstraints>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_POLICY_CONSTRAINTS,
+        )
+        .register_boxed::<ExtKeyUsageSyntax>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_AUTHORITY_EXT_KEY_USAGE,
+        )
+        .register_boxed::<CrlDistributionPoints>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_AUTHORITY_CRL_DISTRIBUTION_POINTS,
+        )
+        // RFC 5280 defines `InhibitAnyPolicy ::= SkipCerts`.
+        .register_boxed::<SkipCerts>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_INHIBIT_ANY_POLICY,
+        )
+        .register_boxed::<FreshestCrl>(Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_FRESHEST_CRL)
+        .register_boxed::<AuthorityInfoAccessSyntax>(
+            Oid::ISO_IDENTIFIED_ORGANISATION_DOD_INTERNET_SECURITY_MECHANISMS_PKIX_PE_AUTHORITY_INFO_ACCESS,
+        )
+        .register_boxed::<SubjectInfoAccessSyntax>(
+            Oid::ISO_IDENTIFIED_ORGANISATION_DOD_INTERNET_SECURITY_MECHANISMS_PKIX_PE_SUBJECT_INFO_ACCESS,
+        )
+        // RFC 5280 CRL and CRL entry extensions.
+        .register_boxed::<CrlNumber>(Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_CRL_NUMBER)
+        .register_boxed::<BaseCrlNumber>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_DELTA_CRL_INDICATOR,
+        )
+        .register_boxed::<IssuingDistributionPoint>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_ISSUING_DISTRIBUTION_POINT,
+        )
+        .register_boxed::<CrlReason>(Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_CRL_REASONS)
+        .register_boxed::<InvalidityDate>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_INVALIDITY_DATE,
+        )
+        .register_boxed::<CertificateIssuer>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_CERTIFICATE_ISSUER,
+        )
+        .register_boxed::<ObjectIdentifier>(
+            Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_HOLD_INSTRUCTION_CODE,
+        )
+        // RFC 5755 attribute certificate extensions.
+        .register_boxed::<OctetString>(AUDIT_IDENTITY)
+        .register_boxed::<AaControls>(AA_CONTROLS)
+        .register_boxed::<ProxyInfo>(AC_PROXYING)
+        .register_boxed::<SequenceOf<Targets>>(TARGET_INFORMATION)
+}
+
+/// Registers every attribute type defined in this crate with `registry`.
+pub fn register_attributes(registry: &mut Registry) -> &mut Registry {
+    registry
+        // RFC 5280 naming attributes.
+        .register_boxed::<X520Name>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_NAME)
+        .register_boxed::<X520Name>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_SURNAME)
+        .register_boxed::<X520Name>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_GIVEN_NAME)
+        .register_boxed::<X520Name>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_INITIALS)
+        .register_boxed::<X520Name>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_GENERATION_QUALIFIER)
+        .register_boxed::<X520CommonName>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_COMMON_NAME)
+        .register_boxed::<X520LocalityName>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_LOCALITY_NAME)
+        .register_boxed::<X520StateOrProvinceName>(
+            Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_STATE_OR_PROVINCE_NAME,
+        )
+        .register_boxed::<X520OrganisationName>(
+            Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_ORGANISATION_NAME,
+        )
+        .register_boxed::<X520OrganisationalUnitName>(
+            Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_ORGANISATIONAL_UNIT_NAME,
+        )
+        .register_boxed::<X520Title>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_TITLE)
+        .register_boxed::<X520DnQualifier>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_DN_QUALIFIER)
+        .register_boxed::<X520CountryName>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_COUNTRY_NAME)
+        .register_boxed::<X520SerialNumber>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_SERIAL_NUMBER)
+        .register_boxed::<X520Pseudonym>(Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_PSEUDONYM)
+        .register_boxed::<DomainComponent>(
+            Oid::ITU_T_DATA_PSS_UCL_PILOT_ATTRIBUTE_TYPE_DOMAIN_COMPONENT,
+        )
+        .register_boxed::<EmailAddress>(Oid::ISO_MEMBER_BODY_US_RSADSI_PKCS9_EMAIL_ADDRESS)
+        // RFC 5755 attribute certificate attributes.
+        .register_boxed::<SvceAuthInfo>(AUTHENTICATION_INFO)
+        .register_boxed::<SvceAuthInfo>(ACCESS_IDENTITY)
+        .register_boxed::<IetfAttrSyntax>(CHARGING_IDENTITY)
+        .register_boxed::<IetfAttrSyntax>(GROUP)
+        .register_boxed::<RoleSyntax>(ROLE)
+        .register_boxed::<Clearance>(CLEARANCE)
+        // RFC 7030 CSR attributes.
+        .register_boxed::<est::AsymmetricDecryptKeyIdentifier>(est::ASYMMETRIC_DECRYPT_KEY_OID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension() {
+        let constraints = BasicConstraints {
+            ca: true,
+            path_len_constraint: Some(0.into()),
+        };
+        let extension = Extension {
+            extn_id: Oid::JOINT_ISO_ITU_T_DS_CERTIFICATE_EXTENSION_BASIC_CONSTRAINTS.into(),
+            critical: true,
+            extn_value: rasn::der::encode(&constraints).unwrap().into(),
+        };
+        let registry = extensions();
+
+        let value = extension.decode_extn_value(&registry).unwrap().unwrap();
+        assert_eq!(Some(&constraints), value.downcast_ref::<BasicConstraints>());
+        assert_eq!(
+            Some(&*extension.extn_value),
+            registry
+                .encode(rasn::Codec::Der, &extension.extn_id, &value)
+                .unwrap()
+                .as_deref()
+        );
+
+        let unknown = Extension {
+            extn_id: Oid::ISO_IDENTIFIED_ORGANISATION_DOD_INTERNET_SECURITY_MECHANISMS_PKIX_PE_IP_ADDR_BLOCKS.into(),
+            ..extension
+        };
+        assert!(unknown.decode_extn_value(&registry).unwrap().is_none());
+    }
+
+    #[test]
+    fn attribute_type_and_value() {
+        let name = X520CommonName::Utf8(alloc::string::String::from("rasn"));
+        let attribute = AttributeTypeAndValue {
+            r#type: Oid::JOINT_ISO_ITU_T_DS_ATTRIBUTE_TYPE_COMMON_NAME.into(),
+            value: Any::new(rasn::der::encode(&name).unwrap()),
+        };
+
+        let value = attribute.decode_value(&attributes()).unwrap().unwrap();
+        assert_eq!(Some(&name), value.downcast_ref::<X520CommonName>());
+        assert!(extensions()
+            .decode_any(rasn::Codec::Der, &attribute.r#type, &attribute.value)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -10,6 +10,10 @@ fn it_works() {
     let cert: rasn_pkix::Certificate = rasn::der::decode(&contents.contents).unwrap();
 
     assert_eq!(contents.contents, rasn::der::encode(&cert).unwrap());
+    assert_eq!(
+        contents.contents.len(),
+        rasn::der::encoded_len(&cert).unwrap()
+    );
 }
 
 #[test]
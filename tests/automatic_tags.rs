@@ -0,0 +1,282 @@
+//! Conformance tests for `AUTOMATIC TAGS` (X.680 §25.3), checked against
+//! encodings of the equivalent ASN.1 definitions.
+
+use rasn::prelude::*;
+
+// Sequence ::= SEQUENCE {
+//     a INTEGER,
+//     b BOOLEAN OPTIONAL,
+//     ...,
+//     c IA5String,
+//     d INTEGER OPTIONAL
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(automatic_tags)]
+#[non_exhaustive]
+struct Sequence {
+    a: Integer,
+    b: Option<bool>,
+    #[rasn(extension_addition)]
+    c: Option<Ia5String>,
+    #[rasn(extension_addition)]
+    d: Option<Integer>,
+}
+
+// Grouped ::= SEQUENCE {
+//     a INTEGER,
+//     ...,
+//     [[ b BOOLEAN,
+//        c INTEGER OPTIONAL ]],
+//     d IA5String OPTIONAL
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(automatic_tags)]
+#[non_exhaustive]
+struct Grouped {
+    a: Integer,
+    #[rasn(extension_addition_group)]
+    group: Option<Group>,
+    #[rasn(extension_addition)]
+    d: Option<Ia5String>,
+}
+
+// The components of the group in `Grouped`, which keep the numbers that
+// follow `a`.
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(extension_addition_group)]
+struct Group {
+    b: bool,
+    c: Option<Integer>,
+}
+
+// Tagged ::= SEQUENCE {
+//     a INTEGER,
+//     b [5] BOOLEAN
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(automatic_tags)]
+struct Tagged {
+    a: Integer,
+    #[rasn(tag(5))]
+    b: bool,
+}
+
+// Set ::= SET {
+//     a INTEGER,
+//     b INTEGER,
+//     ...,
+//     c BOOLEAN
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(set, automatic_tags)]
+#[non_exhaustive]
+struct Set {
+    a: Integer,
+    b: Integer,
+    #[rasn(extension_addition)]
+    c: Option<bool>,
+}
+
+// Choice ::= CHOICE {
+//     a INTEGER,
+//     b BOOLEAN,
+//     ...,
+//     c IA5String
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(choice, automatic_tags)]
+#[non_exhaustive]
+enum Choice {
+    A(Integer),
+    B(bool),
+    #[rasn(extension_addition)]
+    C(Ia5String),
+}
+
+// TaggedChoice ::= CHOICE {
+//     a INTEGER,
+//     b [3] BOOLEAN
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(choice, automatic_tags)]
+enum TaggedChoice {
+    A(Integer),
+    #[rasn(tag(3))]
+    B(bool),
+}
+
+// Colour ::= ENUMERATED { red, green, ..., blue }
+#[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
+#[rasn(enumerated)]
+#[non_exhaustive]
+enum Colour {
+    Red,
+    Green,
+    #[rasn(extension_addition)]
+    Blue,
+}
+
+// Palette ::= SEQUENCE {
+//     colour Colour,
+//     choice Choice
+// }
+#[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+#[rasn(automatic_tags)]
+struct Palette {
+    colour: Colour,
+    choice: Choice,
+}
+
+fn round_trip<T: Decode + Encode + PartialEq + core::fmt::Debug>(value: &T, expected: &[u8]) {
+    let encoded = rasn::der::encode(value).unwrap();
+    assert_eq!(expected, &*encoded);
+    assert_eq!(*value, rasn::der::decode::<T>(&encoded).unwrap());
+}
+
+#[test]
+fn sequence_numbering_continues_across_extension_additions() {
+    round_trip(
+        &Sequence {
+            a: 1.into(),
+            b: Some(true),
+            c: Some(Ia5String::try_from(String::from("hi")).unwrap()),
+            d: Some(5.into()),
+        },
+        &[
+            0x30, 0x0D, 0x80, 0x01, 0x01, 0x81, 0x01, 0xFF, 0x82, 0x02, 0x68, 0x69, 0x83, 0x01,
+            0x05,
+        ],
+    );
+
+    round_trip(
+        &Sequence {
+            a: 1.into(),
+            b: None,
+            c: None,
+            d: Some(5.into()),
+        },
+        &[0x30, 0x06, 0x80, 0x01, 0x01, 0x83, 0x01, 0x05],
+    );
+}
+
+#[test]
+fn numbering_continues_after_extension_addition_groups() {
+    let tags: Vec<_> = Grouped::EXTENDED_FIELDS
+        .unwrap()
+        .iter()
+        .map(|field| field.tag)
+        .collect();
+    assert_eq!(
+        vec![Tag::new(Class::Context, 1), Tag::new(Class::Context, 3)],
+        tags
+    );
+    assert_eq!(1, <Group as rasn::types::ExtensionAdditionGroup>::FIRST_TAG);
+
+    round_trip(
+        &Grouped {
+            a: 1.into(),
+            group: Some(Group {
+                b: true,
+                c: Some(5.into()),
+            }),
+            d: Some(Ia5String::try_from(String::from("hi")).unwrap()),
+        },
+        &[
+            0x30, 0x0D, 0x80, 0x01, 0x01, 0x81, 0x01, 0xFF, 0x82, 0x01, 0x05, 0x83, 0x02, 0x68,
+            0x69,
+        ],
+    );
+
+    round_trip(
+        &Grouped {
+            a: 1.into(),
+            group: Some(Group { b: false, c: None }),
+            d: None,
+        },
+        &[0x30, 0x06, 0x80, 0x01, 0x01, 0x81, 0x01, 0x00],
+    );
+
+    round_trip(
+        &Grouped {
+            a: 1.into(),
+            group: None,
+            d: Some(Ia5String::try_from(String::from("hi")).unwrap()),
+        },
+        &[0x30, 0x07, 0x80, 0x01, 0x01, 0x83, 0x02, 0x68, 0x69],
+    );
+
+    // A group whose components are present but malformed is an error in the
+    // group, rather than an absent group followed by unexpected data.
+    for encoding in [
+        &[0x30, 0x06, 0x80, 0x01, 0x01, 0x82, 0x01, 0x05][..],
+        &[0x30, 0x07, 0x80, 0x01, 0x01, 0x81, 0x02, 0xFF, 0xFF],
+    ] {
+        assert!(matches!(
+            rasn::ber::decode::<Grouped>(encoding),
+            Err(rasn::ber::de::Error::FieldError {
+                name: "Grouped.group",
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn tagged_components_disable_automatic_tagging() {
+    round_trip(
+        &Tagged {
+            a: 1.into(),
+            b: true,
+        },
+        &[0x30, 0x06, 0x02, 0x01, 0x01, 0x85, 0x01, 0xFF],
+    );
+
+    round_trip(&TaggedChoice::A(1.into()), &[0x02, 0x01, 0x01]);
+    round_trip(&TaggedChoice::B(true), &[0x83, 0x01, 0xFF]);
+}
+
+#[test]
+fn set() {
+    round_trip(
+        &Set {
+            a: 1.into(),
+            b: 2.into(),
+            c: Some(false),
+        },
+        &[
+            0x31, 0x09, 0x80, 0x01, 0x01, 0x81, 0x01, 0x02, 0x82, 0x01, 0x00,
+        ],
+    );
+}
+
+#[test]
+fn choice_numbering_continues_across_extension_additions() {
+    round_trip(&Choice::A(1.into()), &[0x80, 0x01, 0x01]);
+    round_trip(&Choice::B(true), &[0x81, 0x01, 0xFF]);
+    round_trip(
+        &Choice::C(Ia5String::try_from(String::from("hi")).unwrap()),
+        &[0x82, 0x02, 0x68, 0x69],
+    );
+}
+
+#[test]
+fn extensible_enumerated() {
+    // Enumerations are never tagged, so only their components in `Palette`
+    // are, with `choice` being explicitly tagged as it's a `CHOICE`.
+    round_trip(
+        &Palette {
+            colour: Colour::Blue,
+            choice: Choice::B(false),
+        },
+        &[0x30, 0x08, 0x80, 0x01, 0x02, 0xA1, 0x03, 0x81, 0x01, 0x00],
+    );
+}
+
+#[test]
+fn per_uses_automatic_tag_order() {
+    // The alternatives are in the order they're defined, rather than the
+    // canonical order of their universal tags, which would put `b` first.
+    // The first bit is the extension bit, then one bit for the index.
+    assert_eq!(&[0x60][..], &*rasn::uper::encode(&Choice::B(true)).unwrap());
+    assert_eq!(Choice::B(true), rasn::uper::decode(&[0x60]).unwrap());
+}
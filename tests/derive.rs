@@ -150,3 +150,296 @@ pub struct BasicConstraints {
     pub ca: bool,
     pub path_len_constraint: Option<Integer>,
 }
+
+#[test]
+fn identifiers() {
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(rename_all = "camelCase")]
+    struct Certificate {
+        tbs_certificate: bool,
+        #[rasn(identifier = "signature-value")]
+        signature: OctetString,
+        r#type: Option<Integer>,
+    }
+
+    #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
+    #[rasn(enumerated, rename_all = "camelCase")]
+    #[non_exhaustive]
+    enum Reason {
+        Unspecified,
+        KeyCompromise,
+        #[rasn(extension_addition, identifier = "aa-compromise")]
+        AaCompromise,
+    }
+
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(choice)]
+    enum Name {
+        #[rasn(identifier = "rdnSequence")]
+        RdnSequence(bool),
+        Other(Integer),
+    }
+
+    let names: Vec<_> = Certificate::FIELDS.iter().map(|field| field.name).collect();
+    assert_eq!(
+        vec![
+            Some("tbsCertificate"),
+            Some("signature-value"),
+            Some("type")
+        ],
+        names
+    );
+
+    assert_eq!(&["unspecified", "keyCompromise"], Reason::IDENTIFIERS);
+    assert_eq!(Some("aa-compromise"), Reason::AaCompromise.identifier());
+    assert_eq!(
+        Some(Reason::KeyCompromise),
+        Reason::from_identifier("keyCompromise")
+    );
+    assert_eq!(None, Reason::from_identifier("KeyCompromise"));
+
+    assert_eq!(&["rdnSequence", "Other"], Name::IDENTIFIERS);
+}
+
+#[test]
+fn default_values() {
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    struct Defaults {
+        #[rasn(tag(explicit(0)), default = 1)]
+        version: Integer,
+        #[rasn(tag(1), default = "-1")]
+        offset: i32,
+        #[rasn(tag(2), default = true)]
+        critical: bool,
+        #[rasn(tag(3), default = "'101'B")]
+        flags: BitString,
+        #[rasn(tag(4), default = "'0A0B'H")]
+        octets: OctetString,
+        #[rasn(tag(5), default = "{ iso(1) 2 840 }")]
+        algorithm: ObjectIdentifier,
+        #[rasn(tag(6), default = "\"v1\"")]
+        name: Ia5String,
+        #[rasn(tag(7), default = b"abc")]
+        bytes: OctetString,
+    }
+
+    let defaults = Defaults {
+        version: 1.into(),
+        offset: -1,
+        critical: true,
+        flags: BitString::from_slice(&[0b1010_0000])[..3].to_bitvec(),
+        octets: OctetString::from_static(&[0x0A, 0x0B]),
+        algorithm: ObjectIdentifier::new(vec![1, 2, 840]).unwrap(),
+        name: Ia5String::try_from("v1").unwrap(),
+        bytes: OctetString::from_static(b"abc"),
+    };
+
+    // Default values are omitted in DER, even when explicitly tagged.
+    assert_eq!(&[0x30, 0x00][..], &*der::encode(&defaults).unwrap());
+    assert_eq!(defaults, der::decode(&[0x30, 0x00]).unwrap());
+
+    // BER can still contain the default values.
+    let version = &[0x30, 0x05, 0xA0, 0x03, 0x02, 0x01, 0x01];
+    assert_eq!(defaults, ber::decode(version).unwrap());
+
+    let version_two = Defaults {
+        version: 2.into(),
+        ..defaults
+    };
+    assert_eq!(
+        &[0x30, 0x05, 0xA0, 0x03, 0x02, 0x01, 0x02][..],
+        &*der::encode(&version_two).unwrap()
+    );
+}
+
+#[test]
+fn named_default_values() {
+    #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
+    #[rasn(delegate)]
+    struct Version(u64);
+
+    impl Version {
+        const V1: Self = Self(0);
+    }
+
+    #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
+    #[rasn(enumerated, rename_all = "camelCase")]
+    enum Administration {
+        Unmanaged,
+        Managed,
+        OtherAdministration,
+    }
+
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    struct Defaults {
+        #[rasn(tag(explicit(0)), default = "v1")]
+        version: Version,
+        #[rasn(tag(1), default = "otherAdministration")]
+        administration: Administration,
+    }
+
+    let defaults = Defaults {
+        version: Version(0),
+        administration: Administration::OtherAdministration,
+    };
+    assert_eq!(&[0x30, 0x00][..], &*der::encode(&defaults).unwrap());
+    assert_eq!(defaults, der::decode(&[0x30, 0x00]).unwrap());
+
+    let managed = Defaults {
+        administration: Administration::Managed,
+        ..defaults
+    };
+    assert_eq!(
+        &[0x30, 0x03, 0x81, 0x01, 0x01][..],
+        &*der::encode(&managed).unwrap()
+    );
+}
+
+#[test]
+fn generics() {
+    // SIGNED{ToBeSigned} ::= SEQUENCE {
+    //     toBeSigned ToBeSigned,
+    //     algorithm OBJECT IDENTIFIER,
+    //     signature BIT STRING
+    // }
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    struct Signed<T: Clone> {
+        to_be_signed: T,
+        algorithm: ObjectIdentifier,
+        signature: BitString,
+    }
+
+    #[derive(AsnType, Clone, Debug, Decode, Encode, PartialEq)]
+    #[rasn(choice)]
+    enum Either<T, U> {
+        Left(T),
+        Right(Vec<U>),
+    }
+
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(set)]
+    struct Pair<T> {
+        #[rasn(tag(0))]
+        first: T,
+        #[rasn(tag(1))]
+        second: Option<T>,
+    }
+
+    trait Kind {
+        type Value;
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Numbers;
+
+    impl Kind for Numbers {
+        type Value = Integer;
+    }
+
+    // `K` itself isn't encoded, so it doesn't need to implement `Encode`.
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(delegate)]
+    struct Typed<K: Kind>(K::Value);
+
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(bound = "T: Encode + Decode + Copy")]
+    struct Bounded<T>(Vec<T>);
+
+    let signed = Signed {
+        to_be_signed: Either::<bool, Integer>::Right(vec![1.into()]),
+        algorithm: ObjectIdentifier::new(vec![1, 2, 840]).unwrap(),
+        signature: BitString::from_slice(&[0xFF]),
+    };
+    assert_eq!(signed, der::decode(&der::encode(&signed).unwrap()).unwrap());
+
+    let pair = Pair {
+        first: Typed::<Numbers>(5.into()),
+        second: None,
+    };
+    assert_eq!(pair, der::decode(&der::encode(&pair).unwrap()).unwrap());
+
+    let bounded = Bounded(vec![1u8, 2]);
+    assert_eq!(
+        bounded,
+        der::decode(&der::encode(&bounded).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn delegate_constraints() {
+    // Name ::= [1] EXPLICIT PrintableString (SIZE (1..4))
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(delegate, size("1..=4"), tag(explicit(1)))]
+    struct Name(PrintableString);
+
+    // Small ::= INTEGER (0..7)
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(delegate, value("0..=7"))]
+    struct Small(u8);
+
+    // Code ::= IA5String (FROM ("a".."d") ^ SIZE (2))
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    #[rasn(delegate, from("a..=d"), size(2))]
+    struct Code(Ia5String);
+
+    #[derive(AsnType, Debug, Decode, Encode, PartialEq)]
+    struct Record {
+        name: Name,
+        small: Small,
+        code: Code,
+    }
+
+    let record = Record {
+        name: Name(PrintableString::from_bytes(b"ab").unwrap()),
+        small: Small(5),
+        code: Code(Ia5String::from_iso646_bytes(b"cd").unwrap()),
+    };
+
+    // The name's length takes two bits, the small integer three bits, and
+    // each character of the code two bits.
+    let encoded = uper::encode(&record).unwrap();
+    assert_eq!(&[0x4D, 0x9C, 0xB6][..], &*encoded);
+    assert_eq!(record, uper::decode(&encoded).unwrap());
+
+    let encoded = der::encode(&record.name).unwrap();
+    assert_eq!(&[0xA1, 0x04, 0x13, 0x02, 0x61, 0x62][..], &*encoded);
+    assert_eq!(record.name, der::decode(&encoded).unwrap());
+}
+
+#[test]
+fn unknown_enumerated() {
+    // Status ::= ENUMERATED { ok(0), failed(7), retry(2), ... }
+    #[derive(AsnType, Clone, Copy, Debug, Decode, Encode, PartialEq)]
+    #[rasn(enumerated)]
+    #[repr(isize)]
+    enum Status {
+        Ok,
+        Failed = 7,
+        Retry,
+        #[rasn(unknown)]
+        Unknown(isize),
+    }
+
+    assert_eq!(
+        &[(Status::Ok, 0), (Status::Failed, 7), (Status::Retry, 2)],
+        Status::DISCRIMINANTS
+    );
+    assert_eq!(Some(Status::Unknown(42)), Status::from_discriminant(42));
+    assert_eq!(42, Status::Unknown(42).discriminant());
+
+    for (value, encoded) in [
+        (Status::Retry, &[0x0A, 0x01, 0x02][..]),
+        (Status::Unknown(-3), &[0x0A, 0x01, 0xFD]),
+        (Status::Unknown(200), &[0x0A, 0x02, 0x00, 0xC8]),
+    ] {
+        assert_eq!(encoded, &*der::encode(&value).unwrap());
+        assert_eq!(value, der::decode::<Status>(encoded).unwrap());
+    }
+
+    // PER only encodes the index of known variants.
+    assert!(uper::encode(&Status::Unknown(200)).is_err());
+    assert_eq!(
+        Status::Retry,
+        uper::decode(&uper::encode(&Status::Retry).unwrap()).unwrap()
+    );
+}
@@ -0,0 +1,12 @@
+use rasn::AsnType;
+
+#[derive(AsnType)]
+#[non_exhaustive]
+struct Sequence {
+    #[rasn(tag(0))]
+    a: Option<bool>,
+    #[rasn(tag(0), extension_addition)]
+    b: Option<bool>,
+}
+
+fn main() {}
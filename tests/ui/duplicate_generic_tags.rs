@@ -0,0 +1,12 @@
+use rasn::{types::Constructed, AsnType};
+
+#[derive(AsnType)]
+#[rasn(set)]
+struct Set<T> {
+    a: T,
+    b: bool,
+}
+
+const _: rasn::types::fields::Fields = <Set<bool> as Constructed>::FIELDS;
+
+fn main() {}
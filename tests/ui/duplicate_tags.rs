@@ -0,0 +1,18 @@
+use rasn::AsnType;
+
+#[derive(AsnType)]
+struct Sequence {
+    #[rasn(tag(0))]
+    a: Option<bool>,
+    #[rasn(tag(0))]
+    b: bool,
+}
+
+#[derive(AsnType)]
+#[rasn(set)]
+struct Set {
+    a: bool,
+    b: bool,
+}
+
+fn main() {}
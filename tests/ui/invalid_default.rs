@@ -0,0 +1,15 @@
+use rasn::{types::*, AsnType};
+
+#[derive(AsnType)]
+struct Sequence {
+    #[rasn(tag(0), default = 300)]
+    a: u8,
+    #[rasn(tag(1), default = true)]
+    b: Integer,
+    #[rasn(tag(2), default = "\"é\"")]
+    c: Ia5String,
+    #[rasn(tag(3), default = "{ 3 1 }")]
+    d: ObjectIdentifier,
+}
+
+fn main() {}